@@ -10,33 +10,27 @@ fn main() {
     let max_steps: u32 = param_order.drain(0..1).collect::<Vec<String>>()[0].parse().unwrap();
     let nr_sticks: u32 = param_order.drain(0..1).collect::<Vec<String>>()[0].parse().unwrap();
 
-    let mut stuff = vec!();
-    for b in 0..=nr_sticks {
-        stuff.push(format!("s{}", b))
-    };
-
-    let sticks = stuff.iter().map(|x| x.as_str()).collect();
-
-    let trans = incremental_sticks(&sticks);
+    let trans = incremental_sticks(&nr_sticks);
 
     let turn_domain = vec!("c", "k");
-    let tf_domain = vec!("t", "f");
+    let sticks_domain: Vec<i32> = (0..=nr_sticks as i32).collect();
+    let sticks = IntVariable::new("sticks", &sticks_domain.iter().collect(), None);
 
     let mut init_predicates = vec!(
         Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("k")),
-        Predicate::EQRL(EnumVariable::new("sticks", "sticks", &sticks, None), String::from(format!("s{}", nr_sticks)))
+        Predicate::IEQRL(sticks.clone(), nr_sticks as i32)
     );
 
     let init = Predicate::AND(init_predicates);
 
     let mut goal_predicates = vec!(
         Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("k")),
-        Predicate::EQRL(EnumVariable::new("sticks", "sticks", &sticks, None), String::from("s0"))
+        Predicate::IEQRL(sticks.clone(), 0)
     );
 
     let mut forb_predicates = vec!(
         Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("c")),
-        Predicate::EQRL(EnumVariable::new("sticks", "sticks", &sticks, None), String::from("s0"))
+        Predicate::IEQRL(sticks.clone(), 0)
     );
 
     let goal = Predicate::AND(goal_predicates);
@@ -69,23 +63,23 @@ fn main() {
     println!("trace: ");
     for t in &result.trace{
  
-        println!("state: {:?}", t.state);
+        println!("state: {:?}", t.state.assignments.iter().map(|x| x.to_string()).collect::<Vec<String>>());
         println!("trans: {:?}", t.trans);
         // println!("=========================");
     }
     println!("trace2: ");
     for t in GetPlanningResult2Z3::new(&result).trace {
  
-        println!("source: {:?}", t.source);
+        println!("source: {:?}", t.source.assignments.iter().map(|x| x.to_string()).collect::<Vec<String>>());
         println!("trans: {:?}", t.trans);
-        println!("sink: {:?}", t.sink);
+        println!("sink: {:?}", t.sink.assignments.iter().map(|x| x.to_string()).collect::<Vec<String>>());
         println!("=========================");
     }
     println!("all frames: ");
     for t in GetAllFrames2::new(&vec!(result)) {
-        println!("source: {:?}", t.source);
+        println!("source: {:?}", t.source.assignments.iter().map(|x| x.to_string()).collect::<Vec<String>>());
         println!("trans: {:?}", t.trans);
-        println!("sink: {:?}", t.sink);
+        println!("sink: {:?}", t.sink.assignments.iter().map(|x| x.to_string()).collect::<Vec<String>>());
         println!("=========================");
     }
 
@@ -180,9 +174,9 @@ fn main() {
     let frames2 = GetAllFrames2::new(&all);
     
     for t in &frames2 {
-        println!("source: {:?}", t.source);
+        println!("source: {:?}", t.source.assignments.iter().map(|x| x.to_string()).collect::<Vec<String>>());
         println!("trans: {:?}", t.trans);
-        println!("sink: {:?}", t.sink);
+        println!("sink: {:?}", t.sink.assignments.iter().map(|x| x.to_string()).collect::<Vec<String>>());
         println!("=========================");
     }
 
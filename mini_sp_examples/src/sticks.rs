@@ -14,175 +14,52 @@
 
 use mini_sp_tools::*;
 
-pub fn incremental_sticks(sticks: &Vec<&str>) -> Vec<Transition> {
+pub fn incremental_sticks(nr_sticks: &u32) -> Vec<Transition> {
 
     let turn_domain = vec!("c", "k");
     let tf_domain = vec!("t", "f");
+    let sticks_domain: Vec<i32> = (0..=*nr_sticks as i32).collect();
 
-    let mut trans = vec!();
-    for stick in sticks {
-        let split: Vec<&str> = stick.split('s').collect();
-        if split[1].to_string().parse::<i32>().unwrap() >= 3 {
-            trans.push(
-                Transition::new(
-                    &format!("{}_sticks_c_takes_3", stick),
-                    &Predicate::AND(
-                        vec!(
-                            Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("c")),
-                            // Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("2")))),
-                            // Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("1")))),
-                            // Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("0")))),
-                            Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from(stick.to_owned()))
-                        )
-                    ),
-                    &Predicate::AND(
-                        vec!(
-                            Predicate::EQRL(EnumVariable::new("picked_1", "picked_1", &tf_domain, None), String::from("f")),
-                            Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), format!("s{}", split[1].to_string().parse::<u32>().unwrap() - 3).to_string()),
-                            Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("s6")))),
-                            Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("k"))
-                        )
-                    )
-                )
-            )
-        }
-    }
-
-    for stick in sticks {
-        let split: Vec<&str> = stick.split('s').collect();
-        if split[1].to_string().parse::<i32>().unwrap() >= 2 {
-            trans.push(
-                Transition::new(
-                    &format!("{}_sticks_c_takes_2", stick),
-                    &Predicate::AND(
-                        vec!(
-                            Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("c")),
-                            // Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("1")))),
-                            // Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("0")))),
-                            Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from(stick.to_owned()))
-                        )
-                    ),
-                    &Predicate::AND(
-                        vec!(
-                            Predicate::EQRL(EnumVariable::new("picked_1", "picked_1", &tf_domain, None), String::from("f")),
-                            Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), format!("s{}", split[1].to_string().parse::<u32>().unwrap() - 2).to_string()),
-                            Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("s6")))),
-                            Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("k"))
-                        )
-                    )
-                )
-            )
-        }
-    }
-
-    for stick in sticks {
-        let split: Vec<&str> = stick.split('s').collect();
-        if split[1].to_string().parse::<i32>().unwrap() >= 1 {
-            trans.push(
-                Transition::new(
-                    &format!("{}_sticks_c_takes_1", stick),
-                    &Predicate::AND(
-                        vec!(
-                            Predicate::EQRL(EnumVariable::new("picked_1", "picked_1", &tf_domain, None), String::from("f")),
-                            Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("c")),
-                            // Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("0")))),
-                            Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from(stick.to_owned()))
-                        )
-                    ),
-                    &Predicate::AND(
-                        vec!(
-                            Predicate::EQRL(EnumVariable::new("picked_1", "picked_1", &tf_domain, None), String::from("t")),
-                            Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), format!("s{}", split[1].to_string().parse::<u32>().unwrap() - 1).to_string()),
-                            Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("s6")))),
-                            Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("k"))
-                        )
-                    )
-                )
-            )
-        }
-    }
-
-    for stick in sticks {
-        let split: Vec<&str> = stick.split('s').collect();
-        if split[1].to_string().parse::<i32>().unwrap() >= 3 {
-            trans.push(
-                Transition::new(
-                    &format!("{}_sticks_k_takes_3", stick),
-                    &Predicate::AND(
-                        vec!(
-                            Predicate::EQRL(EnumVariable::new("k_took_3", "k_took_3", &tf_domain, None), String::from("f")),
-                            Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("k")),
-                            // Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("2")))),
-                            // Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("1")))),
-                            // Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("0")))),
-                            Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from(stick.to_owned()))
-                        )
-                    ),
-                    &Predicate::AND(
-                        vec!(
-                            Predicate::EQRL(EnumVariable::new("picked_1", "picked_1", &tf_domain, None), String::from("f")),
-                            Predicate::EQRL(EnumVariable::new("k_took_3", "k_took_3", &tf_domain, None), String::from("t")),
-                            Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), format!("s{}", split[1].to_string().parse::<u32>().unwrap() - 3).to_string()),
-                            Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("c"))
-                        )
-                    )
-                )
-            )
-        }
-    }
+    let turn = EnumVariable::new("turn", "turn", &turn_domain, None);
+    let picked_1 = EnumVariable::new("picked_1", "picked_1", &tf_domain, None);
+    let k_took_3 = EnumVariable::new("k_took_3", "k_took_3", &tf_domain, None);
+    let sticks = IntVariable::new("sticks", &sticks_domain.iter().collect(), None);
 
-    for stick in sticks {
-        let split: Vec<&str> = stick.split('s').collect();
-        if split[1].to_string().parse::<i32>().unwrap() >= 2 {
-            trans.push(
-                Transition::new(
-                    &format!("{}_sticks_k_takes_2", stick),
-                    &Predicate::AND(
-                        vec!(
-                            Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("k")),
-                            // Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("1")))),
-                            // Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("0")))),
-                            Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from(stick.to_owned()))
-                        )
-                    ),
-                    &Predicate::AND(
-                        vec!(
-                            Predicate::EQRL(EnumVariable::new("picked_1", "picked_1", &tf_domain, None), String::from("f")),
-                            Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), format!("s{}", split[1].to_string().parse::<u32>().unwrap() - 2).to_string()),
-                            Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("c"))
-                        )
-                    )
-                )
-            )
-        }
-    }   
-
-    for stick in sticks {
-        let split: Vec<&str> = stick.split('s').collect();
-        if split[1].to_string().parse::<i32>().unwrap() >= 1 {
-            trans.push(
-                Transition::new(
-                    &format!("{}_sticks_k_takes_1", stick),
-                    &Predicate::AND(
-                        vec!(
-                            Predicate::EQRL(EnumVariable::new("picked_1", "picked_1", &tf_domain, None), String::from("f")),
-                            Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("k")),
-                            // Predicate::NOT(Box::new(Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from("0")))),
-                            Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), String::from(stick.to_owned()))
-                        )
-                    ),
-                    &Predicate::AND(
-                        vec!(
-                            Predicate::EQRL(EnumVariable::new("picked_1", "picked_1", &tf_domain, None), String::from("t")),
-                            Predicate::EQRL(EnumVariable::new("sticks", "sticks", sticks, None), format!("s{}", split[1].to_string().parse::<u32>().unwrap() - 1).to_string()),
-                            Predicate::EQRL(EnumVariable::new("turn", "turn", &turn_domain, None), String::from("c"))
-                        )
-                    )
-                )
-            )
+    let mut trans = vec!();
+    for take in vec!(3, 2, 1) {
+        let mut c_guard = vec!(
+            Predicate::EQRL(turn.clone(), String::from("c")),
+            Predicate::IGE(Term::VAR(sticks.clone()), Term::VAL(take))
+        );
+        let mut k_guard = vec!(
+            Predicate::EQRL(turn.clone(), String::from("k")),
+            Predicate::IGE(Term::VAR(sticks.clone()), Term::VAL(take))
+        );
+        let c_update = vec!(
+            Predicate::EQRL(picked_1.clone(), String::from(if take == 1 { "t" } else { "f" })),
+            Predicate::IEQRT(sticks.clone(), Term::SUB(vec!(Term::VAR(sticks.clone()), Term::VAL(take)))),
+            Predicate::NOT(Box::new(Predicate::IEQRT(sticks.clone(), Term::VAL(6)))),
+            Predicate::EQRL(turn.clone(), String::from("k"))
+        );
+        let mut k_update = vec!(
+            Predicate::EQRL(picked_1.clone(), String::from(if take == 1 { "t" } else { "f" })),
+            Predicate::IEQRT(sticks.clone(), Term::SUB(vec!(Term::VAR(sticks.clone()), Term::VAL(take)))),
+            Predicate::EQRL(turn.clone(), String::from("c"))
+        );
+        match take {
+            1 => {
+                c_guard.push(Predicate::EQRL(picked_1.clone(), String::from("f")));
+                k_guard.push(Predicate::EQRL(picked_1.clone(), String::from("f")));
+            },
+            3 => {
+                k_guard.push(Predicate::EQRL(k_took_3.clone(), String::from("f")));
+                k_update.push(Predicate::EQRL(k_took_3.clone(), String::from("t")));
+            },
+            _ => ()
         }
+        trans.push(Transition::new(&format!("c_takes_{}", take), &Predicate::AND(c_guard), &Predicate::AND(c_update)));
+        trans.push(Transition::new(&format!("k_takes_{}", take), &Predicate::AND(k_guard), &Predicate::AND(k_update)));
     }
 
     trans
 }
-
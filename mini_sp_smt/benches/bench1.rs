@@ -1,6 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use mini_sp_smt::*;
-use z3_sys::*;
 
 #[inline]
 fn cfg_bench() -> ConfigZ3 {
@@ -13,7 +12,7 @@ fn ctx_bench() -> ContextZ3 {
 }
 
 #[inline]
-fn and_bench() -> Z3_ast {
+fn and_bench() -> String {
     let ctx = ContextZ3::new(&ConfigZ3::new());
     let boolsort = BoolSortZ3::new(&ctx);
 
@@ -23,7 +22,8 @@ fn and_bench() -> Z3_ast {
     let x1 = BoolVarZ3::new(&ctx, &boolsort, "x1");
     let x2 = BoolVarZ3::new(&ctx, &boolsort, "x2");
 
-    ANDZ3::new(&ctx, vec!(x1, x2, bool1, bool2))
+    let and = ANDZ3::new(&ctx, vec!(x1, x2, bool1, bool2));
+    and.to_string()
 }
 
 pub fn criterion_benchmark_cfg(c: &mut Criterion) {
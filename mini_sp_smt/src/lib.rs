@@ -4,26 +4,47 @@ pub mod z3config;
 pub use crate::z3config::{ConfigZ3, SetParamZ3};
 
 pub mod z3context;
-pub use crate::z3context::{ContextZ3, UpdateParamZ3};
+pub use crate::z3context::{ContextZ3, ErrorZ3, InterruptHandleZ3, UpdateParamZ3};
+
+pub mod z3ast;
+pub use crate::z3ast::{Ast, Sort, Model, FuncDecl, AstVector, Pattern, AsAstZ3, AsSortZ3, AsModelZ3, AsFuncDeclZ3,
+    AsAstVectorZ3};
 
 pub mod z3solver;
-pub use crate::z3solver::{SolverZ3, SlvAssertZ3, SlvPushZ3, SlvPopZ3, SlvGetPopPointsZ3, SlvAssertAndTrackZ3, 
-    SlvCheckZ3, SlvResetZ3, SlvGetModelZ3, SlvGetModelAndForbidZ3, SlvGetAllModelsZ3,
-    SlvGetNModelsZ3, SlvGetParamDescrZ3, SlvGetProofZ3, SlvGetAssertsZ3, 
-    SlvGetUnsatCoreZ3, SlvToStringZ3, SlvUnsatCoreToStringZ3, SlvProofToStringZ3};
+pub use crate::z3solver::{SolverZ3, SatResultZ3, SlvAssertZ3, SlvPushZ3, SlvPopZ3, SlvGetPopPointsZ3, SlvScopeZ3, SlvAssertAndTrackZ3, 
+    SlvCheckZ3, AssumptionsResultZ3, SlvCheckAssumptionsZ3, ConsequenceZ3, ConsequencesResultZ3, SlvGetConsequencesZ3, 
+    SlvSetTimeoutZ3, SlvSetRlimitZ3, SlvSetCoreMinimizeZ3, SlvGetReasonUnknownZ3, SlvResetZ3, SlvGetModelZ3, SlvGetModelAndForbidZ3, SlvGetAllModelsZ3,
+    SlvGetNModelsZ3, ProjectedModelZ3, SlvModelsZ3, SlvGetParamDescrZ3, SlvGetProofZ3, SlvGetAssertsZ3, 
+    SlvGetUnsatCoreZ3, SlvGetUnsatCoreNamesZ3, SlvToStringZ3, SlvToSmtLib2Z3, SlvUnsatCoreToStringZ3, SlvProofToStringZ3, 
+    SlvFromSmtLib2Z3, SlvFromSmtLib2FileZ3};
 
 pub mod z3optimizer;
-pub use crate::z3optimizer::{OptimizerZ3, OptAssertZ3, OptCheckZ3, OptMaximizeZ3, OptMinimizeZ3, 
-    OptGetModelZ3, OptGetStringZ3};
+pub use crate::z3optimizer::{OptimizerZ3, SoftConstraintZ3, OptAssertZ3, OptAssertSoftZ3, OptGetViolatedSoftZ3, OptGetPenaltyZ3, 
+    OptCheckZ3, OptMaximizeZ3, OptMinimizeZ3, OptPriorityZ3, OptSetPriorityZ3, OptGetLowerZ3, OptGetUpperZ3, 
+    OptGetObjectiveValueZ3, ParetoPointZ3, OptParetoZ3, 
+    OptGetModelZ3, OptGetStringZ3, OptFromSmtLib2Z3, OptFromSmtLib2FileZ3};
+
+pub mod z3statistics;
+pub use crate::z3statistics::{StatValueZ3, StatisticsZ3, SlvGetStatisticsZ3, OptGetStatisticsZ3};
+
+pub mod z3tactics;
+pub use crate::z3tactics::{GoalZ3, TacticZ3, GoalAssertZ3, GoalGetFormulasZ3, TacticAndThenZ3, TacticOrElseZ3, 
+    TacticRepeatZ3, TacticParOrZ3, TacticApplyZ3, SolverFromTacticZ3};
+
+pub mod z3eval;
+pub use crate::z3eval::{ModelValueZ3, ModelEvalZ3};
+
+pub mod z3inspect;
+pub use crate::z3inspect::{QuantifierKindZ3, AstNodeZ3, AstFoldZ3, SimplifyZ3, AstDecomposeZ3, FoldAstZ3};
 
 pub mod z3sorts;
-pub use crate::z3sorts::{IntSortZ3, BoolSortZ3, RealSortZ3, StringSortZ3, EnumSortZ3, GetSortZ3, SortToStringZ3};
+pub use crate::z3sorts::{IntSortZ3, BoolSortZ3, RealSortZ3, StringSortZ3, BitVecSortZ3, ArraySortZ3, EnumSortZ3, GetSortZ3, SortToStringZ3};
 
 pub mod z3values;
-pub use crate::z3values::{BoolZ3, IntZ3, RealZ3, StringZ3};
+pub use crate::z3values::{BoolZ3, IntZ3, RealZ3, StringZ3, BitVecZ3};
 
 pub mod z3variables;
-pub use crate::z3variables::{BoolVarZ3, IntVarZ3, RealVarZ3, StringVarZ3, EnumVarZ3};
+pub use crate::z3variables::{BoolVarZ3, IntVarZ3, RealVarZ3, StringVarZ3, BitVecVarZ3, ArrayVarZ3, EnumVarZ3};
 
 pub mod z3relations;
 pub use crate::z3relations::{EQZ3, NEQZ3, LEZ3, LTZ3, GEZ3, GTZ3};
@@ -31,10 +52,27 @@ pub use crate::z3relations::{EQZ3, NEQZ3, LEZ3, LTZ3, GEZ3, GTZ3};
 pub mod z3operations;
 pub use crate::z3operations::{MULZ3, DIVZ3, MODZ3, REMZ3, ADDZ3, SUBZ3, NEGZ3, POWZ3};
 
+pub mod z3bitvectors;
+pub use crate::z3bitvectors::{BVADDZ3, BVSUBZ3, BVMULZ3, BVUDIVZ3, BVSDIVZ3, BVUREMZ3, BVSREMZ3, 
+    BVANDZ3, BVORZ3, BVXORZ3, BVNOTZ3, BVNEGZ3, BVSHLZ3, BVLSHRZ3, BVASHRZ3, BVULTZ3, BVULEZ3, 
+    BVUGTZ3, BVUGEZ3, BVSLTZ3, BVSLEZ3, BVSGTZ3, BVSGEZ3, BVEXTRACTZ3, BVCONCATZ3};
+
+pub mod z3strings;
+pub use crate::z3strings::{STRCONCATZ3, STRLENZ3, SUBSTRZ3, STRCONTAINSZ3, STRPREFIXOFZ3, STRSUFFIXOFZ3, 
+    STRINDEXOFZ3, STRTOINTZ3, INTTOSTRZ3, STRTOREZ3, INREZ3, RERANGEZ3, REPLUSZ3, RESTARZ3, REOPTIONZ3, 
+    REUNIONZ3, RECONCATZ3};
+
+pub mod z3arrays;
+pub use crate::z3arrays::{SelectZ3, StoreZ3, ConstArrayZ3, ArrayInterp, ModelGetArrayInterpZ3};
+
+pub mod z3functions;
+pub use crate::z3functions::{FuncDeclZ3, AppZ3, GetAppDeclZ3, FuncInterp, ModelGetFuncInterpZ3};
+
 pub mod z3logics;
-pub use crate::z3logics::{ANDZ3, ORZ3, DISTINCTZ3, NOTZ3, ITEZ3, IFFZ3, IMPZ3, XORZ3, PBEQZ3};
+pub use crate::z3logics::{ANDZ3, ORZ3, DISTINCTZ3, NOTZ3, ITEZ3, IFFZ3, IMPZ3, XORZ3, PBEQZ3, 
+    PatternZ3, ForallZ3, ExistsZ3};
 
 pub mod z3utils;
 pub use crate::z3utils::{AstToStringZ3, ModelToStringZ3, ModelGetNumConstsZ3, ModelGetConstDeclZ3, 
     GetDeclNameZ3, ModelGetConstInterpZ3, GetSymbolStringZ3, GetCnfVectorZ3, Z3StringToStringZ3,
-    AstVectorToStringZ3, Z3AstVectorToVectorAstZ3, FreshModelZ3};
\ No newline at end of file
+    AstVectorToStringZ3, Z3AstVectorToVectorAstZ3, FreshModelZ3, ParseSmtLib2Z3, ParseSmtLib2FileZ3};
\ No newline at end of file
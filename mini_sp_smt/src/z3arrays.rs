@@ -0,0 +1,373 @@
+//! Z3 arrays for SP
+
+use std::collections::HashMap;
+use z3_sys::*;
+use super::*;
+use crate::z3eval::ast_to_value_z3;
+
+pub struct SelectZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub array: Z3_ast,
+    pub index: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct StoreZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub array: Z3_ast,
+    pub index: Z3_ast,
+    pub value: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct ConstArrayZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub domain: Z3_sort,
+    pub value: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+/// Interpretation of an array in a model, with the indices and values read as typed values.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ArrayInterp {
+    pub entries: HashMap<ModelValueZ3, ModelValueZ3>,
+    pub default: Option<ModelValueZ3>
+}
+
+pub struct ModelGetArrayInterpZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub model: Z3_model,
+    pub array: Z3_ast,
+    pub r: ArrayInterp
+}
+
+impl <'ctx> SelectZ3<'ctx> {
+    /// Array read. The argument `array` is the array and `index` is the index of the array that gets read.
+    ///
+    /// The node `array` must have an array sort `[domain -> range]`,
+    /// and `index` must have the sort `domain`.
+    /// The sort of the result is `range`.
+    ///
+    /// NOTE: See macro! `select_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, array: impl AsAstZ3<'ctx>, index: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match SelectZ3::try_new(ctx, array, index) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, array: impl AsAstZ3<'ctx>, index: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let array = array.as_ast();
+        let index = index.as_ast();
+        let z3 = unsafe {
+            Z3_mk_select(ctx.r, array, index)
+        };
+        ctx.check_error()?;
+        Ok(SelectZ3 {ctx, array, index, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> StoreZ3<'ctx> {
+    /// Array update.
+    ///
+    /// The node `array` must have an array sort `[domain -> range]`, `index` must have sort `domain`,
+    /// `value` must have sort `range`. The sort of the result is `[domain -> range]`.
+    /// The result is an array that is equal to `array` (with respect to `select`)
+    /// on all indices except for `index`, where it maps to `value`.
+    ///
+    /// NOTE: See macro! `store_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, array: impl AsAstZ3<'ctx>, index: impl AsAstZ3<'ctx>, value: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match StoreZ3::try_new(ctx, array, index, value) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, array: impl AsAstZ3<'ctx>, index: impl AsAstZ3<'ctx>, value: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let array = array.as_ast();
+        let index = index.as_ast();
+        let value = value.as_ast();
+        let z3 = unsafe {
+            Z3_mk_store(ctx.r, array, index, value)
+        };
+        ctx.check_error()?;
+        Ok(StoreZ3 {ctx, array, index, value, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> ConstArrayZ3<'ctx> {
+    /// Create the constant array.
+    ///
+    /// The resulting term is an array, such that a `select` on an arbitrary index
+    /// produces the value `value`.
+    ///
+    /// - `domain`: domain sort for the array.
+    /// - `value`: value that the array maps to.
+    ///
+    /// NOTE: See macro! `const_array_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, domain: impl AsSortZ3, value: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match ConstArrayZ3::try_new(ctx, domain, value) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, domain: impl AsSortZ3, value: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let domain = domain.as_sort();
+        let value = value.as_ast();
+        let z3 = unsafe {
+            Z3_mk_const_array(ctx.r, domain, value)
+        };
+        ctx.check_error()?;
+        Ok(ConstArrayZ3 {ctx, domain, value, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl ArrayInterp {
+    /// The value stored at `index`, or the default value if the index was never stored to.
+    pub fn value(&self, index: &ModelValueZ3) -> Option<&ModelValueZ3> {
+        match self.entries.get(index) {
+            Some(x) => Some(x),
+            None => self.default.as_ref()
+        }
+    }
+}
+
+impl <'ctx> ModelGetArrayInterpZ3<'ctx> {
+    /// Read the interpretation of an array in a model into a map from indices to values.
+    ///
+    /// The `array` can be an array variable, or its interpretation from `ModelGetConstInterpZ3`.
+    /// Both store chains `(store (store ((as const ...) d) i1 v1) i2 v2)` and `as-array`
+    /// function interpretations are supported.
+    ///
+    /// NOTE: See macro! `model_get_array_interp_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, model: impl AsModelZ3, array: impl AsAstZ3<'ctx>) -> ArrayInterp {
+        match ModelGetArrayInterpZ3::try_new(ctx, model, array) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, model: impl AsModelZ3, array: impl AsAstZ3<'ctx>) -> Result<ArrayInterp, ErrorZ3> {
+        let model = model.as_model();
+        let array = array.as_ast();
+
+        let value = |ast: Z3_ast| ast_to_value_z3(ctx, &Ast::new(ctx, ast));
+
+        let mut interp = ArrayInterp::default();
+        unsafe {
+            let mut evaluated: Z3_ast = std::ptr::null_mut();
+            let ok = Z3_model_eval(ctx.r, model, array, true, &mut evaluated);
+            let evaluated = Ast::new(ctx, evaluated);
+            ctx.check_error()?;
+            if ok == 0 || evaluated.r.is_null() {
+                return Err(ErrorZ3 {code: Z3_error_code_Z3_INVALID_ARG, msg: "Failed to evaluate the array in the model.".to_string()})
+            }
+
+            let mut current = evaluated.r;
+            while !current.is_null() {
+                if Z3_is_as_array(ctx.r, current) {
+                    let decl = Z3_get_as_array_func_decl(ctx.r, current);
+                    let finterp = Z3_model_get_func_interp(ctx.r, model, decl);
+                    if !finterp.is_null() {
+                        Z3_func_interp_inc_ref(ctx.r, finterp);
+                        for i in 0..Z3_func_interp_get_num_entries(ctx.r, finterp) {
+                            let entry = Z3_func_interp_get_entry(ctx.r, finterp, i);
+                            Z3_func_entry_inc_ref(ctx.r, entry);
+                            let index = value(Z3_func_entry_get_arg(ctx.r, entry, 0));
+                            let stored = value(Z3_func_entry_get_value(ctx.r, entry));
+                            Z3_func_entry_dec_ref(ctx.r, entry);
+                            interp.entries.entry(index?).or_insert(stored?);
+                        }
+                        let default = value(Z3_func_interp_get_else(ctx.r, finterp));
+                        Z3_func_interp_dec_ref(ctx.r, finterp);
+                        interp.default = Some(default?);
+                    }
+                    current = std::ptr::null_mut();
+                } else if Z3_is_app(ctx.r, current) {
+                    let app = Z3_to_app(ctx.r, current);
+                    let kind = Z3_get_decl_kind(ctx.r, Z3_get_app_decl(ctx.r, app));
+                    if kind == Z3_decl_kind_Z3_OP_STORE {
+                        // the outermost store is the most recent one for an index
+                        interp.entries.entry(value(Z3_get_app_arg(ctx.r, app, 1))?)
+                            .or_insert(value(Z3_get_app_arg(ctx.r, app, 2))?);
+                        current = Z3_get_app_arg(ctx.r, app, 0);
+                    } else {
+                        if kind == Z3_decl_kind_Z3_OP_CONST_ARRAY {
+                            interp.default = Some(value(Z3_get_app_arg(ctx.r, app, 0))?);
+                        }
+                        current = std::ptr::null_mut();
+                    }
+                } else {
+                    current = std::ptr::null_mut();
+                }
+            }
+        }
+        ctx.check_error()?;
+        Ok(ModelGetArrayInterpZ3 {ctx, model, array, r: interp}.r)
+    }
+}
+
+/// array[index]
+///
+/// Macro rule for:
+/// ```text
+/// z3arrays::SelectZ3::new(&ctx, array, index)
+/// ```
+/// Using a specific context:
+/// ```text
+/// select_z3!(&ctx, array, index)
+/// ```
+#[macro_export]
+macro_rules! select_z3 {
+    ($ctx:expr, $a:expr, $i:expr) => {
+        SelectZ3::new($ctx, $a, $i)
+    }
+}
+
+/// array[index] := value
+///
+/// Macro rule for:
+/// ```text
+/// z3arrays::StoreZ3::new(&ctx, array, index, value)
+/// ```
+/// Using a specific context:
+/// ```text
+/// store_z3!(&ctx, array, index, value)
+/// ```
+#[macro_export]
+macro_rules! store_z3 {
+    ($ctx:expr, $a:expr, $i:expr, $v:expr) => {
+        StoreZ3::new($ctx, $a, $i, $v)
+    }
+}
+
+/// [domain -> value]
+///
+/// Macro rule for:
+/// ```text
+/// z3arrays::ConstArrayZ3::new(&ctx, domain, value)
+/// ```
+/// Using a specific context:
+/// ```text
+/// const_array_z3!(&ctx, domain, value)
+/// ```
+#[macro_export]
+macro_rules! const_array_z3 {
+    ($ctx:expr, $d:expr, $v:expr) => {
+        ConstArrayZ3::new($ctx, $d, $v)
+    }
+}
+
+/// read an array interpretation from a model
+#[macro_export]
+macro_rules! model_get_array_interp_z3 {
+    ($ctx:expr, $m:expr, $a:expr) => {
+        ModelGetArrayInterpZ3::new($ctx, $m, $a)
+    }
+}
+
+#[test]
+fn test_new_select_store(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let sort = ArraySortZ3::new(&ctx, &intsort, &intsort);
+
+    let a = ArrayVarZ3::new(&ctx, &sort, "a");
+    let store = StoreZ3::new(&ctx, &a, IntZ3::new(&ctx, &intsort, 1), IntZ3::new(&ctx, &intsort, 5));
+    let select = SelectZ3::new(&ctx, &store, IntZ3::new(&ctx, &intsort, 1));
+
+    assert_eq!("(store a 1 5)", ast_to_string_z3!(&ctx, &store));
+    assert_eq!("(select (store a 1 5) 1)", ast_to_string_z3!(&ctx, &select));
+    assert_eq!("Int", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, select)));
+}
+
+#[test]
+fn test_new_const_array(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let zeros = ConstArrayZ3::new(&ctx, &intsort, IntZ3::new(&ctx, &intsort, 0));
+
+    assert_eq!("((as const (Array Int Int)) 0)", ast_to_string_z3!(&ctx, &zeros));
+}
+
+#[test]
+fn test_array_sort_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let sort = ArraySortZ3::new(&ctx, &intsort, &intsort);
+
+    let a = ArrayVarZ3::new(&ctx, &sort, "a");
+
+    assert!(SelectZ3::try_new(&ctx, &a, BoolZ3::new(&ctx, true)).is_err());
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_array_slot_map(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("none", "red", "blue"));
+    let sort = ArraySortZ3::new(&ctx, &intsort, &balls);
+
+    let slots = ArrayVarZ3::new(&ctx, &sort, "slots");
+    let empty = ConstArrayZ3::new(&ctx, &intsort, &balls.enum_asts[0]);
+    let filled = StoreZ3::new(&ctx, 
+        StoreZ3::new(&ctx, empty, IntZ3::new(&ctx, &intsort, 1), &balls.enum_asts[1]),
+        IntZ3::new(&ctx, &intsort, 2), &balls.enum_asts[2]);
+
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &slots, filled));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let interp = ModelGetArrayInterpZ3::new(&ctx, &model, &slots);
+
+    assert_eq!(Some(&ModelValueZ3::Enum("red".to_string())), interp.value(&ModelValueZ3::Int(1)));
+    assert_eq!(Some(&ModelValueZ3::Enum("blue".to_string())), interp.value(&ModelValueZ3::Int(2)));
+    assert_eq!(Some(&ModelValueZ3::Enum("none".to_string())), interp.value(&ModelValueZ3::Int(3)));
+}
+
+#[test]
+fn test_array_const_interp(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let sort = ArraySortZ3::new(&ctx, &intsort, &intsort);
+
+    let a = ArrayVarZ3::new(&ctx, &sort, "a");
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, SelectZ3::new(&ctx, &a, IntZ3::new(&ctx, &intsort, 1)), IntZ3::new(&ctx, &intsort, 5)));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, SelectZ3::new(&ctx, &a, IntZ3::new(&ctx, &intsort, 2)), IntZ3::new(&ctx, &intsort, 7)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let decl = ModelGetConstDeclZ3::new(&ctx, &model, 0);
+    let value = ModelGetConstInterpZ3::new(&ctx, &model, &decl);
+    let interp = ModelGetArrayInterpZ3::new(&ctx, &model, &value);
+
+    assert_eq!("a", Z3StringToStringZ3::new(GetSymbolStringZ3::new(&ctx, GetDeclNameZ3::new(&ctx, &model, &decl))));
+    assert_eq!(Some(&ModelValueZ3::Int(5)), interp.value(&ModelValueZ3::Int(1)));
+    assert_eq!(Some(&ModelValueZ3::Int(7)), interp.value(&ModelValueZ3::Int(2)));
+}
+
+#[test]
+fn test_array_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let intsort = int_sort_z3!(&ctx);
+    let boolsort = bool_sort_z3!(&ctx);
+
+    let a = array_var_z3!(&ctx, &intsort, &boolsort, "a");
+    let t = const_array_z3!(&ctx, &intsort, bool_z3!(&ctx, true));
+
+    assert_eq!("(Array Int Bool)", sort_to_string_z3!(&ctx, array_sort_z3!(&ctx, &intsort, &boolsort)));
+    assert_eq!("(store a 3 false)", ast_to_string_z3!(&ctx, store_z3!(&ctx, &a, int_z3!(&ctx, 3), bool_z3!(&ctx, false))));
+    assert_eq!("(select a 3)", ast_to_string_z3!(&ctx, select_z3!(&ctx, &a, int_z3!(&ctx, 3))));
+    assert_eq!("((as const (Array Int Bool)) true)", ast_to_string_z3!(&ctx, &t));
+}
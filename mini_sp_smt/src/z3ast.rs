@@ -0,0 +1,384 @@
+//! Reference counted Z3 handles for SP
+
+use std::ffi::{CStr};
+use std::fmt;
+use z3_sys::*;
+use super::*;
+
+/// Owned AST node, tied to the lifetime of its context.
+pub struct Ast<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub r: Z3_ast
+}
+
+/// Owned sort, tied to the lifetime of its context.
+pub struct Sort<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub r: Z3_sort
+}
+
+/// Owned model, tied to the lifetime of its context.
+pub struct Model<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub r: Z3_model
+}
+
+/// Owned function declaration, tied to the lifetime of its context.
+pub struct FuncDecl<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub r: Z3_func_decl
+}
+
+/// Owned vector of AST nodes, tied to the lifetime of its context.
+pub struct AstVector<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub r: Z3_ast_vector
+}
+
+/// Owned quantifier pattern, tied to the lifetime of its context.
+pub struct Pattern<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub r: Z3_pattern
+}
+
+/// Anything that can be passed to Z3 as an AST node of the context `'ctx`.
+pub trait AsAstZ3<'ctx> {
+    fn as_ast(&self) -> Z3_ast;
+}
+
+/// Anything that can be passed to Z3 as a sort.
+pub trait AsSortZ3 {
+    fn as_sort(&self) -> Z3_sort;
+}
+
+/// Anything that can be passed to Z3 as a model.
+pub trait AsModelZ3 {
+    fn as_model(&self) -> Z3_model;
+}
+
+/// Anything that can be passed to Z3 as a function declaration.
+pub trait AsFuncDeclZ3 {
+    fn as_func_decl(&self) -> Z3_func_decl;
+}
+
+/// Anything that can be passed to Z3 as a vector of AST nodes.
+pub trait AsAstVectorZ3 {
+    fn as_ast_vector(&self) -> Z3_ast_vector;
+}
+
+impl <'ctx> Ast<'ctx> {
+    /// Take ownership of a raw AST node by incrementing its reference counter.
+    pub fn new(ctx: &'ctx ContextZ3, r: Z3_ast) -> Ast<'ctx> {
+        unsafe {
+            Z3_inc_ref(ctx.r, r);
+        }
+        Ast {ctx, r}
+    }
+}
+
+impl <'ctx> Sort<'ctx> {
+    /// Take ownership of a raw sort by incrementing its reference counter.
+    pub fn new(ctx: &'ctx ContextZ3, r: Z3_sort) -> Sort<'ctx> {
+        unsafe {
+            Z3_inc_ref(ctx.r, Z3_sort_to_ast(ctx.r, r));
+        }
+        Sort {ctx, r}
+    }
+}
+
+impl <'ctx> Model<'ctx> {
+    /// Take ownership of a raw model by incrementing its reference counter.
+    pub fn new(ctx: &'ctx ContextZ3, r: Z3_model) -> Model<'ctx> {
+        unsafe {
+            Z3_model_inc_ref(ctx.r, r);
+        }
+        Model {ctx, r}
+    }
+}
+
+impl <'ctx> FuncDecl<'ctx> {
+    /// Take ownership of a raw function declaration by incrementing its reference counter.
+    pub fn new(ctx: &'ctx ContextZ3, r: Z3_func_decl) -> FuncDecl<'ctx> {
+        unsafe {
+            Z3_inc_ref(ctx.r, Z3_func_decl_to_ast(ctx.r, r));
+        }
+        FuncDecl {ctx, r}
+    }
+}
+
+impl <'ctx> AstVector<'ctx> {
+    /// Take ownership of a raw AST vector by incrementing its reference counter.
+    pub fn new(ctx: &'ctx ContextZ3, r: Z3_ast_vector) -> AstVector<'ctx> {
+        unsafe {
+            Z3_ast_vector_inc_ref(ctx.r, r);
+        }
+        AstVector {ctx, r}
+    }
+}
+
+impl <'ctx> Pattern<'ctx> {
+    /// Take ownership of a raw pattern by incrementing its reference counter.
+    pub fn new(ctx: &'ctx ContextZ3, r: Z3_pattern) -> Pattern<'ctx> {
+        unsafe {
+            Z3_inc_ref(ctx.r, Z3_pattern_to_ast(ctx.r, r));
+        }
+        Pattern {ctx, r}
+    }
+}
+
+impl <'ctx> Clone for Ast<'ctx> {
+    fn clone(&self) -> Self {
+        Ast::new(self.ctx, self.r)
+    }
+}
+
+impl <'ctx> Clone for Sort<'ctx> {
+    fn clone(&self) -> Self {
+        Sort::new(self.ctx, self.r)
+    }
+}
+
+impl <'ctx> Clone for Model<'ctx> {
+    fn clone(&self) -> Self {
+        Model::new(self.ctx, self.r)
+    }
+}
+
+impl <'ctx> Clone for FuncDecl<'ctx> {
+    fn clone(&self) -> Self {
+        FuncDecl::new(self.ctx, self.r)
+    }
+}
+
+impl <'ctx> Clone for AstVector<'ctx> {
+    fn clone(&self) -> Self {
+        AstVector::new(self.ctx, self.r)
+    }
+}
+
+impl <'ctx> Clone for Pattern<'ctx> {
+    fn clone(&self) -> Self {
+        Pattern::new(self.ctx, self.r)
+    }
+}
+
+impl <'ctx> Drop for Ast<'ctx> {
+    /// Decrement the reference counter of the AST node.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_dec_ref(self.ctx.r, self.r)
+        }
+    }
+}
+
+impl <'ctx> Drop for Sort<'ctx> {
+    /// Decrement the reference counter of the sort.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_dec_ref(self.ctx.r, Z3_sort_to_ast(self.ctx.r, self.r))
+        }
+    }
+}
+
+impl <'ctx> Drop for Model<'ctx> {
+    /// Decrement the reference counter of the model.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_model_dec_ref(self.ctx.r, self.r)
+        }
+    }
+}
+
+impl <'ctx> Drop for FuncDecl<'ctx> {
+    /// Decrement the reference counter of the function declaration.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_dec_ref(self.ctx.r, Z3_func_decl_to_ast(self.ctx.r, self.r))
+        }
+    }
+}
+
+impl <'ctx> Drop for AstVector<'ctx> {
+    /// Decrement the reference counter of the AST vector.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_ast_vector_dec_ref(self.ctx.r, self.r)
+        }
+    }
+}
+
+impl <'ctx> Drop for Pattern<'ctx> {
+    /// Decrement the reference counter of the pattern.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_dec_ref(self.ctx.r, Z3_pattern_to_ast(self.ctx.r, self.r))
+        }
+    }
+}
+
+impl <'ctx> fmt::Display for Ast<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = unsafe {
+            CStr::from_ptr(Z3_ast_to_string(self.ctx.r, self.r)).to_string_lossy().into_owned()
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl <'ctx> fmt::Display for Sort<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = unsafe {
+            CStr::from_ptr(Z3_sort_to_string(self.ctx.r, self.r)).to_string_lossy().into_owned()
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl <'ctx> fmt::Display for Model<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = unsafe {
+            CStr::from_ptr(Z3_model_to_string(self.ctx.r, self.r)).to_string_lossy().into_owned()
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl <'ctx> fmt::Display for FuncDecl<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = unsafe {
+            CStr::from_ptr(Z3_func_decl_to_string(self.ctx.r, self.r)).to_string_lossy().into_owned()
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl <'ctx> fmt::Debug for Ast<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl <'ctx> PartialEq for Ast<'ctx> {
+    /// Two AST nodes are equal if Z3 considers them the same node.
+    fn eq(&self, other: &Ast<'ctx>) -> bool {
+        unsafe {
+            Z3_is_eq_ast(self.ctx.r, self.r, other.r)
+        }
+    }
+}
+
+impl <'ctx> Eq for Ast<'ctx> {}
+
+impl <'ctx> AsAstZ3<'ctx> for Ast<'ctx> {
+    fn as_ast(&self) -> Z3_ast {
+        self.r
+    }
+}
+
+impl <'ctx, T: AsAstZ3<'ctx>> AsAstZ3<'ctx> for &T {
+    fn as_ast(&self) -> Z3_ast {
+        (*self).as_ast()
+    }
+}
+
+impl <'ctx> AsSortZ3 for Sort<'ctx> {
+    fn as_sort(&self) -> Z3_sort {
+        self.r
+    }
+}
+
+impl <T: AsSortZ3> AsSortZ3 for &T {
+    fn as_sort(&self) -> Z3_sort {
+        (*self).as_sort()
+    }
+}
+
+impl <'ctx> AsModelZ3 for Model<'ctx> {
+    fn as_model(&self) -> Z3_model {
+        self.r
+    }
+}
+
+impl <T: AsModelZ3> AsModelZ3 for &T {
+    fn as_model(&self) -> Z3_model {
+        (*self).as_model()
+    }
+}
+
+impl <'ctx> AsFuncDeclZ3 for FuncDecl<'ctx> {
+    fn as_func_decl(&self) -> Z3_func_decl {
+        self.r
+    }
+}
+
+impl <T: AsFuncDeclZ3> AsFuncDeclZ3 for &T {
+    fn as_func_decl(&self) -> Z3_func_decl {
+        (*self).as_func_decl()
+    }
+}
+
+impl <'ctx> AsAstVectorZ3 for AstVector<'ctx> {
+    fn as_ast_vector(&self) -> Z3_ast_vector {
+        self.r
+    }
+}
+
+impl <T: AsAstVectorZ3> AsAstVectorZ3 for &T {
+    fn as_ast_vector(&self) -> Z3_ast_vector {
+        (*self).as_ast_vector()
+    }
+}
+
+#[test]
+fn test_ast_clone_and_display(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let y = x.clone();
+    drop(x);
+
+    assert_eq!("x", format!("{}", y));
+    assert_eq!("(= x 7)", format!("{}", EQZ3::new(&ctx, &y, IntZ3::new(&ctx, &intsort, 7))));
+}
+
+#[test]
+fn test_sort_and_model_display(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 7)));
+    SlvCheckZ3::new(&ctx, &slv);
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let decl = ModelGetConstDeclZ3::new(&ctx, &model, 0);
+
+    assert_eq!("Int", format!("{}", GetSortZ3::new(&ctx, &x).r));
+    assert_eq!("x -> 7\n", format!("{}", model.clone()));
+    assert_eq!("(declare-fun x () Int)", format!("{}", decl));
+}
+
+#[test]
+fn test_handles_outlive_later_calls(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("red", "blue"));
+    let b = EnumVarZ3::new(&ctx, &balls, "b");
+    let p = BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), "p");
+    let q = BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), "q");
+
+    let equiv = z3logics::EQUIVZ3::new(&ctx, &p, &q);
+    let red = EQZ3::new(&ctx, &b, &balls.enum_asts[0]);
+    SlvAssertZ3::new(&ctx, &slv, and_z3!(&ctx, &equiv, NOTZ3::new(&ctx, &red), ORZ3::new(&ctx, vec!(&p, &q))));
+
+    assert_eq!("(and (=> p q) (=> q p))", format!("{}", equiv));
+    assert_eq!("red", format!("{}", balls.enum_asts[0]));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!(ModelValueZ3::Enum("blue".to_string()), ModelEvalZ3::new(&ctx, &model, &b, true));
+}
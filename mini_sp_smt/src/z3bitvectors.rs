@@ -0,0 +1,1365 @@
+//! Z3 bit-vector operations for SP
+
+use z3_sys::*;
+use super::*;
+
+pub struct BVADDZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVSUBZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVMULZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVUDIVZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVSDIVZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVUREMZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVSREMZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVANDZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVORZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVXORZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVSHLZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVLSHRZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVASHRZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVULTZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVULEZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVUGTZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVUGEZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVSLTZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVSLEZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVSGTZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVSGEZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVCONCATZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVNOTZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVNEGZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct BVEXTRACTZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub high: u32,
+    pub low: u32,
+    pub arg: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+impl <'ctx> BVADDZ3<'ctx> {
+    /// Create an AST node representing two's complement addition `arg1 + arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvadd_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVADDZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvadd(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVADDZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVSUBZ3<'ctx> {
+    /// Create an AST node representing two's complement subtraction `arg1 - arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvsub_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVSUBZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvsub(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVSUBZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVMULZ3<'ctx> {
+    /// Create an AST node representing two's complement multiplication `arg1 * arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvmul_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVMULZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvmul(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVMULZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVUDIVZ3<'ctx> {
+    /// Create an AST node representing unsigned division `arg1 / arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvudiv_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVUDIVZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvudiv(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVUDIVZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVSDIVZ3<'ctx> {
+    /// Create an AST node representing two's complement signed division `arg1 / arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvsdiv_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVSDIVZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvsdiv(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVSDIVZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVUREMZ3<'ctx> {
+    /// Create an AST node representing the unsigned remainder of `arg1 / arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvurem_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVUREMZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvurem(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVUREMZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVSREMZ3<'ctx> {
+    /// Create an AST node representing the two's complement signed remainder of `arg1 / arg2`.
+    ///
+    /// NOTE: The sign of the result follows the sign of `arg1`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvsrem_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVSREMZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvsrem(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVSREMZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVANDZ3<'ctx> {
+    /// Create an AST node representing bitwise `arg1 and arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvand_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVANDZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvand(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVANDZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVORZ3<'ctx> {
+    /// Create an AST node representing bitwise `arg1 or arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvor_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVORZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvor(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVORZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVXORZ3<'ctx> {
+    /// Create an AST node representing bitwise `arg1 xor arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvxor_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVXORZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvxor(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVXORZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVSHLZ3<'ctx> {
+    /// Create an AST node representing the shift left `arg1 << arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvshl_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVSHLZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvshl(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVSHLZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVLSHRZ3<'ctx> {
+    /// Create an AST node representing the logical shift right `arg1 >> arg2`.
+    ///
+    /// NOTE: The vacated bits are filled with zeros.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvlshr_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVLSHRZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvlshr(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVLSHRZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVASHRZ3<'ctx> {
+    /// Create an AST node representing the arithmetical shift right `arg1 >> arg2`.
+    ///
+    /// NOTE: The vacated bits are filled with the sign bit of `arg1`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvashr_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVASHRZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvashr(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVASHRZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVULTZ3<'ctx> {
+    /// Create an AST node representing the unsigned comparison `arg1 < arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvult_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVULTZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvult(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVULTZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVULEZ3<'ctx> {
+    /// Create an AST node representing the unsigned comparison `arg1 <= arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvule_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVULEZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvule(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVULEZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVUGTZ3<'ctx> {
+    /// Create an AST node representing the unsigned comparison `arg1 > arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvugt_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVUGTZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvugt(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVUGTZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVUGEZ3<'ctx> {
+    /// Create an AST node representing the unsigned comparison `arg1 >= arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvuge_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVUGEZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvuge(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVUGEZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVSLTZ3<'ctx> {
+    /// Create an AST node representing the two's complement signed comparison `arg1 < arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvslt_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVSLTZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvslt(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVSLTZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVSLEZ3<'ctx> {
+    /// Create an AST node representing the two's complement signed comparison `arg1 <= arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvsle_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVSLEZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvsle(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVSLEZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVSGTZ3<'ctx> {
+    /// Create an AST node representing the two's complement signed comparison `arg1 > arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvsgt_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVSGTZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvsgt(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVSGTZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVSGEZ3<'ctx> {
+    /// Create an AST node representing the two's complement signed comparison `arg1 >= arg2`.
+    ///
+    /// NOTE: The arguments must have the same bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvsge_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVSGEZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvsge(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVSGEZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVCONCATZ3<'ctx> {
+    /// Concatenate the given bit-vectors.
+    ///
+    /// The result is a bit-vector of size `n1+n2`, where `n1` (`n2`) is the size of `arg1` (`arg2`),
+    /// with `arg1` as the most significant part.
+    ///
+    /// NOTE: The arguments must have bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvconcat_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVCONCATZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_concat(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(BVCONCATZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVNOTZ3<'ctx> {
+    /// Create an AST node representing bitwise negation `not arg`.
+    ///
+    /// NOTE: The argument must have bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvnot_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVNOTZ3::try_new(ctx, arg) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvnot(ctx.r, arg)
+        };
+        ctx.check_error()?;
+        Ok(BVNOTZ3 {ctx, arg, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVNEGZ3<'ctx> {
+    /// Create an AST node representing two's complement unary minus `- arg`.
+    ///
+    /// NOTE: The argument must have bit-vector sort.
+    /// 
+    /// NOTE: See macro! `bvneg_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVNEGZ3::try_new(ctx, arg) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
+        let z3 = unsafe {
+            Z3_mk_bvneg(ctx.r, arg)
+        };
+        ctx.check_error()?;
+        Ok(BVNEGZ3 {ctx, arg, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> BVEXTRACTZ3<'ctx> {
+    /// Extract the bits `high` down to `low` from a bit-vector of size `m`
+    /// to yield a new bit-vector of size `n`, where `n = high - low + 1`.
+    ///
+    /// NOTE: The argument must have bit-vector sort and `m > high >= low`.
+    /// 
+    /// NOTE: See macro! `bvextract_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, high: u32, low: u32, arg: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match BVEXTRACTZ3::try_new(ctx, high, low, arg) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, high: u32, low: u32, arg: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
+        let z3 = unsafe {
+            Z3_mk_extract(ctx.r, high, low, arg)
+        };
+        ctx.check_error()?;
+        Ok(BVEXTRACTZ3 {ctx, high, low, arg, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+/// a + b
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVADDZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvadd_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvadd_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVADDZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a - b
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVSUBZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvsub_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvsub_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVSUBZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a * b
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVMULZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvmul_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvmul_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVMULZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a / b (unsigned)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVUDIVZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvudiv_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvudiv_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVUDIVZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a / b (signed)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVSDIVZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvsdiv_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvsdiv_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVSDIVZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a rem b (unsigned)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVUREMZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvurem_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvurem_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVUREMZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a rem b (signed)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVSREMZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvsrem_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvsrem_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVSREMZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a & b
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVANDZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvand_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvand_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVANDZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a | b
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVORZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvor_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvor_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVORZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a ^ b
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVXORZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvxor_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvxor_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVXORZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a << b
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVSHLZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvshl_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvshl_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVSHLZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a >> b (logical)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVLSHRZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvlshr_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvlshr_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVLSHRZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a >> b (arithmetical)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVASHRZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvashr_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvashr_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVASHRZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a < b (unsigned)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVULTZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvult_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvult_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVULTZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a <= b (unsigned)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVULEZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvule_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvule_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVULEZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a > b (unsigned)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVUGTZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvugt_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvugt_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVUGTZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a >= b (unsigned)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVUGEZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvuge_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvuge_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVUGEZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a < b (signed)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVSLTZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvslt_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvslt_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVSLTZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a <= b (signed)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVSLEZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvsle_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvsle_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVSLEZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a > b (signed)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVSGTZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvsgt_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvsgt_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVSGTZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a >= b (signed)
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVSGEZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvsge_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of the same BitVec sort.
+#[macro_export]
+macro_rules! bvsge_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVSGEZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a ++ b
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVCONCATZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvconcat_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of BitVec sort.
+#[macro_export]
+macro_rules! bvconcat_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        BVCONCATZ3::new($ctx, $b, $c)
+    }
+}
+
+/// ~a
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVNOTZ3::new(&ctx, a)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvnot_z3!(&ctx, a)
+/// ```
+/// Requires that a is of BitVec sort.
+#[macro_export]
+macro_rules! bvnot_z3 {
+    ($ctx:expr, $b:expr) => {
+        BVNOTZ3::new($ctx, $b)
+    }
+}
+
+/// -a
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVNEGZ3::new(&ctx, a)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvneg_z3!(&ctx, a)
+/// ```
+/// Requires that a is of BitVec sort.
+#[macro_export]
+macro_rules! bvneg_z3 {
+    ($ctx:expr, $b:expr) => {
+        BVNEGZ3::new($ctx, $b)
+    }
+}
+
+/// a[high:low]
+/// 
+/// Macro rule for:
+/// ```text
+/// z3bitvectors::BVEXTRACTZ3::new(&ctx, high, low, a)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bvextract_z3!(&ctx, high, low, a)
+/// ```
+/// Requires that a is of BitVec sort.
+#[macro_export]
+macro_rules! bvextract_z3 {
+    ($ctx:expr, $h:expr, $l:expr, $b:expr) => {
+        BVEXTRACTZ3::new($ctx, $h, $l, $b)
+    }
+}
+
+#[test]
+fn test_new_bv_arith(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+
+    let x = BitVecVarZ3::new(&ctx, &bvsort, "x");
+    let y = BitVecVarZ3::new(&ctx, &bvsort, "y");
+    let bv1 = BitVecZ3::new(&ctx, &bvsort, 3);
+
+    assert_eq!("(bvadd x #x03)", ast_to_string_z3!(&ctx, BVADDZ3::new(&ctx, &x, &bv1)));
+    assert_eq!("(bvsub x y)", ast_to_string_z3!(&ctx, BVSUBZ3::new(&ctx, &x, &y)));
+    assert_eq!("(bvmul x y)", ast_to_string_z3!(&ctx, BVMULZ3::new(&ctx, &x, &y)));
+    assert_eq!("(bvudiv x #x03)", ast_to_string_z3!(&ctx, BVUDIVZ3::new(&ctx, &x, &bv1)));
+    assert_eq!("(bvsdiv x #x03)", ast_to_string_z3!(&ctx, BVSDIVZ3::new(&ctx, &x, &bv1)));
+    assert_eq!("(bvurem x y)", ast_to_string_z3!(&ctx, BVUREMZ3::new(&ctx, &x, &y)));
+    assert_eq!("(bvsrem x y)", ast_to_string_z3!(&ctx, BVSREMZ3::new(&ctx, &x, &y)));
+    assert_eq!("(bvneg x)", ast_to_string_z3!(&ctx, BVNEGZ3::new(&ctx, &x)));
+}
+
+#[test]
+fn test_new_bv_bitwise(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+
+    let x = BitVecVarZ3::new(&ctx, &bvsort, "x");
+    let mask = BitVecZ3::new(&ctx, &bvsort, 15);
+    let shift = BitVecZ3::new(&ctx, &bvsort, 4);
+
+    assert_eq!("(bvand x #x0f)", ast_to_string_z3!(&ctx, BVANDZ3::new(&ctx, &x, &mask)));
+    assert_eq!("(bvor x #x0f)", ast_to_string_z3!(&ctx, BVORZ3::new(&ctx, &x, &mask)));
+    assert_eq!("(bvxor x #x0f)", ast_to_string_z3!(&ctx, BVXORZ3::new(&ctx, &x, &mask)));
+    assert_eq!("(bvnot x)", ast_to_string_z3!(&ctx, BVNOTZ3::new(&ctx, &x)));
+    assert_eq!("(bvshl x #x04)", ast_to_string_z3!(&ctx, BVSHLZ3::new(&ctx, &x, &shift)));
+    assert_eq!("(bvlshr x #x04)", ast_to_string_z3!(&ctx, BVLSHRZ3::new(&ctx, &x, &shift)));
+    assert_eq!("(bvashr x #x04)", ast_to_string_z3!(&ctx, BVASHRZ3::new(&ctx, &x, &shift)));
+}
+
+#[test]
+fn test_new_bv_compare(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+
+    let minus_one = BitVecZ3::new(&ctx, &bvsort, -1);
+    let one = BitVecZ3::new(&ctx, &bvsort, 1);
+
+    SlvAssertZ3::new(&ctx, &slv, BVSLTZ3::new(&ctx, &minus_one, &one));
+    SlvAssertZ3::new(&ctx, &slv, BVSLEZ3::new(&ctx, &minus_one, &one));
+    SlvAssertZ3::new(&ctx, &slv, BVUGTZ3::new(&ctx, &minus_one, &one));
+    SlvAssertZ3::new(&ctx, &slv, BVUGEZ3::new(&ctx, &minus_one, &one));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    SlvAssertZ3::new(&ctx, &slv, BVULTZ3::new(&ctx, &minus_one, &one));
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+
+    assert_eq!("(bvule #xff #x01)", ast_to_string_z3!(&ctx, BVULEZ3::new(&ctx, &minus_one, &one)));
+    assert_eq!("(bvsgt #xff #x01)", ast_to_string_z3!(&ctx, BVSGTZ3::new(&ctx, &minus_one, &one)));
+    assert_eq!("(bvsge #xff #x01)", ast_to_string_z3!(&ctx, BVSGEZ3::new(&ctx, &minus_one, &one)));
+}
+
+#[test]
+fn test_new_bv_extract_concat(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+
+    let x = BitVecVarZ3::new(&ctx, &bvsort, "x");
+    let y = BitVecVarZ3::new(&ctx, &bvsort, "y");
+
+    let high = BVEXTRACTZ3::new(&ctx, 7, 4, &x);
+    let word = BVCONCATZ3::new(&ctx, &x, &y);
+
+    assert_eq!("((_ extract 7 4) x)", ast_to_string_z3!(&ctx, &high));
+    assert_eq!("(concat x y)", ast_to_string_z3!(&ctx, &word));
+    assert_eq!("(_ BitVec 4)", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, high)));
+    assert_eq!("(_ BitVec 16)", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, word)));
+}
+
+#[test]
+fn test_bv_register_mask(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+
+    let x = BitVecVarZ3::new(&ctx, &bvsort, "x");
+    let low = BVANDZ3::new(&ctx, &x, BitVecZ3::new(&ctx, &bvsort, 15));
+    let high = BVLSHRZ3::new(&ctx, &x, BitVecZ3::new(&ctx, &bvsort, 4));
+
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, low, BitVecZ3::new(&ctx, &bvsort, 5)));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, high, BitVecZ3::new(&ctx, &bvsort, 10)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!("x -> #xa5\n", model_to_string_z3!(&ctx, model));
+}
+
+#[test]
+fn test_bv_sort_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+
+    let x = BitVecVarZ3::new(&ctx, &BitVecSortZ3::new(&ctx, 8), "x");
+    let y = BitVecVarZ3::new(&ctx, &BitVecSortZ3::new(&ctx, 16), "y");
+
+    assert!(BVADDZ3::try_new(&ctx, &x, &y).is_err());
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_bv_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+
+    let x = bv_var_z3!(&ctx, 8, "x");
+    let y = bv_var_z3!(&ctx, 8, "y");
+    let mask = bv_z3!(&ctx, 8, 240);
+
+    assert_eq!("(bvadd x y)", ast_to_string_z3!(&ctx, bvadd_z3!(&ctx, &x, &y)));
+    assert_eq!("(bvand x #xf0)", ast_to_string_z3!(&ctx, bvand_z3!(&ctx, &x, &mask)));
+    assert_eq!("(bvnot x)", ast_to_string_z3!(&ctx, bvnot_z3!(&ctx, &x)));
+    assert_eq!("(bvslt x y)", ast_to_string_z3!(&ctx, bvslt_z3!(&ctx, &x, &y)));
+    assert_eq!("(bvuge x y)", ast_to_string_z3!(&ctx, bvuge_z3!(&ctx, &x, &y)));
+    assert_eq!("((_ extract 3 0) x)", ast_to_string_z3!(&ctx, bvextract_z3!(&ctx, 3, 0, &x)));
+    assert_eq!("(concat x y)", ast_to_string_z3!(&ctx, bvconcat_z3!(&ctx, x, y)));
+}
@@ -1,6 +1,10 @@
 //! Z3 context
 
-use std::ffi::{CString};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::marker::PhantomData;
 use z3_sys::*;
 use super::*;
 
@@ -8,6 +12,41 @@ pub struct ContextZ3 {
     pub r: Z3_context
 }
 
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord)]
+pub struct ErrorZ3 {
+    pub code: Z3_error_code,
+    pub msg: String
+}
+
+thread_local! {
+    // latest error reported by Z3 for each context, taken by `ContextZ3::check_error`
+    static ERRORS_Z3: RefCell<HashMap<usize, ErrorZ3>> = RefCell::new(HashMap::new());
+}
+
+/// Error handler registered in every context, records the code and message
+/// instead of letting Z3 abort the process.
+unsafe extern "C" fn error_handler_z3(ctx: Z3_context, code: Z3_error_code) {
+    let msg = CStr::from_ptr(Z3_get_error_msg(ctx, code)).to_str().unwrap_or("").to_owned();
+    ERRORS_Z3.with(|errors| {
+        errors.borrow_mut().insert(ctx as usize, ErrorZ3 {code, msg});
+    });
+}
+
+/// Handle to stop the checks running in a context from another thread.
+///
+/// The handle borrows the context, so it can be moved into a scoped thread
+/// (see `crossbeam::scope`) but can't outlive the context.
+#[derive(Debug, Clone, Copy)]
+pub struct InterruptHandleZ3<'ctx> {
+    r: Z3_context,
+    ctx: PhantomData<&'ctx ContextZ3>
+}
+
+// Z3_interrupt is the only function of the Z3 API that may be called concurrently
+// with the thread using the context, and it is the only one the handle exposes
+unsafe impl <'ctx> Send for InterruptHandleZ3<'ctx> {}
+unsafe impl <'ctx> Sync for InterruptHandleZ3<'ctx> {}
+
 pub struct UpdateParamZ3<'ctx, 'p, 'v> {
     pub ctx: &'ctx ContextZ3,
     pub param: &'p str,
@@ -24,17 +63,65 @@ impl ContextZ3 {
     /// 
     /// All main interaction with Z3 happens in the context of a `Z3_context`.
     /// 
+    /// NOTE: The context is reference counted, an object returned by Z3 is only
+    /// valid until the next call unless a wrapper (`Ast`, `Sort`, `Model`, ...) owns it.
+    /// 
     /// NOTE: See macro! `ctx_z3!`
     pub fn new(cfg: &ConfigZ3) -> ContextZ3 {
         ContextZ3 {
             r: unsafe {
-                let ctx = Z3_mk_context(cfg.r);
+                let ctx = Z3_mk_context_rc(cfg.r);
+                Z3_set_error_handler(ctx, Some(error_handler_z3));
                 ctx
             }
         }
     }
+
+    /// Take the error recorded by the error handler since the last check, if any.
+    ///
+    /// If more calls failed since then, the error of the latest one is returned.
+    pub fn check_error(&self) -> Result<(), ErrorZ3> {
+        match ERRORS_Z3.with(|errors| errors.borrow_mut().remove(&(self.r as usize))) {
+            Some(err) => Err(err),
+            None => Ok(())
+        }
+    }
+
+    /// Check the call that returned `s` and copy the string, Z3 returns null when the call fails.
+    pub(crate) fn check_string(&self, s: Z3_string) -> Result<String, ErrorZ3> {
+        self.check_error()?;
+        if s.is_null() {
+            return Err(ErrorZ3 {code: Z3_error_code_Z3_EXCEPTION, msg: "No string returned.".to_string()})
+        }
+        Ok(unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned())
+    }
 }
 
+impl <'ctx> InterruptHandleZ3<'ctx> {
+    /// Create a handle to interrupt the context.
+    ///
+    /// NOTE: See macro! `interrupt_handle_z3!`
+    pub fn new(ctx: &'ctx ContextZ3) -> InterruptHandleZ3<'ctx> {
+        InterruptHandleZ3 {r: ctx.r, ctx: PhantomData}
+    }
+
+    /// Interrupt the execution of a Z3 procedure in the context, a running
+    /// check then returns `SatResultZ3::Unknown("interrupted")`.
+    pub fn interrupt(&self) -> () {
+        unsafe {
+            Z3_interrupt(self.r)
+        }
+    }
+}
+
+impl fmt::Display for ErrorZ3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Error Z3 {}: {}.", self.code, self.msg)
+    }
+}
+
+impl std::error::Error for ErrorZ3 {}
+
 impl <'ctx, 'p, 'v> UpdateParamZ3<'ctx, 'p, 'v> {
     /// Update a value of a context parameter. 
     /// 
@@ -58,6 +145,7 @@ impl Default for ContextZ3 {
 impl Drop for ContextZ3 {
     /// Delete the given logical context.
     fn drop(&mut self) {
+        ERRORS_Z3.with(|errors| errors.borrow_mut().remove(&(self.r as usize)));
         unsafe {
             Z3_del_context(self.r)
         }
@@ -72,6 +160,14 @@ macro_rules! ctx_z3 {
     }
 }
 
+/// create a handle to interrupt a context from another thread
+#[macro_export]
+macro_rules! interrupt_handle_z3 {
+    ($a:expr) => {
+        InterruptHandleZ3::new($a)
+    }
+}
+
 /// update a value of a context parameter
 #[macro_export]
 macro_rules! update_param_z3 {
@@ -91,6 +187,25 @@ fn test_default_ctx(){
     ContextZ3::default();
 }
 
+#[test]
+fn test_ctx_check_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_ctx_check_error_latest(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    unsafe {
+        Z3_mk_bv_sort(ctx.r, 0);
+        Z3_get_numeral_string(ctx.r, Z3_mk_true(ctx.r));
+    }
+    assert_eq!(Z3_error_code_Z3_INVALID_ARG, ctx.check_error().unwrap_err().code);
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
 #[test]
 fn test_update_param(){
     let cfg = ConfigZ3::new();
@@ -0,0 +1,277 @@
+//! Z3 model evaluation for SP
+
+use std::ffi::CStr;
+use std::fmt;
+use z3_sys::*;
+use super::*;
+
+/// Value of a term in a model.
+///
+/// Bit-vectors are read as unsigned integers. Numbers that don't fit in an
+/// `i64` are kept as their decimal digits. Terms that are not values, i.e.
+/// unconstrained constants evaluated without model completion or irrational
+/// reals, are kept as `Other` with their printed form.
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
+pub enum ModelValueZ3 {
+    Bool(bool),
+    Int(i64),
+    BigInt(String),
+    Rational(i64, i64),
+    BigRational(String, String),
+    String(String),
+    Enum(String),
+    Other(String)
+}
+
+pub struct ModelEvalZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub model: Z3_model,
+    pub ast: Z3_ast,
+    pub completion: bool,
+    pub r: ModelValueZ3
+}
+
+impl ModelValueZ3 {
+    /// The value as a bool, if it is one.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            ModelValueZ3::Bool(x) => Some(*x),
+            _ => None
+        }
+    }
+
+    /// The value as an i64, if it is an integer or an integral rational that fits.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            ModelValueZ3::Int(x) => Some(*x),
+            ModelValueZ3::Rational(x, 1) => Some(*x),
+            _ => None
+        }
+    }
+
+    /// The value as a double, if it is a number. Big numbers lose precision.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            ModelValueZ3::Int(x) => Some(*x as f64),
+            ModelValueZ3::BigInt(x) => x.parse::<f64>().ok(),
+            ModelValueZ3::Rational(n, d) => Some(*n as f64 / *d as f64),
+            ModelValueZ3::BigRational(n, d) => Some(n.parse::<f64>().ok()? / d.parse::<f64>().ok()?),
+            _ => None
+        }
+    }
+
+    /// The value as a str, if it is a string or the name of an enum constant.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ModelValueZ3::String(x) | ModelValueZ3::Enum(x) => Some(x),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for ModelValueZ3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ModelValueZ3::Bool(x) => write!(f, "{}", x),
+            ModelValueZ3::Int(x) => write!(f, "{}", x),
+            ModelValueZ3::BigInt(x) => write!(f, "{}", x),
+            ModelValueZ3::Rational(n, d) => write!(f, "{}/{}", n, d),
+            ModelValueZ3::BigRational(n, d) => write!(f, "{}/{}", n, d),
+            ModelValueZ3::String(x) => write!(f, "{}", x),
+            ModelValueZ3::Enum(x) => write!(f, "{}", x),
+            ModelValueZ3::Other(x) => write!(f, "{}", x)
+        }
+    }
+}
+
+// an integral numeral as an i64, or its digits if it doesn't fit
+unsafe fn numeral_to_int_z3(ctx: &ContextZ3, num: Z3_ast) -> Result<i64, String> {
+    let mut i: i64 = 0;
+    match Z3_get_numeral_int64(ctx.r, num, &mut i) {
+        true => Ok(i),
+        false => Err(CStr::from_ptr(Z3_get_numeral_string(ctx.r, num)).to_str().unwrap().to_owned())
+    }
+}
+
+// read an evaluated term by its sort, anything that is not a value is printed
+pub(crate) fn ast_to_value_z3(ctx: &ContextZ3, value: &Ast) -> Result<ModelValueZ3, ErrorZ3> {
+    let z3 = unsafe {
+        let kind = Z3_get_sort_kind(ctx.r, Z3_get_sort(ctx.r, value.r));
+        if kind == Z3_sort_kind_Z3_BOOL_SORT && Z3_get_bool_value(ctx.r, value.r) != 0 {
+            ModelValueZ3::Bool(Z3_get_bool_value(ctx.r, value.r) == 1)
+        } else if (kind == Z3_sort_kind_Z3_INT_SORT || kind == Z3_sort_kind_Z3_BV_SORT) && Z3_is_numeral_ast(ctx.r, value.r) {
+            match numeral_to_int_z3(ctx, value.r) {
+                Ok(i) => ModelValueZ3::Int(i),
+                Err(s) => ModelValueZ3::BigInt(s)
+            }
+        } else if kind == Z3_sort_kind_Z3_REAL_SORT && Z3_is_numeral_ast(ctx.r, value.r) {
+            let num = Ast::new(ctx, Z3_get_numerator(ctx.r, value.r));
+            let den = Ast::new(ctx, Z3_get_denominator(ctx.r, value.r));
+            match (numeral_to_int_z3(ctx, num.r), numeral_to_int_z3(ctx, den.r)) {
+                (Ok(n), Ok(d)) => ModelValueZ3::Rational(n, d),
+                _ => ModelValueZ3::BigRational(
+                    CStr::from_ptr(Z3_get_numeral_string(ctx.r, num.r)).to_str().unwrap().to_owned(),
+                    CStr::from_ptr(Z3_get_numeral_string(ctx.r, den.r)).to_str().unwrap().to_owned())
+            }
+        } else if Z3_is_string(ctx.r, value.r) {
+            ModelValueZ3::String(CStr::from_ptr(Z3_get_string(ctx.r, value.r)).to_str().unwrap().to_owned())
+        } else if kind == Z3_sort_kind_Z3_DATATYPE_SORT && Z3_is_app(ctx.r, value.r)
+            && Z3_get_decl_kind(ctx.r, Z3_get_app_decl(ctx.r, Z3_to_app(ctx.r, value.r))) == Z3_decl_kind_Z3_OP_DT_CONSTRUCTOR
+            && Z3_get_app_num_args(ctx.r, Z3_to_app(ctx.r, value.r)) == 0 {
+            let decl = Z3_get_app_decl(ctx.r, Z3_to_app(ctx.r, value.r));
+            ModelValueZ3::Enum(Z3StringToStringZ3::new(Z3_get_symbol_string(ctx.r, Z3_get_decl_name(ctx.r, decl))))
+        } else {
+            ModelValueZ3::Other(AstToStringZ3::new(ctx, value))
+        }
+    };
+    ctx.check_error()?;
+    Ok(z3)
+}
+
+// evaluate a term in a model to a term, the result is only set if the evaluation succeeded
+pub(crate) fn model_eval_ast_z3<'ctx>(ctx: &'ctx ContextZ3, model: Z3_model, ast: Z3_ast, completion: bool) -> Result<Ast<'ctx>, ErrorZ3> {
+    let mut value: Z3_ast = std::ptr::null_mut();
+    let ok = unsafe {
+        Z3_model_eval(ctx.r, model, ast, completion, &mut value)
+    };
+    ctx.check_error()?;
+    if ok == 0 || value.is_null() {
+        return Err(ErrorZ3 {code: Z3_error_code_Z3_INVALID_ARG, msg: "Failed to evaluate the term in the model.".to_string()})
+    }
+    Ok(Ast::new(ctx, value))
+}
+
+impl <'ctx> ModelEvalZ3<'ctx> {
+    /// Evaluate the term `ast` in `model` and read the result as a typed value.
+    ///
+    /// With `completion`, constants that the model doesn't assign get a default
+    /// value, i.e. false, 0 or the first enum constant. Without it, they stay
+    /// uninterpreted and are returned as `ModelValueZ3::Other`.
+    ///
+    /// NOTE: See macro! `model_eval_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, model: impl AsModelZ3, ast: impl AsAstZ3<'ctx>, completion: bool) -> ModelValueZ3 {
+        match ModelEvalZ3::try_new(ctx, model, ast, completion) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, model: impl AsModelZ3, ast: impl AsAstZ3<'ctx>, completion: bool) -> Result<ModelValueZ3, ErrorZ3> {
+        let model = model.as_model();
+        let ast = ast.as_ast();
+        let z3 = ast_to_value_z3(ctx, &model_eval_ast_z3(ctx, model, ast, completion)?)?;
+        Ok(ModelEvalZ3 {ctx, model, ast, completion, r: z3}.r)
+    }
+}
+
+/// evaluate a term in a model, with model completion unless specified
+#[macro_export]
+macro_rules! model_eval_z3 {
+    ($ctx:expr, $a:expr, $b:expr) => {
+        ModelEvalZ3::new($ctx, $a, $b, true)
+    };
+    ($ctx:expr, $a:expr, $b:expr, $c:expr) => {
+        ModelEvalZ3::new($ctx, $a, $b, $c)
+    }
+}
+
+#[test]
+fn test_model_eval_bool_int(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let b = BoolVarZ3::new(&ctx, &boolsort, "b");
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, -7)));
+    SlvAssertZ3::new(&ctx, &slv, NOTZ3::new(&ctx, &b));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!(ModelValueZ3::Int(-7), ModelEvalZ3::new(&ctx, &model, &x, true));
+    assert_eq!(ModelValueZ3::Bool(false), ModelEvalZ3::new(&ctx, &model, &b, true));
+    assert_eq!(ModelValueZ3::Int(-14), ModelEvalZ3::new(&ctx, &model, ADDZ3::new(&ctx, vec!(&x, &x)), true));
+    assert_eq!(Some(-7), ModelEvalZ3::new(&ctx, &model, &x, true).as_i64());
+}
+
+#[test]
+fn test_model_eval_completion(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let y = IntVarZ3::new(&ctx, &intsort, "y");
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 1)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!(ModelValueZ3::Other("y".to_string()), ModelEvalZ3::new(&ctx, &model, &y, false));
+    assert_eq!(ModelValueZ3::Int(0), ModelEvalZ3::new(&ctx, &model, &y, true));
+}
+
+#[test]
+fn test_model_eval_big_int_and_real(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let realsort = RealSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let r = RealVarZ3::new(&ctx, &realsort, "r");
+    let big = MULZ3::new(&ctx, vec!(IntZ3::new(&ctx, &intsort, 2000000000), IntZ3::new(&ctx, &intsort, 2000000000),
+        IntZ3::new(&ctx, &intsort, 2000000000)));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &x, big));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, MULZ3::new(&ctx, vec!(&r, &RealZ3::new(&ctx, &realsort, 3.0))),
+        RealZ3::new(&ctx, &realsort, 1.0)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!(ModelValueZ3::BigInt("8000000000000000000000000000".to_string()), ModelEvalZ3::new(&ctx, &model, &x, true));
+    assert_eq!(ModelValueZ3::Rational(1, 3), ModelEvalZ3::new(&ctx, &model, &r, true));
+    assert_eq!("1/3", format!("{}", ModelEvalZ3::new(&ctx, &model, &r, true)));
+}
+
+#[test]
+fn test_model_eval_string_enum_bv(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let stringsort = StringSortZ3::new(&ctx);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("red", "blue"));
+
+    let s = StringVarZ3::new(&ctx, &stringsort, "s");
+    let v = BitVecVarZ3::new(&ctx, &bvsort, "v");
+    let b = EnumVarZ3::new(&ctx, &balls, "b");
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &s, StringZ3::new(&ctx, "hello")));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &v, BitVecZ3::new(&ctx, &bvsort, 200)));
+    SlvAssertZ3::new(&ctx, &slv, NOTZ3::new(&ctx, EQZ3::new(&ctx, &b, &balls.enum_asts[0])));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!(ModelValueZ3::String("hello".to_string()), ModelEvalZ3::new(&ctx, &model, &s, true));
+    assert_eq!(ModelValueZ3::Int(200), ModelEvalZ3::new(&ctx, &model, &v, true));
+    assert_eq!(ModelValueZ3::Enum("blue".to_string()), ModelEvalZ3::new(&ctx, &model, &b, true));
+    assert_eq!(Some("blue"), ModelEvalZ3::new(&ctx, &model, &b, true).as_str());
+}
+
+#[test]
+fn test_model_eval_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    let x = int_var_z3!(&ctx, "x");
+    let y = int_var_z3!(&ctx, "y");
+    slv_assert_z3!(&ctx, &slv, eq_z3!(&ctx, &x, int_z3!(&ctx, 3)));
+    slv_check_z3!(&ctx, &slv);
+
+    let model = slv_get_model_z3!(&ctx, &slv);
+    assert_eq!(ModelValueZ3::Int(3), model_eval_z3!(&ctx, &model, &x));
+    assert_eq!(ModelValueZ3::Other("y".to_string()), model_eval_z3!(&ctx, &model, &y, false));
+}
@@ -0,0 +1,366 @@
+//! Z3 uninterpreted functions for SP
+
+use std::collections::HashMap;
+use std::ffi::CString;
+use z3_sys::*;
+use super::*;
+use crate::z3eval::ast_to_value_z3;
+
+pub struct FuncDeclZ3<'ctx, 'a> {
+    pub ctx: &'ctx ContextZ3,
+    pub name: &'a str,
+    pub domain: Vec<Z3_sort>,
+    pub range: Z3_sort,
+    pub r: FuncDecl<'ctx>
+}
+
+pub struct AppZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub decl: Z3_func_decl,
+    pub args: Vec<Z3_ast>,
+    pub r: Ast<'ctx>
+}
+
+pub struct GetAppDeclZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub app: Z3_ast,
+    pub r: FuncDecl<'ctx>
+}
+
+/// Interpretation of a function in a model as a finite table of typed values, and
+/// a default (else) term over the arguments for everything that is not in the table.
+#[derive(Clone)]
+pub struct FuncInterp<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub model: Model<'ctx>,
+    pub entries: HashMap<Vec<ModelValueZ3>, ModelValueZ3>,
+    pub default: Option<Ast<'ctx>>
+}
+
+pub struct ModelGetFuncInterpZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub model: Z3_model,
+    pub decl: Z3_func_decl,
+    pub r: FuncInterp<'ctx>
+}
+
+impl <'ctx, 'a> FuncDeclZ3<'ctx, 'a> {
+    /// Declare an uninterpreted function `name: domain[0] x ... x domain[n-1] -> range`.
+    ///
+    /// The `domain` is a rust vector of sorts, i.e. `&intsort`, `&boolsort`, `&enumsort`...
+    /// An empty domain declares a constant.
+    ///
+    /// NOTE: See macro! `func_decl_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, name: &'a str, domain: Vec<impl AsSortZ3>, range: impl AsSortZ3) -> FuncDecl<'ctx> {
+        match FuncDeclZ3::try_new(ctx, name, domain, range) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, name: &'a str, domain: Vec<impl AsSortZ3>, range: impl AsSortZ3) -> Result<FuncDecl<'ctx>, ErrorZ3> {
+        let domain: Vec<Z3_sort> = domain.iter().map(|x| x.as_sort()).collect();
+        let range = range.as_sort();
+        let str_name = CString::new(name).unwrap();
+        let z3 = unsafe {
+            Z3_mk_func_decl(ctx.r, Z3_mk_string_symbol(ctx.r, str_name.as_ptr()),
+                domain.len() as u32, domain.as_ptr(), range)
+        };
+        ctx.check_error()?;
+        Ok(FuncDeclZ3 {ctx, name, domain, range, r: FuncDecl::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> AppZ3<'ctx> {
+    /// Apply the function `decl` to the arguments `args`.
+    ///
+    /// The number and sorts of `args` must match the domain of `decl`.
+    /// The sort of the result is the range of `decl`.
+    ///
+    /// NOTE: See macro! `app_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, decl: impl AsFuncDeclZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Ast<'ctx> {
+        match AppZ3::try_new(ctx, decl, args) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, decl: impl AsFuncDeclZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let decl = decl.as_func_decl();
+        let args: Vec<Z3_ast> = args.iter().map(|x| x.as_ast()).collect();
+        let z3 = unsafe {
+            Z3_mk_app(ctx.r, decl, args.len() as u32, args.as_ptr())
+        };
+        ctx.check_error()?;
+        Ok(AppZ3 {ctx, decl, args, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> GetAppDeclZ3<'ctx> {
+    /// Get the declaration of a function application, i.e. the declaration of a variable.
+    ///
+    /// NOTE: See macro! `get_app_decl_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, app: impl AsAstZ3<'ctx>) -> FuncDecl<'ctx> {
+        match GetAppDeclZ3::try_new(ctx, app) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, app: impl AsAstZ3<'ctx>) -> Result<FuncDecl<'ctx>, ErrorZ3> {
+        let app = app.as_ast();
+        let z3 = unsafe {
+            Z3_get_app_decl(ctx.r, Z3_to_app(ctx.r, app))
+        };
+        ctx.check_error()?;
+        Ok(GetAppDeclZ3 {ctx, app, r: FuncDecl::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> FuncInterp<'ctx> {
+    /// The value of the function for `args`. If there is no entry for the values of
+    /// the arguments, `args` are substituted for the variables of the default term,
+    /// which is then evaluated in the model.
+    ///
+    /// Returns `None` if the model does not assign an interpretation to the function.
+    pub fn value(&self, args: Vec<impl AsAstZ3<'ctx>>) -> Result<Option<ModelValueZ3>, ErrorZ3> {
+        let mut key = vec!();
+        for arg in &args {
+            key.push(ModelEvalZ3::try_new(self.ctx, &self.model, arg, true)?);
+        }
+        if let Some(x) = self.entries.get(&key) {
+            return Ok(Some(x.clone()))
+        }
+        match &self.default {
+            Some(default) => {
+                let args: Vec<Z3_ast> = args.iter().map(|x| x.as_ast()).collect();
+                let z3 = unsafe {
+                    Z3_substitute_vars(self.ctx.r, default.r, args.len() as u32, args.as_ptr())
+                };
+                self.ctx.check_error()?;
+                let z3 = Ast::new(self.ctx, z3);
+                Ok(Some(ModelEvalZ3::try_new(self.ctx, &self.model, &z3, true)?))
+            },
+            None => Ok(None)
+        }
+    }
+}
+
+impl <'ctx> ModelGetFuncInterpZ3<'ctx> {
+    /// Read the interpretation of a function in a model into a table from arguments to values.
+    ///
+    /// Arguments that are not in the table map to the `default` (else) term, for a
+    /// constant the default is its value. If the model does not assign an
+    /// interpretation to `decl`, the table is empty and there is no default.
+    ///
+    /// NOTE: See macro! `model_get_func_interp_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, model: impl AsModelZ3, decl: impl AsFuncDeclZ3) -> FuncInterp<'ctx> {
+        match ModelGetFuncInterpZ3::try_new(ctx, model, decl) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, model: impl AsModelZ3, decl: impl AsFuncDeclZ3) -> Result<FuncInterp<'ctx>, ErrorZ3> {
+        let model = model.as_model();
+        let decl = decl.as_func_decl();
+
+        let value = |ast: Z3_ast| ast_to_value_z3(ctx, &Ast::new(ctx, ast));
+
+        let mut interp = FuncInterp {ctx, model: Model::new(ctx, model), entries: HashMap::new(), default: None};
+        unsafe {
+            if Z3_get_arity(ctx.r, decl) == 0 {
+                let default = Z3_model_get_const_interp(ctx.r, model, decl);
+                if !default.is_null() {
+                    interp.default = Some(Ast::new(ctx, default));
+                }
+            } else {
+                let finterp = Z3_model_get_func_interp(ctx.r, model, decl);
+                if !finterp.is_null() {
+                    Z3_func_interp_inc_ref(ctx.r, finterp);
+                    for i in 0..Z3_func_interp_get_num_entries(ctx.r, finterp) {
+                        let entry = Z3_func_interp_get_entry(ctx.r, finterp, i);
+                        Z3_func_entry_inc_ref(ctx.r, entry);
+                        let args: Result<Vec<ModelValueZ3>, ErrorZ3> = (0..Z3_func_entry_get_num_args(ctx.r, entry))
+                            .map(|j| value(Z3_func_entry_get_arg(ctx.r, entry, j)))
+                            .collect();
+                        let stored = value(Z3_func_entry_get_value(ctx.r, entry));
+                        Z3_func_entry_dec_ref(ctx.r, entry);
+                        interp.entries.insert(args?, stored?);
+                    }
+                    interp.default = Some(Ast::new(ctx, Z3_func_interp_get_else(ctx.r, finterp)));
+                    Z3_func_interp_dec_ref(ctx.r, finterp);
+                }
+            }
+        }
+        ctx.check_error()?;
+        Ok(ModelGetFuncInterpZ3 {ctx, model, decl, r: interp}.r)
+    }
+}
+
+/// declare function name: domain -> range
+///
+/// Macro rule for:
+/// ```text
+/// z3functions::FuncDeclZ3::new(&ctx, name, vec!(a, b), range)
+/// ```
+/// Using a specific context:
+/// ```text
+/// func_decl_z3!(&ctx, name, vec!(a, b), range)
+/// ```
+#[macro_export]
+macro_rules! func_decl_z3 {
+    ($ctx:expr, $n:expr, $d:expr, $r:expr) => {
+        FuncDeclZ3::new($ctx, $n, $d, $r)
+    }
+}
+
+/// f(a, b, c...)
+///
+/// Macro rule for:
+/// ```text
+/// z3functions::AppZ3::new(&ctx, f, vec!(a, b, c))
+/// ```
+/// Using a specific context and passing elements:
+/// ```text
+/// app_z3!(&ctx, f, a, b, c)
+/// ```
+#[macro_export]
+macro_rules! app_z3 {
+    ( $ctx:expr, $f:expr, $( $x:expr ),* ) => {
+        {
+            let mut temp_vec = Vec::new();
+            $(
+                temp_vec.push($crate::Ast::new($ctx, $crate::AsAstZ3::as_ast(&$x)));
+            )*
+            AppZ3::new($ctx, $f, temp_vec)
+        }
+    };
+}
+
+/// declaration of a function application
+#[macro_export]
+macro_rules! get_app_decl_z3 {
+    ($ctx:expr, $a:expr) => {
+        GetAppDeclZ3::new($ctx, $a)
+    }
+}
+
+/// read a function interpretation from a model
+#[macro_export]
+macro_rules! model_get_func_interp_z3 {
+    ($ctx:expr, $m:expr, $f:expr) => {
+        ModelGetFuncInterpZ3::new($ctx, $m, $f)
+    }
+}
+
+#[test]
+fn test_new_func_decl_and_app(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let f = FuncDeclZ3::new(&ctx, "f", vec!(&intsort, &intsort), &boolsort);
+    let app = AppZ3::new(&ctx, &f, vec!(IntZ3::new(&ctx, &intsort, 1), IntVarZ3::new(&ctx, &intsort, "x")));
+
+    assert_eq!("(declare-fun f (Int Int) Bool)", format!("{}", f));
+    assert_eq!("(f 1 x)", ast_to_string_z3!(&ctx, &app));
+    assert_eq!("Bool", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, app)));
+}
+
+#[test]
+fn test_app_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let f = FuncDeclZ3::new(&ctx, "f", vec!(&intsort), &intsort);
+
+    assert!(AppZ3::try_new(&ctx, &f, vec!(BoolZ3::new(&ctx, true))).is_err());
+    assert!(AppZ3::try_new(&ctx, &f, Vec::<Ast>::new()).is_err());
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_func_interp_relation(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("ball1", "ball2"));
+    let rooms = EnumSortZ3::new(&ctx, "rooms", vec!("room_a", "room_b"));
+
+    let at = FuncDeclZ3::new(&ctx, "at", vec!(&balls, &rooms), &boolsort);
+    SlvAssertZ3::new(&ctx, &slv, AppZ3::new(&ctx, &at, vec!(&balls.enum_asts[0], &rooms.enum_asts[0])));
+    SlvAssertZ3::new(&ctx, &slv, NOTZ3::new(&ctx, AppZ3::new(&ctx, &at, vec!(&balls.enum_asts[0], &rooms.enum_asts[1]))));
+    SlvAssertZ3::new(&ctx, &slv, AppZ3::new(&ctx, &at, vec!(&balls.enum_asts[1], &rooms.enum_asts[1])));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let interp = ModelGetFuncInterpZ3::new(&ctx, &model, &at);
+
+    assert_eq!(Some(ModelValueZ3::Bool(true)), interp.value(vec!(&balls.enum_asts[0], &rooms.enum_asts[0])).unwrap());
+    assert_eq!(Some(ModelValueZ3::Bool(false)), interp.value(vec!(&balls.enum_asts[0], &rooms.enum_asts[1])).unwrap());
+    assert_eq!(Some(ModelValueZ3::Bool(true)), interp.value(vec!(&balls.enum_asts[1], &rooms.enum_asts[1])).unwrap());
+}
+
+#[test]
+fn test_func_interp_constant(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let c = FuncDeclZ3::new(&ctx, "c", Vec::<Sort>::new(), &intsort);
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, AppZ3::new(&ctx, &c, Vec::<Ast>::new()), IntZ3::new(&ctx, &intsort, 3)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let interp = ModelGetFuncInterpZ3::new(&ctx, &model, &c);
+
+    assert!(interp.entries.is_empty());
+    assert_eq!(Some(ModelValueZ3::Int(3)), interp.value(Vec::<Ast>::new()).unwrap());
+}
+
+#[test]
+fn test_func_interp_default_term(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let f = FuncDeclZ3::new(&ctx, "f", vec!(&intsort), &intsort);
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let succ = EQZ3::new(&ctx, AppZ3::new(&ctx, &f, vec!(&x)), ADDZ3::new(&ctx, vec!(&x, &IntZ3::new(&ctx, &intsort, 1))));
+    SlvAssertZ3::new(&ctx, &slv, ForallZ3::new(&ctx, vec!(&x), succ));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let interp = ModelGetFuncInterpZ3::new(&ctx, &model, &f);
+
+    assert!(interp.default.is_some());
+    assert_eq!(Some(ModelValueZ3::Int(6)), interp.value(vec!(IntZ3::new(&ctx, &intsort, 5))).unwrap());
+    assert_eq!(Some(ModelValueZ3::Int(-41)), interp.value(vec!(IntZ3::new(&ctx, &intsort, -42))).unwrap());
+}
+
+#[test]
+fn test_func_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let f = func_decl_z3!(&ctx, "f", vec!(&intsort), &intsort);
+    let x = int_var_z3!(&ctx, "x");
+    slv_assert_z3!(&ctx, &slv, eq_z3!(&ctx, app_z3!(&ctx, &f, int_z3!(&ctx, 1)), int_z3!(&ctx, 10)));
+    slv_assert_z3!(&ctx, &slv, eq_z3!(&ctx, app_z3!(&ctx, &f, &x), int_z3!(&ctx, 20)));
+    slv_assert_z3!(&ctx, &slv, eq_z3!(&ctx, &x, int_z3!(&ctx, 2)));
+    assert_eq!(SatResultZ3::Sat, slv_check_z3!(&ctx, &slv));
+
+    let model = slv_get_model_z3!(&ctx, &slv);
+    let interp = model_get_func_interp_z3!(&ctx, &model, &f);
+
+    assert_eq!(Some(ModelValueZ3::Int(10)), interp.value(vec!(int_z3!(&ctx, 1))).unwrap());
+    assert_eq!(Some(ModelValueZ3::Int(20)), interp.value(vec!(int_z3!(&ctx, 2))).unwrap());
+}
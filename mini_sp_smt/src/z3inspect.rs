@@ -0,0 +1,442 @@
+//! Z3 simplifier and AST inspection for SP
+
+use std::collections::HashMap;
+use std::ffi::CString;
+use z3_sys::*;
+use super::*;
+use crate::z3eval::ast_to_value_z3;
+
+/// Kind of a quantifier node.
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum QuantifierKindZ3 {
+    Forall,
+    Exists,
+    Lambda
+}
+
+/// One level of an AST, the children are left as ASTs to be decomposed further.
+///
+/// Constants, including `true` and `false`, are applications without arguments.
+/// Numerals of int, real and bit-vector sort are read like model values.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AstNodeZ3<'ctx> {
+    Numeral(ModelValueZ3),
+    App {
+        kind: Z3_decl_kind,
+        name: String,
+        args: Vec<Ast<'ctx>>
+    },
+    Var(u32),
+    Quantifier {
+        kind: QuantifierKindZ3,
+        bound: Vec<String>,
+        body: Ast<'ctx>
+    }
+}
+
+/// Fold over an AST bottom up, see `FoldAstZ3::new`. Every node gets the
+/// results of its children.
+pub trait AstFoldZ3<'ctx> {
+    type Result;
+
+    fn numeral(&mut self, ast: &Ast<'ctx>, value: ModelValueZ3) -> Self::Result;
+    fn app(&mut self, ast: &Ast<'ctx>, kind: Z3_decl_kind, name: &str, args: Vec<Self::Result>) -> Self::Result;
+    fn var(&mut self, ast: &Ast<'ctx>, index: u32) -> Self::Result;
+    fn quantifier(&mut self, ast: &Ast<'ctx>, kind: QuantifierKindZ3, bound: Vec<String>, body: Self::Result) -> Self::Result;
+}
+
+pub struct SimplifyZ3<'ctx, 'p> {
+    pub ctx: &'ctx ContextZ3,
+    pub ast: Z3_ast,
+    pub params: Vec<(&'p str, &'p str)>,
+    pub r: Ast<'ctx>
+}
+
+pub struct AstDecomposeZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub ast: Z3_ast,
+    pub r: AstNodeZ3<'ctx>
+}
+
+pub struct FoldAstZ3<'ctx, R> {
+    pub ctx: &'ctx ContextZ3,
+    pub ast: Z3_ast,
+    pub r: R
+}
+
+// set the simplifier parameters, a value is parsed by the kind of its parameter
+unsafe fn simplify_set_params_z3(ctx: &ContextZ3, descrs: Z3_param_descrs, z3_params: Z3_params, params: &[(&str, &str)]) -> Result<(), ErrorZ3> {
+    for (name, value) in params {
+        let str_name = CString::new(*name).unwrap();
+        let key = Z3_mk_string_symbol(ctx.r, str_name.as_ptr());
+        let kind = Z3_param_descrs_get_kind(ctx.r, descrs, key);
+        let invalid = || ErrorZ3 {
+            code: Z3_error_code_Z3_INVALID_ARG, 
+            msg: format!("Invalid value {} for the simplifier parameter {}.", value, name)
+        };
+        match kind {
+            k if k == Z3_param_kind_Z3_PK_BOOL => Z3_params_set_bool(ctx.r, z3_params, key, value.parse::<bool>().map_err(|_| invalid())?),
+            k if k == Z3_param_kind_Z3_PK_UINT => Z3_params_set_uint(ctx.r, z3_params, key, value.parse::<u32>().map_err(|_| invalid())?),
+            k if k == Z3_param_kind_Z3_PK_DOUBLE => Z3_params_set_double(ctx.r, z3_params, key, value.parse::<f64>().map_err(|_| invalid())?),
+            k if k == Z3_param_kind_Z3_PK_SYMBOL || k == Z3_param_kind_Z3_PK_STRING => {
+                let str_value = CString::new(*value).map_err(|_| invalid())?;
+                Z3_params_set_symbol(ctx.r, z3_params, key, Z3_mk_string_symbol(ctx.r, str_value.as_ptr()))
+            },
+            _ => return Err(ErrorZ3 {code: Z3_error_code_Z3_INVALID_ARG, msg: format!("Unknown simplifier parameter {}.", name)})
+        }
+        ctx.check_error()?;
+    }
+    Ok(())
+}
+
+impl <'ctx, 'p> SimplifyZ3<'ctx, 'p> {
+    /// Simplify the term `ast` with the simplifier `params`, i.e. `("som", "true")`
+    /// to write polynomials as sums of monomials or `("elim_and", "true")`.
+    /// Values are passed as strings, like for `SetParamZ3`, and are read by the
+    /// type the simplifier declares for the parameter.
+    ///
+    /// NOTE: See macro! `simplify_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, ast: impl AsAstZ3<'ctx>, params: Vec<(&'p str, &'p str)>) -> Ast<'ctx> {
+        match SimplifyZ3::try_new(ctx, ast, params) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, ast: impl AsAstZ3<'ctx>, params: Vec<(&'p str, &'p str)>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let ast = ast.as_ast();
+        let z3 = unsafe {
+            let descrs = Z3_simplify_get_param_descrs(ctx.r);
+            Z3_param_descrs_inc_ref(ctx.r, descrs);
+            let z3_params = Z3_mk_params(ctx.r);
+            Z3_params_inc_ref(ctx.r, z3_params);
+            let set = simplify_set_params_z3(ctx, descrs, z3_params, &params);
+            let simplified = match set {
+                Ok(()) => Z3_simplify_ex(ctx.r, ast, z3_params),
+                Err(_) => std::ptr::null_mut()
+            };
+            Z3_params_dec_ref(ctx.r, z3_params);
+            Z3_param_descrs_dec_ref(ctx.r, descrs);
+            set?;
+            simplified
+        };
+        ctx.check_error()?;
+        Ok(SimplifyZ3 {ctx, ast, params, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> AstDecomposeZ3<'ctx> {
+    /// Decompose the top level of the term `ast` into an `AstNodeZ3`.
+    ///
+    /// NOTE: See macro! `ast_decompose_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, ast: impl AsAstZ3<'ctx>) -> AstNodeZ3<'ctx> {
+        match AstDecomposeZ3::try_new(ctx, ast) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, ast: impl AsAstZ3<'ctx>) -> Result<AstNodeZ3<'ctx>, ErrorZ3> {
+        let ast = ast.as_ast();
+        let z3 = unsafe {
+            let kind = Z3_get_ast_kind(ctx.r, ast);
+            if kind == Z3_ast_kind_Z3_NUMERAL_AST {
+                AstNodeZ3::Numeral(ast_to_value_z3(ctx, &Ast::new(ctx, ast))?)
+            } else if kind == Z3_ast_kind_Z3_APP_AST {
+                let app = Z3_to_app(ctx.r, ast);
+                let decl = Z3_get_app_decl(ctx.r, app);
+                AstNodeZ3::App {
+                    kind: Z3_get_decl_kind(ctx.r, decl),
+                    name: Z3StringToStringZ3::new(Z3_get_symbol_string(ctx.r, Z3_get_decl_name(ctx.r, decl))),
+                    args: (0..Z3_get_app_num_args(ctx.r, app)).map(|i| Ast::new(ctx, Z3_get_app_arg(ctx.r, app, i))).collect()
+                }
+            } else if kind == Z3_ast_kind_Z3_VAR_AST {
+                AstNodeZ3::Var(Z3_get_index_value(ctx.r, ast))
+            } else if kind == Z3_ast_kind_Z3_QUANTIFIER_AST {
+                AstNodeZ3::Quantifier {
+                    kind: match (Z3_is_quantifier_forall(ctx.r, ast), Z3_is_lambda(ctx.r, ast)) {
+                        (true, _) => QuantifierKindZ3::Forall,
+                        (false, true) => QuantifierKindZ3::Lambda,
+                        (false, false) => QuantifierKindZ3::Exists
+                    },
+                    bound: (0..Z3_get_quantifier_num_bound(ctx.r, ast))
+                        .map(|i| Z3StringToStringZ3::new(Z3_get_symbol_string(ctx.r, Z3_get_quantifier_bound_name(ctx.r, ast, i))))
+                        .collect(),
+                    body: Ast::new(ctx, Z3_get_quantifier_body(ctx.r, ast))
+                }
+            } else {
+                return Err(ErrorZ3 {code: Z3_error_code_Z3_INVALID_ARG, msg: "The AST is not a term.".to_string()})
+            }
+        };
+        ctx.check_error()?;
+        Ok(AstDecomposeZ3 {ctx, ast, r: z3}.r)
+    }
+}
+
+impl <'ctx, R: Clone> FoldAstZ3<'ctx, R> {
+    /// Fold the term `ast` bottom up with `folder`. Shared subterms are
+    /// folded once, their result is cloned for every occurrence. Deep terms
+    /// are folded without recursion.
+    ///
+    /// NOTE: See macro! `fold_ast_z3!`
+    pub fn new<F: AstFoldZ3<'ctx, Result = R>>(ctx: &'ctx ContextZ3, ast: impl AsAstZ3<'ctx>, folder: &mut F) -> R {
+        match FoldAstZ3::try_new(ctx, ast, folder) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new<F: AstFoldZ3<'ctx, Result = R>>(ctx: &'ctx ContextZ3, ast: impl AsAstZ3<'ctx>, folder: &mut F) -> Result<R, ErrorZ3> {
+        let ast = Ast::new(ctx, ast.as_ast());
+        let id = |x: &Ast| unsafe {
+            Z3_get_ast_id(ctx.r, x.r)
+        };
+        // the folded subterms by id, a node is decomposed on the way down and folded on the way up
+        let mut folded: HashMap<u32, R> = HashMap::new();
+        let mut stack: Vec<(Ast<'ctx>, Option<AstNodeZ3<'ctx>>)> = vec!((ast.clone(), None));
+        while let Some((term, node)) = stack.pop() {
+            if folded.contains_key(&id(&term)) {
+                continue
+            }
+            let node = match node {
+                Some(node) => node,
+                None => {
+                    let node = AstDecomposeZ3::try_new(ctx, &term)?;
+                    let children = match &node {
+                        AstNodeZ3::App {args, ..} => args.clone(),
+                        AstNodeZ3::Quantifier {body, ..} => vec!(body.clone()),
+                        _ => vec!()
+                    };
+                    if !children.is_empty() {
+                        stack.push((term, Some(node)));
+                        // reversed, so the children are folded from left to right
+                        stack.extend(children.into_iter().rev().map(|x| (x, None)));
+                        continue
+                    }
+                    node
+                }
+            };
+            let result = match node {
+                AstNodeZ3::Numeral(value) => folder.numeral(&term, value),
+                AstNodeZ3::App {kind, name, args} => {
+                    let args = args.iter().map(|x| folded[&id(x)].clone()).collect();
+                    folder.app(&term, kind, &name, args)
+                },
+                AstNodeZ3::Var(index) => folder.var(&term, index),
+                AstNodeZ3::Quantifier {kind, bound, body} => {
+                    let body = folded[&id(&body)].clone();
+                    folder.quantifier(&term, kind, bound, body)
+                }
+            };
+            folded.insert(id(&term), result);
+        }
+        let z3 = folded.remove(&id(&ast)).unwrap();
+        Ok(FoldAstZ3 {ctx, ast: ast.r, r: z3}.r)
+    }
+}
+
+/// simplify a term, optionally with simplifier parameters
+#[macro_export]
+macro_rules! simplify_z3 {
+    ($ctx:expr, $a:expr) => {
+        SimplifyZ3::new($ctx, $a, vec!())
+    };
+    ($ctx:expr, $a:expr, $b:expr) => {
+        SimplifyZ3::new($ctx, $a, $b)
+    }
+}
+
+/// decompose the top level of a term
+#[macro_export]
+macro_rules! ast_decompose_z3 {
+    ($ctx:expr, $a:expr) => {
+        AstDecomposeZ3::new($ctx, $a)
+    }
+}
+
+/// fold a term bottom up
+#[macro_export]
+macro_rules! fold_ast_z3 {
+    ($ctx:expr, $a:expr, $f:expr) => {
+        FoldAstZ3::new($ctx, $a, $f)
+    }
+}
+
+// collects the names of the uninterpreted constants of a term
+#[cfg(test)]
+struct ConstNames;
+
+#[cfg(test)]
+impl <'ctx> AstFoldZ3<'ctx> for ConstNames {
+    type Result = Vec<String>;
+
+    fn numeral(&mut self, _ast: &Ast<'ctx>, _value: ModelValueZ3) -> Vec<String> {
+        vec!()
+    }
+
+    fn app(&mut self, _ast: &Ast<'ctx>, kind: Z3_decl_kind, name: &str, args: Vec<Vec<String>>) -> Vec<String> {
+        let mut names: Vec<String> = args.concat();
+        if kind == Z3_decl_kind_Z3_OP_UNINTERPRETED && !names.contains(&name.to_string()) {
+            names.push(name.to_string());
+        }
+        names
+    }
+
+    fn var(&mut self, _ast: &Ast<'ctx>, _index: u32) -> Vec<String> {
+        vec!()
+    }
+
+    fn quantifier(&mut self, _ast: &Ast<'ctx>, _kind: QuantifierKindZ3, _bound: Vec<String>, body: Vec<String>) -> Vec<String> {
+        body
+    }
+}
+
+// counts the folded nodes, the result is the depth of the term
+#[cfg(test)]
+struct Depth {
+    nodes: u32
+}
+
+#[cfg(test)]
+impl <'ctx> AstFoldZ3<'ctx> for Depth {
+    type Result = u32;
+
+    fn numeral(&mut self, _ast: &Ast<'ctx>, _value: ModelValueZ3) -> u32 {
+        self.nodes += 1;
+        1
+    }
+
+    fn app(&mut self, _ast: &Ast<'ctx>, _kind: Z3_decl_kind, _name: &str, args: Vec<u32>) -> u32 {
+        self.nodes += 1;
+        1 + args.into_iter().max().unwrap_or(0)
+    }
+
+    fn var(&mut self, _ast: &Ast<'ctx>, _index: u32) -> u32 {
+        self.nodes += 1;
+        1
+    }
+
+    fn quantifier(&mut self, _ast: &Ast<'ctx>, _kind: QuantifierKindZ3, _bound: Vec<String>, body: u32) -> u32 {
+        self.nodes += 1;
+        1 + body
+    }
+}
+
+#[test]
+fn test_simplify(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let sum = ADDZ3::new(&ctx, vec!(&x, &IntZ3::new(&ctx, &intsort, 1), &IntZ3::new(&ctx, &intsort, 2)));
+    assert_eq!("(+ 3 x)", AstToStringZ3::new(&ctx, SimplifyZ3::new(&ctx, &sum, vec!())));
+
+    let b = BoolVarZ3::new(&ctx, &boolsort, "b");
+    let c = BoolVarZ3::new(&ctx, &boolsort, "c");
+    let and = ANDZ3::new(&ctx, vec!(&b, &c));
+    assert_eq!("(and b c)", AstToStringZ3::new(&ctx, SimplifyZ3::new(&ctx, &and, vec!())));
+    assert_eq!("(not (or (not b) (not c)))", AstToStringZ3::new(&ctx, SimplifyZ3::new(&ctx, &and, vec!(("elim_and", "true")))));
+    assert!(SimplifyZ3::try_new(&ctx, &and, vec!(("elim_and", "1"))).is_err());
+    assert!(SimplifyZ3::try_new(&ctx, &and, vec!(("no_such_param", "true"))).is_err());
+}
+
+#[test]
+fn test_ast_decompose(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let b = BoolVarZ3::new(&ctx, &boolsort, "b");
+    let five = IntZ3::new(&ctx, &intsort, 5);
+    let gt = GTZ3::new(&ctx, &x, &five);
+
+    assert_eq!(AstNodeZ3::App {kind: Z3_decl_kind_Z3_OP_GT, name: ">".to_string(), args: vec!(x.clone(), five.clone())},
+        AstDecomposeZ3::new(&ctx, &gt));
+    assert_eq!(AstNodeZ3::App {kind: Z3_decl_kind_Z3_OP_UNINTERPRETED, name: "b".to_string(), args: vec!()},
+        AstDecomposeZ3::new(&ctx, &b));
+    assert_eq!(AstNodeZ3::Numeral(ModelValueZ3::Int(5)), AstDecomposeZ3::new(&ctx, &five));
+
+    let forall = ForallZ3::new(&ctx, vec!(&x), GEZ3::new(&ctx, MULZ3::new(&ctx, vec!(&x, &x)), IntZ3::new(&ctx, &intsort, 0)));
+    match AstDecomposeZ3::new(&ctx, &forall) {
+        AstNodeZ3::Quantifier {kind, bound, body} => {
+            assert_eq!(QuantifierKindZ3::Forall, kind);
+            assert_eq!(vec!("x"), bound);
+            assert_eq!("(>= (* (:var 0) (:var 0)) 0)", AstToStringZ3::new(&ctx, &body));
+        },
+        x => panic!("expected a quantifier, got {:?}", x)
+    };
+}
+
+#[test]
+fn test_fold_cnf(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let sort = BoolSortZ3::new(&ctx);
+
+    let x = BoolVarZ3::new(&ctx, &sort, "x");
+    let y = BoolVarZ3::new(&ctx, &sort, "y");
+    let z = BoolVarZ3::new(&ctx, &sort, "z");
+    let cnf = GetCnfVectorZ3::new(&ctx, vec!(IMPZ3::new(&ctx, &y, &x), XORZ3::new(&ctx, &x, &z)));
+
+    // every clause is a disjunction of literals or a single literal
+    for clause in &cnf {
+        let literals = match AstDecomposeZ3::new(&ctx, clause) {
+            AstNodeZ3::App {kind, args, ..} if kind == Z3_decl_kind_Z3_OP_OR => args,
+            _ => vec!(clause.clone())
+        };
+        for literal in literals {
+            match AstDecomposeZ3::new(&ctx, &literal) {
+                AstNodeZ3::App {kind, args, ..} if kind == Z3_decl_kind_Z3_OP_NOT =>
+                    assert!(matches!(AstDecomposeZ3::new(&ctx, &args[0]), AstNodeZ3::App {kind, ..} if kind == Z3_decl_kind_Z3_OP_UNINTERPRETED)),
+                node => assert!(matches!(node, AstNodeZ3::App {kind, ..} if kind == Z3_decl_kind_Z3_OP_UNINTERPRETED))
+            }
+        }
+    }
+
+    let mut names: Vec<String> = cnf.iter().flat_map(|c| FoldAstZ3::new(&ctx, c, &mut ConstNames)).collect();
+    names.sort();
+    names.dedup();
+    assert_eq!(vec!("x", "y", "z"), names);
+}
+
+#[test]
+fn test_fold_shared_and_deep(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    // x occurs 2^50 times, but there are only 51 different subterms
+    let mut shared = IntVarZ3::new(&ctx, &intsort, "x");
+    for _ in 0..50 {
+        shared = ADDZ3::new(&ctx, vec!(&shared, &shared));
+    }
+    let mut depth = Depth {nodes: 0};
+    assert_eq!(51, FoldAstZ3::new(&ctx, &shared, &mut depth));
+    assert_eq!(51, depth.nodes);
+
+    let mut deep = BoolVarZ3::new(&ctx, &boolsort, "b");
+    for _ in 0..100000 {
+        deep = NOTZ3::new(&ctx, &deep);
+    }
+    assert_eq!(100001, FoldAstZ3::new(&ctx, &deep, &mut Depth {nodes: 0}));
+}
+
+#[test]
+fn test_inspect_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+
+    let x = int_var_z3!(&ctx, "x");
+    let y = int_var_z3!(&ctx, "y");
+    let t = add_z3!(&ctx, mul_z3!(&ctx, int_z3!(&ctx, 0), &x), &y);
+
+    let s = simplify_z3!(&ctx, &t);
+    assert_eq!("y", ast_to_string_z3!(&ctx, &s));
+    assert_eq!(AstNodeZ3::App {kind: Z3_decl_kind_Z3_OP_UNINTERPRETED, name: "y".to_string(), args: vec!()}, ast_decompose_z3!(&ctx, &s));
+    assert_eq!(vec!("x", "y"), fold_ast_z3!(&ctx, &t, &mut ConstNames));
+}
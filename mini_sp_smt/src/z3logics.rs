@@ -6,25 +6,25 @@ use super::*;
 pub struct ANDZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub args: Vec<Z3_ast>,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct ORZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub args: Vec<Z3_ast>,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct DISTINCTZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub args: Vec<Z3_ast>,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct NOTZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub arg: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct ITEZ3<'ctx> {
@@ -32,46 +32,68 @@ pub struct ITEZ3<'ctx> {
     pub ifz3: Z3_ast,
     pub thenz3: Z3_ast,
     pub elsez3: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct IFFZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub left: Z3_ast,
     pub right: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct IMPZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub left: Z3_ast,
     pub right: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct EQUIVZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub left: Z3_ast,
     pub right: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct NTRUEZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct XORZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub left: Z3_ast,
     pub right: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct PBEQZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub args: Vec<Z3_ast>,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
+}
+
+pub struct PatternZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub terms: Vec<Z3_ast>,
+    pub r: Pattern<'ctx>
+}
+
+pub struct ForallZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub bound: Vec<Z3_ast>,
+    pub patterns: Vec<Z3_pattern>,
+    pub body: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct ExistsZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub bound: Vec<Z3_ast>,
+    pub patterns: Vec<Z3_pattern>,
+    pub body: Z3_ast,
+    pub r: Ast<'ctx>
 }
 
 impl<'ctx> ANDZ3<'ctx> {
@@ -83,12 +105,21 @@ impl<'ctx> ANDZ3<'ctx> {
     /// NOTE: The number of arguments must be greater than zero.
     /// 
     /// NOTE: See macro! `and_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, args: Vec<Z3_ast>) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Ast<'ctx> {
+        match ANDZ3::try_new(ctx, args) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let args: Vec<Z3_ast> = args.iter().map(|x| x.as_ast()).collect();
         let args_slice = &args;
         let z3 = unsafe {
             Z3_mk_and(ctx.r, args_slice.len() as u32, args_slice.as_ptr())
         };
-        ANDZ3{ctx, r: z3, args}.r   
+        ctx.check_error()?;
+        Ok(ANDZ3{ctx, r: Ast::new(ctx, z3), args}.r)
     }
 }
 
@@ -101,12 +132,21 @@ impl<'ctx> ORZ3<'ctx> {
     /// NOTE: The number of arguments must be greater than zero.
     /// 
     /// NOTE: See macro! `or_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, args: Vec<Z3_ast>) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Ast<'ctx> {
+        match ORZ3::try_new(ctx, args) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let args: Vec<Z3_ast> = args.iter().map(|x| x.as_ast()).collect();
         let args_slice = &args;
         let z3 = unsafe {
             Z3_mk_or(ctx.r, args_slice.len() as u32, args_slice.as_ptr())
         };
-        ORZ3 {ctx, r: z3, args}.r       
+        ctx.check_error()?;
+        Ok(ORZ3 {ctx, r: Ast::new(ctx, z3), args}.r)
     }
 }
 
@@ -119,12 +159,21 @@ impl<'ctx> DISTINCTZ3<'ctx> {
     /// NOTE: The number of arguments must be greater than zero.
     /// 
     /// NOTE: See macro! `distinct_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, args: Vec<Z3_ast>) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Ast<'ctx> {
+        match DISTINCTZ3::try_new(ctx, args) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let args: Vec<Z3_ast> = args.iter().map(|x| x.as_ast()).collect();
         let args_slice = &args;
         let z3 = unsafe {
             Z3_mk_distinct(ctx.r, args_slice.len() as u32, args_slice.as_ptr())
         };
-        DISTINCTZ3 {ctx, r: z3, args}.r       
+        ctx.check_error()?;
+        Ok(DISTINCTZ3 {ctx, r: Ast::new(ctx, z3), args}.r)
     }
 }
 
@@ -134,11 +183,20 @@ impl<'ctx> NOTZ3<'ctx> {
     /// NOTE: The node `arg` must have Boolean sort.
     /// 
     /// NOTE: See macro! `not_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, arg: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match NOTZ3::try_new(ctx, arg) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
         let z3 = unsafe {
             Z3_mk_not(ctx.r, arg)
         };
-        NOTZ3 {ctx, r: z3, arg}.r  
+        ctx.check_error()?;
+        Ok(NOTZ3 {ctx, r: Ast::new(ctx, z3), arg}.r)
     }
 }
 
@@ -149,11 +207,22 @@ impl<'ctx> ITEZ3<'ctx> {
     /// The sort of the new node is equal to the sort of `thenz3` and `elsez3`.
     /// 
     /// NOTE: See macro! `ite_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, ifz3: Z3_ast, thenz3: Z3_ast, elsez3: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, ifz3: impl AsAstZ3<'ctx>, thenz3: impl AsAstZ3<'ctx>, elsez3: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match ITEZ3::try_new(ctx, ifz3, thenz3, elsez3) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, ifz3: impl AsAstZ3<'ctx>, thenz3: impl AsAstZ3<'ctx>, elsez3: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let ifz3 = ifz3.as_ast();
+        let thenz3 = thenz3.as_ast();
+        let elsez3 = elsez3.as_ast();
         let z3 = unsafe {
             Z3_mk_ite(ctx.r, ifz3, thenz3, elsez3)
         };
-        ITEZ3 {ctx, ifz3, thenz3, elsez3, r: z3}.r
+        ctx.check_error()?;
+        Ok(ITEZ3 {ctx, ifz3, thenz3, elsez3, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -163,11 +232,21 @@ impl<'ctx> IFFZ3<'ctx> {
     /// NOTE: The nodes `left` and `right` must have Boolean sort.
     /// 
     /// NOTE: See macro! `iff_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, left: Z3_ast, right: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match IFFZ3::try_new(ctx, left, right) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let left = left.as_ast();
+        let right = right.as_ast();
         let z3 = unsafe {
             Z3_mk_iff(ctx.r, left, right)
         };
-        IFFZ3 {ctx, left, right, r: z3}.r        
+        ctx.check_error()?;
+        Ok(IFFZ3 {ctx, left, right, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -177,11 +256,21 @@ impl<'ctx> IMPZ3<'ctx> {
     /// NOTE: The nodes `left` and `right` must have Boolean sort.
     /// 
     /// NOTE: See macro! `imp_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, left: Z3_ast, right: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match IMPZ3::try_new(ctx, left, right) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let left = left.as_ast();
+        let right = right.as_ast();
         let z3 = unsafe {
             Z3_mk_implies(ctx.r, left, right)
         };
-        IMPZ3 {ctx, left, right, r: z3}.r       
+        ctx.check_error()?;
+        Ok(IMPZ3 {ctx, left, right, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -191,12 +280,22 @@ impl<'ctx> EQUIVZ3<'ctx> {
     /// NOTE: The nodes `left` and `right` must have Boolean sort.
     /// 
     /// NOTE: See macro! `equiv_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, left: Z3_ast, right: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match EQUIVZ3::try_new(ctx, left, right) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let left = left.as_ast();
+        let right = right.as_ast();
         let z3 = unsafe {
-            let vec = vec!(Z3_mk_implies(ctx.r, left, right), Z3_mk_implies(ctx.r, right, left));
-            Z3_mk_and(ctx.r, 2 as u32, vec.as_ptr())
+            let vec = vec!(Ast::new(ctx, Z3_mk_implies(ctx.r, left, right)), Ast::new(ctx, Z3_mk_implies(ctx.r, right, left)));
+            Z3_mk_and(ctx.r, 2 as u32, [vec[0].r, vec[1].r].as_ptr())
         };
-        EQUIVZ3 {ctx, left, right, r: z3}.r       
+        ctx.check_error()?;
+        Ok(EQUIVZ3 {ctx, left, right, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -206,20 +305,30 @@ impl<'ctx> NTRUEZ3<'ctx> {
     /// NOTE: The nodes must have Boolean sort.
     /// 
     /// NOTE: See macro! `ntrue_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, t: Vec<Z3_ast>, f: Vec<Z3_ast>) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, t: Vec<impl AsAstZ3<'ctx>>, f: Vec<impl AsAstZ3<'ctx>>) -> Ast<'ctx> {
+        match NTRUEZ3::try_new(ctx, t, f) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, t: Vec<impl AsAstZ3<'ctx>>, f: Vec<impl AsAstZ3<'ctx>>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let t: Vec<Z3_ast> = t.iter().map(|x| x.as_ast()).collect();
+        let f: Vec<Z3_ast> = f.iter().map(|x| x.as_ast()).collect();
 
         let z3 = unsafe {
            
             let mut falses_init = Vec::new();
             for fs in &f {
-               falses_init.push(Z3_mk_not(ctx.r, *fs));
+               falses_init.push(Ast::new(ctx, Z3_mk_not(ctx.r, *fs)));
             }
 
-            let together = [t, falses_init].concat();
+            let together = [t, falses_init.iter().map(|x| x.r).collect()].concat();
             Z3_mk_and(ctx.r, together.len() as u32, together.as_ptr())
         };
 
-        NTRUEZ3 {ctx, r: z3}.r       
+        ctx.check_error()?;
+        Ok(NTRUEZ3 {ctx, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -229,11 +338,21 @@ impl<'ctx> XORZ3<'ctx> {
     /// NOTE: The nodes `left` and `right` must have Boolean sort.
     /// 
     /// NOTE: See macro! `xor_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, left: Z3_ast, right: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match XORZ3::try_new(ctx, left, right) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let left = left.as_ast();
+        let right = right.as_ast();
         let z3 = unsafe {
             Z3_mk_xor(ctx.r, left, right)
         };
-        XORZ3 {ctx, left, right, r: z3}.r  
+        ctx.check_error()?;
+        Ok(XORZ3 {ctx, left, right, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -245,13 +364,134 @@ impl<'ctx> PBEQZ3<'ctx> {
     /// Encode k1*p1 + k2*p2 + ... + kn*pn = k
     /// 
     /// NOTE: See macro! `pbeq_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, args: Vec<Z3_ast>, sum: i32) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>, sum: i32) -> Ast<'ctx> {
+        match PBEQZ3::try_new(ctx, args, sum) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>, sum: i32) -> Result<Ast<'ctx>, ErrorZ3> {
+        let args: Vec<Z3_ast> = args.iter().map(|x| x.as_ast()).collect();
         let args_slice = &args;
         let coeffs_slice = &vec![1; args_slice.len()];
         let z3 = unsafe {
             Z3_mk_pbeq(ctx.r, args_slice.len() as u32, args_slice.as_ptr(), coeffs_slice.as_ptr(), sum)
         };
-        PBEQZ3{ctx, r: z3, args}.r   
+        ctx.check_error()?;
+        Ok(PBEQZ3{ctx, r: Ast::new(ctx, z3), args}.r)
+    }
+}
+
+impl<'ctx> PatternZ3<'ctx> {
+    /// Create a pattern for quantifier instantiation.
+    ///
+    /// Z3 uses pattern matching to instantiate quantifiers. If a pattern is not provided 
+    /// for a quantifier, then Z3 will automatically compute a set of patterns for it. 
+    /// A pattern is a non-empty list of terms that together mention all bound variables.
+    ///
+    /// NOTE: See macro! `pattern_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, terms: Vec<impl AsAstZ3<'ctx>>) -> Pattern<'ctx> {
+        match PatternZ3::try_new(ctx, terms) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, terms: Vec<impl AsAstZ3<'ctx>>) -> Result<Pattern<'ctx>, ErrorZ3> {
+        let terms: Vec<Z3_ast> = terms.iter().map(|x| x.as_ast()).collect();
+        let z3 = unsafe {
+            Z3_mk_pattern(ctx.r, terms.len() as u32, terms.as_ptr())
+        };
+        ctx.check_error()?;
+        Ok(PatternZ3 {ctx, terms, r: Pattern::new(ctx, z3)}.r)
+    }
+}
+
+// the bound variables are constants, Z3 abstracts them into de-Bruijn indices
+fn quantifier_z3<'ctx>(ctx: &'ctx ContextZ3, is_forall: bool, bound: &[Z3_ast], patterns: &[Z3_pattern], body: Z3_ast) -> Result<Ast<'ctx>, ErrorZ3> {
+    let z3 = unsafe {
+        let mut apps: Vec<Z3_app> = vec!();
+        for b in bound {
+            apps.push(Z3_to_app(ctx.r, *b));
+        }
+        ctx.check_error()?;
+        Z3_mk_quantifier_const(ctx.r, is_forall, 1, apps.len() as u32, apps.as_ptr(), 
+            patterns.len() as u32, patterns.as_ptr(), body)
+    };
+    ctx.check_error()?;
+    Ok(Ast::new(ctx, z3))
+}
+
+impl<'ctx> ForallZ3<'ctx> {
+    /// Create a universal quantifier `forall bound. body` over typed bound variables.
+    ///
+    /// The `bound` is a rust vector of variables, i.e. `IntVarZ3`, `BoolVarZ3`, `EnumVarZ3`...
+    /// Their occurrences in `body` become bound, the variables themselves stay free elsewhere.
+    ///
+    /// NOTE: The body must have Bool sort.
+    ///
+    /// NOTE: See macro! `forall_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, bound: Vec<impl AsAstZ3<'ctx>>, body: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        ForallZ3::new_with_patterns(ctx, bound, vec!(), body)
+    }
+
+    /// Same as `ForallZ3::new`, but with the instantiation `patterns` made by `PatternZ3`.
+    ///
+    /// NOTE: See macro! `forall_z3!`
+    pub fn new_with_patterns(ctx: &'ctx ContextZ3, bound: Vec<impl AsAstZ3<'ctx>>, patterns: Vec<Pattern<'ctx>>, body: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match ForallZ3::try_new_with_patterns(ctx, bound, patterns, body) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, bound: Vec<impl AsAstZ3<'ctx>>, body: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        ForallZ3::try_new_with_patterns(ctx, bound, vec!(), body)
+    }
+
+    pub fn try_new_with_patterns(ctx: &'ctx ContextZ3, bound: Vec<impl AsAstZ3<'ctx>>, patterns: Vec<Pattern<'ctx>>, body: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let bound: Vec<Z3_ast> = bound.iter().map(|x| x.as_ast()).collect();
+        let patterns: Vec<Z3_pattern> = patterns.iter().map(|x| x.r).collect();
+        let body = body.as_ast();
+        let z3 = quantifier_z3(ctx, true, &bound, &patterns, body)?;
+        Ok(ForallZ3 {ctx, bound, patterns, body, r: z3}.r)
+    }
+}
+
+impl<'ctx> ExistsZ3<'ctx> {
+    /// Create an existential quantifier `exists bound. body` over typed bound variables.
+    ///
+    /// The `bound` is a rust vector of variables, i.e. `IntVarZ3`, `BoolVarZ3`, `EnumVarZ3`...
+    /// Their occurrences in `body` become bound, the variables themselves stay free elsewhere.
+    ///
+    /// NOTE: The body must have Bool sort.
+    ///
+    /// NOTE: See macro! `exists_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, bound: Vec<impl AsAstZ3<'ctx>>, body: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        ExistsZ3::new_with_patterns(ctx, bound, vec!(), body)
+    }
+
+    /// Same as `ExistsZ3::new`, but with the instantiation `patterns` made by `PatternZ3`.
+    ///
+    /// NOTE: See macro! `exists_z3!`
+    pub fn new_with_patterns(ctx: &'ctx ContextZ3, bound: Vec<impl AsAstZ3<'ctx>>, patterns: Vec<Pattern<'ctx>>, body: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match ExistsZ3::try_new_with_patterns(ctx, bound, patterns, body) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, bound: Vec<impl AsAstZ3<'ctx>>, body: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        ExistsZ3::try_new_with_patterns(ctx, bound, vec!(), body)
+    }
+
+    pub fn try_new_with_patterns(ctx: &'ctx ContextZ3, bound: Vec<impl AsAstZ3<'ctx>>, patterns: Vec<Pattern<'ctx>>, body: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let bound: Vec<Z3_ast> = bound.iter().map(|x| x.as_ast()).collect();
+        let patterns: Vec<Z3_pattern> = patterns.iter().map(|x| x.r).collect();
+        let body = body.as_ast();
+        let z3 = quantifier_z3(ctx, false, &bound, &patterns, body)?;
+        Ok(ExistsZ3 {ctx, bound, patterns, body, r: z3}.r)
     }
 }
 
@@ -280,7 +520,7 @@ macro_rules! and_z3 {
         {
             let mut temp_vec = Vec::new();
             $(
-                temp_vec.push($x);
+                temp_vec.push($crate::Ast::new($ctx, $crate::AsAstZ3::as_ast(&$x)));
             )*
             ANDZ3::new($ctx, temp_vec)
         }
@@ -312,7 +552,7 @@ macro_rules! or_z3 {
         {
             let mut temp_vec = Vec::new();
             $(
-                temp_vec.push($x);
+                temp_vec.push($crate::Ast::new($ctx, $crate::AsAstZ3::as_ast(&$x)));
             )*
             ORZ3::new($ctx, temp_vec)
         }
@@ -344,7 +584,7 @@ macro_rules! distinct_z3 {
         {
             let mut temp_vec = Vec::new();
             $(
-                temp_vec.push($x);
+                temp_vec.push($crate::Ast::new($ctx, $crate::AsAstZ3::as_ast(&$x)));
             )*
             DISTINCTZ3::new($ctx, temp_vec)
         }
@@ -480,6 +720,79 @@ macro_rules! xor_z3 {
     }
 }
 
+/// pattern for quantifier instantiation
+/// 
+/// Macro rule for:
+/// ```text
+/// z3logics::PatternZ3::new(&ctx, vec!(a, b))
+/// ```
+/// Using a specific context and passing elements:
+/// ```text
+/// pattern_z3!(&ctx, a, b)
+/// ```
+#[macro_export]
+macro_rules! pattern_z3 {
+    ( $ctx:expr, $( $x:expr ),* ) => {
+        {
+            let mut temp_vec = Vec::new();
+            $(
+                temp_vec.push($crate::Ast::new($ctx, $crate::AsAstZ3::as_ast(&$x)));
+            )*
+            PatternZ3::new($ctx, temp_vec)
+        }
+    };
+}
+
+/// for all x, y, ... body
+/// 
+/// Macro rule for:
+/// ```text
+/// z3logics::ForallZ3::new(&ctx, vec!(x, y), body)
+/// ```
+/// Using a specific context:
+/// ```text
+/// forall_z3!(&ctx, vec!(x, y), body)
+/// ```
+/// Or with instantiation patterns:
+/// ```text
+/// forall_z3!(&ctx, vec!(x, y), vec!(pattern_z3!(&ctx, a)), body)
+/// ```
+/// Requires that body is of Bool sort.
+#[macro_export]
+macro_rules! forall_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        ForallZ3::new($ctx, $b, $c)
+    };
+    ($ctx:expr, $b:expr, $p:expr, $c:expr) => {
+        ForallZ3::new_with_patterns($ctx, $b, $p, $c)
+    };
+}
+
+/// exists x, y, ... body
+/// 
+/// Macro rule for:
+/// ```text
+/// z3logics::ExistsZ3::new(&ctx, vec!(x, y), body)
+/// ```
+/// Using a specific context:
+/// ```text
+/// exists_z3!(&ctx, vec!(x, y), body)
+/// ```
+/// Or with instantiation patterns:
+/// ```text
+/// exists_z3!(&ctx, vec!(x, y), vec!(pattern_z3!(&ctx, a)), body)
+/// ```
+/// Requires that body is of Bool sort.
+#[macro_export]
+macro_rules! exists_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        ExistsZ3::new($ctx, $b, $c)
+    };
+    ($ctx:expr, $b:expr, $p:expr, $c:expr) => {
+        ExistsZ3::new_with_patterns($ctx, $b, $p, $c)
+    };
+}
+
 #[test]
 fn test_new_and(){
     let conf = ConfigZ3::new();
@@ -497,6 +810,18 @@ fn test_new_and(){
     assert_eq!("(and x1 x2 true false)", ast_to_string_z3!(&ctx, and1));
 }
 
+#[test]
+fn test_and_sort_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let y = IntVarZ3::new(&ctx, &intsort, "y");
+
+    assert!(ANDZ3::try_new(&ctx, vec!(x, y)).is_err());
+}
+
 #[test]
 fn test_new_or(){
     let conf = ConfigZ3::new();
@@ -541,7 +866,7 @@ fn test_new_not(){
 
     let not1 = NOTZ3::new(&ctx, bool1);
     let not2 = NOTZ3::new(&ctx, x1);
-    let not3 = NOTZ3::new(&ctx, not2);
+    let not3 = NOTZ3::new(&ctx, &not2);
 
     assert_eq!("(not true)", ast_to_string_z3!(&ctx, not1));
     assert_eq!("(not x1)", ast_to_string_z3!(&ctx, not2));
@@ -560,7 +885,7 @@ fn test_new_ite(){
     let int1 = IntZ3::new(&ctx, &intsort, 3);
     let int2 = IntZ3::new(&ctx, &intsort, 7);
 
-    let ite1 = ITEZ3::new(&ctx, bool1, int1, int2);
+    let ite1 = ITEZ3::new(&ctx, bool1, &int1, &int2);
     let ite2 = ITEZ3::new(&ctx, bool2, int1, int2);
     
     assert_eq!("(ite true 3 7)", ast_to_string_z3!(&ctx, ite1));
@@ -579,9 +904,9 @@ fn test_new_iff(){
     let x1 = BoolVarZ3::new(&ctx, &boolsort, "x1");
     let x2 = BoolVarZ3::new(&ctx, &boolsort, "x2");
 
-    let iff1 = IFFZ3::new(&ctx, bool1, bool2);
-    let iff2 = IFFZ3::new(&ctx, bool1, x1);
-    let iff3 = IFFZ3::new(&ctx, x2, bool2);
+    let iff1 = IFFZ3::new(&ctx, &bool1, &bool2);
+    let iff2 = IFFZ3::new(&ctx, bool1, &x1);
+    let iff3 = IFFZ3::new(&ctx, &x2, bool2);
     let iff4 = IFFZ3::new(&ctx, x1, x2);
     
     assert_eq!("(= true false)", ast_to_string_z3!(&ctx, iff1));
@@ -602,9 +927,9 @@ fn test_new_imp(){
     let x1 = BoolVarZ3::new(&ctx, &boolsort, "x1");
     let x2 = BoolVarZ3::new(&ctx, &boolsort, "x2");
 
-    let imp1 = IMPZ3::new(&ctx, bool1, bool2);
-    let imp2 = IMPZ3::new(&ctx, bool1, x1);
-    let imp3 = IMPZ3::new(&ctx, x2, bool2);
+    let imp1 = IMPZ3::new(&ctx, &bool1, &bool2);
+    let imp2 = IMPZ3::new(&ctx, bool1, &x1);
+    let imp3 = IMPZ3::new(&ctx, &x2, bool2);
     let imp4 = IMPZ3::new(&ctx, x1, x2);
     
     assert_eq!("(=> true false)", ast_to_string_z3!(&ctx, imp1));
@@ -662,7 +987,7 @@ fn test_new_ntrue_2(){
     let x5 = BoolVarZ3::new(&ctx, &boolsort, "x5");
     let x6 = BoolVarZ3::new(&ctx, &boolsort, "x6");
 
-    let ntrue1 = NTRUEZ3::new(&ctx, vec!(), vec!(x1, x2, x3, x4, x5, x6));
+    let ntrue1 = NTRUEZ3::new(&ctx, Vec::<Ast>::new(), vec!(x1, x2, x3, x4, x5, x6));
 
     assert_eq!("(and (not x1) (not x2) (not x3) (not x4) (not x5) (not x6))", ast_to_string_z3!(&ctx, ntrue1));
 }
@@ -709,9 +1034,9 @@ fn test_new_xor(){
     let x1 = BoolVarZ3::new(&ctx, &boolsort, "x1");
     let x2 = BoolVarZ3::new(&ctx, &boolsort, "x2");
 
-    let xor1 = XORZ3::new(&ctx, bool1, bool2);
-    let xor2 = XORZ3::new(&ctx, bool1, x1);
-    let xor3 = XORZ3::new(&ctx, x2, bool2);
+    let xor1 = XORZ3::new(&ctx, &bool1, &bool2);
+    let xor2 = XORZ3::new(&ctx, bool1, &x1);
+    let xor3 = XORZ3::new(&ctx, &x2, bool2);
     let xor4 = XORZ3::new(&ctx, x1, x2);
     
     assert_eq!("(xor true false)", ast_to_string_z3!(&ctx, xor1));
@@ -809,7 +1134,7 @@ fn test_distinct_macro_3(){
     slv_assert_z3!(&ctx, &slv, dist1);
     slv_check_z3!(&ctx, &slv);
     let model = slv_get_model_z3!(&ctx, &slv);
-    println!("{}", model_to_string_z3!(&ctx, model));
+    println!("{}", model_to_string_z3!(&ctx, &model));
     assert_eq!("z -> (- 3)
 y -> (- 4)
 x -> (- 5)
@@ -897,4 +1222,103 @@ fn test_xor_macro_1(){
         bool_var_z3!(&ctx, "y")
     );
     assert_eq!("(xor x y)", ast_to_string_z3!(&ctx, xor1));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_forall_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let intsort = IntSortZ3::new(&ctx);
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let one = IntZ3::new(&ctx, &intsort, 1);
+    let forall1 = ForallZ3::new(&ctx, vec!(&x), GTZ3::new(&ctx, ADDZ3::new(&ctx, vec!(&x, &one)), &x));
+    assert_eq!("(forall ((x Int)) (> (+ x 1) x))", ast_to_string_z3!(&ctx, forall1));
+}
+
+#[test]
+fn test_exists_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let intsort = IntSortZ3::new(&ctx);
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let y = IntVarZ3::new(&ctx, &intsort, "y");
+    let exists1 = ExistsZ3::new(&ctx, vec!(&x, &y), EQZ3::new(&ctx, &x, &y));
+    assert_eq!("(exists ((x Int) (y Int)) (= x y))", ast_to_string_z3!(&ctx, exists1));
+}
+
+#[test]
+fn test_forall_with_patterns_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let intsort = IntSortZ3::new(&ctx);
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let two = IntZ3::new(&ctx, &intsort, 2);
+    let pattern = PatternZ3::new(&ctx, vec!(MULZ3::new(&ctx, vec!(&two, &x))));
+    let forall1 = ForallZ3::new_with_patterns(&ctx, vec!(&x), vec!(pattern), 
+        GEZ3::new(&ctx, MULZ3::new(&ctx, vec!(&two, &x)), &x));
+    assert_eq!("(forall ((x Int)) (! (>= (* 2 x) x) :pattern ((* 2 x))))", ast_to_string_z3!(&ctx, forall1));
+}
+
+#[test]
+fn test_forall_sat_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+    let x = int_var_z3!(&ctx, "x");
+    let y = int_var_z3!(&ctx, "y");
+    slv_assert_z3!(&ctx, &slv, forall_z3!(&ctx, vec!(&x), ge_z3!(&ctx, mul_z3!(&ctx, &x, &x), &y)));
+    assert_eq!(SatResultZ3::Sat, slv_check_z3!(&ctx, &slv));
+}
+
+#[test]
+fn test_forall_unsat_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+    let x = int_var_z3!(&ctx, "x");
+    slv_assert_z3!(&ctx, &slv, forall_z3!(&ctx, vec!(&x), gt_z3!(&ctx, &x, int_z3!(&ctx, 0))));
+    assert_eq!(SatResultZ3::Unsat, slv_check_z3!(&ctx, &slv));
+}
+
+#[test]
+fn test_forall_unknown_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+    let x = int_var_z3!(&ctx, "x");
+    let y = int_var_z3!(&ctx, "y");
+    let z = int_var_z3!(&ctx, "z");
+    let positive = and_z3!(&ctx, 
+        gt_z3!(&ctx, &x, int_z3!(&ctx, 0)), 
+        gt_z3!(&ctx, &y, int_z3!(&ctx, 0)), 
+        gt_z3!(&ctx, &z, int_z3!(&ctx, 0)));
+    let cubes = eq_z3!(&ctx, 
+        add_z3!(&ctx, mul_z3!(&ctx, &x, &x, &x), mul_z3!(&ctx, &y, &y, &y)), 
+        mul_z3!(&ctx, &z, &z, &z));
+    slv_assert_z3!(&ctx, &slv, forall_z3!(&ctx, vec!(&x, &y, &z), imp_z3!(&ctx, positive, not_z3!(&ctx, cubes))));
+    slv_set_rlimit_z3!(&ctx, &slv, 1000);
+    assert_eq!(SatResultZ3::Unknown("max. resource limit exceeded".to_string()), slv_check_z3!(&ctx, &slv));
+}
+
+#[test]
+fn test_exists_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+    let x = int_var_z3!(&ctx, "x");
+    let exists1 = exists_z3!(&ctx, vec!(&x), 
+        and_z3!(&ctx, gt_z3!(&ctx, &x, int_z3!(&ctx, 3)), lt_z3!(&ctx, &x, int_z3!(&ctx, 5))));
+    slv_assert_z3!(&ctx, &slv, &exists1);
+    assert_eq!(SatResultZ3::Sat, slv_check_z3!(&ctx, &slv));
+    assert_eq!("(exists ((x Int)) (and (> x 3) (< x 5)))", ast_to_string_z3!(&ctx, exists1));
+}
+
+#[test]
+fn test_forall_macro_with_patterns_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let x = int_var_z3!(&ctx, "x");
+    let forall1 = forall_z3!(&ctx, vec!(&x), vec!(pattern_z3!(&ctx, add_z3!(&ctx, &x, int_z3!(&ctx, 1)))), 
+        gt_z3!(&ctx, add_z3!(&ctx, &x, int_z3!(&ctx, 1)), &x));
+    assert_eq!("(forall ((x Int)) (! (> (+ x 1) x) :pattern ((+ x 1))))", ast_to_string_z3!(&ctx, forall1));
+}
@@ -6,53 +6,53 @@ use super::*;
 pub struct MULZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub args: Vec<Z3_ast>,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct DIVZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub arg1: Z3_ast,
     pub arg2: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct MODZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub arg1: Z3_ast,
     pub arg2: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct REMZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub arg1: Z3_ast,
     pub arg2: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct ADDZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub args: Vec<Z3_ast>,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct SUBZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub args: Vec<Z3_ast>,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct NEGZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub arg: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct POWZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub arg1: Z3_ast,
     pub arg2: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 impl<'ctx> MULZ3<'ctx> {
@@ -66,12 +66,21 @@ impl<'ctx> MULZ3<'ctx> {
     /// NOTE: The number of arguments must be greater than zero.
     /// 
     /// NOTE: See macro! mul_z3!
-    pub fn new(ctx: &'ctx ContextZ3, args: Vec<Z3_ast>) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Ast<'ctx> {
+        match MULZ3::try_new(ctx, args) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let args: Vec<Z3_ast> = args.iter().map(|x| x.as_ast()).collect();
         let args_slice = &args;
         let z3 = unsafe {
             Z3_mk_mul(ctx.r, args_slice.len() as u32, args_slice.as_ptr())
         };
-        MULZ3 {ctx, r: z3, args}.r
+        ctx.check_error()?;
+        Ok(MULZ3 {ctx, r: Ast::new(ctx, z3), args}.r)
     }
 }
 
@@ -83,11 +92,21 @@ impl <'ctx> DIVZ3<'ctx> {
     /// the result type is real.
     /// 
     /// NOTE: See macro! div_z3!
-    pub fn new(ctx: &'ctx ContextZ3, arg1: Z3_ast, arg2: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match DIVZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
         let z3 = unsafe {
             Z3_mk_div(ctx.r, arg1, arg2)
         };
-        DIVZ3 {ctx, r: z3, arg1, arg2}.r
+        ctx.check_error()?;
+        Ok(DIVZ3 {ctx, r: Ast::new(ctx, z3), arg1, arg2}.r)
     }
 }
 
@@ -97,11 +116,21 @@ impl <'ctx> MODZ3<'ctx> {
     /// NOTE: The arguments must have int type.
     /// 
     /// NOTE: See macro! modz3!
-    pub fn new(ctx: &'ctx ContextZ3, arg1: Z3_ast, arg2: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match MODZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
         let z3 = unsafe {
             Z3_mk_mod(ctx.r, arg1, arg2)
         };
-        MODZ3 {ctx, arg1, arg2, r: z3}.r
+        ctx.check_error()?;
+        Ok(MODZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -111,11 +140,21 @@ impl <'ctx> REMZ3<'ctx> {
     /// NOTE: The arguments must have int type.
     /// 
     /// NOTE: See macro! rem_z3!
-    pub fn new(ctx: &'ctx ContextZ3, arg1: Z3_ast, arg2: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match REMZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
         let z3 = unsafe {
             Z3_mk_rem(ctx.r, arg1, arg2)
         };
-        REMZ3 {ctx, arg1, arg2, r: z3}.r
+        ctx.check_error()?;
+        Ok(REMZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -129,12 +168,21 @@ impl<'ctx> ADDZ3<'ctx> {
     /// NOTE: The number of arguments must be greater than zero.
     /// 
     /// NOTE: See macro! add_z3!
-    pub fn new(ctx: &'ctx ContextZ3, args: Vec<Z3_ast>) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Ast<'ctx> {
+        match ADDZ3::try_new(ctx, args) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let args: Vec<Z3_ast> = args.iter().map(|x| x.as_ast()).collect();
         let args_slice = &args;
         let z3 = unsafe {
             Z3_mk_add(ctx.r, args_slice.len() as u32, args_slice.as_ptr())
         };
-        ADDZ3 {ctx, r: z3, args}.r
+        ctx.check_error()?;
+        Ok(ADDZ3 {ctx, r: Ast::new(ctx, z3), args}.r)
     }
 }
 
@@ -148,12 +196,21 @@ impl<'ctx> SUBZ3<'ctx> {
     /// NOTE: The number of arguments must be greater than zero.
     /// 
     /// NOTE: See macro! sub_z3!
-    pub fn new(ctx: &'ctx ContextZ3, args: Vec<Z3_ast>) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Ast<'ctx> {
+        match SUBZ3::try_new(ctx, args) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let args: Vec<Z3_ast> = args.iter().map(|x| x.as_ast()).collect();
         let args_slice = &args;
         let z3 = unsafe {
             Z3_mk_sub(ctx.r, args_slice.len() as u32, args_slice.as_ptr())
         };
-        SUBZ3 {ctx, r: z3, args}.r 
+        ctx.check_error()?;
+        Ok(SUBZ3 {ctx, r: Ast::new(ctx, z3), args}.r)
     }
 }
 
@@ -163,11 +220,20 @@ impl<'ctx> NEGZ3<'ctx> {
     /// NOTE: The arguments must have int or real type.
     /// 
     /// NOTE: See macro! neg_z3!
-    pub fn new(ctx: &'ctx ContextZ3, arg: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match NEGZ3::try_new(ctx, arg) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
         let z3 = unsafe {
             Z3_mk_unary_minus(ctx.r, arg)
         };
-        NEGZ3 {ctx, r: z3, arg}.r  
+        ctx.check_error()?;
+        Ok(NEGZ3 {ctx, r: Ast::new(ctx, z3), arg}.r)
     }
 }
 
@@ -177,11 +243,21 @@ impl <'ctx> POWZ3<'ctx> {
     /// NOTE: The arguments must have int or real type.
     /// 
     /// NOTE: See macro! pow_z3!
-    pub fn new(ctx: &'ctx ContextZ3, arg1: Z3_ast, arg2: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match POWZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
         let z3 = unsafe {
             Z3_mk_power(ctx.r, arg1, arg2)
         };
-        POWZ3 {ctx, arg1, arg2, r: z3}.r
+        ctx.check_error()?;
+        Ok(POWZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -210,7 +286,7 @@ macro_rules! mul_z3 {
         {
             let mut temp_vec = Vec::new();
             $(
-                temp_vec.push($x);
+                temp_vec.push($crate::Ast::new($ctx, $crate::AsAstZ3::as_ast(&$x)));
             )*
             MULZ3::new($ctx, temp_vec)
         }
@@ -296,7 +372,7 @@ macro_rules! add_z3 {
         {
             let mut temp_vec = Vec::new();
             $(
-                temp_vec.push($x);
+                temp_vec.push($crate::Ast::new($ctx, $crate::AsAstZ3::as_ast(&$x)));
             )*
             ADDZ3::new($ctx, temp_vec)
         }
@@ -328,7 +404,7 @@ macro_rules! sub_z3 {
         {
             let mut temp_vec = Vec::new();
             $(
-                temp_vec.push($x);
+                temp_vec.push($crate::Ast::new($ctx, $crate::AsAstZ3::as_ast(&$x)));
             )*
             SUBZ3::new($ctx, temp_vec)
         }
@@ -415,10 +491,10 @@ fn test_new_div(){
     let x1 = IntVarZ3::new(&ctx, &intsort, "x1");
     let x3 = RealVarZ3::new(&ctx, &realsort, "x3");
 
-    let div1 = DIVZ3::new(&ctx, int1, int2);
-    let div2 = DIVZ3::new(&ctx, int1, real2);
+    let div1 = DIVZ3::new(&ctx, &int1, int2);
+    let div2 = DIVZ3::new(&ctx, &int1, &real2);
     let div3 = DIVZ3::new(&ctx, x1, real2);
-    let div4 = DIVZ3::new(&ctx, x3, int1);
+    let div4 = DIVZ3::new(&ctx, &x3, int1);
     let div5 = DIVZ3::new(&ctx, x3, real1);
 
     assert_eq!("(div 7 (- 1012))", ast_to_string_z3!(&ctx, div1));
@@ -440,8 +516,8 @@ fn test_new_mod(){
     let x1 = IntVarZ3::new(&ctx, &intsort, "x1");
     let x2 = IntVarZ3::new(&ctx, &intsort, "x2");
 
-    let mod1 = MODZ3::new(&ctx, int1, int2);
-    let mod2 = MODZ3::new(&ctx, x1, x2);
+    let mod1 = MODZ3::new(&ctx, &int1, &int2);
+    let mod2 = MODZ3::new(&ctx, &x1, &x2);
     let mod3 = MODZ3::new(&ctx, x1, int1);
     let mod4 = MODZ3::new(&ctx, int2, x2);
 
@@ -463,8 +539,8 @@ fn test_new_rem(){
     let x1 = IntVarZ3::new(&ctx, &intsort, "x1");
     let x2 = IntVarZ3::new(&ctx, &intsort, "x2");
 
-    let rem1 = REMZ3::new(&ctx, int1, int2);
-    let rem2 = REMZ3::new(&ctx, x1, x2);
+    let rem1 = REMZ3::new(&ctx, &int1, &int2);
+    let rem2 = REMZ3::new(&ctx, &x1, &x2);
     let rem3 = REMZ3::new(&ctx, x1, int1);
     let rem4 = REMZ3::new(&ctx, int2, x2);
 
@@ -566,8 +642,8 @@ fn test_new_pow(){
     let x1 = IntVarZ3::new(&ctx, &intsort, "x1");
     let x2 = IntVarZ3::new(&ctx, &intsort, "x2");
 
-    let pow1 = POWZ3::new(&ctx, int1, int2);
-    let pow2 = POWZ3::new(&ctx, x1, x2);
+    let pow1 = POWZ3::new(&ctx, &int1, &int2);
+    let pow2 = POWZ3::new(&ctx, &x1, &x2);
     let pow3 = POWZ3::new(&ctx, x1, int1);
     let pow4 = POWZ3::new(&ctx, int2, x2);
 
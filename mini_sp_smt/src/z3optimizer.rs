@@ -1,12 +1,25 @@
 //! Z3 optimizer for SP
 
-use std::ffi::{CStr};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ffi::CString;
 use z3_sys::*;
 use super::*;
+use crate::z3eval::ast_to_value_z3;
 
 pub struct OptimizerZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub r: Z3_optimize,
+    pub soft: RefCell<Vec<SoftConstraintZ3<'ctx>>>,
+    pub objectives: RefCell<BTreeMap<u32, Ast<'ctx>>>
+}
+
+/// A soft constraint added with `OptAssertSoftZ3`, violating it costs `weight`.
+#[derive(Clone)]
+pub struct SoftConstraintZ3<'ctx> {
+    pub cst: Ast<'ctx>,
+    pub weight: u32,
+    pub group: String
 }
 
 pub struct OptAssertZ3<'ctx, 'opt> {
@@ -16,31 +29,104 @@ pub struct OptAssertZ3<'ctx, 'opt> {
     pub r: ()
 }
 
+pub struct OptAssertSoftZ3<'ctx, 'opt, 'g> {
+    pub ctx: &'ctx ContextZ3,
+    pub opt: &'opt OptimizerZ3<'ctx>,
+    pub cst: Z3_ast,
+    pub weight: u32,
+    pub group: &'g str,
+    pub r: u32
+}
+
+pub struct OptGetViolatedSoftZ3<'ctx, 'opt> {
+    pub ctx: &'ctx ContextZ3,
+    pub opt: &'opt OptimizerZ3<'ctx>,
+    pub r: Vec<SoftConstraintZ3<'ctx>>
+}
+
+pub struct OptGetPenaltyZ3<'ctx, 'opt> {
+    pub ctx: &'ctx ContextZ3,
+    pub opt: &'opt OptimizerZ3<'ctx>,
+    pub r: u32
+}
+
 pub struct OptMaximizeZ3<'ctx, 'opt> {
     pub ctx: &'ctx ContextZ3,
     pub opt: &'opt OptimizerZ3<'ctx>,
     pub cst: Z3_ast,
-    pub r: ()
+    pub r: u32
 }
 
 pub struct OptMinimizeZ3<'ctx, 'opt> {
     pub ctx: &'ctx ContextZ3,
     pub opt: &'opt OptimizerZ3<'ctx>,
     pub cst: Z3_ast,
+    pub r: u32
+}
+
+/// How the optimizer combines several objectives.
+#[derive(Debug, PartialEq, Clone, Copy, PartialOrd, Eq, Ord)]
+pub enum OptPriorityZ3 {
+    /// Optimize the objectives one after the other, in the order they were added.
+    Lex,
+    /// Find models that can't be improved in one objective without getting worse in another.
+    Pareto,
+    /// Optimize every objective independently of the others.
+    Box
+}
+
+pub struct OptSetPriorityZ3<'ctx, 'opt> {
+    pub ctx: &'ctx ContextZ3,
+    pub opt: &'opt OptimizerZ3<'ctx>,
+    pub priority: OptPriorityZ3,
     pub r: ()
 }
 
+pub struct OptGetLowerZ3<'ctx, 'opt> {
+    pub ctx: &'ctx ContextZ3,
+    pub opt: &'opt OptimizerZ3<'ctx>,
+    pub idx: u32,
+    pub r: ModelValueZ3
+}
+
+pub struct OptGetUpperZ3<'ctx, 'opt> {
+    pub ctx: &'ctx ContextZ3,
+    pub opt: &'opt OptimizerZ3<'ctx>,
+    pub idx: u32,
+    pub r: ModelValueZ3
+}
+
+pub struct OptGetObjectiveValueZ3<'ctx, 'opt> {
+    pub ctx: &'ctx ContextZ3,
+    pub opt: &'opt OptimizerZ3<'ctx>,
+    pub idx: u32,
+    pub r: ModelValueZ3
+}
+
+/// A Pareto-optimal model and the values of the objectives in it, by objective index.
+pub struct ParetoPointZ3<'ctx> {
+    pub model: Model<'ctx>,
+    pub values: Vec<ModelValueZ3>
+}
+
+/// Iterator over the Pareto front of an optimizer, see `OptParetoZ3::new`.
+pub struct OptParetoZ3<'ctx, 'opt> {
+    pub ctx: &'ctx ContextZ3,
+    pub opt: &'opt OptimizerZ3<'ctx>,
+    pub done: bool
+}
+
 pub struct OptCheckZ3<'ctx, 'opt> {
     pub ctx: &'ctx ContextZ3,
     pub opt: &'opt OptimizerZ3<'ctx>,
     pub args: Vec<Z3_ast>,
-    pub r: Z3_lbool
+    pub r: SatResultZ3
 }
 
 pub struct OptGetModelZ3<'ctx, 'opt> {
     pub ctx: &'ctx ContextZ3,
     pub opt: &'opt OptimizerZ3<'ctx>,
-    pub r: Z3_model
+    pub r: Model<'ctx>
 }
 
 pub struct OptGetStringZ3<'ctx, 'opt> {
@@ -49,6 +135,20 @@ pub struct OptGetStringZ3<'ctx, 'opt> {
     pub r: String
 }
 
+pub struct OptFromSmtLib2Z3<'ctx, 'opt, 's> {
+    pub ctx: &'ctx ContextZ3,
+    pub opt: &'opt OptimizerZ3<'ctx>,
+    pub script: &'s str,
+    pub r: ()
+}
+
+pub struct OptFromSmtLib2FileZ3<'ctx, 'opt, 'f> {
+    pub ctx: &'ctx ContextZ3,
+    pub opt: &'opt OptimizerZ3<'ctx>,
+    pub file: &'f str,
+    pub r: ()
+}
+
 impl <'ctx> OptimizerZ3<'ctx> {
     /// Create a new optimization context.
     /// 
@@ -59,7 +159,7 @@ impl <'ctx> OptimizerZ3<'ctx> {
             Z3_optimize_inc_ref(ctx.r, opt);
             opt
         };
-        OptimizerZ3 {ctx, r: z3}
+        OptimizerZ3 {ctx, r: z3, soft: RefCell::new(vec!()), objectives: RefCell::new(BTreeMap::new())}
     }
 }
 
@@ -67,11 +167,105 @@ impl <'ctx, 'opt> OptAssertZ3<'ctx, 'opt> {
     /// Assert hard constraint to the optimization context.
     /// 
     /// NOTE: See macro! `opt_assert_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, cst: Z3_ast) -> () {
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, cst: impl AsAstZ3<'ctx>) -> () {
+        match OptAssertZ3::try_new(ctx, opt, cst) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, cst: impl AsAstZ3<'ctx>) -> Result<(), ErrorZ3> {
+        unsafe {
+            Z3_optimize_assert(ctx.r, opt.r, cst.as_ast())
+        };
+        ctx.check_error()
+    }
+}
+
+impl <'ctx, 'opt, 'g> OptAssertSoftZ3<'ctx, 'opt, 'g> {
+    /// Assert soft constraint to the optimization context.
+    /// - `ctx`: - context
+    /// - `opt`: - optimization context
+    /// - `cst`: - boolean formula
+    /// - `weight`: - penalty for violating the constraint
+    /// - `group`: - soft constraints of a group are minimized together as one objective
+    ///
+    /// Returns the index of the objective of the group.
+    ///
+    /// NOTE: See macro! `opt_assert_soft_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, cst: impl AsAstZ3<'ctx>, weight: u32, group: &'g str) -> u32 {
+        match OptAssertSoftZ3::try_new(ctx, opt, cst, weight, group) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, cst: impl AsAstZ3<'ctx>, weight: u32, group: &'g str) -> Result<u32, ErrorZ3> {
+        let cst = cst.as_ast();
+        let str_weight = CString::new(weight.to_string()).unwrap();
+        let str_group = match CString::new(group) {
+            Ok(x) => x,
+            Err(_) => return Err(ErrorZ3 {code: Z3_error_code_Z3_INVALID_ARG, msg: "The group name contains a nul byte.".to_string()})
+        };
         let z3 = unsafe {
-            Z3_optimize_assert(ctx.r, opt.r, cst);
+            let id = Z3_mk_string_symbol(ctx.r, str_group.as_ptr());
+            Z3_optimize_assert_soft(ctx.r, opt.r, cst, str_weight.as_ptr(), id)
         };
-        OptAssertZ3 {ctx, opt, cst, r: z3};
+        ctx.check_error()?;
+        opt.soft.borrow_mut().push(SoftConstraintZ3 {cst: Ast::new(ctx, cst), weight, group: group.to_string()});
+        Ok(OptAssertSoftZ3 {ctx, opt, cst, weight, group, r: z3}.r)
+    }
+}
+
+impl <'ctx, 'opt> OptGetViolatedSoftZ3<'ctx, 'opt> {
+    /// The soft constraints that are false in the model of the last `OptCheckZ3::new`,
+    /// constants that the model doesn't assign get their default value (model completion).
+    ///
+    /// NOTE: See macro! `opt_get_violated_soft_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>) -> Vec<SoftConstraintZ3<'ctx>> {
+        match OptGetViolatedSoftZ3::try_new(ctx, opt) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>) -> Result<Vec<SoftConstraintZ3<'ctx>>, ErrorZ3> {
+        let model = OptGetModelZ3::try_new(ctx, opt)?;
+        let mut z3 = vec!();
+        for soft in opt.soft.borrow().iter() {
+            match ModelEvalZ3::try_new(ctx, &model, &soft.cst, true)? {
+                ModelValueZ3::Bool(true) => (),
+                ModelValueZ3::Bool(false) => z3.push(soft.clone()),
+                other => return Err(ErrorZ3 {
+                    code: Z3_error_code_Z3_SORT_ERROR,
+                    msg: format!("The soft constraint {} evaluated to {}, not to a bool.", soft.cst, other)
+                })
+            }
+        }
+        Ok(OptGetViolatedSoftZ3 {ctx, opt, r: z3}.r)
+    }
+}
+
+impl <'ctx, 'opt> OptGetPenaltyZ3<'ctx, 'opt> {
+    /// The sum of the weights of the violated soft constraints, over all groups.
+    ///
+    /// NOTE: See macro! `opt_get_penalty_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>) -> u32 {
+        match OptGetPenaltyZ3::try_new(ctx, opt) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>) -> Result<u32, ErrorZ3> {
+        let mut z3: u32 = 0;
+        for soft in OptGetViolatedSoftZ3::try_new(ctx, opt)? {
+            z3 = match z3.checked_add(soft.weight) {
+                Some(x) => x,
+                None => return Err(ErrorZ3 {code: Z3_error_code_Z3_EXCEPTION, msg: "The penalty does not fit into a u32.".to_string()})
+            };
+        }
+        Ok(OptGetPenaltyZ3 {ctx, opt, r: z3}.r)
     }
 }
 
@@ -80,13 +274,25 @@ impl <'ctx, 'opt> OptMaximizeZ3<'ctx, 'opt> {
     /// - `ctx`: - context
     /// - `opt`: - optimization context
     /// - `cst`: - arithmetical term
+    ///
+    /// Returns the index of the objective, see `OptGetLowerZ3`, `OptGetUpperZ3` and `OptGetObjectiveValueZ3`.
     /// 
     /// NOTE: See macro! `opt_maxizime_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, cst: Z3_ast) -> () {
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, cst: impl AsAstZ3<'ctx>) -> u32 {
+        match OptMaximizeZ3::try_new(ctx, opt, cst) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, cst: impl AsAstZ3<'ctx>) -> Result<u32, ErrorZ3> {
+        let cst = cst.as_ast();
         let z3 = unsafe {
-            Z3_optimize_maximize(ctx.r, opt.r, cst);
+            Z3_optimize_maximize(ctx.r, opt.r, cst)
         };
-        OptMaximizeZ3 {ctx, opt, cst, r: z3};
+        ctx.check_error()?;
+        opt.objectives.borrow_mut().insert(z3, Ast::new(ctx, cst));
+        Ok(OptMaximizeZ3 {ctx, opt, cst, r: z3}.r)
     }
 }
 
@@ -95,13 +301,216 @@ impl <'ctx, 'opt> OptMinimizeZ3<'ctx, 'opt> {
     /// - `ctx`: - context
     /// - `opt`: - optimization context
     /// - `cst`: - arithmetical term
+    ///
+    /// Returns the index of the objective, see `OptGetLowerZ3`, `OptGetUpperZ3` and `OptGetObjectiveValueZ3`.
     /// 
     /// NOTE: See macro! `opt_minimize_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, cst: Z3_ast) -> () {
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, cst: impl AsAstZ3<'ctx>) -> u32 {
+        match OptMinimizeZ3::try_new(ctx, opt, cst) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, cst: impl AsAstZ3<'ctx>) -> Result<u32, ErrorZ3> {
+        let cst = cst.as_ast();
         let z3 = unsafe {
-            Z3_optimize_minimize(ctx.r, opt.r, cst);
+            Z3_optimize_minimize(ctx.r, opt.r, cst)
+        };
+        ctx.check_error()?;
+        opt.objectives.borrow_mut().insert(z3, Ast::new(ctx, cst));
+        Ok(OptMinimizeZ3 {ctx, opt, cst, r: z3}.r)
+    }
+}
+
+impl <'ctx, 'opt> OptSetPriorityZ3<'ctx, 'opt> {
+    /// Set how the objectives are combined, the default is `OptPriorityZ3::Lex`.
+    ///
+    /// NOTE: See macro! `opt_set_priority_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, priority: OptPriorityZ3) -> () {
+        match OptSetPriorityZ3::try_new(ctx, opt, priority) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, priority: OptPriorityZ3) -> Result<(), ErrorZ3> {
+        let str_key = CString::new("priority").unwrap();
+        let str_value = CString::new(match priority {
+            OptPriorityZ3::Lex => "lex",
+            OptPriorityZ3::Pareto => "pareto",
+            OptPriorityZ3::Box => "box"
+        }).unwrap();
+        unsafe {
+            let params = Z3_mk_params(ctx.r);
+            Z3_params_inc_ref(ctx.r, params);
+            Z3_params_set_symbol(ctx.r, params, Z3_mk_string_symbol(ctx.r, str_key.as_ptr()), Z3_mk_string_symbol(ctx.r, str_value.as_ptr()));
+            Z3_optimize_set_params(ctx.r, opt.r, params);
+            Z3_params_dec_ref(ctx.r, params);
+        };
+        ctx.check_error()
+    }
+}
+
+// the objectives as z3 keeps them, maximized terms are negated
+fn opt_objectives_z3<'ctx>(ctx: &'ctx ContextZ3, opt: &OptimizerZ3<'ctx>) -> Result<AstVector<'ctx>, ErrorZ3> {
+    let objectives = unsafe {
+        Z3_optimize_get_objectives(ctx.r, opt.r)
+    };
+    ctx.check_error()?;
+    Ok(AstVector::new(ctx, objectives))
+}
+
+// a bound is `inf * oo + value + eps * epsilon`, an infinite bound is reported as `oo` or `-oo`
+// and the epsilon of a strict bound is dropped
+fn opt_bound_z3(ctx: &ContextZ3, bound: &AstVector) -> Result<ModelValueZ3, ErrorZ3> {
+    let (inf, value) = unsafe {
+        (Ast::new(ctx, Z3_ast_vector_get(ctx.r, bound.r, 0)), Ast::new(ctx, Z3_ast_vector_get(ctx.r, bound.r, 1)))
+    };
+    ctx.check_error()?;
+    match ast_to_value_z3(ctx, &inf)?.as_f64() {
+        Some(x) if x > 0.0 => Ok(ModelValueZ3::Other("oo".to_string())),
+        Some(x) if x < 0.0 => Ok(ModelValueZ3::Other("-oo".to_string())),
+        _ => ast_to_value_z3(ctx, &value)
+    }
+}
+
+impl <'ctx, 'opt> OptGetLowerZ3<'ctx, 'opt> {
+    /// Lower bound of the objective `idx` after the last `OptCheckZ3::new`, an
+    /// unbounded objective has the bound `ModelValueZ3::Other("-oo")` or `Other("oo")`.
+    ///
+    /// NOTE: See macro! `opt_get_lower_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, idx: u32) -> ModelValueZ3 {
+        match OptGetLowerZ3::try_new(ctx, opt, idx) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, idx: u32) -> Result<ModelValueZ3, ErrorZ3> {
+        let bound = unsafe {
+            Z3_optimize_get_lower_as_vector(ctx.r, opt.r, idx)
+        };
+        ctx.check_error()?;
+        let bound = AstVector::new(ctx, bound);
+        Ok(OptGetLowerZ3 {ctx, opt, idx, r: opt_bound_z3(ctx, &bound)?}.r)
+    }
+}
+
+impl <'ctx, 'opt> OptGetUpperZ3<'ctx, 'opt> {
+    /// Upper bound of the objective `idx` after the last `OptCheckZ3::new`, an
+    /// unbounded objective has the bound `ModelValueZ3::Other("-oo")` or `Other("oo")`.
+    ///
+    /// NOTE: See macro! `opt_get_upper_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, idx: u32) -> ModelValueZ3 {
+        match OptGetUpperZ3::try_new(ctx, opt, idx) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, idx: u32) -> Result<ModelValueZ3, ErrorZ3> {
+        let bound = unsafe {
+            Z3_optimize_get_upper_as_vector(ctx.r, opt.r, idx)
+        };
+        ctx.check_error()?;
+        let bound = AstVector::new(ctx, bound);
+        Ok(OptGetUpperZ3 {ctx, opt, idx, r: opt_bound_z3(ctx, &bound)?}.r)
+    }
+}
+
+impl <'ctx, 'opt> OptGetObjectiveValueZ3<'ctx, 'opt> {
+    /// Value of the objective `idx` in the model of the last `OptCheckZ3::new`, the
+    /// objective of a group of soft constraints is the penalty of the group.
+    ///
+    /// NOTE: See macro! `opt_get_objective_value_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, idx: u32) -> ModelValueZ3 {
+        match OptGetObjectiveValueZ3::try_new(ctx, opt, idx) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, idx: u32) -> Result<ModelValueZ3, ErrorZ3> {
+        let model = OptGetModelZ3::try_new(ctx, opt)?;
+        // z3 keeps maximized terms negated, so the added terms are evaluated instead
+        let added = opt.objectives.borrow().get(&idx).cloned();
+        let term = match added {
+            Some(term) => term,
+            None => {
+                let objectives = opt_objectives_z3(ctx, opt)?;
+                let term = unsafe {
+                    Z3_ast_vector_get(ctx.r, objectives.r, idx)
+                };
+                ctx.check_error()?;
+                Ast::new(ctx, term)
+            }
         };
-        OptMinimizeZ3 {ctx, opt, cst, r: z3};
+        let z3 = ModelEvalZ3::try_new(ctx, &model, &term, true)?;
+        Ok(OptGetObjectiveValueZ3 {ctx, opt, idx, r: z3}.r)
+    }
+}
+
+impl <'ctx, 'opt> OptParetoZ3<'ctx, 'opt> {
+    /// Iterate over the Pareto-optimal models of the optimizer, every step
+    /// checks the optimizer again until it is unsat, an unknown check result
+    /// is returned as an error and ends the iteration.
+    ///
+    /// Sets the priority of the optimizer to `OptPriorityZ3::Pareto`. With fewer
+    /// than two objectives there is no front, the only point is the optimum.
+    ///
+    /// NOTE: See macro! `opt_pareto_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>) -> OptParetoZ3<'ctx, 'opt> {
+        match OptParetoZ3::try_new(ctx, opt) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>) -> Result<OptParetoZ3<'ctx, 'opt>, ErrorZ3> {
+        OptSetPriorityZ3::try_new(ctx, opt, OptPriorityZ3::Pareto)?;
+        Ok(OptParetoZ3 {ctx, opt, done: false})
+    }
+
+    // check for the next point, none if the front is exhausted
+    fn next_point(&mut self) -> Result<Option<ParetoPointZ3<'ctx>>, ErrorZ3> {
+        match OptCheckZ3::try_new(self.ctx, self.opt, Vec::<Ast>::new())? {
+            SatResultZ3::Sat => (),
+            SatResultZ3::Unsat => return Ok(None),
+            SatResultZ3::Unknown(reason) =>
+                return Err(ErrorZ3 {code: Z3_error_code_Z3_EXCEPTION, msg: format!("The optimizer returned unknown: {}", reason)})
+        }
+        let nr_objectives = unsafe {
+            Z3_ast_vector_size(self.ctx.r, opt_objectives_z3(self.ctx, self.opt)?.r)
+        };
+        // z3 only moves along the front with several objectives, otherwise it keeps returning the optimum
+        self.done = nr_objectives < 2;
+        let mut values = vec!();
+        for i in 0..nr_objectives {
+            values.push(OptGetObjectiveValueZ3::try_new(self.ctx, self.opt, i)?);
+        }
+        Ok(Some(ParetoPointZ3 {model: OptGetModelZ3::try_new(self.ctx, self.opt)?, values}))
+    }
+}
+
+impl <'ctx, 'opt> Iterator for OptParetoZ3<'ctx, 'opt> {
+    type Item = Result<ParetoPointZ3<'ctx>, ErrorZ3>;
+
+    fn next(&mut self) -> Option<Result<ParetoPointZ3<'ctx>, ErrorZ3>> {
+        if self.done {
+            return None
+        }
+        match self.next_point() {
+            Ok(Some(x)) => Some(Ok(x)),
+            Ok(None) => {
+                self.done = true;
+                None
+            },
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
     }
 }
 
@@ -111,18 +520,41 @@ impl <'ctx, 'opt> OptCheckZ3<'ctx, 'opt> {
     /// - `ctx`: - context
     /// - `opt`: - optimization context
     /// - `args`: - vector of additional assumptions
+    ///
+    /// The check returns `SatResultZ3::Unknown` with the reason if the optimizer
+    /// runs out of time or resources, or if it is stopped with an `InterruptHandleZ3`.
     /// 
     /// NOTE: See macro! `opt_check_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, args: Vec<Z3_ast>) -> Z3_lbool {
-        let z3 = unsafe {
-            let args_slice = &args;
-            let opt_res = Z3_optimize_check(ctx.r, opt.r, args_slice.len() as u32, args_slice.as_ptr());
-            opt_res
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, args: Vec<impl AsAstZ3<'ctx>>) -> SatResultZ3 {
+        match OptCheckZ3::try_new(ctx, opt, args) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, args: Vec<impl AsAstZ3<'ctx>>) -> Result<SatResultZ3, ErrorZ3> {
+        let args: Vec<Z3_ast> = args.iter().map(|x| x.as_ast()).collect();
+        let res = unsafe {
+            Z3_optimize_check(ctx.r, opt.r, args.len() as u32, args.as_ptr())
         };
-        OptCheckZ3 {ctx, opt, r: z3, args}.r
+        ctx.check_error()?;
+        let z3 = match res {
+            1 => SatResultZ3::Sat,
+            -1 => SatResultZ3::Unsat,
+            _ => SatResultZ3::Unknown(opt_reason_unknown_z3(ctx, opt)?)
+        };
+        Ok(OptCheckZ3 {ctx, opt, r: z3, args}.r)
     }
 }
 
+// the reason z3 gives for the unknown result of the last check of the optimizer
+fn opt_reason_unknown_z3(ctx: &ContextZ3, opt: &OptimizerZ3) -> Result<String, ErrorZ3> {
+    let reason = unsafe {
+        Z3_optimize_get_reason_unknown(ctx.r, opt.r)
+    };
+    ctx.check_string(reason)
+}
+
 impl<'ctx, 'opt> OptGetModelZ3<'ctx, 'opt> {
     /// Retrieve the model for the last `OptCheckZ3::new`
     ///
@@ -131,11 +563,22 @@ impl<'ctx, 'opt> OptGetModelZ3<'ctx, 'opt> {
     /// solver, or if the result was `Z3_L_FALSE`.
     /// 
     /// NOTE: See macro! `opt_get_model_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>) -> Z3_model {
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>) -> Model<'ctx> {
+        match OptGetModelZ3::try_new(ctx, opt) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>) -> Result<Model<'ctx>, ErrorZ3> {
         let z3 = unsafe {
             Z3_optimize_get_model(ctx.r, opt.r)
         };
-        OptGetModelZ3 {ctx, r: z3, opt}.r
+        ctx.check_error()?;
+        if z3.is_null() {
+            return Err(ErrorZ3 {code: Z3_error_code_Z3_INVALID_USAGE, msg: "There is no model, the last check of the optimizer was not sat.".to_string()})
+        }
+        Ok(OptGetModelZ3 {ctx, r: Model::new(ctx, z3), opt}.r)
     }
 }
 
@@ -144,10 +587,66 @@ impl<'ctx, 'opt> OptGetStringZ3<'ctx, 'opt> {
     /// 
     /// NOTE: See macro! `opt_get_string_z3!`
     pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>) -> String {
+        match OptGetStringZ3::try_new(ctx, opt) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>) -> Result<String, ErrorZ3> {
         let z3 = unsafe {
-            CStr::from_ptr(Z3_optimize_to_string(ctx.r, opt.r)).to_str().unwrap().to_owned()
+            Z3_optimize_to_string(ctx.r, opt.r)
         };
-        OptGetStringZ3 {ctx, opt, r: z3}.r
+        let z3 = ctx.check_string(z3)?;
+        Ok(OptGetStringZ3 {ctx, opt, r: z3}.r)
+    }
+}
+
+impl <'ctx, 'opt, 's> OptFromSmtLib2Z3<'ctx, 'opt, 's> {
+    /// Parse an SMT-LIB2 script and assert the formulas into the OptimizerZ3.
+    ///
+    /// The `sorts` and `decls` are sorts and function declarations already created in 
+    /// the context that the script can refer to, see `ParseSmtLib2Z3`. If the script 
+    /// does not parse, nothing is asserted and the parse error is returned.
+    ///
+    /// NOTE: See macro! `opt_from_smtlib2_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, script: &'s str, sorts: Vec<impl AsSortZ3>, decls: Vec<impl AsFuncDeclZ3>) -> () {
+        match OptFromSmtLib2Z3::try_new(ctx, opt, script, sorts, decls) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, script: &'s str, sorts: Vec<impl AsSortZ3>, decls: Vec<impl AsFuncDeclZ3>) -> Result<(), ErrorZ3> {
+        for cst in ParseSmtLib2Z3::try_new(ctx, script, sorts, decls)? {
+            unsafe {
+                Z3_optimize_assert(ctx.r, opt.r, cst.r);
+            }
+            ctx.check_error()?;
+        }
+        Ok(())
+    }
+}
+
+impl <'ctx, 'opt, 'f> OptFromSmtLib2FileZ3<'ctx, 'opt, 'f> {
+    /// Same as `OptFromSmtLib2Z3`, but the script is read from `file`.
+    ///
+    /// NOTE: See macro! `opt_from_smtlib2_file_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, file: &'f str, sorts: Vec<impl AsSortZ3>, decls: Vec<impl AsFuncDeclZ3>) -> () {
+        match OptFromSmtLib2FileZ3::try_new(ctx, opt, file, sorts, decls) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>, file: &'f str, sorts: Vec<impl AsSortZ3>, decls: Vec<impl AsFuncDeclZ3>) -> Result<(), ErrorZ3> {
+        for cst in ParseSmtLib2FileZ3::try_new(ctx, file, sorts, decls)? {
+            unsafe {
+                Z3_optimize_assert(ctx.r, opt.r, cst.r);
+            }
+            ctx.check_error()?;
+        }
+        Ok(())
     }
 }
 
@@ -195,6 +694,85 @@ macro_rules! opt_assert_z3 {
     }
 }
 
+/// assert soft optimization constraint, in the default group if no group is given
+/// 
+/// Macro rule for:
+/// ```text
+/// z3optimizer::OptAssertSoftZ3::new(&ctx, &opt, a, 2, "group")
+/// ```
+/// Using a specific context:
+/// ```text
+/// opt_assert_soft_z3!(&ctx, &opt, a, 2)
+/// opt_assert_soft_z3!(&ctx, &opt, a, 2, "group")
+/// ```
+#[macro_export]
+macro_rules! opt_assert_soft_z3 {
+    ($ctx:expr, $opt:expr, $a:expr, $w:expr) => {
+        OptAssertSoftZ3::new($ctx, $opt, $a, $w, "")
+    };
+    ($ctx:expr, $opt:expr, $a:expr, $w:expr, $g:expr) => {
+        OptAssertSoftZ3::new($ctx, $opt, $a, $w, $g)
+    };
+}
+
+/// get the soft constraints violated by the last check
+#[macro_export]
+macro_rules! opt_get_violated_soft_z3 {
+    ($ctx:expr, $opt:expr) => {
+        OptGetViolatedSoftZ3::new($ctx, $opt)
+    }
+}
+
+/// get the total penalty of the soft constraints violated by the last check
+#[macro_export]
+macro_rules! opt_get_penalty_z3 {
+    ($ctx:expr, $opt:expr) => {
+        OptGetPenaltyZ3::new($ctx, $opt)
+    }
+}
+
+/// parse an SMT-LIB2 script and assert it
+/// 
+/// Macro rule for:
+/// ```text
+/// z3optimizer::OptFromSmtLib2Z3::new(&ctx, &opt, script, vec!(sort), vec!(decl))
+/// ```
+/// Using a specific context, without or with known sorts and declarations:
+/// ```text
+/// opt_from_smtlib2_z3!(&ctx, &opt, script)
+/// opt_from_smtlib2_z3!(&ctx, &opt, script, vec!(sort), vec!(decl))
+/// ```
+#[macro_export]
+macro_rules! opt_from_smtlib2_z3 {
+    ($ctx:expr, $opt:expr, $s:expr) => {
+        OptFromSmtLib2Z3::new($ctx, $opt, $s, Vec::<Sort>::new(), Vec::<FuncDecl>::new())
+    };
+    ($ctx:expr, $opt:expr, $s:expr, $b:expr, $c:expr) => {
+        OptFromSmtLib2Z3::new($ctx, $opt, $s, $b, $c)
+    };
+}
+
+/// parse an SMT-LIB2 file and assert it
+/// 
+/// Macro rule for:
+/// ```text
+/// z3optimizer::OptFromSmtLib2FileZ3::new(&ctx, &opt, file, vec!(sort), vec!(decl))
+/// ```
+/// Using a specific context, without or with known sorts and declarations:
+/// ```text
+/// opt_from_smtlib2_file_z3!(&ctx, &opt, file)
+/// opt_from_smtlib2_file_z3!(&ctx, &opt, file, vec!(sort), vec!(decl))
+/// ```
+#[macro_export]
+macro_rules! opt_from_smtlib2_file_z3 {
+    ($ctx:expr, $opt:expr, $f:expr) => {
+        OptFromSmtLib2FileZ3::new($ctx, $opt, $f, Vec::<Sort>::new(), Vec::<FuncDecl>::new())
+    };
+    ($ctx:expr, $opt:expr, $f:expr, $b:expr, $c:expr) => {
+        OptFromSmtLib2FileZ3::new($ctx, $opt, $f, $b, $c)
+    };
+}
+
 /// assert a maximization constraint 
 /// 
 /// Macro rule for:
@@ -212,6 +790,46 @@ macro_rules! opt_maximize_z3 {
     }
 }
 
+/// set how the objectives are combined
+#[macro_export]
+macro_rules! opt_set_priority_z3 {
+    ($ctx:expr, $opt:expr, $p:expr) => {
+        OptSetPriorityZ3::new($ctx, $opt, $p)
+    }
+}
+
+/// get the lower bound of an objective
+#[macro_export]
+macro_rules! opt_get_lower_z3 {
+    ($ctx:expr, $opt:expr, $i:expr) => {
+        OptGetLowerZ3::new($ctx, $opt, $i)
+    }
+}
+
+/// get the upper bound of an objective
+#[macro_export]
+macro_rules! opt_get_upper_z3 {
+    ($ctx:expr, $opt:expr, $i:expr) => {
+        OptGetUpperZ3::new($ctx, $opt, $i)
+    }
+}
+
+/// get the value of an objective in the last model
+#[macro_export]
+macro_rules! opt_get_objective_value_z3 {
+    ($ctx:expr, $opt:expr, $i:expr) => {
+        OptGetObjectiveValueZ3::new($ctx, $opt, $i)
+    }
+}
+
+/// iterate over the pareto-optimal models
+#[macro_export]
+macro_rules! opt_pareto_z3 {
+    ($ctx:expr, $opt:expr) => {
+        OptParetoZ3::new($ctx, $opt)
+    }
+}
+
 /// assert a minimization constraint 
 /// 
 /// Macro rule for:
@@ -255,10 +873,7 @@ macro_rules! opt_check_z3 {
     };
     ( $ctx:expr, $opt:expr, $( $x:expr ),* ) => {
         {
-            let temp_vec = Vec::new();
-            $(
-                temp_vec.push($x);
-            )*
+            let temp_vec: Vec<$crate::Ast> = vec!($( $crate::Ast::new($ctx, $crate::AsAstZ3::as_ast(&$x)) ),*);
             OptCheckZ3::new($ctx, $opt, temp_vec)
         }
     };
@@ -312,6 +927,17 @@ fn test_new_oassert(){
     assert_eq!("", model_to_string_z3!(&ctx, model));
 }
 
+#[test]
+fn test_oassert_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+
+    let intsort = IntSortZ3::new(&ctx);
+    assert!(OptAssertZ3::try_new(&ctx, &opt, IntZ3::new(&ctx, &intsort, 1)).is_err());
+    assert_eq!(Ok("(check-sat)\n".to_string()), OptGetStringZ3::try_new(&ctx, &opt));
+}
+
 #[test]
 fn test_new_ocheck(){
     let conf = ConfigZ3::new();
@@ -334,9 +960,9 @@ fn test_new_ocheck(){
 (assert (= y (- (/ 271549371.0 500000.0))))
 (check-sat)\n", opt_to_string_z3!(&ctx, &opt));
 
-    let res1 = OptCheckZ3::new(&ctx, &opt, vec!());
+    let res1 = OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
     println!("This is the return of the check:");
-    println!("{}", res1);
+    println!("{:?}", res1);
 
     println!("This is the opt context with an assertion after the check:");
     assert_eq!("(declare-fun y () Real)
@@ -358,11 +984,11 @@ fn test_new_maximize(){
     let x = IntVarZ3::new(&ctx, &intsort, "x");
     let int1 = IntZ3::new(&ctx, &intsort, 100);
 
-    let lt1 = LTZ3::new(&ctx, x, int1);
+    let lt1 = LTZ3::new(&ctx, &x, int1);
 
     OptAssertZ3::new(&ctx, &opt, lt1);
     OptMaximizeZ3::new(&ctx, &opt, x);
-    OptCheckZ3::new(&ctx, &opt, vec!());
+    OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
 
     let model = opt_get_model_z3!(&ctx, &opt);
     assert_eq!("x -> 99\n", model_to_string_z3!(&ctx, model));
@@ -378,16 +1004,30 @@ fn test_new_minimize(){
     let x = IntVarZ3::new(&ctx, &intsort, "x");
     let int1 = IntZ3::new(&ctx, &intsort, 11);
 
-    let gt1 = GTZ3::new(&ctx, x, int1);
+    let gt1 = GTZ3::new(&ctx, &x, int1);
 
     OptAssertZ3::new(&ctx, &opt, gt1);
     OptMinimizeZ3::new(&ctx, &opt, x);
-    OptCheckZ3::new(&ctx, &opt, vec!());
+    OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
 
     let model = opt_get_model_z3!(&ctx, &opt);
     assert_eq!("x -> 12\n", model_to_string_z3!(&ctx, model));
 }
 
+#[test]
+fn test_maximize_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+
+    let boolsort = BoolSortZ3::new(&ctx);
+    let b = BoolVarZ3::new(&ctx, &boolsort, "b");
+
+    assert!(OptMaximizeZ3::try_new(&ctx, &opt, &b).is_err());
+    assert!(OptMinimizeZ3::try_new(&ctx, &opt, &b).is_err());
+    assert!(opt.objectives.borrow().is_empty());
+}
+
 #[test]
 fn test_opt_macro_1(){
     let cfg = cfg_z3!();
@@ -432,7 +1072,7 @@ fn test_ocheck_macro_1(){
 (check-sat)\n", opt_to_string_z3!(&ctx, &opt));
 
     let res1 = opt_check_z3!(&ctx, &opt, );
-    println!("{}", res1);
+    println!("{:?}", res1);
 }
 
 #[test]
@@ -455,7 +1095,7 @@ fn test_omax_macro_1(){
 (check-sat)\n", opt_to_string_z3!(&ctx, &opt));
 
     let res1 = opt_check_z3!(&ctx, &opt, );
-    println!("{}", res1);
+    println!("{:?}", res1);
 
     let model = opt_get_model_z3!(&ctx, &opt);
     assert_eq!("y -> 99.0\n", model_to_string_z3!(&ctx, model));
@@ -481,8 +1121,231 @@ fn test_omin_macro_1(){
 (check-sat)\n", opt_to_string_z3!(&ctx, &opt));
 
     let res1 = opt_check_z3!(&ctx, &opt, );
-    println!("{}", res1);
+    println!("{:?}", res1);
 
     let model = opt_get_model_z3!(&ctx, &opt);
     assert_eq!("y -> 12.0\n", model_to_string_z3!(&ctx, model));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_opt_from_smtlib2_1(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    OptFromSmtLib2Z3::new(&ctx, &opt, "(assert (< x 10)) (assert (> x 2))", 
+        Vec::<Sort>::new(), vec!(GetAppDeclZ3::new(&ctx, &x)));
+    OptMaximizeZ3::new(&ctx, &opt, &x);
+    assert_eq!(SatResultZ3::Sat, OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new()));
+
+    let model = OptGetModelZ3::new(&ctx, &opt);
+    assert_eq!("x -> 9\n", model_to_string_z3!(&ctx, model));
+    assert!(OptFromSmtLib2Z3::try_new(&ctx, &opt, "(assert (< z 10))", Vec::<Sort>::new(), Vec::<FuncDecl>::new()).is_err());
+}
+
+#[test]
+fn test_opt_from_smtlib2_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let opt = opt_z3!(&ctx);
+
+    let y = int_var_z3!(&ctx, "y");
+    opt_from_smtlib2_z3!(&ctx, &opt, "(assert (> y 11))", Vec::<Sort>::new(), vec!(get_app_decl_z3!(&ctx, &y)));
+    opt_minimize_z3!(&ctx, &opt, &y);
+    opt_check_z3!(&ctx, &opt, );
+
+    let model = opt_get_model_z3!(&ctx, &opt);
+    assert_eq!("y -> 12\n", model_to_string_z3!(&ctx, model));
+}
+
+#[test]
+fn test_opt_assert_soft(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    // a path from room a to room c goes through room b or room d
+    let via_b = BoolVarZ3::new(&ctx, &boolsort, "via_b");
+    let via_d = BoolVarZ3::new(&ctx, &boolsort, "via_d");
+    OptAssertZ3::new(&ctx, &opt, ORZ3::new(&ctx, vec!(&via_b, &via_d)));
+
+    assert_eq!(0, OptAssertSoftZ3::new(&ctx, &opt, NOTZ3::new(&ctx, &via_b), 5, "rooms"));
+    assert_eq!(0, OptAssertSoftZ3::new(&ctx, &opt, NOTZ3::new(&ctx, &via_d), 2, "rooms"));
+
+    // prefer the left gripper, but it is broken
+    let left = BoolVarZ3::new(&ctx, &boolsort, "left");
+    OptAssertZ3::new(&ctx, &opt, NOTZ3::new(&ctx, &left));
+    assert_eq!(1, OptAssertSoftZ3::new(&ctx, &opt, &left, 1, "grippers"));
+
+    assert_eq!(SatResultZ3::Sat, OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new()));
+
+    let violated = OptGetViolatedSoftZ3::new(&ctx, &opt);
+    assert_eq!(vec!("(not via_d)", "left"), violated.iter().map(|x| x.cst.to_string()).collect::<Vec<String>>());
+    assert_eq!(vec!("rooms", "grippers"), violated.iter().map(|x| x.group.as_str()).collect::<Vec<&str>>());
+    assert_eq!(3, OptGetPenaltyZ3::new(&ctx, &opt));
+}
+
+#[test]
+fn test_opt_assert_soft_none_violated(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    OptAssertSoftZ3::new(&ctx, &opt, GTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 3)), 4, "");
+    OptAssertSoftZ3::new(&ctx, &opt, LTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 5)), 4, "");
+    OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
+
+    assert!(OptGetViolatedSoftZ3::new(&ctx, &opt).is_empty());
+    assert_eq!(0, OptGetPenaltyZ3::new(&ctx, &opt));
+    assert_eq!("x -> 4\n", model_to_string_z3!(&ctx, opt_get_model_z3!(&ctx, &opt)));
+}
+
+#[test]
+fn test_opt_assert_soft_errors(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let x = BoolVarZ3::new(&ctx, &boolsort, "x");
+    let y = BoolVarZ3::new(&ctx, &boolsort, "y");
+    let err = OptAssertSoftZ3::try_new(&ctx, &opt, &x, 1, "gro\0up").unwrap_err();
+    assert_eq!(Z3_error_code_Z3_INVALID_ARG, err.code);
+    assert_eq!("The group name contains a nul byte.", err.msg);
+
+    // both heavy soft constraints are violated, their penalty overflows
+    OptAssertZ3::new(&ctx, &opt, NOTZ3::new(&ctx, &x));
+    OptAssertZ3::new(&ctx, &opt, NOTZ3::new(&ctx, &y));
+    OptAssertSoftZ3::new(&ctx, &opt, &x, u32::MAX, "heavy");
+    OptAssertSoftZ3::new(&ctx, &opt, &y, u32::MAX, "heavy");
+    OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
+
+    assert_eq!(2, OptGetViolatedSoftZ3::new(&ctx, &opt).len());
+    assert!(OptGetPenaltyZ3::try_new(&ctx, &opt).is_err());
+}
+
+#[test]
+fn test_opt_assert_soft_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let opt = opt_z3!(&ctx);
+
+    let x = bool_var_z3!(&ctx, "x");
+    opt_assert_z3!(&ctx, &opt, not_z3!(&ctx, &x));
+    opt_assert_soft_z3!(&ctx, &opt, &x, 7);
+    opt_assert_soft_z3!(&ctx, &opt, not_z3!(&ctx, &x), 1, "other");
+    opt_check_z3!(&ctx, &opt, Vec::<Ast>::new());
+
+    assert_eq!(1, opt_get_violated_soft_z3!(&ctx, &opt).len());
+    assert_eq!(7, opt_get_penalty_z3!(&ctx, &opt));
+}
+
+#[test]
+fn test_opt_priority_lex_and_box(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+
+    // shorter plans cost more energy
+    let len = IntVarZ3::new(&ctx, &intsort, "len");
+    let energy = IntVarZ3::new(&ctx, &intsort, "energy");
+    let constraints = vec!(
+        GEZ3::new(&ctx, &len, IntZ3::new(&ctx, &intsort, 2)),
+        LEZ3::new(&ctx, &len, IntZ3::new(&ctx, &intsort, 8)),
+        GEZ3::new(&ctx, ADDZ3::new(&ctx, vec!(&len, &energy)), IntZ3::new(&ctx, &intsort, 10))
+    );
+
+    let lex = OptimizerZ3::new(&ctx);
+    for cst in &constraints {
+        OptAssertZ3::new(&ctx, &lex, cst);
+    }
+    OptSetPriorityZ3::new(&ctx, &lex, OptPriorityZ3::Lex);
+    assert_eq!(0, OptMinimizeZ3::new(&ctx, &lex, &len));
+    assert_eq!(1, OptMinimizeZ3::new(&ctx, &lex, &energy));
+    assert_eq!(SatResultZ3::Sat, OptCheckZ3::new(&ctx, &lex, Vec::<Ast>::new()));
+    assert_eq!((ModelValueZ3::Int(2), ModelValueZ3::Int(2)), (OptGetLowerZ3::new(&ctx, &lex, 0), OptGetUpperZ3::new(&ctx, &lex, 0)));
+    assert_eq!(ModelValueZ3::Int(2), OptGetObjectiveValueZ3::new(&ctx, &lex, 0));
+    assert_eq!(ModelValueZ3::Int(8), OptGetObjectiveValueZ3::new(&ctx, &lex, 1));
+
+    let boxed = OptimizerZ3::new(&ctx);
+    for cst in &constraints {
+        OptAssertZ3::new(&ctx, &boxed, cst);
+    }
+    OptSetPriorityZ3::new(&ctx, &boxed, OptPriorityZ3::Box);
+    OptMinimizeZ3::new(&ctx, &boxed, &len);
+    OptMinimizeZ3::new(&ctx, &boxed, &energy);
+    assert_eq!(SatResultZ3::Sat, OptCheckZ3::new(&ctx, &boxed, Vec::<Ast>::new()));
+    assert_eq!(ModelValueZ3::Int(2), OptGetLowerZ3::new(&ctx, &boxed, 0));
+    assert_eq!(ModelValueZ3::Int(2), OptGetLowerZ3::new(&ctx, &boxed, 1));
+}
+
+#[test]
+fn test_opt_pareto(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let len = IntVarZ3::new(&ctx, &intsort, "len");
+    let energy = IntVarZ3::new(&ctx, &intsort, "energy");
+    OptAssertZ3::new(&ctx, &opt, GEZ3::new(&ctx, &len, IntZ3::new(&ctx, &intsort, 2)));
+    OptAssertZ3::new(&ctx, &opt, LEZ3::new(&ctx, &len, IntZ3::new(&ctx, &intsort, 5)));
+    OptAssertZ3::new(&ctx, &opt, GEZ3::new(&ctx, ADDZ3::new(&ctx, vec!(&len, &energy)), IntZ3::new(&ctx, &intsort, 10)));
+    OptMinimizeZ3::new(&ctx, &opt, &len);
+    OptMinimizeZ3::new(&ctx, &opt, &energy);
+
+    let mut front: Vec<Vec<ModelValueZ3>> = OptParetoZ3::new(&ctx, &opt).map(|x| x.unwrap().values).collect();
+    front.sort();
+    let point = |l, e| vec!(ModelValueZ3::Int(l), ModelValueZ3::Int(e));
+    assert_eq!(vec!(point(2, 8), point(3, 7), point(4, 6), point(5, 5)), front);
+
+    OptAssertZ3::new(&ctx, &opt, LEZ3::new(&ctx, &len, IntZ3::new(&ctx, &intsort, 1)));
+    assert_eq!(0, OptParetoZ3::new(&ctx, &opt).count());
+}
+
+#[test]
+fn test_opt_unbounded_and_bounded(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let realsort = RealSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let max_x = OptMaximizeZ3::new(&ctx, &opt, &x);
+    OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
+    assert_eq!(ModelValueZ3::Other("oo".to_string()), OptGetUpperZ3::new(&ctx, &opt, max_x));
+    assert!(OptGetLowerZ3::try_new(&ctx, &opt, 7).is_err());
+
+    let bounded = OptimizerZ3::new(&ctx);
+    let y = RealVarZ3::new(&ctx, &realsort, "y");
+    OptAssertZ3::new(&ctx, &bounded, LEZ3::new(&ctx, &y, RealZ3::new(&ctx, &realsort, 2.5)));
+    let max_y = OptMaximizeZ3::new(&ctx, &bounded, &y);
+    OptCheckZ3::new(&ctx, &bounded, Vec::<Ast>::new());
+    assert_eq!(ModelValueZ3::Rational(5, 2), OptGetUpperZ3::new(&ctx, &bounded, max_y));
+    assert_eq!(ModelValueZ3::Rational(5, 2), OptGetObjectiveValueZ3::new(&ctx, &bounded, max_y));
+}
+
+#[test]
+fn test_opt_objectives_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let opt = opt_z3!(&ctx);
+
+    let x = int_var_z3!(&ctx, "x");
+    opt_assert_z3!(&ctx, &opt, le_z3!(&ctx, &x, int_z3!(&ctx, 3)));
+    opt_set_priority_z3!(&ctx, &opt, OptPriorityZ3::Pareto);
+    let idx = opt_maximize_z3!(&ctx, &opt, &x);
+
+    let points: Vec<ParetoPointZ3> = opt_pareto_z3!(&ctx, &opt).collect::<Result<_, _>>().unwrap();
+    assert_eq!(1, points.len());
+    assert_eq!(vec!(ModelValueZ3::Int(3)), points[0].values);
+    assert_eq!(ModelValueZ3::Int(3), opt_get_lower_z3!(&ctx, &opt, idx));
+    assert_eq!(ModelValueZ3::Int(3), opt_get_upper_z3!(&ctx, &opt, idx));
+    assert_eq!(ModelValueZ3::Int(3), opt_get_objective_value_z3!(&ctx, &opt, idx));
+}
@@ -7,42 +7,42 @@ pub struct EQZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub left: Z3_ast,
     pub right: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct NEQZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub left: Z3_ast,
     pub right: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct LEZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub left: Z3_ast,
     pub right: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct LTZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub left: Z3_ast,
     pub right: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct GEZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub left: Z3_ast,
     pub right: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct GTZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub left: Z3_ast,
     pub right: Z3_ast,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 impl <'ctx> EQZ3<'ctx> {
@@ -51,11 +51,21 @@ impl <'ctx> EQZ3<'ctx> {
     /// NOTE: The nodes `left` and `right` must have the same type.
     /// 
     /// NOTE: See macro! `eq_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, left: Z3_ast, right: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match EQZ3::try_new(ctx, left, right) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let left = left.as_ast();
+        let right = right.as_ast();
         let z3 = unsafe {
             Z3_mk_eq(ctx.r, left, right)
         };
-        EQZ3 {ctx, left, right, r: z3}.r
+        ctx.check_error()?;
+        Ok(EQZ3 {ctx, left, right, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -65,11 +75,21 @@ impl <'ctx> NEQZ3<'ctx> {
     /// NOTE: The nodes `left` and `right` must have the same type.
     /// 
     /// NOTE: See macro! `neq_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, left: Z3_ast, right: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match NEQZ3::try_new(ctx, left, right) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let left = left.as_ast();
+        let right = right.as_ast();
         let z3 = unsafe {
             Z3_mk_not(ctx.r, Z3_mk_eq(ctx.r, left, right)) 
         };
-        NEQZ3 {ctx, left, right, r: z3}.r
+        ctx.check_error()?;
+        Ok(NEQZ3 {ctx, left, right, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -79,11 +99,21 @@ impl <'ctx> LEZ3<'ctx> {
     /// NOTE: The nodes `left` and `right` must have the same sort, and must be int or real.
     /// 
     /// NOTE: See macro! `le_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, left: Z3_ast, right: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match LEZ3::try_new(ctx, left, right) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let left = left.as_ast();
+        let right = right.as_ast();
         let z3 = unsafe {
             Z3_mk_le(ctx.r, left, right)
         };
-        LEZ3 {ctx, left, right, r: z3}.r
+        ctx.check_error()?;
+        Ok(LEZ3 {ctx, left, right, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -93,11 +123,21 @@ impl <'ctx> LTZ3<'ctx> {
     /// NOTE: The nodes `left` and `right` must have the same sort, and must be int or real.
     /// 
     /// NOTE: See macro! `lt_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, left: Z3_ast, right: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match LTZ3::try_new(ctx, left, right) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let left = left.as_ast();
+        let right = right.as_ast();
         let z3 = unsafe {
             Z3_mk_lt(ctx.r, left, right)
         };
-        LTZ3 {ctx, left, right, r: z3}.r
+        ctx.check_error()?;
+        Ok(LTZ3 {ctx, left, right, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -107,11 +147,21 @@ impl <'ctx> GEZ3<'ctx> {
     /// NOTE: The nodes `left` and `right` must have the same sort, and must be int or real.
     /// 
     /// NOTE: See macro! `ge_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, left: Z3_ast, right: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match GEZ3::try_new(ctx, left, right) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let left = left.as_ast();
+        let right = right.as_ast();
         let z3 = unsafe {
             Z3_mk_ge(ctx.r, left, right)
         };
-        GEZ3 {ctx, left, right, r: z3}.r
+        ctx.check_error()?;
+        Ok(GEZ3 {ctx, left, right, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -121,11 +171,21 @@ impl <'ctx> GTZ3<'ctx> {
     /// NOTE: The nodes `left` and `right` must have the same sort, and must be int or real.
     /// 
     /// NOTE: See macro! `gt_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, left: Z3_ast, right: Z3_ast) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match GTZ3::try_new(ctx, left, right) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, left: impl AsAstZ3<'ctx>, right: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let left = left.as_ast();
+        let right = right.as_ast();
         let z3 = unsafe {
             Z3_mk_gt(ctx.r, left, right)
         };
-        GTZ3 {ctx, left, right, r: z3}.r
+        ctx.check_error()?;
+        Ok(GTZ3 {ctx, left, right, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -231,8 +291,8 @@ fn test_new_eq_1(){
     let int1 = IntZ3::new(&ctx, &intsort, 7);
     let real1 = RealZ3::new(&ctx, &realsort, -543.098742);
 
-    let rel1 = EQZ3::new(&ctx, x, int1);
-    let rel2 = EQZ3::new(&ctx, y, real1);
+    let rel1 = EQZ3::new(&ctx, &x, &int1);
+    let rel2 = EQZ3::new(&ctx, &y, &real1);
     let rel3 = EQZ3::new(&ctx, y, x);
     let rel4 = EQZ3::new(&ctx, int1, real1);
 
@@ -242,6 +302,21 @@ fn test_new_eq_1(){
     assert_eq!("(= (to_real 7) (- (/ 271549371.0 500000.0)))", ast_to_string_z3!(&ctx, rel4));
 }
 
+#[test]
+fn test_eq_sort_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let boolsort = BoolSortZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = BoolVarZ3::new(&ctx, &boolsort, "x");
+    let int1 = IntZ3::new(&ctx, &intsort, 7);
+
+    let err = EQZ3::try_new(&ctx, x, int1).unwrap_err();
+    assert_eq!("Sorts Bool and Int are incompatible", err.msg);
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
 #[test]
 fn test_new_neq_1(){
     let conf = ConfigZ3::new();
@@ -254,8 +329,8 @@ fn test_new_neq_1(){
     let int1 = IntZ3::new(&ctx, &intsort, 7);
     let real1 = RealZ3::new(&ctx, &realsort, -543.098742);
 
-    let rel1 = NEQZ3::new(&ctx, x, int1);
-    let rel2 = NEQZ3::new(&ctx, y, real1);
+    let rel1 = NEQZ3::new(&ctx, &x, &int1);
+    let rel2 = NEQZ3::new(&ctx, &y, &real1);
     let rel3 = NEQZ3::new(&ctx, y, x);
     let rel4 = NEQZ3::new(&ctx, int1, real1);
 
@@ -278,8 +353,8 @@ fn test_new_le(){
     let int1 = IntZ3::new(&ctx, &intsort, 7);
     let real1 = RealZ3::new(&ctx, &realsort, -543.098742);
 
-    let rel1 = LEZ3::new(&ctx, x, int1);
-    let rel2 = LEZ3::new(&ctx, y, real1);
+    let rel1 = LEZ3::new(&ctx, &x, &int1);
+    let rel2 = LEZ3::new(&ctx, &y, &real1);
     let rel3 = LEZ3::new(&ctx, y, x);
     let rel4 = LEZ3::new(&ctx, int1, real1);
 
@@ -301,8 +376,8 @@ fn test_new_lt(){
     let int1 = IntZ3::new(&ctx, &intsort, 7);
     let real1 = RealZ3::new(&ctx, &realsort, -543.098742);
 
-    let rel1 = LTZ3::new(&ctx, x, int1);
-    let rel2 = LTZ3::new(&ctx, y, real1);
+    let rel1 = LTZ3::new(&ctx, &x, &int1);
+    let rel2 = LTZ3::new(&ctx, &y, &real1);
     let rel3 = LTZ3::new(&ctx, y, x);
     let rel4 = LTZ3::new(&ctx, int1, real1);
 
@@ -324,8 +399,8 @@ fn test_new_ge(){
     let int1 = IntZ3::new(&ctx, &intsort, 7);
     let real1 = RealZ3::new(&ctx, &realsort, -543.098742);
 
-    let rel1 = GEZ3::new(&ctx, x, int1);
-    let rel2 = GEZ3::new(&ctx, y, real1);
+    let rel1 = GEZ3::new(&ctx, &x, &int1);
+    let rel2 = GEZ3::new(&ctx, &y, &real1);
     let rel3 = GEZ3::new(&ctx, y, x);
     let rel4 = GEZ3::new(&ctx, int1, real1);
 
@@ -347,8 +422,8 @@ fn test_new_gt(){
     let int1 = IntZ3::new(&ctx, &intsort, 7);
     let real1 = RealZ3::new(&ctx, &realsort, -543.098742);
 
-    let rel1 = GTZ3::new(&ctx, x, int1);
-    let rel2 = GTZ3::new(&ctx, y, real1);
+    let rel1 = GTZ3::new(&ctx, &x, &int1);
+    let rel2 = GTZ3::new(&ctx, &y, &real1);
     let rel3 = GTZ3::new(&ctx, y, x);
     let rel4 = GTZ3::new(&ctx, int1, real1);
 
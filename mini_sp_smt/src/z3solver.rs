@@ -1,12 +1,26 @@
 //! Z3 solver for SP
 
-use std::ffi::{CStr};
+use std::cell::{Cell, RefCell};
+use std::ffi::CString;
 use z3_sys::*;
 use super::*;
+use crate::z3eval::{ast_to_value_z3, model_eval_ast_z3};
 
 pub struct SolverZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub r: Z3_solver,
+    /// Generations of the open scopes, innermost last, see `SolverZ3::push`.
+    pub scopes: RefCell<Vec<u64>>,
+    pub generation: Cell<u64>
+}
+
+/// Result of a check, an unknown result carries the reason reported by Z3,
+/// i.e. `timeout`, `interrupted` or `incomplete quantifiers`.
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord)]
+pub enum SatResultZ3 {
+    Sat,
+    Unsat,
+    Unknown(String)
 }
 
 pub struct SlvAssertZ3<'ctx, 'slv> {
@@ -33,6 +47,17 @@ pub struct SlvGetPopPointsZ3<'ctx, 'slv> {
     pub r: u32
 }
 
+/// A backtracking point of a solver that is popped when dropped, see `SolverZ3::push`.
+/// `level` is the number of backtracking points of the solver right after the push,
+/// `generation` tells the scope apart from later scopes at the same level.
+pub struct SlvScopeZ3<'ctx, 'slv> {
+    pub ctx: &'ctx ContextZ3,
+    pub slv: &'slv SolverZ3<'ctx>,
+    pub level: u32,
+    pub generation: u64,
+    pub open: bool
+}
+
 pub struct SlvAssertAndTrackZ3<'ctx, 'slv, 't> {
     pub ctx: &'ctx ContextZ3,
     pub slv: &'slv SolverZ3<'ctx>,
@@ -44,7 +69,75 @@ pub struct SlvAssertAndTrackZ3<'ctx, 'slv, 't> {
 pub struct SlvCheckZ3<'ctx, 'slv> {
     pub ctx: &'ctx ContextZ3,
     pub slv: &'slv SolverZ3<'ctx>,
-    pub r: Z3_lbool
+    pub r: SatResultZ3
+}
+
+/// Result of a check under assumptions, an unsat result carries the
+/// assumptions responsible for it, a subset of the given ones.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AssumptionsResultZ3<'ctx> {
+    Sat,
+    Unsat(Vec<Ast<'ctx>>),
+    Unknown(String)
+}
+
+pub struct SlvCheckAssumptionsZ3<'ctx, 'slv> {
+    pub ctx: &'ctx ContextZ3,
+    pub slv: &'slv SolverZ3<'ctx>,
+    pub assumptions: Vec<Z3_ast>,
+    pub r: AssumptionsResultZ3<'ctx>
+}
+
+/// A consequence `assumptions => var = value` of the solver, see `SlvGetConsequencesZ3`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConsequenceZ3<'ctx> {
+    pub assumptions: Vec<Ast<'ctx>>,
+    pub var: Ast<'ctx>,
+    pub value: ModelValueZ3
+}
+
+/// Result of getting consequences, like `AssumptionsResultZ3` but a sat result
+/// carries the consequences.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConsequencesResultZ3<'ctx> {
+    Sat(Vec<ConsequenceZ3<'ctx>>),
+    Unsat(Vec<Ast<'ctx>>),
+    Unknown(String)
+}
+
+pub struct SlvGetConsequencesZ3<'ctx, 'slv> {
+    pub ctx: &'ctx ContextZ3,
+    pub slv: &'slv SolverZ3<'ctx>,
+    pub assumptions: Vec<Z3_ast>,
+    pub vars: Vec<Z3_ast>,
+    pub r: ConsequencesResultZ3<'ctx>
+}
+
+pub struct SlvSetTimeoutZ3<'ctx, 'slv> {
+    pub ctx: &'ctx ContextZ3,
+    pub slv: &'slv SolverZ3<'ctx>,
+    pub ms: u32,
+    pub r: ()
+}
+
+pub struct SlvSetRlimitZ3<'ctx, 'slv> {
+    pub ctx: &'ctx ContextZ3,
+    pub slv: &'slv SolverZ3<'ctx>,
+    pub rlimit: u32,
+    pub r: ()
+}
+
+pub struct SlvSetCoreMinimizeZ3<'ctx, 'slv> {
+    pub ctx: &'ctx ContextZ3,
+    pub slv: &'slv SolverZ3<'ctx>,
+    pub minimize: bool,
+    pub r: ()
+}
+
+pub struct SlvGetReasonUnknownZ3<'ctx, 'slv> {
+    pub ctx: &'ctx ContextZ3,
+    pub slv: &'slv SolverZ3<'ctx>,
+    pub r: String
 }
 
 pub struct SlvResetZ3<'ctx, 'slv> {
@@ -55,7 +148,7 @@ pub struct SlvResetZ3<'ctx, 'slv> {
 pub struct SlvGetModelZ3<'ctx, 'slv> {
     pub ctx: &'ctx ContextZ3,
     pub slv: &'slv SolverZ3<'ctx>,
-    pub r: Z3_model
+    pub r: Model<'ctx>
 }
 
 pub struct SlvGetParamDescrZ3<'ctx, 'slv> {
@@ -66,20 +159,20 @@ pub struct SlvGetParamDescrZ3<'ctx, 'slv> {
 pub struct SlvGetProofZ3<'ctx, 'slv> {
     pub ctx: &'ctx ContextZ3,
     pub slv: &'slv SolverZ3<'ctx>,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct SlvGetAssertsZ3<'ctx, 'slv> {
     pub ctx: &'ctx ContextZ3,
     pub slv: &'slv SolverZ3<'ctx>,
-    pub r: Z3_ast_vector
+    pub r: AstVector<'ctx>
 }
 
 pub struct SlvGetNModelsZ3<'ctx, 'slv> {
     pub ctx: &'ctx ContextZ3,
     pub slv: &'slv SolverZ3<'ctx>,
     pub n: u32,
-    pub r: Vec<Z3_model>,
+    pub r: Vec<Model<'ctx>>,
     pub s: Vec<String>
 }
 
@@ -87,21 +180,45 @@ pub struct SlvGetModelAndForbidZ3<'ctx, 'slv> {
     pub ctx: &'ctx ContextZ3,
     pub slv: &'slv SolverZ3<'ctx>,
     pub n: u32,
-    pub r: Z3_model
+    pub r: Model<'ctx>
 }
 
 pub struct SlvGetAllModelsZ3<'ctx, 'slv> {
     pub ctx: &'ctx ContextZ3,
     pub slv: &'slv SolverZ3<'ctx>,
     pub n: u32,
-    pub r: Vec<Z3_model>,
+    pub r: Vec<Model<'ctx>>,
     pub s: Vec<String>
 }
 
+/// A model found by `SlvModelsZ3`, with the values of the projection
+/// variables in the order they were given.
+pub struct ProjectedModelZ3<'ctx> {
+    pub model: Model<'ctx>,
+    pub values: Vec<ModelValueZ3>
+}
+
+/// Iterator over the models of a solver that differ in the projection
+/// variables, see `SlvModelsZ3::new`.
+pub struct SlvModelsZ3<'ctx, 'slv> {
+    pub ctx: &'ctx ContextZ3,
+    pub slv: &'slv SolverZ3<'ctx>,
+    pub vars: Vec<Ast<'ctx>>,
+    pub assumptions: Vec<Ast<'ctx>>,
+    pub scope: SlvScopeZ3<'ctx, 'slv>,
+    pub done: bool
+}
+
 pub struct SlvGetUnsatCoreZ3<'ctx, 'slv> {
     pub ctx: &'ctx ContextZ3,
     pub slv: &'slv SolverZ3<'ctx>,
-    pub r: Z3_ast_vector
+    pub r: AstVector<'ctx>
+}
+
+pub struct SlvGetUnsatCoreNamesZ3<'ctx, 'slv> {
+    pub ctx: &'ctx ContextZ3,
+    pub slv: &'slv SolverZ3<'ctx>,
+    pub r: Vec<String>
 }
 
 pub struct SlvToStringZ3<'ctx, 'slv> {
@@ -110,6 +227,13 @@ pub struct SlvToStringZ3<'ctx, 'slv> {
     pub r: String
 }
 
+pub struct SlvToSmtLib2Z3<'ctx, 'slv, 'n> {
+    pub ctx: &'ctx ContextZ3,
+    pub slv: &'slv SolverZ3<'ctx>,
+    pub name: &'n str,
+    pub r: String
+}
+
 pub struct SlvUnsatCoreToStringZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub core: Z3_ast_vector,
@@ -122,6 +246,20 @@ pub struct SlvProofToStringZ3<'ctx> {
     pub r: String
 }
 
+pub struct SlvFromSmtLib2Z3<'ctx, 'slv, 's> {
+    pub ctx: &'ctx ContextZ3,
+    pub slv: &'slv SolverZ3<'ctx>,
+    pub script: &'s str,
+    pub r: ()
+}
+
+pub struct SlvFromSmtLib2FileZ3<'ctx, 'slv, 'f> {
+    pub ctx: &'ctx ContextZ3,
+    pub slv: &'slv SolverZ3<'ctx>,
+    pub file: &'f str,
+    pub r: ()
+}
+
 impl <'ctx> SolverZ3<'ctx> {
     /// Create a new solver. This solver is a "combined solver" (see
     /// combined_solver module) that internally uses a non-incremental (solver1) and an
@@ -147,7 +285,30 @@ impl <'ctx> SolverZ3<'ctx> {
             Z3_solver_inc_ref(ctx.r, solv);
             solv
         };
-        SolverZ3 {ctx, r: z3}
+        SolverZ3 {ctx, r: z3, scopes: RefCell::new(vec!()), generation: Cell::new(0)}
+    }
+
+    /// Create a backtracking point that is popped when the returned scope is
+    /// dropped, so that pushes and pops can't get out of pair.
+    ///
+    /// Use `SlvScopeZ3::discard` to pop the scope early, or `SlvScopeZ3::commit` to
+    /// keep its assertions. Scopes nest, popping a scope also pops the scopes pushed
+    /// after it. Their guards are then stale and do nothing when dropped, discarding
+    /// them returns an error instead of popping a newer scope at the same level.
+    pub fn push<'slv>(&'slv self) -> SlvScopeZ3<'ctx, 'slv> {
+        match self.try_push() {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_push<'slv>(&'slv self) -> Result<SlvScopeZ3<'ctx, 'slv>, ErrorZ3> {
+        SlvPushZ3::try_new(self.ctx, self)?;
+        let level = SlvGetPopPointsZ3::try_new(self.ctx, self)?;
+        let generation = self.generation.get() + 1;
+        self.generation.set(generation);
+        self.scopes.borrow_mut().push(generation);
+        Ok(SlvScopeZ3 {ctx: self.ctx, slv: self, level, generation, open: true})
     }
 }
 
@@ -155,11 +316,18 @@ impl <'ctx, 'slv> SlvAssertZ3<'ctx, 'slv> {
     /// Assert a new constraint into the solver.
     ///
     /// NOTE: See macro! `slv_assert_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, cst: Z3_ast) -> () {
-        let z3 = unsafe {
-            Z3_solver_assert(ctx.r, slv.r, cst)
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, cst: impl AsAstZ3<'ctx>) -> () {
+        match SlvAssertZ3::try_new(ctx, slv, cst) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, cst: impl AsAstZ3<'ctx>) -> Result<(), ErrorZ3> {
+        unsafe {
+            Z3_solver_assert(ctx.r, slv.r, cst.as_ast())
         };
-        SlvAssertZ3 {ctx, slv, cst, r: z3}.r
+        ctx.check_error()
     }
 }
 
@@ -171,9 +339,17 @@ impl <'ctx, 'slv> SlvPushZ3<'ctx, 'slv> {
     ///
     /// NOTE: See macro! `slv_push_z3!`
     pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> () {
+        match SlvPushZ3::try_new(ctx, slv) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Result<(), ErrorZ3> {
         unsafe {
             Z3_solver_push(ctx.r, slv.r)
         };
+        ctx.check_error()
     }
 }
 
@@ -185,9 +361,17 @@ impl <'ctx, 'slv> SlvPopZ3<'ctx, 'slv> {
     ///
     /// NOTE: See macro! `slv_pop_z3!`
     pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, p: u32) -> () {
+        match SlvPopZ3::try_new(ctx, slv, p) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, p: u32) -> Result<(), ErrorZ3> {
         unsafe {
             Z3_solver_pop(ctx.r, slv.r, p)
         };
+        ctx.check_error()
     }
 }
 
@@ -196,10 +380,75 @@ impl <'ctx, 'slv> SlvGetPopPointsZ3<'ctx, 'slv> {
     ///
     /// NOTE: See macro! `slv_get_pop_points_z3!`
     pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> u32 {
+        match SlvGetPopPointsZ3::try_new(ctx, slv) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Result<u32, ErrorZ3> {
         let z3 = unsafe {
             Z3_solver_get_num_scopes(ctx.r, slv.r)
         };
-        SlvGetPopPointsZ3 {ctx, slv, r: z3}.r
+        ctx.check_error()?;
+        Ok(SlvGetPopPointsZ3 {ctx, slv, r: z3}.r)
+    }
+}
+
+impl <'ctx, 'slv> SlvScopeZ3<'ctx, 'slv> {
+    /// Keep the assertions made in the scope, the backtracking point stays
+    /// and is only popped with an outer scope or by `SlvPopZ3`.
+    pub fn commit(mut self) -> () {
+        self.open = false;
+        self.slv.scopes.borrow_mut().retain(|x| *x != self.generation);
+    }
+
+    /// Pop the scope now, removing the assertions made in it and in the
+    /// scopes pushed after it.
+    pub fn discard(self) -> () {
+        match self.try_discard() {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_discard(mut self) -> Result<(), ErrorZ3> {
+        self.open = false;
+        self.pop()
+    }
+
+    // pop back to the level before the push, unless the scope was already popped
+    fn pop(&self) -> Result<(), ErrorZ3> {
+        {
+            let mut scopes = self.slv.scopes.borrow_mut();
+            match scopes.iter().position(|x| *x == self.generation) {
+                Some(i) => scopes.truncate(i),
+                None => return Err(ErrorZ3 {
+                    code: Z3_error_code_Z3_INVALID_USAGE,
+                    msg: format!("Scope at level {} was already popped with an outer scope.", self.level)
+                })
+            }
+        }
+        let points = SlvGetPopPointsZ3::try_new(self.ctx, self.slv)?;
+        match points < self.level {
+            true => Err(ErrorZ3 {
+                code: Z3_error_code_Z3_INVALID_USAGE,
+                msg: format!("Scope at level {} was already popped, the solver has {} backtracking points.", self.level, points)
+            }),
+            false => SlvPopZ3::try_new(self.ctx, self.slv, points - self.level + 1)
+        }
+    }
+}
+
+impl <'ctx, 'slv> Drop for SlvScopeZ3<'ctx, 'slv> {
+    /// Pop the scope if it was neither committed nor discarded, a scope that
+    /// can't be popped anymore is left as it is. Use `SlvScopeZ3::try_discard`
+    /// to get the error.
+    fn drop(&mut self) {
+        if self.open {
+            self.open = false;
+            let _ = self.pop();
+        }
     }
 }
 
@@ -209,25 +458,258 @@ impl <'ctx, 'slv, 't> SlvAssertAndTrackZ3<'ctx, 'slv, 't> {
     /// unsat cores.
     /// 
     /// NOTE: See macro! `slv_assert_and_track_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, cst: Z3_ast, tracker: &'t str) -> () {
-        let z3 = unsafe {
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, cst: impl AsAstZ3<'ctx>, tracker: &'t str) -> () {
+        match SlvAssertAndTrackZ3::try_new(ctx, slv, cst, tracker) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, cst: impl AsAstZ3<'ctx>, tracker: &'t str) -> Result<(), ErrorZ3> {
+        unsafe {
                 let sort = BoolSortZ3::new(&ctx);
                 let var = BoolVarZ3::new(&ctx, &sort, tracker);
-                Z3_solver_assert_and_track(ctx.r, slv.r, cst, var)
+                Z3_solver_assert_and_track(ctx.r, slv.r, cst.as_ast(), var.r)
             };
-        SlvAssertAndTrackZ3 {ctx, slv, cst, tracker, r: z3}.r
+        ctx.check_error()
     }
 }
 
 impl <'ctx, 'slv> SlvCheckZ3<'ctx, 'slv> {
     /// Check whether the assertions in a given solver are consistent or not.
     ///
+    /// The check returns `SatResultZ3::Unknown` with the reason if it runs out of
+    /// time or resources (see `SlvSetTimeoutZ3` and `SlvSetRlimitZ3`), or if it
+    /// is stopped with an `InterruptHandleZ3`.
+    ///
     /// NOTE: See macro! `slv_check_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Z3_lbool {
-        let z3 = unsafe {
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> SatResultZ3 {
+        match SlvCheckZ3::try_new(ctx, slv) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Result<SatResultZ3, ErrorZ3> {
+        let res = unsafe {
             Z3_solver_check(ctx.r, slv.r)
         };
-        SlvCheckZ3 {ctx, slv, r: z3}.r
+        ctx.check_error()?;
+        let z3 = match res {
+            1 => SatResultZ3::Sat,
+            -1 => SatResultZ3::Unsat,
+            _ => SatResultZ3::Unknown(SlvGetReasonUnknownZ3::try_new(ctx, slv)?)
+        };
+        Ok(SlvCheckZ3 {ctx, slv, r: z3}.r)
+    }
+}
+
+impl <'ctx, 'slv> SlvCheckAssumptionsZ3<'ctx, 'slv> {
+    /// Check whether the assertions in the solver are consistent together with
+    /// the `assumptions`, Boolean constants or their negations. The assumptions
+    /// only hold for this check, so they can switch tracked constraints on and
+    /// off without pushing and popping scopes.
+    ///
+    /// If the result is unsat, it carries the assumptions responsible for it.
+    ///
+    /// NOTE: See macro! `slv_check_assumptions_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, assumptions: Vec<impl AsAstZ3<'ctx>>) -> AssumptionsResultZ3<'ctx> {
+        match SlvCheckAssumptionsZ3::try_new(ctx, slv, assumptions) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, assumptions: Vec<impl AsAstZ3<'ctx>>) -> Result<AssumptionsResultZ3<'ctx>, ErrorZ3> {
+        let assumptions: Vec<Z3_ast> = assumptions.iter().map(|x| x.as_ast()).collect();
+        let res = unsafe {
+            Z3_solver_check_assumptions(ctx.r, slv.r, assumptions.len() as u32, assumptions.as_ptr())
+        };
+        ctx.check_error()?;
+        let z3 = match res {
+            1 => AssumptionsResultZ3::Sat,
+            -1 => AssumptionsResultZ3::Unsat(slv_get_core_asts_z3(ctx, slv)?),
+            _ => AssumptionsResultZ3::Unknown(SlvGetReasonUnknownZ3::try_new(ctx, slv)?)
+        };
+        Ok(SlvCheckAssumptionsZ3 {ctx, slv, assumptions, r: z3}.r)
+    }
+}
+
+impl <'ctx, 'slv> SlvGetConsequencesZ3<'ctx, 'slv> {
+    /// Find the values of `vars` that are forced by the assertions in the solver
+    /// together with the `assumptions`. A variable that can take different values
+    /// has no consequence. Every consequence carries the assumptions it depends on.
+    ///
+    /// If the result is unsat, it carries the assumptions responsible for it.
+    ///
+    /// NOTE: See macro! `slv_get_consequences_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, assumptions: Vec<impl AsAstZ3<'ctx>>, vars: Vec<impl AsAstZ3<'ctx>>) -> ConsequencesResultZ3<'ctx> {
+        match SlvGetConsequencesZ3::try_new(ctx, slv, assumptions, vars) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, assumptions: Vec<impl AsAstZ3<'ctx>>, vars: Vec<impl AsAstZ3<'ctx>>) -> Result<ConsequencesResultZ3<'ctx>, ErrorZ3> {
+        let assumptions: Vec<Z3_ast> = assumptions.iter().map(|x| x.as_ast()).collect();
+        let vars: Vec<Z3_ast> = vars.iter().map(|x| x.as_ast()).collect();
+        let (res, consequences) = unsafe {
+            let to_vector = |asts: &Vec<Z3_ast>| {
+                let v = AstVector::new(ctx, Z3_mk_ast_vector(ctx.r));
+                asts.iter().for_each(|x| Z3_ast_vector_push(ctx.r, v.r, *x));
+                v
+            };
+            let z3_assumptions = to_vector(&assumptions);
+            let z3_vars = to_vector(&vars);
+            let z3_consequences = to_vector(&vec!());
+            let res = Z3_solver_get_consequences(ctx.r, slv.r, z3_assumptions.r, z3_vars.r, z3_consequences.r);
+            (res, z3_consequences)
+        };
+        ctx.check_error()?;
+        let consequences = Z3AstVectorToVectorAstZ3::try_new(ctx, &consequences)?;
+        let z3 = match res {
+            1 => ConsequencesResultZ3::Sat(consequences.iter().map(|x| consequence_z3(ctx, x)).collect::<Result<_, _>>()?),
+            -1 => ConsequencesResultZ3::Unsat(slv_get_core_asts_z3(ctx, slv)?),
+            _ => ConsequencesResultZ3::Unknown(SlvGetReasonUnknownZ3::try_new(ctx, slv)?)
+        };
+        Ok(SlvGetConsequencesZ3 {ctx, slv, assumptions, vars, r: z3}.r)
+    }
+}
+
+// the unsat core after a check under assumptions, as owned asts
+fn slv_get_core_asts_z3<'ctx>(ctx: &'ctx ContextZ3, slv: &SolverZ3<'ctx>) -> Result<Vec<Ast<'ctx>>, ErrorZ3> {
+    let core = SlvGetUnsatCoreZ3::try_new(ctx, slv)?;
+    Z3AstVectorToVectorAstZ3::try_new(ctx, &core)
+}
+
+// z3 reports a consequence as `(=> (and a1 ... an) lit)`, where `lit` is `x`, `(not x)` or `(= x v)`
+fn consequence_z3<'ctx>(ctx: &'ctx ContextZ3, cons: &Ast<'ctx>) -> Result<ConsequenceZ3<'ctx>, ErrorZ3> {
+    unsafe {
+        let kind = |a: Z3_ast| Z3_get_decl_kind(ctx.r, Z3_get_app_decl(ctx.r, Z3_to_app(ctx.r, a)));
+        let args = |a: Z3_ast| -> Vec<Ast<'ctx>> {
+            let app = Z3_to_app(ctx.r, a);
+            (0..Z3_get_app_num_args(ctx.r, app)).map(|i| Ast::new(ctx, Z3_get_app_arg(ctx.r, app, i))).collect()
+        };
+        let implication = args(cons.r);
+        let (antecedent, lit) = (&implication[0], &implication[1]);
+        let assumptions = match kind(antecedent.r) {
+            k if k == Z3_decl_kind_Z3_OP_AND => args(antecedent.r),
+            k if k == Z3_decl_kind_Z3_OP_TRUE => vec!(),
+            _ => vec!(antecedent.clone())
+        };
+        let (var, value) = match kind(lit.r) {
+            k if k == Z3_decl_kind_Z3_OP_NOT => (args(lit.r)[0].clone(), ModelValueZ3::Bool(false)),
+            k if k == Z3_decl_kind_Z3_OP_EQ => {
+                let eq = args(lit.r);
+                (eq[0].clone(), ast_to_value_z3(ctx, &eq[1])?)
+            },
+            _ => (lit.clone(), ModelValueZ3::Bool(true))
+        };
+        ctx.check_error()?;
+        Ok(ConsequenceZ3 {assumptions, var, value})
+    }
+}
+
+// solver parameters are set one at a time, the others keep their values
+fn slv_set_uint_param_z3(ctx: &ContextZ3, slv: &SolverZ3, name: &str, value: u32) -> Result<(), ErrorZ3> {
+    let str_name = CString::new(name).unwrap();
+    unsafe {
+        let params = Z3_mk_params(ctx.r);
+        Z3_params_inc_ref(ctx.r, params);
+        Z3_params_set_uint(ctx.r, params, Z3_mk_string_symbol(ctx.r, str_name.as_ptr()), value);
+        Z3_solver_set_params(ctx.r, slv.r, params);
+        Z3_params_dec_ref(ctx.r, params);
+    }
+    ctx.check_error()
+}
+
+fn slv_set_bool_param_z3(ctx: &ContextZ3, slv: &SolverZ3, name: &str, value: bool) -> Result<(), ErrorZ3> {
+    let str_name = CString::new(name).unwrap();
+    unsafe {
+        let params = Z3_mk_params(ctx.r);
+        Z3_params_inc_ref(ctx.r, params);
+        Z3_params_set_bool(ctx.r, params, Z3_mk_string_symbol(ctx.r, str_name.as_ptr()), value);
+        Z3_solver_set_params(ctx.r, slv.r, params);
+        Z3_params_dec_ref(ctx.r, params);
+    }
+    ctx.check_error()
+}
+
+impl <'ctx, 'slv> SlvSetTimeoutZ3<'ctx, 'slv> {
+    /// Limit every following check of the solver to `ms` milliseconds, a check
+    /// that times out returns `SatResultZ3::Unknown("timeout")`.
+    ///
+    /// Use `u32::MAX` to remove the limit.
+    ///
+    /// NOTE: See macro! `slv_set_timeout_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, ms: u32) -> () {
+        match SlvSetTimeoutZ3::try_new(ctx, slv, ms) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, ms: u32) -> Result<(), ErrorZ3> {
+        slv_set_uint_param_z3(ctx, slv, "timeout", ms)
+    }
+}
+
+impl <'ctx, 'slv> SlvSetRlimitZ3<'ctx, 'slv> {
+    /// Limit the resources used by every following check of the solver. Unlike a
+    /// timeout, the resource limit is deterministic, a check that exceeds it
+    /// returns `SatResultZ3::Unknown`.
+    ///
+    /// Use `0` to remove the limit.
+    ///
+    /// NOTE: See macro! `slv_set_rlimit_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, rlimit: u32) -> () {
+        match SlvSetRlimitZ3::try_new(ctx, slv, rlimit) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, rlimit: u32) -> Result<(), ErrorZ3> {
+        slv_set_uint_param_z3(ctx, slv, "rlimit", rlimit)
+    }
+}
+
+impl <'ctx, 'slv> SlvSetCoreMinimizeZ3<'ctx, 'slv> {
+    /// Make the following unsat cores of the solver minimal, so that dropping
+    /// any tracked constraint from the core makes it satisfiable. Minimization
+    /// makes getting the core slower, it is off by default.
+    ///
+    /// NOTE: See macro! `slv_set_core_minimize_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, minimize: bool) -> () {
+        match SlvSetCoreMinimizeZ3::try_new(ctx, slv, minimize) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, minimize: bool) -> Result<(), ErrorZ3> {
+        slv_set_bool_param_z3(ctx, slv, "core.minimize", minimize)
+    }
+}
+
+impl <'ctx, 'slv> SlvGetReasonUnknownZ3<'ctx, 'slv> {
+    /// Return a brief justification for an "unknown" result (i.e., `SatResultZ3::Unknown`) for
+    /// the last `SlvCheckZ3::new`.
+    ///
+    /// NOTE: See macro! `slv_get_reason_unknown_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> String {
+        match SlvGetReasonUnknownZ3::try_new(ctx, slv) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Result<String, ErrorZ3> {
+        let z3 = unsafe {
+            Z3_solver_get_reason_unknown(ctx.r, slv.r)
+        };
+        let z3 = ctx.check_string(z3)?;
+        Ok(SlvGetReasonUnknownZ3 {ctx, slv, r: z3}.r)
     }
 }
 
@@ -236,9 +718,17 @@ impl <'ctx, 'slv> SlvResetZ3<'ctx, 'slv> {
     ///
     /// NOTE: See macro! `slv_reset_z3!`
     pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> () {
+        match SlvResetZ3::try_new(ctx, slv) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Result<(), ErrorZ3> {
         unsafe {
             Z3_solver_reset(ctx.r, slv.r)
         };
+        ctx.check_error()
     }
 }
 
@@ -249,11 +739,19 @@ impl<'ctx, 'slv> SlvGetModelZ3<'ctx, 'slv> {
     /// the commands above were not invoked for the given solver, or if the result was `Z3_L_FALSE`.
     /// 
     /// NOTE: See macro! `slv_get_model_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Z3_model {
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Model<'ctx> {
+        match SlvGetModelZ3::try_new(ctx, slv) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Result<Model<'ctx>, ErrorZ3> {
         let z3 = unsafe {
             Z3_solver_get_model(ctx.r, slv.r)
         };
-        SlvGetModelZ3 {ctx, r: z3, slv}.r        
+        ctx.check_error()?;
+        Ok(SlvGetModelZ3 {ctx, r: Model::new(ctx, z3), slv}.r)
     }
 }
 
@@ -262,12 +760,22 @@ impl <'ctx, 'slv> SlvGetParamDescrZ3<'ctx, 'slv> {
     /// 
     /// NOTE: See macro! `slv_get_param_descr!`
     pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> String {
-        unsafe {
-            let descr = Z3_solver_get_param_descrs(ctx.r, slv.r);
-            let desc_str = Z3_param_descrs_to_string(ctx.r, descr);
-            CStr::from_ptr(desc_str).to_str().unwrap().to_owned()
+        match SlvGetParamDescrZ3::try_new(ctx, slv) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
         }
     }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Result<String, ErrorZ3> {
+        let r = unsafe {
+            let descr = Z3_solver_get_param_descrs(ctx.r, slv.r);
+            Z3_param_descrs_inc_ref(ctx.r, descr);
+            let r = Z3_param_descrs_to_string(ctx.r, descr);
+            Z3_param_descrs_dec_ref(ctx.r, descr);
+            r
+        };
+        ctx.check_string(r)
+    }
 }
 
 impl <'ctx, 'slv> SlvGetProofZ3<'ctx, 'slv> {
@@ -278,21 +786,37 @@ impl <'ctx, 'slv> SlvGetProofZ3<'ctx, 'slv> {
     /// or if the result was different from `Z3_L_FALSE`.
     /// 
     /// NOTE: See macro! `slv_get_proof!`
-    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Ast<'ctx> {
+        match SlvGetProofZ3::try_new(ctx, slv) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
         let z3 = unsafe {
             Z3_solver_get_proof(ctx.r, slv.r)
         };
-        SlvGetProofZ3 {ctx, slv, r: z3}.r
+        ctx.check_error()?;
+        Ok(SlvGetProofZ3 {ctx, slv, r: Ast::new(ctx, z3)}.r)
     }
 }
 
 impl <'ctx, 'slv> SlvGetAssertsZ3<'ctx, 'slv> {
     /// Get assertions
-    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Z3_ast_vector {
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> AstVector<'ctx> {
+        match SlvGetAssertsZ3::try_new(ctx, slv) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Result<AstVector<'ctx>, ErrorZ3> {
         let z3 = unsafe {
             Z3_solver_get_assertions(ctx.r, slv.r)
         };
-        SlvGetAssertsZ3 {ctx, slv, r: z3}.r
+        ctx.check_error()?;
+        Ok(SlvGetAssertsZ3 {ctx, slv, r: AstVector::new(ctx, z3)}.r)
     }
 }
 
@@ -302,11 +826,44 @@ impl <'ctx, 'slv> SlvGetUnsatCoreZ3<'ctx, 'slv> {
     /// NOTE: The unsat core is a subset of the assumptions `a`.
     /// 
     /// NOTE: See macro! `slv_get_unsat_core!`
-    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Z3_ast_vector {
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> AstVector<'ctx> {
+        match SlvGetUnsatCoreZ3::try_new(ctx, slv) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Result<AstVector<'ctx>, ErrorZ3> {
         let z3 = unsafe {
             Z3_solver_get_unsat_core(ctx.r, slv.r)
         };
-        SlvGetUnsatCoreZ3 {ctx, slv, r: z3}.r
+        ctx.check_error()?;
+        Ok(SlvGetUnsatCoreZ3 {ctx, slv, r: AstVector::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx, 'slv> SlvGetUnsatCoreNamesZ3<'ctx, 'slv> {
+    /// Retrieve the unsat core for the last `SlvCheckZ3::new` as the names of the
+    /// trackers given to `SlvAssertAndTrackZ3::new`, in the order Z3 reports them.
+    ///
+    /// NOTE: See macro! `slv_get_unsat_core_names_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Vec<String> {
+        match SlvGetUnsatCoreNamesZ3::try_new(ctx, slv) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Result<Vec<String>, ErrorZ3> {
+        let core = Z3AstVectorToVectorAstZ3::try_new(ctx, SlvGetUnsatCoreZ3::try_new(ctx, slv)?)?;
+        let z3 = unsafe {
+            core.iter().map(|tracker| {
+                let decl = Z3_get_app_decl(ctx.r, Z3_to_app(ctx.r, tracker.r));
+                Z3StringToStringZ3::new(Z3_get_symbol_string(ctx.r, Z3_get_decl_name(ctx.r, decl)))
+            }).collect()
+        };
+        ctx.check_error()?;
+        Ok(SlvGetUnsatCoreNamesZ3 {ctx, slv, r: z3}.r)
     }
 }
 
@@ -315,13 +872,72 @@ impl<'ctx, 'slv> SlvToStringZ3<'ctx, 'slv> {
     /// 
     /// NOTE: See macro! `slv_to_string_z3!`
     pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> String {
+        match SlvToStringZ3::try_new(ctx, slv) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Result<String, ErrorZ3> {
         let z3 = unsafe {
-            CStr::from_ptr(Z3_solver_to_string(ctx.r, slv.r)).to_str().unwrap().to_owned()
+            Z3_solver_to_string(ctx.r, slv.r)
         };
-        SlvToStringZ3 {ctx, slv, r: z3}.r
+        let z3 = ctx.check_string(z3)?;
+        Ok(SlvToStringZ3 {ctx, slv, r: z3}.r)
     }
 }
 
+impl<'ctx, 'slv, 'n> SlvToSmtLib2Z3<'ctx, 'slv, 'n> {
+    /// Z3 solver to a standalone SMT-LIB2 benchmark.
+    ///
+    /// Unlike `SlvToStringZ3`, the result is a complete script that other solvers can run:
+    /// it starts with the benchmark `name` as a comment, declares all sorts (including
+    /// enum sorts as datatypes) and constants, asserts every formula on the solver
+    /// stack and ends with `(check-sat)`.
+    ///
+    /// NOTE: See macro! `slv_to_smtlib2_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, name: &'n str) -> String {
+        match SlvToSmtLib2Z3::try_new(ctx, slv, name) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, name: &'n str) -> Result<String, ErrorZ3> {
+        let mut asserts = Z3AstVectorToVectorAstZ3::try_new(ctx, SlvGetAssertsZ3::try_new(ctx, slv)?)?;
+        // the last assertion is the benchmark formula, the others are printed before it
+        let formula = match asserts.pop() {
+            Some(x) => x,
+            None => BoolZ3::new(ctx, true)
+        };
+        let others: Vec<Z3_ast> = asserts.iter().map(|x| x.r).collect();
+        let str_name = match CString::new(name) {
+            Ok(x) => x,
+            Err(_) => return Err(ErrorZ3 {code: Z3_error_code_Z3_INVALID_ARG, msg: "The benchmark name contains a nul byte.".to_string()})
+        };
+        let str_empty = CString::new("").unwrap();
+        let str_status = CString::new("unknown").unwrap();
+        let z3 = ctx.check_string(unsafe {
+            Z3_benchmark_to_smtlib_string(ctx.r, str_name.as_ptr(), str_empty.as_ptr(), 
+                str_status.as_ptr(), str_empty.as_ptr(), others.len() as u32, others.as_ptr(), formula.r)
+        })?;
+        Ok(SlvToSmtLib2Z3 {ctx, slv, name, r: z3}.r)
+    }
+}
+
+// assert that the constants of the solver don't all take their values in `model` again,
+// the constants are rebuilt from their declarations so that constants of any sort can be forbidden
+fn slv_forbid_model_z3<'ctx>(ctx: &'ctx ContextZ3, slv: &SolverZ3<'ctx>, model: &Model<'ctx>) {
+    let mut to_assert = Vec::new();
+    let num = ModelGetNumConstsZ3::new(ctx, model);
+    for i in 0..num {
+        let decl = ModelGetConstDeclZ3::new(ctx, model, i);
+        let val = ModelGetConstInterpZ3::new(ctx, model, &decl);
+        to_assert.push(EQZ3::new(ctx, AppZ3::new(ctx, &decl, Vec::<Ast>::new()), val));
+    }
+    SlvAssertZ3::new(ctx, slv, NOTZ3::new(ctx, ANDZ3::new(ctx, to_assert)));
+}
+
 impl<'ctx, 'slv> SlvGetAllModelsZ3<'ctx, 'slv> {
     /// Retrieve all models for previos assertions (actually, specify the nr of solutions you want). 
     /// This method works iteratively, adding constraints one by one from feasible solutions.
@@ -332,33 +948,16 @@ impl<'ctx, 'slv> SlvGetAllModelsZ3<'ctx, 'slv> {
     pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> SlvGetAllModelsZ3<'ctx, 'slv> {
         let mut nr_st: u32 = 0;
 
-            let mut models: Vec<Z3_model> = Vec::new();
+            let mut models: Vec<Model<'ctx>> = Vec::new();
             let mut models_str: Vec<String> = Vec::new();
             
-            while SlvCheckZ3::new(&ctx, &slv) == 1 {
+            while SlvCheckZ3::new(&ctx, &slv) == SatResultZ3::Sat {
                 nr_st = nr_st + 1;
                 let model = SlvGetModelZ3::new(&ctx, &slv);
-                models.push(model);
+                models.push(model.clone());
                 models_str.push(nr_st.to_string());
-                models_str.push(ModelToStringZ3::new(&ctx, model));
-                let mut to_assert = Vec::new();
-                let num = ModelGetNumConstsZ3::new(&ctx, model);
-                for i in 0..num {
-                    let decl = ModelGetConstDeclZ3::new(&ctx, model, i);
-                    let var = GetDeclNameZ3::new(&ctx, model, decl);
-                    let var_str_init = GetSymbolStringZ3::new(&ctx, var);
-                    let var_str = &Z3StringToStringZ3::new(var_str_init);
-                    let val = ModelGetConstInterpZ3::new(&ctx, model, decl);
-                    
-                    if SortToStringZ3::new(&ctx, GetSortZ3::new(&ctx, val).r) == "Bool" {
-                        to_assert.push(eq_z3!(&ctx, bool_var_z3!(&ctx, var_str), val));
-                    } else if SortToStringZ3::new(&ctx, GetSortZ3::new(&ctx, val).r) == "Int" {
-                        to_assert.push(eq_z3!(&ctx, int_var_z3!(&ctx, var_str), val));
-                    } else {
-                        to_assert.push(eq_z3!(&ctx, enum_var_z3!(&ctx, GetSortZ3::new(&ctx, val).r, var_str), val));
-                    }
-                }
-                slv_assert_z3!(&ctx, &slv, not_z3!(&ctx, and_z3!(&ctx, to_assert)));
+                models_str.push(ModelToStringZ3::new(&ctx, &model));
+                slv_forbid_model_z3(ctx, slv, &model);
             }
             SlvGetAllModelsZ3 {ctx, n: nr_st, r: models, s: models_str, slv}        
     }
@@ -366,28 +965,10 @@ impl<'ctx, 'slv> SlvGetAllModelsZ3<'ctx, 'slv> {
 
 impl<'ctx, 'slv> SlvGetModelAndForbidZ3<'ctx, 'slv> {
     /// Write stuff here
-    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Z3_model {
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Model<'ctx> {
     
         let model = SlvGetModelZ3::new(&ctx, &slv);
-        let num = ModelGetNumConstsZ3::new(&ctx, model);
-        let mut to_assert = Vec::new();
-
-        for i in 0..num {
-            let decl = ModelGetConstDeclZ3::new(&ctx, model, i);
-            let var = GetDeclNameZ3::new(&ctx, model, decl);
-            let var_str_init = GetSymbolStringZ3::new(&ctx, var);
-            let var_str = &Z3StringToStringZ3::new(var_str_init);
-            let val = ModelGetConstInterpZ3::new(&ctx, model, decl);
-                    
-            if SortToStringZ3::new(&ctx, GetSortZ3::new(&ctx, val).r) == "Bool" {
-                to_assert.push(eq_z3!(&ctx, bool_var_z3!(&ctx, var_str), val));
-            } else if SortToStringZ3::new(&ctx, GetSortZ3::new(&ctx, val).r) == "Int" {
-                to_assert.push(eq_z3!(&ctx, int_var_z3!(&ctx, var_str), val));
-            } else {
-                to_assert.push(eq_z3!(&ctx, enum_var_z3!(&ctx, GetSortZ3::new(&ctx, val).r, var_str), val));
-            }
-        }
-        slv_assert_z3!(&ctx, &slv, not_z3!(&ctx, and_z3!(&ctx, to_assert)));
+        slv_forbid_model_z3(ctx, slv, &model);
         model
     }       
 }
@@ -402,45 +983,114 @@ impl<'ctx, 'slv> SlvGetNModelsZ3<'ctx, 'slv> {
     pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, nr_solutions: u32) -> SlvGetNModelsZ3<'ctx, 'slv> {
         let mut nr_st: u32 = 0;
 
-            let mut models: Vec<Z3_model> = Vec::new();
+            let mut models: Vec<Model<'ctx>> = Vec::new();
             let mut models_str: Vec<String> = Vec::new();
             
-            while SlvCheckZ3::new(&ctx, &slv) == 1 && nr_st < nr_solutions {
+            while SlvCheckZ3::new(&ctx, &slv) == SatResultZ3::Sat && nr_st < nr_solutions {
                 nr_st = nr_st + 1;
                 let model = SlvGetModelZ3::new(&ctx, &slv);
-                models.push(model);
+                models.push(model.clone());
                 models_str.push(nr_st.to_string());
-                models_str.push(ModelToStringZ3::new(&ctx, model));
-                let mut to_assert = Vec::new();
-                let num = ModelGetNumConstsZ3::new(&ctx, model);
-                for i in 0..num {
-                    let decl = ModelGetConstDeclZ3::new(&ctx, model, i);
-                    let var = GetDeclNameZ3::new(&ctx, model, decl);
-                    let var_str = &Z3StringToStringZ3::new(GetSymbolStringZ3::new(&ctx, var));
-                    let val = ModelGetConstInterpZ3::new(&ctx, model, decl);
-                    if SortToStringZ3::new(&ctx, GetSortZ3::new(&ctx, val).r) == "Bool" {
-                        to_assert.push(eq_z3!(&ctx, bool_var_z3!(&ctx, var_str), val));
-                    } else if SortToStringZ3::new(&ctx, GetSortZ3::new(&ctx, val).r) == "Int" {
-                        to_assert.push(eq_z3!(&ctx, int_var_z3!(&ctx, var_str), val));
-                    } else {
-                        to_assert.push(eq_z3!(&ctx, enum_var_z3!(&ctx, GetSortZ3::new(&ctx, val).r, var_str), val));
-                    }
-                }
-                slv_assert_z3!(&ctx, &slv, not_z3!(&ctx, and_z3!(&ctx, to_assert)));
+                models_str.push(ModelToStringZ3::new(&ctx, &model));
+                slv_forbid_model_z3(ctx, slv, &model);
             }
             SlvGetNModelsZ3 {ctx, n: nr_st, r: models, s: models_str, slv}        
     }
 }
 
+impl <'ctx, 'slv> SlvModelsZ3<'ctx, 'slv> {
+    /// Iterate lazily over the models of the solver under the `assumptions`,
+    /// every step checks the solver again. A found model is only blocked on the
+    /// projection variables `vars`, so models that differ just in other
+    /// (auxiliary) constants are not yielded twice. With no `vars`, only the
+    /// first model is yielded.
+    ///
+    /// The iteration ends when the solver is unsat, an unknown check result or a failed
+    /// evaluation of a projection variable is returned as an error and ends it as well.
+    ///
+    /// The blocking constraints are asserted in a new scope of the solver that is
+    /// popped when the iterator is dropped, don't push or pop the solver while iterating.
+    ///
+    /// NOTE: See macro! `slv_models_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, vars: Vec<impl AsAstZ3<'ctx>>, assumptions: Vec<impl AsAstZ3<'ctx>>) -> SlvModelsZ3<'ctx, 'slv> {
+        match SlvModelsZ3::try_new(ctx, slv, vars, assumptions) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, vars: Vec<impl AsAstZ3<'ctx>>, assumptions: Vec<impl AsAstZ3<'ctx>>) -> Result<SlvModelsZ3<'ctx, 'slv>, ErrorZ3> {
+        let vars = vars.iter().map(|x| Ast::new(ctx, x.as_ast())).collect();
+        let assumptions = assumptions.iter().map(|x| Ast::new(ctx, x.as_ast())).collect();
+        let scope = slv.try_push()?;
+        Ok(SlvModelsZ3 {ctx, slv, vars, assumptions, scope, done: false})
+    }
+}
+
+impl <'ctx, 'slv> SlvModelsZ3<'ctx, 'slv> {
+    // check for the next model and block it, none if there are no more models
+    fn next_model(&mut self) -> Result<Option<ProjectedModelZ3<'ctx>>, ErrorZ3> {
+        match SlvCheckAssumptionsZ3::try_new(self.ctx, self.slv, self.assumptions.iter().collect())? {
+            AssumptionsResultZ3::Sat => (),
+            AssumptionsResultZ3::Unsat(_) => return Ok(None),
+            AssumptionsResultZ3::Unknown(reason) => 
+                return Err(ErrorZ3 {code: Z3_error_code_Z3_EXCEPTION, msg: format!("The solver returned unknown: {}", reason)})
+        }
+        let model = SlvGetModelZ3::try_new(self.ctx, self.slv)?;
+        let mut to_block = vec!();
+        let mut values = vec!();
+        for var in &self.vars {
+            let value = model_eval_ast_z3(self.ctx, model.r, var.r, true)?;
+            values.push(ast_to_value_z3(self.ctx, &value)?);
+            to_block.push(EQZ3::try_new(self.ctx, var, value)?);
+        }
+        match to_block.is_empty() {
+            true => self.done = true,
+            false => SlvAssertZ3::try_new(self.ctx, self.slv, NOTZ3::try_new(self.ctx, ANDZ3::try_new(self.ctx, to_block)?)?)?
+        }
+        Ok(Some(ProjectedModelZ3 {model, values}))
+    }
+}
+
+impl <'ctx, 'slv> Iterator for SlvModelsZ3<'ctx, 'slv> {
+    type Item = Result<ProjectedModelZ3<'ctx>, ErrorZ3>;
+
+    fn next(&mut self) -> Option<Result<ProjectedModelZ3<'ctx>, ErrorZ3>> {
+        if self.done {
+            return None
+        }
+        match self.next_model() {
+            Ok(Some(x)) => Some(Ok(x)),
+            Ok(None) => {
+                self.done = true;
+                None
+            },
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
 impl<'ctx> SlvUnsatCoreToStringZ3<'ctx> {
     /// Z3 optimizer to readable string
     /// 
     /// NOTE: See macro! `slv_unsat_core_to_string_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, core: Z3_ast_vector) -> String {
+    pub fn new(ctx: &'ctx ContextZ3, core: impl AsAstVectorZ3) -> String {
+        match SlvUnsatCoreToStringZ3::try_new(ctx, core) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, core: impl AsAstVectorZ3) -> Result<String, ErrorZ3> {
+        let core = core.as_ast_vector();
         let z3 = unsafe {
-            CStr::from_ptr(Z3_ast_vector_to_string(ctx.r, core)).to_str().unwrap().to_owned()
+            Z3_ast_vector_to_string(ctx.r, core)
         };
-        SlvUnsatCoreToStringZ3 {ctx, core, r: z3}.r
+        let z3 = ctx.check_string(z3)?;
+        Ok(SlvUnsatCoreToStringZ3 {ctx, core, r: z3}.r)
     }
 }
 
@@ -448,11 +1098,68 @@ impl<'ctx> SlvProofToStringZ3<'ctx> {
     /// Z3 optimizer to readable string
     /// 
     /// NOTE: See macro! `slv_proof_to_string_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, what: Z3_ast) -> String {
+    pub fn new(ctx: &'ctx ContextZ3, what: impl AsAstZ3<'ctx>) -> String {
+        match SlvProofToStringZ3::try_new(ctx, what) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, what: impl AsAstZ3<'ctx>) -> Result<String, ErrorZ3> {
+        let what = what.as_ast();
         let z3 = unsafe {
-            CStr::from_ptr(Z3_ast_to_string(ctx.r, what)).to_str().unwrap().to_owned()
+            Z3_ast_to_string(ctx.r, what)
         };
-        SlvProofToStringZ3 {ctx, what, r: z3}.r
+        let z3 = ctx.check_string(z3)?;
+        Ok(SlvProofToStringZ3 {ctx, what, r: z3}.r)
+    }
+}
+
+impl <'ctx, 'slv, 's> SlvFromSmtLib2Z3<'ctx, 'slv, 's> {
+    /// Parse an SMT-LIB2 script and assert the formulas into the SolverZ3.
+    ///
+    /// The `sorts` and `decls` are sorts and function declarations already created in 
+    /// the context that the script can refer to, see `ParseSmtLib2Z3`. If the script 
+    /// does not parse, nothing is asserted and the parse error is returned.
+    ///
+    /// NOTE: See macro! `slv_from_smtlib2_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, script: &'s str, sorts: Vec<impl AsSortZ3>, decls: Vec<impl AsFuncDeclZ3>) -> () {
+        match SlvFromSmtLib2Z3::try_new(ctx, slv, script, sorts, decls) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, script: &'s str, sorts: Vec<impl AsSortZ3>, decls: Vec<impl AsFuncDeclZ3>) -> Result<(), ErrorZ3> {
+        for cst in ParseSmtLib2Z3::try_new(ctx, script, sorts, decls)? {
+            unsafe {
+                Z3_solver_assert(ctx.r, slv.r, cst.r);
+            }
+            ctx.check_error()?;
+        }
+        Ok(())
+    }
+}
+
+impl <'ctx, 'slv, 'f> SlvFromSmtLib2FileZ3<'ctx, 'slv, 'f> {
+    /// Same as `SlvFromSmtLib2Z3`, but the script is read from `file`.
+    ///
+    /// NOTE: See macro! `slv_from_smtlib2_file_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, file: &'f str, sorts: Vec<impl AsSortZ3>, decls: Vec<impl AsFuncDeclZ3>) -> () {
+        match SlvFromSmtLib2FileZ3::try_new(ctx, slv, file, sorts, decls) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>, file: &'f str, sorts: Vec<impl AsSortZ3>, decls: Vec<impl AsFuncDeclZ3>) -> Result<(), ErrorZ3> {
+        for cst in ParseSmtLib2FileZ3::try_new(ctx, file, sorts, decls)? {
+            unsafe {
+                Z3_solver_assert(ctx.r, slv.r, cst.r);
+            }
+            ctx.check_error()?;
+        }
+        Ok(())
     }
 }
 
@@ -499,6 +1206,48 @@ macro_rules! slv_assert_z3 {
     }
 }
 
+/// parse an SMT-LIB2 script and assert it
+/// 
+/// Macro rule for:
+/// ```text
+/// z3solver::SlvFromSmtLib2Z3::new(&ctx, &slv, script, vec!(sort), vec!(decl))
+/// ```
+/// Using a specific context, without or with known sorts and declarations:
+/// ```text
+/// slv_from_smtlib2_z3!(&ctx, &slv, script)
+/// slv_from_smtlib2_z3!(&ctx, &slv, script, vec!(sort), vec!(decl))
+/// ```
+#[macro_export]
+macro_rules! slv_from_smtlib2_z3 {
+    ($ctx:expr, $slv:expr, $s:expr) => {
+        SlvFromSmtLib2Z3::new($ctx, $slv, $s, Vec::<Sort>::new(), Vec::<FuncDecl>::new())
+    };
+    ($ctx:expr, $slv:expr, $s:expr, $b:expr, $c:expr) => {
+        SlvFromSmtLib2Z3::new($ctx, $slv, $s, $b, $c)
+    };
+}
+
+/// parse an SMT-LIB2 file and assert it
+/// 
+/// Macro rule for:
+/// ```text
+/// z3solver::SlvFromSmtLib2FileZ3::new(&ctx, &slv, file, vec!(sort), vec!(decl))
+/// ```
+/// Using a specific context, without or with known sorts and declarations:
+/// ```text
+/// slv_from_smtlib2_file_z3!(&ctx, &slv, file)
+/// slv_from_smtlib2_file_z3!(&ctx, &slv, file, vec!(sort), vec!(decl))
+/// ```
+#[macro_export]
+macro_rules! slv_from_smtlib2_file_z3 {
+    ($ctx:expr, $slv:expr, $f:expr) => {
+        SlvFromSmtLib2FileZ3::new($ctx, $slv, $f, Vec::<Sort>::new(), Vec::<FuncDecl>::new())
+    };
+    ($ctx:expr, $slv:expr, $f:expr, $b:expr, $c:expr) => {
+        SlvFromSmtLib2FileZ3::new($ctx, $slv, $f, $b, $c)
+    };
+}
+
 /// assert constraint and track it for unsat core extraction
 /// 
 /// Macro rule for:
@@ -528,9 +1277,57 @@ macro_rules! slv_assert_and_track_z3 {
 /// slv_check_z3!(&ctx, &slv, a)
 /// ```
 #[macro_export]
-macro_rules! slv_check_z3 {
-    ($ctx:expr, $slv:expr) => {
-        SlvCheckZ3::new($ctx, $slv)
+macro_rules! slv_check_z3 {
+    ($ctx:expr, $slv:expr) => {
+        SlvCheckZ3::new($ctx, $slv)
+    }
+}
+
+/// check a solver under assumptions
+#[macro_export]
+macro_rules! slv_check_assumptions_z3 {
+    ($ctx:expr, $slv:expr, $assumptions:expr) => {
+        SlvCheckAssumptionsZ3::new($ctx, $slv, $assumptions)
+    }
+}
+
+/// get the values of variables forced by the assertions of a solver and assumptions
+#[macro_export]
+macro_rules! slv_get_consequences_z3 {
+    ($ctx:expr, $slv:expr, $assumptions:expr, $vars:expr) => {
+        SlvGetConsequencesZ3::new($ctx, $slv, $assumptions, $vars)
+    }
+}
+
+/// limit the following checks of a solver to a number of milliseconds
+#[macro_export]
+macro_rules! slv_set_timeout_z3 {
+    ($ctx:expr, $slv:expr, $ms:expr) => {
+        SlvSetTimeoutZ3::new($ctx, $slv, $ms)
+    }
+}
+
+/// limit the resources used by the following checks of a solver
+#[macro_export]
+macro_rules! slv_set_rlimit_z3 {
+    ($ctx:expr, $slv:expr, $rlimit:expr) => {
+        SlvSetRlimitZ3::new($ctx, $slv, $rlimit)
+    }
+}
+
+/// make the following unsat cores of a solver minimal
+#[macro_export]
+macro_rules! slv_set_core_minimize_z3 {
+    ($ctx:expr, $slv:expr, $minimize:expr) => {
+        SlvSetCoreMinimizeZ3::new($ctx, $slv, $minimize)
+    }
+}
+
+/// get the reason for an unknown check result
+#[macro_export]
+macro_rules! slv_get_reason_unknown_z3 {
+    ($ctx:expr, $a:expr) => {
+        SlvGetReasonUnknownZ3::new($ctx, $a)
     }
 }
 
@@ -550,6 +1347,17 @@ macro_rules! slv_get_all_models_z3 {
     }
 }
 
+/// iterate over the models of a solver, distinct in the projection variables
+#[macro_export]
+macro_rules! slv_models_z3 {
+    ($ctx:expr, $slv:expr, $vars:expr) => {
+        SlvModelsZ3::new($ctx, $slv, $vars, Vec::<Ast>::new())
+    };
+    ($ctx:expr, $slv:expr, $vars:expr, $assumptions:expr) => {
+        SlvModelsZ3::new($ctx, $slv, $vars, $assumptions)
+    }
+}
+
 /// get the parameter description
 #[macro_export]
 macro_rules! slv_get_param_descr_z3 {
@@ -574,6 +1382,14 @@ macro_rules! slv_get_unsat_core_z3 {
     }
 }
 
+/// get the unsat core as tracker names if unsat
+#[macro_export]
+macro_rules! slv_get_unsat_core_names_z3 {
+    ($ctx:expr, $a:expr) => {
+        SlvGetUnsatCoreNamesZ3::new($ctx, $a)
+    }
+}
+
 /// solver context to readable string
 #[macro_export]
 macro_rules! slv_to_string_z3 {
@@ -582,6 +1398,14 @@ macro_rules! slv_to_string_z3 {
     }
 }
 
+/// solver context to a standalone SMT-LIB2 benchmark
+#[macro_export]
+macro_rules! slv_to_smtlib2_z3 {
+    ($ctx:expr, $a:expr, $n:expr) => {
+        SlvToSmtLib2Z3::new($ctx, $a, $n)
+    }
+}
+
 /// unsat core to readable string
 #[macro_export]
 macro_rules! slv_unsat_core_to_string_z3 {
@@ -632,30 +1456,26 @@ fn test_push_pop(){
     let boolsort = BoolSortZ3::new(&ctx);
     let x = BoolVarZ3::new(&ctx, &boolsort, "x");
 
-    SlvAssertZ3::new(&ctx, &solv, x);
+    SlvAssertZ3::new(&ctx, &solv, &x);
 
     SlvPushZ3::new(&ctx, &solv);
     SlvAssertZ3::new(&ctx, &solv, NOTZ3::new(&ctx, x));
     let res1 = SlvCheckZ3::new(&ctx, &solv);
     
-    if res1 == 1 {
-        println!("SAT");
-    } else if res1 == -1 {
-        println!("UNSAT");
-    } else {
-        println!("UNDEF");
+    match res1 {
+        SatResultZ3::Sat => println!("SAT"),
+        SatResultZ3::Unsat => println!("UNSAT"),
+        SatResultZ3::Unknown(_) => println!("UNDEF")
     };
     
     SlvPopZ3::new(&ctx, &solv, 1);
 
     let res1 = SlvCheckZ3::new(&ctx, &solv);
     
-    if res1 == 1 {
-        println!("SAT");
-    } else if res1 == -1 {
-        println!("UNSAT");
-    } else {
-        println!("UNDEF");
+    match res1 {
+        SatResultZ3::Sat => println!("SAT"),
+        SatResultZ3::Unsat => println!("UNSAT"),
+        SatResultZ3::Unknown(_) => println!("UNDEF")
     };
 }
 
@@ -698,7 +1518,7 @@ fn test_new_scheck(){
 
     let res1 = SlvCheckZ3::new(&ctx, &solv);
 
-    assert_eq!(1, res1);
+    assert_eq!(SatResultZ3::Sat, res1);
 
     assert_eq!("(declare-fun y () Real)
 (assert (= y (- (/ 271549371.0 500000.0))))
@@ -710,6 +1530,35 @@ y -> (- (/ 271549371.0 500000.0))
     assert_eq!("y -> (- (/ 271549371.0 500000.0))\n", model_to_string_z3!(&ctx, model));
 }
 
+#[test]
+fn test_get_model_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let solv = SolverZ3::new(&ctx);
+
+    SlvAssertZ3::new(&ctx, &solv, BoolZ3::new(&ctx, false));
+
+    assert_eq!(SatResultZ3::Unsat, slv_check_z3!(&ctx, &solv));
+    assert!(SlvGetModelZ3::try_new(&ctx, &solv).is_err());
+}
+
+#[test]
+fn test_get_reason_unknown(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let solv = SolverZ3::new(&ctx);
+
+    let intsort = IntSortZ3::new(&ctx);
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let int1 = IntZ3::new(&ctx, &intsort, 7);
+
+    SlvAssertZ3::new(&ctx, &solv, EQZ3::new(&ctx, x, int1));
+
+    assert_eq!(SatResultZ3::Sat, slv_check_z3!(&ctx, &solv));
+    // no reason is given when the check is not unknown
+    assert_eq!("", slv_get_reason_unknown_z3!(&ctx, &solv));
+}
+
 #[test]
 fn test_new_solver_get_param_descr(){
     let conf = ConfigZ3::new();
@@ -731,7 +1580,7 @@ fn get_proof_test() {
     let three = IntZ3::new(&ctx, &sort, 3);
     let two = IntZ3::new(&ctx, &sort, 2);
 
-    SlvAssertZ3::new(&ctx, &slv, ANDZ3::new(&ctx, vec!(GTZ3::new(&ctx, x, three), LTZ3::new(&ctx, x, two))));
+    SlvAssertZ3::new(&ctx, &slv, ANDZ3::new(&ctx, vec!(GTZ3::new(&ctx, &x, three), LTZ3::new(&ctx, x, two))));
 
     SlvCheckZ3::new(&ctx, &slv);
     let proof = SlvGetProofZ3::new(&ctx, &slv);
@@ -794,6 +1643,77 @@ fn get_n_models_test() {
     }
 }
 
+#[test]
+fn test_slv_scope_pops_on_drop() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+    let x = BoolVarZ3::new(&ctx, &boolsort, "x");
+
+    SlvAssertZ3::new(&ctx, &slv, &x);
+    {
+        let scope = slv.push();
+        assert_eq!(1, scope.level);
+        SlvAssertZ3::new(&ctx, &slv, NOTZ3::new(&ctx, &x));
+        assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+    }
+    assert_eq!(0, SlvGetPopPointsZ3::new(&ctx, &slv));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_slv_scope_commit_and_discard() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+    let x = BoolVarZ3::new(&ctx, &boolsort, "x");
+
+    let outer = slv.push();
+    let inner = slv.push();
+    assert_eq!(2, inner.level);
+    SlvAssertZ3::new(&ctx, &slv, &x);
+    inner.commit();
+    assert_eq!(2, SlvGetPopPointsZ3::new(&ctx, &slv));
+
+    // the outer scope also removes the committed inner one
+    SlvAssertZ3::new(&ctx, &slv, NOTZ3::new(&ctx, &x));
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+    outer.discard();
+    assert_eq!(0, SlvGetPopPointsZ3::new(&ctx, &slv));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_slv_scope_stale() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+    let x = BoolVarZ3::new(&ctx, &boolsort, "x");
+
+    // the outer scope also pops the inner ones
+    let outer = slv.push();
+    let inner = slv.push();
+    let dropped = slv.push();
+    outer.discard();
+    assert_eq!(0, SlvGetPopPointsZ3::new(&ctx, &slv));
+
+    // the stale guards don't pop the newer scopes at their levels
+    let newer = slv.push();
+    let newest = slv.push();
+    SlvAssertZ3::new(&ctx, &slv, &x);
+    assert!(inner.try_discard().is_err());
+    drop(dropped);
+    assert_eq!(2, SlvGetPopPointsZ3::new(&ctx, &slv));
+    assert_eq!(1, Z3AstVectorToVectorAstZ3::new(&ctx, SlvGetAssertsZ3::new(&ctx, &slv)).len());
+
+    newest.discard();
+    newer.discard();
+    assert_eq!(0, SlvGetPopPointsZ3::new(&ctx, &slv));
+}
+
 #[test]
 fn get_unsat_core_test() {
     let cfg = ConfigZ3::new();
@@ -808,8 +1728,8 @@ fn get_unsat_core_test() {
     let two = IntZ3::new(&ctx, &sort, 2);
     let one = IntZ3::new(&ctx, &sort, 1);
 
-    SlvAssertAndTrackZ3::new(&ctx, &slv, GTZ3::new(&ctx, x, three), "a1");
-    SlvAssertAndTrackZ3::new(&ctx, &slv, LTZ3::new(&ctx, x, two), "a2");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, GTZ3::new(&ctx, &x, three), "a1");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LTZ3::new(&ctx, &x, two), "a2");
     SlvAssertAndTrackZ3::new(&ctx, &slv, EQZ3::new(&ctx, x, one), "a3");
 
     SlvCheckZ3::new(&ctx, &slv);
@@ -819,6 +1739,227 @@ fn get_unsat_core_test() {
   a2)", slv_unsat_core_to_string_z3!(&ctx, unsat_core));
 }
 
+#[test]
+fn test_get_unsat_core_names() {
+    let cfg = ConfigZ3::new();
+    SetParamZ3::new(&cfg, "unsat_core", "true");
+    let ctx = ContextZ3::new(&cfg);
+    let slv = SolverZ3::new(&ctx);
+
+    let sort = IntSortZ3::new(&ctx);
+    let x = IntVarZ3::new(&ctx, &sort, "x");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, GTZ3::new(&ctx, &x, IntZ3::new(&ctx, &sort, 3)), "goal_1");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LTZ3::new(&ctx, &x, IntZ3::new(&ctx, &sort, 2)), "spec_1");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LTZ3::new(&ctx, &x, IntZ3::new(&ctx, &sort, 10)), "spec_2");
+
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+    let mut core = SlvGetUnsatCoreNamesZ3::new(&ctx, &slv);
+    core.sort();
+    assert_eq!(vec!("goal_1", "spec_1"), core);
+}
+
+#[test]
+fn test_get_unsat_core_names_minimized() {
+    let cfg = ConfigZ3::new();
+    SetParamZ3::new(&cfg, "unsat_core", "true");
+    let ctx = ContextZ3::new(&cfg);
+    let slv = SolverZ3::new(&ctx);
+    SlvSetCoreMinimizeZ3::new(&ctx, &slv, true);
+
+    let sort = IntSortZ3::new(&ctx);
+    let x = IntVarZ3::new(&ctx, &sort, "x");
+    let y = IntVarZ3::new(&ctx, &sort, "y");
+    let z = IntVarZ3::new(&ctx, &sort, "z");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LEZ3::new(&ctx, &x, IntZ3::new(&ctx, &sort, 4)), "t1");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LEZ3::new(&ctx, &y, IntZ3::new(&ctx, &sort, 6)), "t2");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LEZ3::new(&ctx, &z, IntZ3::new(&ctx, &sort, 6)), "t3");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, GEZ3::new(&ctx, ADDZ3::new(&ctx, vec!(&x, &y, &z)), IntZ3::new(&ctx, &sort, 20)), "t4");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LEZ3::new(&ctx, &x, IntZ3::new(&ctx, &sort, 1)), "t5");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LEZ3::new(&ctx, &y, IntZ3::new(&ctx, &sort, 1)), "t6");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LEZ3::new(&ctx, &z, IntZ3::new(&ctx, &sort, 1)), "t7");
+
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+    let core = SlvGetUnsatCoreNamesZ3::new(&ctx, &slv);
+    assert_eq!(4, core.len());
+    assert!(core.contains(&"t4".to_string()));
+}
+
+#[test]
+fn test_get_unsat_core_names_macro_1() {
+    let cfg = cfg_z3!();
+    SetParamZ3::new(&cfg, "unsat_core", "true");
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+    slv_set_core_minimize_z3!(&ctx, &slv, true);
+
+    let x = bool_var_z3!(&ctx, "x");
+    slv_assert_and_track_z3!(&ctx, &slv, x.clone(), "a1");
+    slv_assert_and_track_z3!(&ctx, &slv, not_z3!(&ctx, x), "a2");
+    slv_check_z3!(&ctx, &slv);
+
+    let mut core = slv_get_unsat_core_names_z3!(&ctx, &slv);
+    core.sort();
+    assert_eq!(vec!("a1", "a2"), core);
+}
+
+#[test]
+fn test_slv_models_projection() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let aux = BoolVarZ3::new(&ctx, &boolsort, "aux");
+    SlvAssertZ3::new(&ctx, &slv, GEZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 1)));
+    SlvAssertZ3::new(&ctx, &slv, LEZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 3)));
+    SlvAssertZ3::new(&ctx, &slv, ORZ3::new(&ctx, vec!(&aux, &NOTZ3::new(&ctx, &aux))));
+
+    let mut values: Vec<ModelValueZ3> = SlvModelsZ3::new(&ctx, &slv, vec!(&x), Vec::<Ast>::new())
+        .map(|m| m.unwrap().values[0].clone()).collect();
+    values.sort();
+    assert_eq!(vec!(ModelValueZ3::Int(1), ModelValueZ3::Int(2), ModelValueZ3::Int(3)), values);
+
+    // the blocking constraints are gone when the iterator is dropped
+    assert_eq!(3, SlvModelsZ3::new(&ctx, &slv, vec!(&x), Vec::<Ast>::new()).count());
+    assert_eq!(1, SlvModelsZ3::new(&ctx, &slv, Vec::<Ast>::new(), Vec::<Ast>::new()).count());
+}
+
+#[test]
+fn test_slv_models_assumptions() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("red", "blue", "green"));
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let b = EnumVarZ3::new(&ctx, &balls, "b");
+    let no_red = NOTZ3::new(&ctx, EQZ3::new(&ctx, &b, &balls.enum_asts[0]));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 5)));
+
+    let mut models = SlvModelsZ3::new(&ctx, &slv, vec!(&b, &x), vec!(&no_red));
+    let first = models.next().unwrap().unwrap();
+    assert_eq!(ModelValueZ3::Int(5), first.values[1]);
+    assert!(first.values[0] != ModelValueZ3::Enum("red".to_string()));
+    assert_eq!(1, models.count());
+}
+
+#[test]
+fn test_slv_models_unknown() {
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    let x = int_var_z3!(&ctx, "x");
+    let y = int_var_z3!(&ctx, "y");
+    let z = int_var_z3!(&ctx, "z");
+    let positive = and_z3!(&ctx, 
+        gt_z3!(&ctx, &x, int_z3!(&ctx, 0)), 
+        gt_z3!(&ctx, &y, int_z3!(&ctx, 0)), 
+        gt_z3!(&ctx, &z, int_z3!(&ctx, 0)));
+    let cubes = eq_z3!(&ctx, 
+        add_z3!(&ctx, mul_z3!(&ctx, &x, &x, &x), mul_z3!(&ctx, &y, &y, &y)), 
+        mul_z3!(&ctx, &z, &z, &z));
+    slv_assert_z3!(&ctx, &slv, forall_z3!(&ctx, vec!(&x, &y, &z), imp_z3!(&ctx, positive, not_z3!(&ctx, cubes))));
+    slv_set_rlimit_z3!(&ctx, &slv, 1000);
+
+    // an unknown check is not the end of the models
+    let models: Vec<_> = slv_models_z3!(&ctx, &slv, vec!(&x)).collect();
+    assert_eq!(1, models.len());
+    assert_eq!("The solver returned unknown: (incomplete quantifiers)", models[0].as_ref().err().unwrap().msg);
+}
+
+#[test]
+fn test_slv_models_macro_1() {
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    let x = bool_var_z3!(&ctx, "x");
+    let y = bool_var_z3!(&ctx, "y");
+    slv_assert_z3!(&ctx, &slv, or_z3!(&ctx, vec!(&x, &y)));
+
+    assert_eq!(2, slv_models_z3!(&ctx, &slv, vec!(&x)).count());
+    assert_eq!(1, slv_models_z3!(&ctx, &slv, vec!(&x), vec!(&x)).count());
+    assert_eq!(3, slv_models_z3!(&ctx, &slv, vec!(&x, &y)).count());
+}
+
+#[test]
+fn test_slv_check_assumptions() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let goal_1 = BoolVarZ3::new(&ctx, &boolsort, "goal_1");
+    let goal_2 = BoolVarZ3::new(&ctx, &boolsort, "goal_2");
+    SlvAssertZ3::new(&ctx, &slv, IMPZ3::new(&ctx, &goal_1, GTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 5))));
+    SlvAssertZ3::new(&ctx, &slv, IMPZ3::new(&ctx, &goal_2, LTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 3))));
+
+    assert_eq!(AssumptionsResultZ3::Sat, SlvCheckAssumptionsZ3::new(&ctx, &slv, vec!(&goal_1)));
+    assert_eq!(AssumptionsResultZ3::Sat, SlvCheckAssumptionsZ3::new(&ctx, &slv, vec!(&goal_2)));
+    match SlvCheckAssumptionsZ3::new(&ctx, &slv, vec!(&goal_1, &goal_2)) {
+        AssumptionsResultZ3::Unsat(core) => {
+            assert_eq!(2, core.len());
+            assert!(core.contains(&goal_1) && core.contains(&goal_2));
+        },
+        x => panic!("expected unsat, got {:?}", x)
+    }
+    // the assumptions don't stay in the solver
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_slv_get_consequences() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let a = BoolVarZ3::new(&ctx, &boolsort, "a");
+    let p = BoolVarZ3::new(&ctx, &boolsort, "p");
+    let q = BoolVarZ3::new(&ctx, &boolsort, "q");
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let y = IntVarZ3::new(&ctx, &intsort, "y");
+    SlvAssertZ3::new(&ctx, &slv, IMPZ3::new(&ctx, &a, EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 3))));
+    SlvAssertZ3::new(&ctx, &slv, IMPZ3::new(&ctx, &a, NOTZ3::new(&ctx, &p)));
+    SlvAssertZ3::new(&ctx, &slv, &q);
+
+    match SlvGetConsequencesZ3::new(&ctx, &slv, vec!(&a), vec!(&p, &q, &x, &y)) {
+        ConsequencesResultZ3::Sat(cons) => {
+            assert_eq!(3, cons.len());
+            assert!(cons.contains(&ConsequenceZ3 {assumptions: vec!(), var: q.clone(), value: ModelValueZ3::Bool(true)}));
+            assert!(cons.contains(&ConsequenceZ3 {assumptions: vec!(a.clone()), var: p.clone(), value: ModelValueZ3::Bool(false)}));
+            assert!(cons.contains(&ConsequenceZ3 {assumptions: vec!(a.clone()), var: x.clone(), value: ModelValueZ3::Int(3)}));
+        },
+        x => panic!("expected sat, got {:?}", x)
+    }
+
+    SlvAssertZ3::new(&ctx, &slv, &p);
+    assert_eq!(ConsequencesResultZ3::Unsat(vec!(a.clone())), SlvGetConsequencesZ3::new(&ctx, &slv, vec!(&a), vec!(&x)));
+}
+
+#[test]
+fn test_slv_check_assumptions_macro_1() {
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    let x = bool_var_z3!(&ctx, "x");
+    let y = bool_var_z3!(&ctx, "y");
+    slv_assert_z3!(&ctx, &slv, iff_z3!(&ctx, &x, not_z3!(&ctx, &y)));
+
+    assert_eq!(AssumptionsResultZ3::Sat, slv_check_assumptions_z3!(&ctx, &slv, vec!(&x)));
+    assert!(matches!(slv_check_assumptions_z3!(&ctx, &slv, vec!(&x, &y)), AssumptionsResultZ3::Unsat(_)));
+    assert_eq!(ConsequencesResultZ3::Sat(vec!(ConsequenceZ3 {assumptions: vec!(x.clone()), var: y.clone(), value: ModelValueZ3::Bool(false)})),
+        slv_get_consequences_z3!(&ctx, &slv, vec!(&x), vec!(&y)));
+}
+
 #[test]
 fn test_slv_macro_1(){
     let cfg = cfg_z3!();
@@ -878,7 +2019,7 @@ fn test_slv_check_macro_1(){
 
     let res1 = slv_check_z3!(&ctx, &slv);
 
-    assert_eq!(1, res1);
+    assert_eq!(SatResultZ3::Sat, res1);
 
     assert_eq!("(declare-fun y () Real)
 (assert (= y (- (/ 271549371.0 500000.0))))
@@ -910,7 +2051,7 @@ fn test_get_proof_macro_1(){
     let three = IntZ3::new(&ctx, &sort, 3);
     let two = IntZ3::new(&ctx, &sort, 2);
 
-    SlvAssertZ3::new(&ctx, &slv, ANDZ3::new(&ctx, vec!(GTZ3::new(&ctx, x, three), LTZ3::new(&ctx, x, two))));
+    SlvAssertZ3::new(&ctx, &slv, ANDZ3::new(&ctx, vec!(GTZ3::new(&ctx, &x, three), LTZ3::new(&ctx, x, two))));
 
     SlvCheckZ3::new(&ctx, &slv);
     let proof = slv_get_proof_z3!(&ctx, &slv);
@@ -949,7 +2090,7 @@ fn test_get_proof_macro_1(){
     );
 
     let res1 = slv_check_z3!(&ctx, &slv);
-    assert_eq!(-1, res1);
+    assert_eq!(SatResultZ3::Unsat, res1);
 
     let proof = slv_get_proof_z3!(&ctx, &slv);
     assert_eq!("(mp (and-elim (mp (asserted (and (= y (- 543)) (< y (- 600)) (> y 300)))
@@ -1034,8 +2175,8 @@ fn test_get_unsat_core_macro_1() {
     let two = IntZ3::new(&ctx, &sort, 2);
     let one = IntZ3::new(&ctx, &sort, 1);
 
-    SlvAssertAndTrackZ3::new(&ctx, &slv, GTZ3::new(&ctx, x, three), "a1");
-    SlvAssertAndTrackZ3::new(&ctx, &slv, LTZ3::new(&ctx, x, two), "a2");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, GTZ3::new(&ctx, &x, three), "a1");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LTZ3::new(&ctx, &x, two), "a2");
     SlvAssertAndTrackZ3::new(&ctx, &slv, EQZ3::new(&ctx, x, one), "a3");
 
     SlvCheckZ3::new(&ctx, &slv);
@@ -1043,4 +2184,207 @@ fn test_get_unsat_core_macro_1() {
     assert_eq!("(ast-vector
   a1
   a2)", slv_unsat_core_to_string_z3!(&ctx, unsat_core));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_slv_from_smtlib2_1(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("red", "blue"));
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let b = EnumVarZ3::new(&ctx, &balls, "b");
+    SlvFromSmtLib2Z3::new(&ctx, &slv, "(assert (> x 5)) (assert (< x 7)) (assert (not (= b red)))", 
+        vec!(&balls), vec!(GetAppDeclZ3::new(&ctx, &x), GetAppDeclZ3::new(&ctx, &b)));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 6)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!("b -> blue\nx -> 6\n", model_to_string_z3!(&ctx, model));
+}
+
+#[test]
+fn test_slv_from_smtlib2_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+
+    let err = SlvFromSmtLib2Z3::try_new(&ctx, &slv, "(assert (> y 5))", Vec::<Sort>::new(), Vec::<FuncDecl>::new()).unwrap_err();
+    assert_eq!(Z3_error_code_Z3_PARSER_ERROR, err.code);
+    assert_eq!("(error \"line 1 column 12: unknown constant y\")\n", err.msg);
+
+    let err = SlvFromSmtLib2Z3::try_new(&ctx, &slv, "(assert (> 1 true))", Vec::<Sort>::new(), Vec::<FuncDecl>::new()).unwrap_err();
+    assert_eq!(Z3_error_code_Z3_PARSER_ERROR, err.code);
+    assert_eq!("", SlvToStringZ3::new(&ctx, &slv));
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_slv_from_smtlib2_file_1(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+
+    let path = std::env::temp_dir().join("mini_sp_smt_slv_from_smtlib2_file_1.smt2");
+    std::fs::write(&path, "(declare-const y Int)\n(assert (> y 2))\n(assert (< y 2))\n").unwrap();
+    SlvFromSmtLib2FileZ3::new(&ctx, &slv, path.to_str().unwrap(), Vec::<Sort>::new(), Vec::<FuncDecl>::new());
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+    assert!(SlvFromSmtLib2FileZ3::try_new(&ctx, &slv, path.to_str().unwrap(), Vec::<Sort>::new(), Vec::<FuncDecl>::new()).is_err());
+}
+
+#[test]
+fn test_slv_from_smtlib2_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    let x = int_var_z3!(&ctx, "x");
+    slv_from_smtlib2_z3!(&ctx, &slv, "(declare-const y Int) (assert (= y 3))");
+    slv_from_smtlib2_z3!(&ctx, &slv, "(assert (= x (* 2 3)))", Vec::<Sort>::new(), vec!(get_app_decl_z3!(&ctx, &x)));
+    assert_eq!(SatResultZ3::Sat, slv_check_z3!(&ctx, &slv));
+
+    slv_assert_z3!(&ctx, &slv, not_z3!(&ctx, eq_z3!(&ctx, &x, int_z3!(&ctx, 6))));
+    assert_eq!(SatResultZ3::Unsat, slv_check_z3!(&ctx, &slv));
+}
+
+#[test]
+fn test_slv_to_smtlib2_1(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("red", "blue"));
+
+    let b = EnumVarZ3::new(&ctx, &balls, "b");
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &b, &balls.enum_asts[0]));
+    SlvAssertZ3::new(&ctx, &slv, GTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 2)));
+
+    let script = SlvToSmtLib2Z3::new(&ctx, &slv, "test");
+    assert_eq!("; test
+(set-info :status unknown)
+(declare-datatypes ((balls 0)) (((red) (blue))))
+ (declare-fun b () balls)
+(declare-fun x () Int)
+(assert
+ (= b red))
+(assert
+ (> x 2))
+(check-sat)
+", script);
+
+    // the script is standalone, a fresh context can load it without knowing the sorts
+    let conf2 = ConfigZ3::new();
+    let ctx2 = ContextZ3::new(&conf2);
+    let slv2 = SolverZ3::new(&ctx2);
+    SlvFromSmtLib2Z3::new(&ctx2, &slv2, &script, Vec::<Sort>::new(), Vec::<FuncDecl>::new());
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx2, &slv2));
+}
+
+#[test]
+fn test_slv_to_smtlib2_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+
+    let err = SlvToSmtLib2Z3::try_new(&ctx, &slv, "te\0st").unwrap_err();
+    assert_eq!(Z3_error_code_Z3_INVALID_ARG, err.code);
+    assert_eq!("The benchmark name contains a nul byte.", err.msg);
+}
+
+#[test]
+fn test_slv_to_smtlib2_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    assert_eq!("; empty\n(set-info :status unknown)\n(check-sat)\n", slv_to_smtlib2_z3!(&ctx, &slv, "empty"));
+}
+
+// pigeonhole problems are hard for the solver, so the checks run long enough to be stopped
+#[cfg(test)]
+fn pigeonhole_z3<'ctx>(ctx: &'ctx ContextZ3, slv: &SolverZ3<'ctx>, holes: u32) {
+    let p = |i: u32, h: u32| BoolVarZ3::new(ctx, &BoolSortZ3::new(ctx), &format!("p_{}_{}", i, h));
+    for i in 0..holes + 1 {
+        SlvAssertZ3::new(ctx, slv, ORZ3::new(ctx, (0..holes).map(|h| p(i, h)).collect()));
+    }
+    for h in 0..holes {
+        for i in 0..holes + 1 {
+            for j in i + 1..holes + 1 {
+                SlvAssertZ3::new(ctx, slv, NOTZ3::new(ctx, ANDZ3::new(ctx, vec!(p(i, h), p(j, h)))));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_slv_set_timeout(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+
+    pigeonhole_z3(&ctx, &slv, 10);
+    SlvSetTimeoutZ3::new(&ctx, &slv, 20);
+    assert_eq!(SatResultZ3::Unknown("timeout".to_string()), SlvCheckZ3::new(&ctx, &slv));
+    assert_eq!("timeout", SlvGetReasonUnknownZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_slv_set_rlimit(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+
+    pigeonhole_z3(&ctx, &slv, 10);
+    SlvSetRlimitZ3::new(&ctx, &slv, 1000);
+    assert_eq!(SatResultZ3::Unknown("max. resource limit exceeded".to_string()), SlvCheckZ3::new(&ctx, &slv));
+
+    SlvResetZ3::new(&ctx, &slv);
+    SlvSetRlimitZ3::new(&ctx, &slv, 0);
+    let boolsort = BoolSortZ3::new(&ctx);
+    SlvAssertZ3::new(&ctx, &slv, BoolVarZ3::new(&ctx, &boolsort, "x"));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_interrupt_from_other_thread(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+
+    pigeonhole_z3(&ctx, &slv, 10);
+    let handle = InterruptHandleZ3::new(&ctx);
+    let res = crossbeam::scope(|s| {
+        s.spawn(move |_| {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            handle.interrupt();
+        });
+        SlvCheckZ3::new(&ctx, &slv)
+    }).unwrap();
+    assert_eq!(SatResultZ3::Unknown("interrupted".to_string()), res);
+
+    // the context can be used again after the interrupt
+    let slv2 = SolverZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+    SlvAssertZ3::new(&ctx, &slv2, BoolVarZ3::new(&ctx, &boolsort, "x"));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv2));
+}
+
+#[test]
+fn test_limits_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    pigeonhole_z3(&ctx, &slv, 10);
+    slv_set_rlimit_z3!(&ctx, &slv, 1000);
+    slv_set_timeout_z3!(&ctx, &slv, 60000);
+    assert!(matches!(slv_check_z3!(&ctx, &slv), SatResultZ3::Unknown(_)));
+
+    let handle = interrupt_handle_z3!(&ctx);
+    handle.interrupt();
+}
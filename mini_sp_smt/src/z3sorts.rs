@@ -24,21 +24,34 @@ pub struct StringSortZ3<'ctx> {
     pub r: Z3_sort
 }
 
+pub struct BitVecSortZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub size: u32,
+    pub r: Z3_sort
+}
+
+pub struct ArraySortZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub domain: Z3_sort,
+    pub range: Z3_sort,
+    pub r: Z3_sort
+}
+
 pub struct EnumSortZ3<'ctx, 'a> {
     pub ctx: &'ctx ContextZ3,
     pub name: &'a str,
     pub nr: u32,
     pub enum_names: Vec<Z3_symbol>,
-    pub enum_consts: Vec<Z3_func_decl>,
-    pub enum_testers: Vec<Z3_func_decl>,
-    pub enum_asts: Vec<Z3_ast>,
+    pub enum_consts: Vec<FuncDecl<'ctx>>,
+    pub enum_testers: Vec<FuncDecl<'ctx>>,
+    pub enum_asts: Vec<Ast<'ctx>>,
     pub r: Z3_sort
 }
 
 pub struct GetSortZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub arg: Z3_ast,
-    pub r: Z3_sort
+    pub r: Sort<'ctx>
 }
 pub struct SortToStringZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
@@ -50,7 +63,8 @@ impl<'ctx> SortToStringZ3<'ctx> {
     /// Z3 optimizer to readable string
     /// 
     /// NOTE: See macro! `sort_to_string_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, what: Z3_sort) -> String {
+    pub fn new(ctx: &'ctx ContextZ3, what: impl AsSortZ3) -> String {
+        let what = what.as_sort();
         let z3 = unsafe {
             CStr::from_ptr(Z3_sort_to_string(ctx.r, what)).to_str().unwrap().to_owned()
         };
@@ -66,7 +80,9 @@ impl <'ctx> BoolSortZ3<'ctx> {
     /// NOTE: See macro! `bool_sort_z3!`
     pub fn new(ctx: &'ctx ContextZ3) -> BoolSortZ3 {
         let z3 = unsafe { 
-            Z3_mk_bool_sort(ctx.r)
+            let sort = Z3_mk_bool_sort(ctx.r);
+            Z3_inc_ref(ctx.r, Z3_sort_to_ast(ctx.r, sort));
+            sort
         };
         BoolSortZ3 {ctx, r: z3}
     }
@@ -81,7 +97,9 @@ impl <'ctx> IntSortZ3<'ctx> {
     /// NOTE: See macro! `int_sort_z3!`
     pub fn new(ctx: &'ctx ContextZ3) -> IntSortZ3 {
         let z3 = unsafe {
-            Z3_mk_int_sort(ctx.r)
+            let sort = Z3_mk_int_sort(ctx.r);
+            Z3_inc_ref(ctx.r, Z3_sort_to_ast(ctx.r, sort));
+            sort
         };
         IntSortZ3 {ctx, r: z3}
     }
@@ -95,12 +113,60 @@ impl <'ctx> RealSortZ3<'ctx> {
     /// NOTE: See macro! `real_sort_z3!`
     pub fn new(ctx: &'ctx ContextZ3) -> RealSortZ3 {
         let z3 = unsafe {
-            Z3_mk_real_sort(ctx.r)
+            let sort = Z3_mk_real_sort(ctx.r);
+            Z3_inc_ref(ctx.r, Z3_sort_to_ast(ctx.r, sort));
+            sort
         };
         RealSortZ3 {ctx, r: z3}
     }
 }
 
+impl <'ctx> BitVecSortZ3<'ctx> {
+    /// Create a bit-vector type of the given size.
+    ///
+    /// This type can also be seen as a machine integer.
+    ///
+    /// NOTE: The size of the bit-vector type must be greater than zero.
+    /// 
+    /// NOTE: See macro! `bv_sort_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, size: u32) -> BitVecSortZ3 {
+        match BitVecSortZ3::try_new(ctx, size) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, size: u32) -> Result<BitVecSortZ3, ErrorZ3> {
+        let z3 = unsafe {
+            Z3_mk_bv_sort(ctx.r, size)
+        };
+        ctx.check_error()?;
+        unsafe {
+            Z3_inc_ref(ctx.r, Z3_sort_to_ast(ctx.r, z3));
+        }
+        Ok(BitVecSortZ3 {ctx, size, r: z3})
+    }
+}
+
+impl <'ctx> ArraySortZ3<'ctx> {
+    /// Create an array type.
+    ///
+    /// We usually represent the array type as: `[domain -> range]`.
+    /// Arrays are usually used to model the heap/memory in software verification.
+    /// 
+    /// NOTE: See macro! `array_sort_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, domain: impl AsSortZ3, range: impl AsSortZ3) -> ArraySortZ3 {
+        let domain = domain.as_sort();
+        let range = range.as_sort();
+        let z3 = unsafe {
+            let sort = Z3_mk_array_sort(ctx.r, domain, range);
+            Z3_inc_ref(ctx.r, Z3_sort_to_ast(ctx.r, sort));
+            sort
+        };
+        ArraySortZ3 {ctx, domain, range, r: z3}
+    }
+}
+
 impl <'ctx, 'a> EnumSortZ3<'ctx, 'a> {
     /// Create an enumeration type.
     ///
@@ -115,7 +181,6 @@ impl <'ctx, 'a> EnumSortZ3<'ctx, 'a> {
             let mut enum_names: Vec<Z3_symbol> = vec![std::ptr::null_mut(); enum_elements.len()];
             let mut enum_consts: Vec<Z3_func_decl> = vec![std::ptr::null_mut(); enum_elements.len()];
             let mut enum_testers: Vec<Z3_func_decl> = vec![std::ptr::null_mut(); enum_elements.len()];
-            let mut enum_asts: Vec<Z3_ast> = vec![std::ptr::null_mut(); enum_elements.len()];
 
             for s in &enum_elements {
                 let index = enum_elements.iter().position(|&r| r == *s).unwrap();
@@ -123,11 +188,11 @@ impl <'ctx, 'a> EnumSortZ3<'ctx, 'a> {
             }
 
             let enum1 = Z3_mk_enumeration_sort(ctx.r, enum_name_symbol, enum_names.len() as u32, enum_names.as_ptr(), enum_consts.as_mut_ptr(), enum_testers.as_mut_ptr());
-            
-            for c in &enum_names {
-                let index = enum_names.iter().position(|&r| r == *c).unwrap();
-                enum_asts[index] = Z3_mk_app(ctx.r, enum_consts[index], 0, [].as_ptr());
-            }
+            Z3_inc_ref(ctx.r, Z3_sort_to_ast(ctx.r, enum1));
+
+            let enum_consts: Vec<FuncDecl<'ctx>> = enum_consts.into_iter().map(|c| FuncDecl::new(ctx, c)).collect();
+            let enum_testers: Vec<FuncDecl<'ctx>> = enum_testers.into_iter().map(|t| FuncDecl::new(ctx, t)).collect();
+            let enum_asts: Vec<Ast<'ctx>> = enum_consts.iter().map(|c| Ast::new(ctx, Z3_mk_app(ctx.r, c.r, 0, [].as_ptr()))).collect();
 
             (len, enum_names, enum_consts, enum_testers, enum_asts, enum1)
         };
@@ -144,7 +209,9 @@ impl <'ctx> StringSortZ3<'ctx> {
     /// NOTE: See macro! `string_sort_z3!`
     pub fn new(ctx: &'ctx ContextZ3) -> StringSortZ3 {
         let z3 = unsafe {
-            Z3_mk_string_sort(ctx.r)
+            let sort = Z3_mk_string_sort(ctx.r);
+            Z3_inc_ref(ctx.r, Z3_sort_to_ast(ctx.r, sort));
+            sort
         };
         StringSortZ3 {ctx, r: z3}
     }
@@ -156,11 +223,117 @@ impl<'ctx> GetSortZ3<'ctx> {
     /// NOTE: The AST node must be a constant, application, numeral, bound variable, or quantifier. 
     /// 
     /// NOTE: See macro! `get_sort_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, arg: Z3_ast) -> GetSortZ3 {
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> GetSortZ3 {
+        let arg = arg.as_ast();
         let z3 = unsafe {
             Z3_get_sort(ctx.r, arg)
         };
-        GetSortZ3 {ctx, r: z3, arg}        
+        GetSortZ3 {ctx, r: Sort::new(ctx, z3), arg}        
+    }
+}
+
+impl <'ctx> Drop for BoolSortZ3<'ctx> {
+    /// Decrement the reference counter of the sort.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_dec_ref(self.ctx.r, Z3_sort_to_ast(self.ctx.r, self.r))
+        }
+    }
+}
+
+impl <'ctx> Drop for IntSortZ3<'ctx> {
+    /// Decrement the reference counter of the sort.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_dec_ref(self.ctx.r, Z3_sort_to_ast(self.ctx.r, self.r))
+        }
+    }
+}
+
+impl <'ctx> Drop for RealSortZ3<'ctx> {
+    /// Decrement the reference counter of the sort.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_dec_ref(self.ctx.r, Z3_sort_to_ast(self.ctx.r, self.r))
+        }
+    }
+}
+
+impl <'ctx> Drop for StringSortZ3<'ctx> {
+    /// Decrement the reference counter of the sort.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_dec_ref(self.ctx.r, Z3_sort_to_ast(self.ctx.r, self.r))
+        }
+    }
+}
+
+impl <'ctx> Drop for BitVecSortZ3<'ctx> {
+    /// Decrement the reference counter of the sort.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_dec_ref(self.ctx.r, Z3_sort_to_ast(self.ctx.r, self.r))
+        }
+    }
+}
+
+impl <'ctx> Drop for ArraySortZ3<'ctx> {
+    /// Decrement the reference counter of the sort.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_dec_ref(self.ctx.r, Z3_sort_to_ast(self.ctx.r, self.r))
+        }
+    }
+}
+
+impl <'ctx, 'a> Drop for EnumSortZ3<'ctx, 'a> {
+    /// Decrement the reference counter of the sort.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_dec_ref(self.ctx.r, Z3_sort_to_ast(self.ctx.r, self.r))
+        }
+    }
+}
+
+impl <'ctx> AsSortZ3 for BoolSortZ3<'ctx> {
+    fn as_sort(&self) -> Z3_sort {
+        self.r
+    }
+}
+
+impl <'ctx> AsSortZ3 for IntSortZ3<'ctx> {
+    fn as_sort(&self) -> Z3_sort {
+        self.r
+    }
+}
+
+impl <'ctx> AsSortZ3 for RealSortZ3<'ctx> {
+    fn as_sort(&self) -> Z3_sort {
+        self.r
+    }
+}
+
+impl <'ctx> AsSortZ3 for StringSortZ3<'ctx> {
+    fn as_sort(&self) -> Z3_sort {
+        self.r
+    }
+}
+
+impl <'ctx> AsSortZ3 for BitVecSortZ3<'ctx> {
+    fn as_sort(&self) -> Z3_sort {
+        self.r
+    }
+}
+
+impl <'ctx> AsSortZ3 for ArraySortZ3<'ctx> {
+    fn as_sort(&self) -> Z3_sort {
+        self.r
+    }
+}
+
+impl <'ctx, 'a> AsSortZ3 for EnumSortZ3<'ctx, 'a> {
+    fn as_sort(&self) -> Z3_sort {
+        self.r
     }
 }
 
@@ -196,6 +369,22 @@ macro_rules! string_sort_z3 {
     }
 }
 
+/// define a bit-vector sort 
+#[macro_export]
+macro_rules! bv_sort_z3 {
+    ($ctx:expr, $a:expr) => {
+        BitVecSortZ3::new($ctx, $a)
+    }
+}
+
+/// define an array sort 
+#[macro_export]
+macro_rules! array_sort_z3 {
+    ($ctx:expr, $a:expr, $b:expr) => {
+        ArraySortZ3::new($ctx, $a, $b)
+    }
+}
+
 /// get sort 
 #[macro_export]
 macro_rules! get_sort_z3 {
@@ -225,7 +414,7 @@ fn test_bool_sort(){
     let conf = ConfigZ3::new();
     let ctx = ContextZ3::new(&conf);
     let sort = BoolSortZ3::new(&ctx);
-    assert_eq!("Bool", sort_to_string_z3!(&ctx, sort.r));
+    assert_eq!("Bool", sort_to_string_z3!(&ctx, &sort));
 }
 
 #[test]
@@ -233,7 +422,7 @@ fn test_int_sort(){
     let conf = ConfigZ3::new();
     let ctx = ContextZ3::new(&conf);
     let sort = IntSortZ3::new(&ctx);
-    assert_eq!("Int", sort_to_string_z3!(&ctx, sort.r));
+    assert_eq!("Int", sort_to_string_z3!(&ctx, &sort));
 }
 
 #[test]
@@ -241,7 +430,7 @@ fn test_real_sort(){
     let conf = ConfigZ3::new();
     let ctx = ContextZ3::new(&conf);
     let sort = RealSortZ3::new(&ctx);
-    assert_eq!("Real", sort_to_string_z3!(&ctx, sort.r));
+    assert_eq!("Real", sort_to_string_z3!(&ctx, &sort));
 }
 
 #[test]
@@ -249,7 +438,26 @@ fn test_string_sort(){
     let conf = ConfigZ3::new();
     let ctx = ContextZ3::new(&conf);
     let sort = StringSortZ3::new(&ctx);
-    assert_eq!("String", sort_to_string_z3!(&ctx, sort.r));
+    assert_eq!("String", sort_to_string_z3!(&ctx, &sort));
+}
+
+#[test]
+fn test_bv_sort(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let sort = BitVecSortZ3::new(&ctx, 16);
+    assert_eq!("(_ BitVec 16)", sort_to_string_z3!(&ctx, &sort));
+    assert!(BitVecSortZ3::try_new(&ctx, 0).is_err());
+}
+
+#[test]
+fn test_array_sort(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+    let sort = ArraySortZ3::new(&ctx, &intsort, &boolsort);
+    assert_eq!("(Array Int Bool)", sort_to_string_z3!(&ctx, &sort));
 }
 
 #[test]
@@ -257,7 +465,7 @@ fn test_enum_sort(){
     let conf = ConfigZ3::new();
     let ctx = ContextZ3::new(&conf);
     let sort = EnumSortZ3::new(&ctx, "fruit", vec!("apple", "banana", "orange"));
-    assert_eq!("fruit", sort_to_string_z3!(&ctx, sort.r));
+    assert_eq!("fruit", sort_to_string_z3!(&ctx, &sort));
 }
 
 
@@ -266,7 +474,7 @@ fn test_bool_sort_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let bsrt = bool_sort_z3!(&ctx);
-    assert_eq!("Bool", sort_to_string_z3!(&ctx, bsrt.r));
+    assert_eq!("Bool", sort_to_string_z3!(&ctx, &bsrt));
 }
 
 #[test]
@@ -274,7 +482,7 @@ fn test_int_sort_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let isrt = int_sort_z3!(&ctx);
-    assert_eq!("Int", sort_to_string_z3!(&ctx, isrt.r));
+    assert_eq!("Int", sort_to_string_z3!(&ctx, &isrt));
 }
 
 #[test]
@@ -282,7 +490,7 @@ fn test_real_sort_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let rsrt = real_sort_z3!(&ctx);
-    assert_eq!("Real", sort_to_string_z3!(&ctx, rsrt.r));
+    assert_eq!("Real", sort_to_string_z3!(&ctx, &rsrt));
 }
 
 #[test]
@@ -290,5 +498,5 @@ fn test_string_sort_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let ssrt = string_sort_z3!(&ctx);
-    assert_eq!("String", sort_to_string_z3!(&ctx, ssrt.r));
+    assert_eq!("String", sort_to_string_z3!(&ctx, &ssrt));
 }
\ No newline at end of file
@@ -0,0 +1,218 @@
+//! Z3 statistics for SP
+
+use std::collections::BTreeMap;
+use std::fmt;
+use z3_sys::*;
+use super::*;
+
+/// Value of a statistic, Z3 reports counters as unsigned integers and
+/// measurements like `time` or `memory` as doubles.
+#[derive(Debug, PartialEq, Clone, Copy, PartialOrd)]
+pub enum StatValueZ3 {
+    UInt(u32),
+    Double(f64)
+}
+
+/// Statistics of a solver or optimizer, keyed by the Z3 statistic name,
+/// i.e. `conflicts`, `decisions`, `memory`, `time`...
+#[derive(Debug, PartialEq, Clone, Default, PartialOrd)]
+pub struct StatisticsZ3 {
+    pub entries: BTreeMap<String, StatValueZ3>
+}
+
+pub struct SlvGetStatisticsZ3<'ctx, 'slv> {
+    pub ctx: &'ctx ContextZ3,
+    pub slv: &'slv SolverZ3<'ctx>,
+    pub r: StatisticsZ3
+}
+
+pub struct OptGetStatisticsZ3<'ctx, 'opt> {
+    pub ctx: &'ctx ContextZ3,
+    pub opt: &'opt OptimizerZ3<'ctx>,
+    pub r: StatisticsZ3
+}
+
+impl StatValueZ3 {
+    /// The value as a double, counters are converted.
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            StatValueZ3::UInt(x) => *x as f64,
+            StatValueZ3::Double(x) => *x
+        }
+    }
+}
+
+impl fmt::Display for StatValueZ3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StatValueZ3::UInt(x) => write!(f, "{}", x),
+            StatValueZ3::Double(x) => write!(f, "{}", x)
+        }
+    }
+}
+
+impl StatisticsZ3 {
+    /// The value of the statistic `key`, if Z3 reported it.
+    pub fn get(&self, key: &str) -> Option<&StatValueZ3> {
+        self.entries.get(key)
+    }
+
+    /// The value of the statistic `key` as a double, or 0.0 if Z3 did not report it.
+    pub fn value(&self, key: &str) -> f64 {
+        match self.entries.get(key) {
+            Some(x) => x.as_f64(),
+            None => 0.0
+        }
+    }
+}
+
+// Z3 statistics objects are reference counted, read them into a map and release them
+fn stats_to_map_z3(ctx: &ContextZ3, stats: Z3_stats) -> Result<StatisticsZ3, ErrorZ3> {
+    unsafe {
+        Z3_stats_inc_ref(ctx.r, stats);
+    }
+    let map = stats_entries_z3(ctx, stats);
+    unsafe {
+        Z3_stats_dec_ref(ctx.r, stats);
+    }
+    map
+}
+
+// the entries of the statistics, every call to z3 is checked before its result is used
+fn stats_entries_z3(ctx: &ContextZ3, stats: Z3_stats) -> Result<StatisticsZ3, ErrorZ3> {
+    let mut map = StatisticsZ3::default();
+    let size = unsafe {
+        Z3_stats_size(ctx.r, stats)
+    };
+    ctx.check_error()?;
+    for i in 0..size {
+        let key = ctx.check_string(unsafe { Z3_stats_get_key(ctx.r, stats, i) })?;
+        let value = unsafe {
+            match Z3_stats_is_uint(ctx.r, stats, i) {
+                true => StatValueZ3::UInt(Z3_stats_get_uint_value(ctx.r, stats, i)),
+                false => StatValueZ3::Double(Z3_stats_get_double_value(ctx.r, stats, i))
+            }
+        };
+        ctx.check_error()?;
+        map.entries.insert(key, value);
+    }
+    Ok(map)
+}
+
+impl <'ctx, 'slv> SlvGetStatisticsZ3<'ctx, 'slv> {
+    /// Statistics of the solver, accumulated over all checks since it was created or reset.
+    ///
+    /// NOTE: See macro! `slv_get_statistics_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> StatisticsZ3 {
+        match SlvGetStatisticsZ3::try_new(ctx, slv) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, slv: &'slv SolverZ3<'ctx>) -> Result<StatisticsZ3, ErrorZ3> {
+        let stats = unsafe {
+            Z3_solver_get_statistics(ctx.r, slv.r)
+        };
+        ctx.check_error()?;
+        let z3 = stats_to_map_z3(ctx, stats)?;
+        Ok(SlvGetStatisticsZ3 {ctx, slv, r: z3}.r)
+    }
+}
+
+impl <'ctx, 'opt> OptGetStatisticsZ3<'ctx, 'opt> {
+    /// Statistics of the optimizer, accumulated over all checks.
+    ///
+    /// NOTE: See macro! `opt_get_statistics_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>) -> StatisticsZ3 {
+        match OptGetStatisticsZ3::try_new(ctx, opt) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, opt: &'opt OptimizerZ3<'ctx>) -> Result<StatisticsZ3, ErrorZ3> {
+        let stats = unsafe {
+            Z3_optimize_get_statistics(ctx.r, opt.r)
+        };
+        ctx.check_error()?;
+        let z3 = stats_to_map_z3(ctx, stats)?;
+        Ok(OptGetStatisticsZ3 {ctx, opt, r: z3}.r)
+    }
+}
+
+/// get the statistics of a solver
+#[macro_export]
+macro_rules! slv_get_statistics_z3 {
+    ($ctx:expr, $a:expr) => {
+        SlvGetStatisticsZ3::new($ctx, $a)
+    }
+}
+
+/// get the statistics of an optimizer
+#[macro_export]
+macro_rules! opt_get_statistics_z3 {
+    ($ctx:expr, $a:expr) => {
+        OptGetStatisticsZ3::new($ctx, $a)
+    }
+}
+
+#[test]
+fn test_slv_get_statistics(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let y = IntVarZ3::new(&ctx, &intsort, "y");
+    SlvAssertZ3::new(&ctx, &slv, GTZ3::new(&ctx, &x, &y));
+    SlvAssertZ3::new(&ctx, &slv, ORZ3::new(&ctx, vec!(EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 1)),
+        EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 2)))));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let stats = SlvGetStatisticsZ3::new(&ctx, &slv);
+    match stats.get("memory") {
+        Some(StatValueZ3::Double(x)) => assert!(*x > 0.0),
+        _ => panic!("memory is reported as a double")
+    }
+    assert!(stats.value("max memory") >= stats.value("memory"));
+    assert_eq!(0.0, stats.value("no such statistic"));
+}
+
+#[test]
+fn test_opt_get_statistics(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    OptAssertZ3::new(&ctx, &opt, LTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 10)));
+    OptMaximizeZ3::new(&ctx, &opt, &x);
+    OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
+
+    let stats = OptGetStatisticsZ3::new(&ctx, &opt);
+    assert!(stats.get("memory").is_some());
+}
+
+#[test]
+fn test_stat_value_order(){
+    assert_eq!(StatValueZ3::Double(1.5), StatValueZ3::Double(1.5));
+    assert!(StatValueZ3::UInt(7) < StatValueZ3::Double(0.5));
+    assert_eq!(3.0, StatValueZ3::UInt(3).as_f64());
+    assert_eq!("3", format!("{}", StatValueZ3::UInt(3)));
+}
+
+#[test]
+fn test_get_statistics_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    slv_assert_z3!(&ctx, &slv, bool_var_z3!(&ctx, "x"));
+    slv_check_z3!(&ctx, &slv);
+
+    let stats = slv_get_statistics_z3!(&ctx, &slv);
+    assert!(!stats.entries.is_empty());
+}
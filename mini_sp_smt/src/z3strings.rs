@@ -0,0 +1,1024 @@
+//! Z3 string and sequence operations for SP
+
+use z3_sys::*;
+use super::*;
+
+pub struct STRCONCATZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub args: Vec<Z3_ast>,
+    pub r: Ast<'ctx>
+}
+
+pub struct STRLENZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct SUBSTRZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg: Z3_ast,
+    pub offset: Z3_ast,
+    pub length: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct STRCONTAINSZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct STRPREFIXOFZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct STRSUFFIXOFZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct STRINDEXOFZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg: Z3_ast,
+    pub substr: Z3_ast,
+    pub offset: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct STRTOINTZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct INTTOSTRZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct STRTOREZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct INREZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg: Z3_ast,
+    pub re: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct RERANGEZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg1: Z3_ast,
+    pub arg2: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct REPLUSZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct RESTARZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct REOPTIONZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub arg: Z3_ast,
+    pub r: Ast<'ctx>
+}
+
+pub struct REUNIONZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub args: Vec<Z3_ast>,
+    pub r: Ast<'ctx>
+}
+
+pub struct RECONCATZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub args: Vec<Z3_ast>,
+    pub r: Ast<'ctx>
+}
+
+impl <'ctx> STRCONCATZ3<'ctx> {
+    /// Concatenate the strings `args[0] ++ ... ++ args[num_args-1]`.
+    ///
+    /// The `args` is a rust vector, use `vec!()`.
+    ///
+    /// NOTE: All arguments must have string sort.
+    ///
+    /// NOTE: The number of arguments must be greater than zero.
+    /// 
+    /// NOTE: See macro! `str_concat_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Ast<'ctx> {
+        match STRCONCATZ3::try_new(ctx, args) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let args: Vec<Z3_ast> = args.iter().map(|x| x.as_ast()).collect();
+        let z3 = unsafe {
+            Z3_mk_seq_concat(ctx.r, args.len() as u32, args.as_ptr())
+        };
+        ctx.check_error()?;
+        Ok(STRCONCATZ3 {ctx, args, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> STRLENZ3<'ctx> {
+    /// Create an AST node representing the length of the string `arg`.
+    ///
+    /// NOTE: The argument must have string sort, the result has int sort.
+    /// 
+    /// NOTE: See macro! `str_len_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match STRLENZ3::try_new(ctx, arg) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
+        let z3 = unsafe {
+            Z3_mk_seq_length(ctx.r, arg)
+        };
+        ctx.check_error()?;
+        Ok(STRLENZ3 {ctx, arg, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> SUBSTRZ3<'ctx> {
+    /// Extract the substring of `arg` that starts at `offset` and has at most `length` characters.
+    ///
+    /// If `offset` is negative or past the end of `arg`, the result is the empty string.
+    ///
+    /// NOTE: `arg` must have string sort, `offset` and `length` must have int sort.
+    /// 
+    /// NOTE: See macro! `substr_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>, offset: impl AsAstZ3<'ctx>, length: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match SUBSTRZ3::try_new(ctx, arg, offset, length) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>, offset: impl AsAstZ3<'ctx>, length: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
+        let offset = offset.as_ast();
+        let length = length.as_ast();
+        let z3 = unsafe {
+            Z3_mk_seq_extract(ctx.r, arg, offset, length)
+        };
+        ctx.check_error()?;
+        Ok(SUBSTRZ3 {ctx, arg, offset, length, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> STRCONTAINSZ3<'ctx> {
+    /// Check if `arg2` is a substring of `arg1`.
+    ///
+    /// NOTE: The arguments must have string sort.
+    /// 
+    /// NOTE: See macro! `str_contains_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match STRCONTAINSZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_seq_contains(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(STRCONTAINSZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> STRPREFIXOFZ3<'ctx> {
+    /// Check if `arg1` is a prefix of `arg2`.
+    ///
+    /// NOTE: The arguments must have string sort.
+    /// 
+    /// NOTE: See macro! `str_prefix_of_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match STRPREFIXOFZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_seq_prefix(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(STRPREFIXOFZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> STRSUFFIXOFZ3<'ctx> {
+    /// Check if `arg1` is a suffix of `arg2`.
+    ///
+    /// NOTE: The arguments must have string sort.
+    /// 
+    /// NOTE: See macro! `str_suffix_of_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match STRSUFFIXOFZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_seq_suffix(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(STRSUFFIXOFZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> STRINDEXOFZ3<'ctx> {
+    /// Return the index of the first occurrence of `substr` in `arg` starting from `offset`.
+    ///
+    /// The result is `-1` if `substr` does not occur in `arg` at or after `offset`.
+    ///
+    /// NOTE: `arg` and `substr` must have string sort, `offset` must have int sort.
+    /// 
+    /// NOTE: See macro! `str_index_of_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>, substr: impl AsAstZ3<'ctx>, offset: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match STRINDEXOFZ3::try_new(ctx, arg, substr, offset) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>, substr: impl AsAstZ3<'ctx>, offset: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
+        let substr = substr.as_ast();
+        let offset = offset.as_ast();
+        let z3 = unsafe {
+            Z3_mk_seq_index(ctx.r, arg, substr, offset)
+        };
+        ctx.check_error()?;
+        Ok(STRINDEXOFZ3 {ctx, arg, substr, offset, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> STRTOINTZ3<'ctx> {
+    /// Convert the string `arg` to a non-negative integer.
+    ///
+    /// The result is `-1` if `arg` is not a string of decimal digits.
+    ///
+    /// NOTE: The argument must have string sort.
+    /// 
+    /// NOTE: See macro! `str_to_int_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match STRTOINTZ3::try_new(ctx, arg) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
+        let z3 = unsafe {
+            Z3_mk_str_to_int(ctx.r, arg)
+        };
+        ctx.check_error()?;
+        Ok(STRTOINTZ3 {ctx, arg, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> INTTOSTRZ3<'ctx> {
+    /// Convert the non-negative integer `arg` to its decimal string representation.
+    ///
+    /// The result is the empty string if `arg` is negative.
+    ///
+    /// NOTE: The argument must have int sort.
+    /// 
+    /// NOTE: See macro! `int_to_str_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match INTTOSTRZ3::try_new(ctx, arg) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
+        let z3 = unsafe {
+            Z3_mk_int_to_str(ctx.r, arg)
+        };
+        ctx.check_error()?;
+        Ok(INTTOSTRZ3 {ctx, arg, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> STRTOREZ3<'ctx> {
+    /// Create a regular expression that accepts exactly the string `arg`.
+    ///
+    /// NOTE: The argument must have string sort.
+    /// 
+    /// NOTE: See macro! `str_to_re_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match STRTOREZ3::try_new(ctx, arg) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
+        let z3 = unsafe {
+            Z3_mk_seq_to_re(ctx.r, arg)
+        };
+        ctx.check_error()?;
+        Ok(STRTOREZ3 {ctx, arg, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> INREZ3<'ctx> {
+    /// Check if the string `arg` is a member of the regular expression `re`.
+    ///
+    /// NOTE: `arg` must have string sort and `re` must be a regular expression over strings.
+    /// 
+    /// NOTE: See macro! `in_re_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>, re: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match INREZ3::try_new(ctx, arg, re) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>, re: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
+        let re = re.as_ast();
+        let z3 = unsafe {
+            Z3_mk_seq_in_re(ctx.r, arg, re)
+        };
+        ctx.check_error()?;
+        Ok(INREZ3 {ctx, arg, re, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> RERANGEZ3<'ctx> {
+    /// Create a regular expression that accepts a single character in the range `arg1` to `arg2`.
+    ///
+    /// NOTE: The arguments must be string constants of length one.
+    /// 
+    /// NOTE: See macro! `re_range_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match RERANGEZ3::try_new(ctx, arg1, arg2) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg1: impl AsAstZ3<'ctx>, arg2: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg1 = arg1.as_ast();
+        let arg2 = arg2.as_ast();
+        let z3 = unsafe {
+            Z3_mk_re_range(ctx.r, arg1, arg2)
+        };
+        ctx.check_error()?;
+        Ok(RERANGEZ3 {ctx, arg1, arg2, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> REPLUSZ3<'ctx> {
+    /// Create the regular expression `arg+`.
+    ///
+    /// NOTE: The argument must be a regular expression.
+    /// 
+    /// NOTE: See macro! `re_plus_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match REPLUSZ3::try_new(ctx, arg) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
+        let z3 = unsafe {
+            Z3_mk_re_plus(ctx.r, arg)
+        };
+        ctx.check_error()?;
+        Ok(REPLUSZ3 {ctx, arg, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> RESTARZ3<'ctx> {
+    /// Create the regular expression `arg*`.
+    ///
+    /// NOTE: The argument must be a regular expression.
+    /// 
+    /// NOTE: See macro! `re_star_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match RESTARZ3::try_new(ctx, arg) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
+        let z3 = unsafe {
+            Z3_mk_re_star(ctx.r, arg)
+        };
+        ctx.check_error()?;
+        Ok(RESTARZ3 {ctx, arg, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> REOPTIONZ3<'ctx> {
+    /// Create the regular expression `arg?`.
+    ///
+    /// NOTE: The argument must be a regular expression.
+    /// 
+    /// NOTE: See macro! `re_option_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Ast<'ctx> {
+        match REOPTIONZ3::try_new(ctx, arg) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, arg: impl AsAstZ3<'ctx>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let arg = arg.as_ast();
+        let z3 = unsafe {
+            Z3_mk_re_option(ctx.r, arg)
+        };
+        ctx.check_error()?;
+        Ok(REOPTIONZ3 {ctx, arg, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> REUNIONZ3<'ctx> {
+    /// Create the union of the regular expressions `args[0] | ... | args[num_args-1]`.
+    ///
+    /// The `args` is a rust vector, use `vec!()`.
+    ///
+    /// NOTE: All arguments must be regular expressions of the same sort.
+    ///
+    /// NOTE: The number of arguments must be greater than zero.
+    /// 
+    /// NOTE: See macro! `re_union_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Ast<'ctx> {
+        match REUNIONZ3::try_new(ctx, args) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let args: Vec<Z3_ast> = args.iter().map(|x| x.as_ast()).collect();
+        let z3 = unsafe {
+            Z3_mk_re_union(ctx.r, args.len() as u32, args.as_ptr())
+        };
+        ctx.check_error()?;
+        Ok(REUNIONZ3 {ctx, args, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> RECONCATZ3<'ctx> {
+    /// Create the concatenation of the regular expressions `args[0] ... args[num_args-1]`.
+    ///
+    /// The `args` is a rust vector, use `vec!()`.
+    ///
+    /// NOTE: All arguments must be regular expressions of the same sort.
+    ///
+    /// NOTE: The number of arguments must be greater than zero.
+    /// 
+    /// NOTE: See macro! `re_concat_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Ast<'ctx> {
+        match RECONCATZ3::try_new(ctx, args) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Result<Ast<'ctx>, ErrorZ3> {
+        let args: Vec<Z3_ast> = args.iter().map(|x| x.as_ast()).collect();
+        let z3 = unsafe {
+            Z3_mk_re_concat(ctx.r, args.len() as u32, args.as_ptr())
+        };
+        ctx.check_error()?;
+        Ok(RECONCATZ3 {ctx, args, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+/// a ++ b ++ c ++ ...
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::STRCONCATZ3::new(&ctx, vec!(a, b, c))
+/// ```
+/// Using a specific context and passing elements:
+/// ```text
+/// str_concat_z3!(&ctx, a, b, c)
+/// ```
+/// Or make a vector first and pass it:
+/// ```text
+/// let some = vec!(a, b, c);
+/// str_concat_z3!(&ctx, some)
+/// ```
+/// Requires that a, b, c... are of String sort.
+#[macro_export]
+macro_rules! str_concat_z3 {
+    ($ctx:expr, $b:expr) => {
+        STRCONCATZ3::new($ctx, $b)
+    };
+    ( $ctx:expr, $( $x:expr ),* ) => {
+        {
+            let mut temp_vec = Vec::new();
+            $(
+                temp_vec.push($crate::Ast::new($ctx, $crate::AsAstZ3::as_ast(&$x)));
+            )*
+            STRCONCATZ3::new($ctx, temp_vec)
+        }
+    };
+}
+
+/// length of a
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::STRLENZ3::new(&ctx, a)
+/// ```
+/// Using a specific context:
+/// ```text
+/// str_len_z3!(&ctx, a)
+/// ```
+/// Requires that a is of String sort.
+#[macro_export]
+macro_rules! str_len_z3 {
+    ($ctx:expr, $b:expr) => {
+        STRLENZ3::new($ctx, $b)
+    }
+}
+
+/// substring of a starting at offset with length l
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::SUBSTRZ3::new(&ctx, a, offset, l)
+/// ```
+/// Using a specific context:
+/// ```text
+/// substr_z3!(&ctx, a, offset, l)
+/// ```
+/// Requires that a is of String sort and offset and l are of Int sort.
+#[macro_export]
+macro_rules! substr_z3 {
+    ($ctx:expr, $b:expr, $c:expr, $d:expr) => {
+        SUBSTRZ3::new($ctx, $b, $c, $d)
+    }
+}
+
+/// a contains b
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::STRCONTAINSZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// str_contains_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of String sort.
+#[macro_export]
+macro_rules! str_contains_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        STRCONTAINSZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a is a prefix of b
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::STRPREFIXOFZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// str_prefix_of_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of String sort.
+#[macro_export]
+macro_rules! str_prefix_of_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        STRPREFIXOFZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a is a suffix of b
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::STRSUFFIXOFZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// str_suffix_of_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are of String sort.
+#[macro_export]
+macro_rules! str_suffix_of_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        STRSUFFIXOFZ3::new($ctx, $b, $c)
+    }
+}
+
+/// index of b in a starting from offset
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::STRINDEXOFZ3::new(&ctx, a, b, offset)
+/// ```
+/// Using a specific context:
+/// ```text
+/// str_index_of_z3!(&ctx, a, b, offset)
+/// ```
+/// Requires that a and b are of String sort and offset is of Int sort.
+#[macro_export]
+macro_rules! str_index_of_z3 {
+    ($ctx:expr, $b:expr, $c:expr, $d:expr) => {
+        STRINDEXOFZ3::new($ctx, $b, $c, $d)
+    }
+}
+
+/// a as an integer
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::STRTOINTZ3::new(&ctx, a)
+/// ```
+/// Using a specific context:
+/// ```text
+/// str_to_int_z3!(&ctx, a)
+/// ```
+/// Requires that a is of String sort.
+#[macro_export]
+macro_rules! str_to_int_z3 {
+    ($ctx:expr, $b:expr) => {
+        STRTOINTZ3::new($ctx, $b)
+    }
+}
+
+/// a as a string
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::INTTOSTRZ3::new(&ctx, a)
+/// ```
+/// Using a specific context:
+/// ```text
+/// int_to_str_z3!(&ctx, a)
+/// ```
+/// Requires that a is of Int sort.
+#[macro_export]
+macro_rules! int_to_str_z3 {
+    ($ctx:expr, $b:expr) => {
+        INTTOSTRZ3::new($ctx, $b)
+    }
+}
+
+/// regular expression accepting exactly a
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::STRTOREZ3::new(&ctx, a)
+/// ```
+/// Using a specific context:
+/// ```text
+/// str_to_re_z3!(&ctx, a)
+/// ```
+/// Requires that a is of String sort.
+#[macro_export]
+macro_rules! str_to_re_z3 {
+    ($ctx:expr, $b:expr) => {
+        STRTOREZ3::new($ctx, $b)
+    }
+}
+
+/// a is accepted by re
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::INREZ3::new(&ctx, a, re)
+/// ```
+/// Using a specific context:
+/// ```text
+/// in_re_z3!(&ctx, a, re)
+/// ```
+/// Requires that a is of String sort and re is a regular expression.
+#[macro_export]
+macro_rules! in_re_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        INREZ3::new($ctx, $b, $c)
+    }
+}
+
+/// character range from a to b
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::RERANGEZ3::new(&ctx, a, b)
+/// ```
+/// Using a specific context:
+/// ```text
+/// re_range_z3!(&ctx, a, b)
+/// ```
+/// Requires that a and b are String constants of length one.
+#[macro_export]
+macro_rules! re_range_z3 {
+    ($ctx:expr, $b:expr, $c:expr) => {
+        RERANGEZ3::new($ctx, $b, $c)
+    }
+}
+
+/// a+
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::REPLUSZ3::new(&ctx, a)
+/// ```
+/// Using a specific context:
+/// ```text
+/// re_plus_z3!(&ctx, a)
+/// ```
+/// Requires that a is a regular expression.
+#[macro_export]
+macro_rules! re_plus_z3 {
+    ($ctx:expr, $b:expr) => {
+        REPLUSZ3::new($ctx, $b)
+    }
+}
+
+/// a*
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::RESTARZ3::new(&ctx, a)
+/// ```
+/// Using a specific context:
+/// ```text
+/// re_star_z3!(&ctx, a)
+/// ```
+/// Requires that a is a regular expression.
+#[macro_export]
+macro_rules! re_star_z3 {
+    ($ctx:expr, $b:expr) => {
+        RESTARZ3::new($ctx, $b)
+    }
+}
+
+/// a?
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::REOPTIONZ3::new(&ctx, a)
+/// ```
+/// Using a specific context:
+/// ```text
+/// re_option_z3!(&ctx, a)
+/// ```
+/// Requires that a is a regular expression.
+#[macro_export]
+macro_rules! re_option_z3 {
+    ($ctx:expr, $b:expr) => {
+        REOPTIONZ3::new($ctx, $b)
+    }
+}
+
+/// a | b | c | ...
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::REUNIONZ3::new(&ctx, vec!(a, b, c))
+/// ```
+/// Using a specific context and passing elements:
+/// ```text
+/// re_union_z3!(&ctx, a, b, c)
+/// ```
+/// Or make a vector first and pass it:
+/// ```text
+/// let some = vec!(a, b, c);
+/// re_union_z3!(&ctx, some)
+/// ```
+/// Requires that a, b, c... are regular expressions.
+#[macro_export]
+macro_rules! re_union_z3 {
+    ($ctx:expr, $b:expr) => {
+        REUNIONZ3::new($ctx, $b)
+    };
+    ( $ctx:expr, $( $x:expr ),* ) => {
+        {
+            let mut temp_vec = Vec::new();
+            $(
+                temp_vec.push($crate::Ast::new($ctx, $crate::AsAstZ3::as_ast(&$x)));
+            )*
+            REUNIONZ3::new($ctx, temp_vec)
+        }
+    };
+}
+
+/// a b c ...
+/// 
+/// Macro rule for:
+/// ```text
+/// z3strings::RECONCATZ3::new(&ctx, vec!(a, b, c))
+/// ```
+/// Using a specific context and passing elements:
+/// ```text
+/// re_concat_z3!(&ctx, a, b, c)
+/// ```
+/// Or make a vector first and pass it:
+/// ```text
+/// let some = vec!(a, b, c);
+/// re_concat_z3!(&ctx, some)
+/// ```
+/// Requires that a, b, c... are regular expressions.
+#[macro_export]
+macro_rules! re_concat_z3 {
+    ($ctx:expr, $b:expr) => {
+        RECONCATZ3::new($ctx, $b)
+    };
+    ( $ctx:expr, $( $x:expr ),* ) => {
+        {
+            let mut temp_vec = Vec::new();
+            $(
+                temp_vec.push($crate::Ast::new($ctx, $crate::AsAstZ3::as_ast(&$x)));
+            )*
+            RECONCATZ3::new($ctx, temp_vec)
+        }
+    };
+}
+
+#[test]
+fn test_new_str_ops(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let stringsort = StringSortZ3::new(&ctx);
+
+    let x = StringVarZ3::new(&ctx, &stringsort, "x");
+    let y = StringVarZ3::new(&ctx, &stringsort, "y");
+    let zero = IntZ3::new(&ctx, &IntSortZ3::new(&ctx), 0);
+    let three = IntZ3::new(&ctx, &IntSortZ3::new(&ctx), 3);
+
+    assert_eq!("(str.++ x y)", ast_to_string_z3!(&ctx, STRCONCATZ3::new(&ctx, vec!(&x, &y))));
+    assert_eq!("(str.len x)", ast_to_string_z3!(&ctx, STRLENZ3::new(&ctx, &x)));
+    assert_eq!("(str.substr x 0 3)", ast_to_string_z3!(&ctx, SUBSTRZ3::new(&ctx, &x, &zero, &three)));
+    assert_eq!("(str.contains x y)", ast_to_string_z3!(&ctx, STRCONTAINSZ3::new(&ctx, &x, &y)));
+    assert_eq!("(str.prefixof y x)", ast_to_string_z3!(&ctx, STRPREFIXOFZ3::new(&ctx, &y, &x)));
+    assert_eq!("(str.suffixof y x)", ast_to_string_z3!(&ctx, STRSUFFIXOFZ3::new(&ctx, &y, &x)));
+    assert_eq!("(str.indexof x y 0)", ast_to_string_z3!(&ctx, STRINDEXOFZ3::new(&ctx, &x, &y, &zero)));
+    assert_eq!("Int", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, STRLENZ3::new(&ctx, &x))));
+}
+
+#[test]
+fn test_new_str_conversions(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let stringsort = StringSortZ3::new(&ctx);
+
+    let s = StringVarZ3::new(&ctx, &stringsort, "s");
+    let n = IntVarZ3::new(&ctx, &intsort, "n");
+
+    assert_eq!("(str.to_int s)", ast_to_string_z3!(&ctx, STRTOINTZ3::new(&ctx, &s)));
+    assert_eq!("(str.from_int n)", ast_to_string_z3!(&ctx, INTTOSTRZ3::new(&ctx, &n)));
+
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &s, INTTOSTRZ3::new(&ctx, IntZ3::new(&ctx, &intsort, 42))));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &n, STRTOINTZ3::new(&ctx, StringZ3::new(&ctx, "017"))));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!(ModelValueZ3::String("42".to_string()), ModelEvalZ3::new(&ctx, &model, &s, true));
+    assert_eq!(ModelValueZ3::Int(17), ModelEvalZ3::new(&ctx, &model, &n, true));
+}
+
+#[test]
+fn test_new_re_ops(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let stringsort = StringSortZ3::new(&ctx);
+
+    let x = StringVarZ3::new(&ctx, &stringsort, "x");
+    let ab = STRTOREZ3::new(&ctx, StringZ3::new(&ctx, "ab"));
+    let digit = RERANGEZ3::new(&ctx, StringZ3::new(&ctx, "0"), StringZ3::new(&ctx, "9"));
+
+    assert_eq!("(str.to_re \"ab\")", ast_to_string_z3!(&ctx, &ab));
+    assert_eq!("(re.range \"0\" \"9\")", ast_to_string_z3!(&ctx, &digit));
+    assert_eq!("(re.+ (str.to_re \"ab\"))", ast_to_string_z3!(&ctx, REPLUSZ3::new(&ctx, &ab)));
+    assert_eq!("(re.* (str.to_re \"ab\"))", ast_to_string_z3!(&ctx, RESTARZ3::new(&ctx, &ab)));
+    assert_eq!("(re.opt (str.to_re \"ab\"))", ast_to_string_z3!(&ctx, REOPTIONZ3::new(&ctx, &ab)));
+    assert_eq!("(re.union (str.to_re \"ab\") (re.range \"0\" \"9\"))",
+        ast_to_string_z3!(&ctx, REUNIONZ3::new(&ctx, vec!(&ab, &digit))));
+    assert_eq!("(re.++ (str.to_re \"ab\") (re.range \"0\" \"9\"))",
+        ast_to_string_z3!(&ctx, RECONCATZ3::new(&ctx, vec!(&ab, &digit))));
+    assert_eq!("(str.in_re x (str.to_re \"ab\"))", ast_to_string_z3!(&ctx, INREZ3::new(&ctx, &x, &ab)));
+    assert_eq!("(RegEx String)", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, ab)));
+}
+
+#[test]
+fn test_str_batch_id_guard(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let stringsort = StringSortZ3::new(&ctx);
+
+    let id = StringVarZ3::new(&ctx, &stringsort, "id");
+    let digits = REPLUSZ3::new(&ctx, RERANGEZ3::new(&ctx, StringZ3::new(&ctx, "0"), StringZ3::new(&ctx, "9")));
+    let format = RECONCATZ3::new(&ctx, vec!(STRTOREZ3::new(&ctx, StringZ3::new(&ctx, "PRD-")), digits));
+
+    SlvAssertZ3::new(&ctx, &slv, INREZ3::new(&ctx, &id, &format));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, STRLENZ3::new(&ctx, &id), IntZ3::new(&ctx, &intsort, 8)));
+    SlvAssertZ3::new(&ctx, &slv, STRSUFFIXOFZ3::new(&ctx, StringZ3::new(&ctx, "7"), &id));
+    SlvAssertZ3::new(&ctx, &slv, GTZ3::new(&ctx,
+        STRTOINTZ3::new(&ctx, SUBSTRZ3::new(&ctx, &id, IntZ3::new(&ctx, &intsort, 4), IntZ3::new(&ctx, &intsort, 4))),
+        IntZ3::new(&ctx, &intsort, 9000)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let value = ModelEvalZ3::new(&ctx, &model, &id, true);
+    let value = value.as_str().unwrap();
+    assert!(value.starts_with("PRD-"));
+    assert!(value.ends_with('7'));
+    assert!(value[4..].parse::<u32>().unwrap() > 9000);
+
+    SlvAssertZ3::new(&ctx, &slv, STRCONTAINSZ3::new(&ctx, &id, StringZ3::new(&ctx, "X")));
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_str_sort_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+
+    let s = StringVarZ3::new(&ctx, &StringSortZ3::new(&ctx), "s");
+    let n = IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), "n");
+
+    assert!(STRLENZ3::try_new(&ctx, &n).is_err());
+    assert!(STRCONTAINSZ3::try_new(&ctx, &s, &n).is_err());
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_str_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+
+    let x = string_var_z3!(&ctx, "x");
+    let y = string_var_z3!(&ctx, "y");
+    let n = int_var_z3!(&ctx, "n");
+
+    assert_eq!("(str.++ x \"-\" y)", ast_to_string_z3!(&ctx, str_concat_z3!(&ctx, &x, string_z3!(&ctx, "-"), &y)));
+    assert_eq!("(str.len x)", ast_to_string_z3!(&ctx, str_len_z3!(&ctx, &x)));
+    assert_eq!("(str.substr x n 2)", ast_to_string_z3!(&ctx, substr_z3!(&ctx, &x, &n, int_z3!(&ctx, 2))));
+    assert_eq!("(str.contains x y)", ast_to_string_z3!(&ctx, str_contains_z3!(&ctx, &x, &y)));
+    assert_eq!("(str.prefixof y x)", ast_to_string_z3!(&ctx, str_prefix_of_z3!(&ctx, &y, &x)));
+    assert_eq!("(str.suffixof y x)", ast_to_string_z3!(&ctx, str_suffix_of_z3!(&ctx, &y, &x)));
+    assert_eq!("(str.indexof x y n)", ast_to_string_z3!(&ctx, str_index_of_z3!(&ctx, &x, &y, &n)));
+    assert_eq!("(str.to_int x)", ast_to_string_z3!(&ctx, str_to_int_z3!(&ctx, &x)));
+    assert_eq!("(str.from_int n)", ast_to_string_z3!(&ctx, int_to_str_z3!(&ctx, &n)));
+
+    let lower = re_star_z3!(&ctx, re_range_z3!(&ctx, string_z3!(&ctx, "a"), string_z3!(&ctx, "z")));
+    let zeros = re_option_z3!(&ctx, re_plus_z3!(&ctx, str_to_re_z3!(&ctx, string_z3!(&ctx, "0"))));
+    assert_eq!("(re.* (re.range \"a\" \"z\"))", ast_to_string_z3!(&ctx, &lower));
+    assert_eq!("(re.opt (re.+ (str.to_re \"0\")))", ast_to_string_z3!(&ctx, &zeros));
+    assert_eq!("(re.++ (str.to_re y) (re.* (re.range \"a\" \"z\")))",
+        ast_to_string_z3!(&ctx, re_concat_z3!(&ctx, str_to_re_z3!(&ctx, &y), &lower)));
+    assert_eq!("(re.union (re.* (re.range \"a\" \"z\")) (re.opt (re.+ (str.to_re \"0\"))))",
+        ast_to_string_z3!(&ctx, re_union_z3!(&ctx, &lower, &zeros)));
+    assert_eq!("(str.in_re x (re.* (re.range \"a\" \"z\")))", ast_to_string_z3!(&ctx, in_re_z3!(&ctx, x, lower)));
+}
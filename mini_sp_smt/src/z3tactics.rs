@@ -0,0 +1,614 @@
+//! Z3 tactics and goals for SP
+
+use std::cell::{Cell, RefCell};
+use std::ffi::{CStr, CString};
+use std::fmt;
+use z3_sys::*;
+use super::*;
+
+pub struct GoalZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub r: Z3_goal
+}
+
+pub struct TacticZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub r: Z3_tactic
+}
+
+pub struct GoalAssertZ3<'ctx, 'g> {
+    pub ctx: &'ctx ContextZ3,
+    pub goal: &'g GoalZ3<'ctx>,
+    pub cst: Z3_ast,
+    pub r: ()
+}
+
+pub struct GoalGetFormulasZ3<'ctx, 'g> {
+    pub ctx: &'ctx ContextZ3,
+    pub goal: &'g GoalZ3<'ctx>,
+    pub r: Vec<Ast<'ctx>>
+}
+
+pub struct TacticAndThenZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub first: Z3_tactic,
+    pub second: Z3_tactic,
+    pub r: TacticZ3<'ctx>
+}
+
+pub struct TacticOrElseZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub first: Z3_tactic,
+    pub second: Z3_tactic,
+    pub r: TacticZ3<'ctx>
+}
+
+pub struct TacticRepeatZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub tactic: Z3_tactic,
+    pub max: u32,
+    pub r: TacticZ3<'ctx>
+}
+
+pub struct TacticParOrZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub tactics: Vec<Z3_tactic>,
+    pub r: TacticZ3<'ctx>
+}
+
+pub struct TacticApplyZ3<'ctx, 't, 'g> {
+    pub ctx: &'ctx ContextZ3,
+    pub tactic: &'t TacticZ3<'ctx>,
+    pub goal: &'g GoalZ3<'ctx>,
+    pub r: Vec<GoalZ3<'ctx>>
+}
+
+pub struct SolverFromTacticZ3<'ctx, 't> {
+    pub ctx: &'ctx ContextZ3,
+    pub tactic: &'t TacticZ3<'ctx>,
+    pub r: SolverZ3<'ctx>
+}
+
+impl <'ctx> GoalZ3<'ctx> {
+    /// Create a goal, a collection of formulas that tactics transform into subgoals.
+    ///
+    /// If `models` is true, then model generation is enabled for the new goal.
+    /// If `unsat_cores` is true, then unsat core generation is enabled for the new goal.
+    /// If `proofs` is true, then proof generation is enabled for the new goal.
+    ///
+    /// NOTE: See macro! `goal_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, models: bool, unsat_cores: bool, proofs: bool) -> GoalZ3<'ctx> {
+        match GoalZ3::try_new(ctx, models, unsat_cores, proofs) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, models: bool, unsat_cores: bool, proofs: bool) -> Result<GoalZ3<'ctx>, ErrorZ3> {
+        let z3 = unsafe {
+            Z3_mk_goal(ctx.r, models, unsat_cores, proofs)
+        };
+        ctx.check_error()?;
+        Ok(GoalZ3 {ctx, r: unsafe { GoalZ3::own(ctx, z3) }})
+    }
+
+    // goals returned by Z3 are only valid while their reference counter is positive
+    unsafe fn own(ctx: &'ctx ContextZ3, goal: Z3_goal) -> Z3_goal {
+        Z3_goal_inc_ref(ctx.r, goal);
+        goal
+    }
+}
+
+impl <'ctx> TacticZ3<'ctx> {
+    /// Create a tactic by name, i.e. `simplify`, `solve-eqs`, `bit-blast`, `tseitin-cnf`, `sat` or `smt`.
+    ///
+    /// Tactics are combined with `TacticAndThenZ3`, `TacticOrElseZ3`, `TacticRepeatZ3`
+    /// and `TacticParOrZ3`, applied to goals with `TacticApplyZ3` or turned into a
+    /// solver with `SolverFromTacticZ3`.
+    ///
+    /// NOTE: See macro! `tactic_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, name: &str) -> TacticZ3<'ctx> {
+        match TacticZ3::try_new(ctx, name) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, name: &str) -> Result<TacticZ3<'ctx>, ErrorZ3> {
+        let str_name = CString::new(name).unwrap();
+        let z3 = unsafe {
+            Z3_mk_tactic(ctx.r, str_name.as_ptr())
+        };
+        ctx.check_error()?;
+        Ok(TacticZ3::own(ctx, z3))
+    }
+
+    fn own(ctx: &'ctx ContextZ3, tactic: Z3_tactic) -> TacticZ3<'ctx> {
+        unsafe {
+            Z3_tactic_inc_ref(ctx.r, tactic);
+        }
+        TacticZ3 {ctx, r: tactic}
+    }
+}
+
+impl <'ctx, 'g> GoalAssertZ3<'ctx, 'g> {
+    /// Add a new formula to the goal.
+    ///
+    /// NOTE: See macro! `goal_assert_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, goal: &'g GoalZ3<'ctx>, cst: impl AsAstZ3<'ctx>) -> () {
+        match GoalAssertZ3::try_new(ctx, goal, cst) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, goal: &'g GoalZ3<'ctx>, cst: impl AsAstZ3<'ctx>) -> Result<(), ErrorZ3> {
+        unsafe {
+            Z3_goal_assert(ctx.r, goal.r, cst.as_ast())
+        };
+        ctx.check_error()
+    }
+}
+
+impl <'ctx, 'g> GoalGetFormulasZ3<'ctx, 'g> {
+    /// The formulas in the goal. A goal that is decided sat has no formulas,
+    /// a goal that is decided unsat contains only `false`.
+    ///
+    /// NOTE: See macro! `goal_get_formulas_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, goal: &'g GoalZ3<'ctx>) -> Vec<Ast<'ctx>> {
+        match GoalGetFormulasZ3::try_new(ctx, goal) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, goal: &'g GoalZ3<'ctx>) -> Result<Vec<Ast<'ctx>>, ErrorZ3> {
+        let size = unsafe {
+            Z3_goal_size(ctx.r, goal.r)
+        };
+        ctx.check_error()?;
+        let mut z3 = vec!();
+        for i in 0..size {
+            let formula = unsafe {
+                Z3_goal_formula(ctx.r, goal.r, i)
+            };
+            ctx.check_error()?;
+            z3.push(Ast::new(ctx, formula));
+        }
+        Ok(GoalGetFormulasZ3 {ctx, goal, r: z3}.r)
+    }
+}
+
+impl <'ctx> TacticAndThenZ3<'ctx> {
+    /// A tactic that applies `first` to a goal and then `second` to every subgoal produced by `first`.
+    ///
+    /// NOTE: See macro! `and_then_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, first: &TacticZ3<'ctx>, second: &TacticZ3<'ctx>) -> TacticZ3<'ctx> {
+        match TacticAndThenZ3::try_new(ctx, first, second) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, first: &TacticZ3<'ctx>, second: &TacticZ3<'ctx>) -> Result<TacticZ3<'ctx>, ErrorZ3> {
+        let (first, second) = (first.r, second.r);
+        let z3 = unsafe {
+            Z3_tactic_and_then(ctx.r, first, second)
+        };
+        ctx.check_error()?;
+        Ok(TacticAndThenZ3 {ctx, first, second, r: TacticZ3::own(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> TacticOrElseZ3<'ctx> {
+    /// A tactic that first applies `first` to a goal, if it fails then returns the result of `second` applied to the goal.
+    ///
+    /// NOTE: See macro! `or_else_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, first: &TacticZ3<'ctx>, second: &TacticZ3<'ctx>) -> TacticZ3<'ctx> {
+        match TacticOrElseZ3::try_new(ctx, first, second) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, first: &TacticZ3<'ctx>, second: &TacticZ3<'ctx>) -> Result<TacticZ3<'ctx>, ErrorZ3> {
+        let (first, second) = (first.r, second.r);
+        let z3 = unsafe {
+            Z3_tactic_or_else(ctx.r, first, second)
+        };
+        ctx.check_error()?;
+        Ok(TacticOrElseZ3 {ctx, first, second, r: TacticZ3::own(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> TacticRepeatZ3<'ctx> {
+    /// A tactic that keeps applying `tactic` until no subgoal is modified by it, at most `max` times.
+    ///
+    /// NOTE: See macro! `repeat_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, tactic: &TacticZ3<'ctx>, max: u32) -> TacticZ3<'ctx> {
+        match TacticRepeatZ3::try_new(ctx, tactic, max) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, tactic: &TacticZ3<'ctx>, max: u32) -> Result<TacticZ3<'ctx>, ErrorZ3> {
+        let tactic = tactic.r;
+        let z3 = unsafe {
+            Z3_tactic_repeat(ctx.r, tactic, max)
+        };
+        ctx.check_error()?;
+        Ok(TacticRepeatZ3 {ctx, tactic, max, r: TacticZ3::own(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx> TacticParOrZ3<'ctx> {
+    /// A tactic that applies the `tactics` in parallel to a goal, and returns the result of the first one to succeed.
+    ///
+    /// NOTE: See macro! `par_or_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, tactics: Vec<&TacticZ3<'ctx>>) -> TacticZ3<'ctx> {
+        match TacticParOrZ3::try_new(ctx, tactics) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, tactics: Vec<&TacticZ3<'ctx>>) -> Result<TacticZ3<'ctx>, ErrorZ3> {
+        let tactics: Vec<Z3_tactic> = tactics.iter().map(|x| x.r).collect();
+        let z3 = unsafe {
+            Z3_tactic_par_or(ctx.r, tactics.len() as u32, tactics.as_ptr())
+        };
+        ctx.check_error()?;
+        Ok(TacticParOrZ3 {ctx, tactics, r: TacticZ3::own(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx, 't, 'g> TacticApplyZ3<'ctx, 't, 'g> {
+    /// Apply the tactic to the goal and return the resulting subgoals.
+    ///
+    /// NOTE: See macro! `tactic_apply_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, tactic: &'t TacticZ3<'ctx>, goal: &'g GoalZ3<'ctx>) -> Vec<GoalZ3<'ctx>> {
+        match TacticApplyZ3::try_new(ctx, tactic, goal) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, tactic: &'t TacticZ3<'ctx>, goal: &'g GoalZ3<'ctx>) -> Result<Vec<GoalZ3<'ctx>>, ErrorZ3> {
+        let applied = unsafe {
+            Z3_tactic_apply(ctx.r, tactic.r, goal.r)
+        };
+        ctx.check_error()?;
+        let z3 = unsafe {
+            Z3_apply_result_inc_ref(ctx.r, applied);
+            let subgoals = (0..Z3_apply_result_get_num_subgoals(ctx.r, applied))
+                .map(|i| GoalZ3 {ctx, r: GoalZ3::own(ctx, Z3_apply_result_get_subgoal(ctx.r, applied, i))})
+                .collect();
+            Z3_apply_result_dec_ref(ctx.r, applied);
+            subgoals
+        };
+        Ok(TacticApplyZ3 {ctx, tactic, goal, r: z3}.r)
+    }
+}
+
+impl <'ctx, 't> SolverFromTacticZ3<'ctx, 't> {
+    /// Create a solver that is implemented using the given tactic.
+    ///
+    /// The solver supports the commands `SlvPushZ3` and `SlvPopZ3`, but it
+    /// will always solve each `SlvCheckZ3` from scratch.
+    ///
+    /// NOTE: See macro! `slv_from_tactic_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, tactic: &'t TacticZ3<'ctx>) -> SolverZ3<'ctx> {
+        match SolverFromTacticZ3::try_new(ctx, tactic) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, tactic: &'t TacticZ3<'ctx>) -> Result<SolverZ3<'ctx>, ErrorZ3> {
+        let z3 = unsafe {
+            Z3_mk_solver_from_tactic(ctx.r, tactic.r)
+        };
+        ctx.check_error()?;
+        unsafe {
+            Z3_solver_inc_ref(ctx.r, z3);
+        }
+        Ok(SolverFromTacticZ3 {ctx, tactic, r: SolverZ3 {ctx, r: z3, scopes: RefCell::new(vec!()), generation: Cell::new(0)}}.r)
+    }
+}
+
+impl <'ctx> Drop for GoalZ3<'ctx> {
+    /// Decrement the reference counter of the goal.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_goal_dec_ref(self.ctx.r, self.r)
+        }
+    }
+}
+
+impl <'ctx> Drop for TacticZ3<'ctx> {
+    /// Decrement the reference counter of the tactic.
+    fn drop(&mut self) {
+        unsafe {
+            Z3_tactic_dec_ref(self.ctx.r, self.r)
+        }
+    }
+}
+
+impl <'ctx> fmt::Display for GoalZ3<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = unsafe {
+            CStr::from_ptr(Z3_goal_to_string(self.ctx.r, self.r)).to_str().unwrap().to_owned()
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// create a new goal with model generation enabled
+///
+/// Macro rule for:
+/// ```text
+/// z3tactics::GoalZ3::new(&ctx, true, false, false)
+/// ```
+/// Using a specific context:
+/// ```text
+/// goal_z3!(&ctx)
+/// ```
+#[macro_export]
+macro_rules! goal_z3 {
+    ($ctx:expr) => {
+        GoalZ3::new($ctx, true, false, false)
+    }
+}
+
+/// add formulas to a goal
+///
+/// Macro rule for:
+/// ```text
+/// z3tactics::GoalAssertZ3::new(&ctx, &goal, a)
+/// ```
+/// Using a specific context and passing elements:
+/// ```text
+/// goal_assert_z3!(&ctx, &goal, a, b, c)
+/// ```
+#[macro_export]
+macro_rules! goal_assert_z3 {
+    ( $ctx:expr, $g:expr, $( $x:expr ),* ) => {
+        {
+            $(
+                GoalAssertZ3::new($ctx, $g, $x);
+            )*
+        }
+    };
+}
+
+/// the formulas in a goal
+#[macro_export]
+macro_rules! goal_get_formulas_z3 {
+    ($ctx:expr, $g:expr) => {
+        GoalGetFormulasZ3::new($ctx, $g)
+    }
+}
+
+/// create a tactic by name
+///
+/// Macro rule for:
+/// ```text
+/// z3tactics::TacticZ3::new(&ctx, "simplify")
+/// ```
+/// Using a specific context:
+/// ```text
+/// tactic_z3!(&ctx, "simplify")
+/// ```
+#[macro_export]
+macro_rules! tactic_z3 {
+    ($ctx:expr, $a:expr) => {
+        TacticZ3::new($ctx, $a)
+    }
+}
+
+/// t1 and then t2 and then t3...
+///
+/// Macro rule for:
+/// ```text
+/// z3tactics::TacticAndThenZ3::new(&ctx, &TacticAndThenZ3::new(&ctx, &t1, &t2), &t3)
+/// ```
+/// Using a specific context and passing elements:
+/// ```text
+/// and_then_z3!(&ctx, t1, t2, t3)
+/// ```
+#[macro_export]
+macro_rules! and_then_z3 {
+    ($ctx:expr, $a:expr) => {
+        $a
+    };
+    ($ctx:expr, $a:expr, $( $x:expr ),+ ) => {
+        {
+            let mut temp = $a;
+            $(
+                temp = TacticAndThenZ3::new($ctx, &temp, &$x);
+            )+
+            temp
+        }
+    };
+}
+
+/// t1 or else t2 or else t3...
+///
+/// Macro rule for:
+/// ```text
+/// z3tactics::TacticOrElseZ3::new(&ctx, &TacticOrElseZ3::new(&ctx, &t1, &t2), &t3)
+/// ```
+/// Using a specific context and passing elements:
+/// ```text
+/// or_else_z3!(&ctx, t1, t2, t3)
+/// ```
+#[macro_export]
+macro_rules! or_else_z3 {
+    ($ctx:expr, $a:expr) => {
+        $a
+    };
+    ($ctx:expr, $a:expr, $( $x:expr ),+ ) => {
+        {
+            let mut temp = $a;
+            $(
+                temp = TacticOrElseZ3::new($ctx, &temp, &$x);
+            )+
+            temp
+        }
+    };
+}
+
+/// repeat t at most max times
+#[macro_export]
+macro_rules! repeat_z3 {
+    ($ctx:expr, $t:expr, $max:expr) => {
+        TacticRepeatZ3::new($ctx, &$t, $max)
+    }
+}
+
+/// t1 in parallel with t2, t3...
+///
+/// Macro rule for:
+/// ```text
+/// z3tactics::TacticParOrZ3::new(&ctx, vec!(&t1, &t2, &t3))
+/// ```
+/// Using a specific context and passing elements:
+/// ```text
+/// par_or_z3!(&ctx, t1, t2, t3)
+/// ```
+#[macro_export]
+macro_rules! par_or_z3 {
+    ( $ctx:expr, $( $x:expr ),* ) => {
+        TacticParOrZ3::new($ctx, vec!($( &$x ),*))
+    };
+}
+
+/// apply a tactic to a goal
+#[macro_export]
+macro_rules! tactic_apply_z3 {
+    ($ctx:expr, $t:expr, $g:expr) => {
+        TacticApplyZ3::new($ctx, $t, $g)
+    }
+}
+
+/// create a solver from a tactic
+#[macro_export]
+macro_rules! slv_from_tactic_z3 {
+    ($ctx:expr, $t:expr) => {
+        SolverFromTacticZ3::new($ctx, $t)
+    }
+}
+
+#[test]
+fn test_new_tactic_and_goal(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let y = IntVarZ3::new(&ctx, &intsort, "y");
+    let goal = GoalZ3::new(&ctx, true, false, false);
+    GoalAssertZ3::new(&ctx, &goal, EQZ3::new(&ctx, &x, ADDZ3::new(&ctx, vec!(&y, &IntZ3::new(&ctx, &intsort, 1)))));
+    GoalAssertZ3::new(&ctx, &goal, GTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 2)));
+
+    let pipeline = TacticAndThenZ3::new(&ctx, &TacticZ3::new(&ctx, "solve-eqs"), &TacticZ3::new(&ctx, "simplify"));
+    let subgoals = TacticApplyZ3::new(&ctx, &pipeline, &goal);
+
+    assert_eq!(1, subgoals.len());
+    assert_eq!(vec!("(not (<= y 1))"), GoalGetFormulasZ3::new(&ctx, &subgoals[0]).iter().map(|x| x.to_string()).collect::<Vec<String>>());
+    assert_eq!(2, GoalGetFormulasZ3::new(&ctx, &goal).len());
+}
+
+#[test]
+fn test_unknown_tactic(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+
+    assert!(TacticZ3::try_new(&ctx, "no-such-tactic").is_err());
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_goal_and_combinator_errors(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let simplify = TacticZ3::new(&ctx, "simplify");
+    let smt = TacticZ3::new(&ctx, "smt");
+
+    // proofs are not enabled in the context
+    assert!(GoalZ3::try_new(&ctx, true, false, true).is_err());
+    assert_eq!(Ok(()), ctx.check_error());
+
+    let goal = GoalZ3::try_new(&ctx, true, false, false).unwrap();
+    assert_eq!(0, GoalGetFormulasZ3::try_new(&ctx, &goal).unwrap().len());
+    assert!(TacticAndThenZ3::try_new(&ctx, &simplify, &smt).is_ok());
+    assert!(TacticOrElseZ3::try_new(&ctx, &simplify, &smt).is_ok());
+    assert!(TacticRepeatZ3::try_new(&ctx, &simplify, 3).is_ok());
+    assert!(TacticParOrZ3::try_new(&ctx, vec!(&simplify, &smt)).is_ok());
+    assert!(SolverFromTacticZ3::try_new(&ctx, &smt).is_ok());
+}
+
+#[test]
+fn test_tactic_combinators(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let a = BoolVarZ3::new(&ctx, &boolsort, "a");
+    let b = BoolVarZ3::new(&ctx, &boolsort, "b");
+    let goal = GoalZ3::new(&ctx, true, false, false);
+    GoalAssertZ3::new(&ctx, &goal, ORZ3::new(&ctx, vec!(&a, &b)));
+    GoalAssertZ3::new(&ctx, &goal, NOTZ3::new(&ctx, &a));
+    GoalAssertZ3::new(&ctx, &goal, NOTZ3::new(&ctx, &b));
+
+    let simplify = TacticZ3::new(&ctx, "simplify");
+    let pipeline = TacticAndThenZ3::new(&ctx, &TacticRepeatZ3::new(&ctx, &simplify, 5),
+        &TacticOrElseZ3::new(&ctx, &TacticZ3::new(&ctx, "fail"), &TacticZ3::new(&ctx, "sat")));
+    let subgoals = TacticApplyZ3::new(&ctx, &pipeline, &goal);
+
+    assert_eq!(1, subgoals.len());
+    assert_eq!("(goal\n  false)", subgoals[0].to_string());
+
+    let par = TacticParOrZ3::new(&ctx, vec!(&TacticZ3::new(&ctx, "sat"), &TacticZ3::new(&ctx, "smt")));
+    let subgoals = TacticApplyZ3::new(&ctx, &par, &goal);
+    assert_eq!(vec!("false"), GoalGetFormulasZ3::new(&ctx, &subgoals[0]).iter().map(|x| x.to_string()).collect::<Vec<String>>());
+}
+
+#[test]
+fn test_solver_from_tactic(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let tactic = TacticAndThenZ3::new(&ctx, &TacticZ3::new(&ctx, "simplify"), &TacticZ3::new(&ctx, "smt"));
+    let slv = SolverFromTacticZ3::new(&ctx, &tactic);
+
+    let a = BoolVarZ3::new(&ctx, &boolsort, "a");
+    SlvAssertZ3::new(&ctx, &slv, &a);
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+    SlvAssertZ3::new(&ctx, &slv, NOTZ3::new(&ctx, &a));
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_tactic_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+
+    let goal = goal_z3!(&ctx);
+    let x = bv_var_z3!(&ctx, 4, "x");
+    goal_assert_z3!(&ctx, &goal,
+        eq_z3!(&ctx, bvadd_z3!(&ctx, &x, bv_z3!(&ctx, 4, 1)), bv_z3!(&ctx, 4, 3)),
+        bool_var_z3!(&ctx, "p"));
+
+    let tactic = and_then_z3!(&ctx, tactic_z3!(&ctx, "simplify"), tactic_z3!(&ctx, "bit-blast"),
+        repeat_z3!(&ctx, tactic_z3!(&ctx, "solve-eqs"), 2));
+    let subgoals = tactic_apply_z3!(&ctx, &tactic, &goal);
+    assert_eq!(1, subgoals.len());
+
+    let either = or_else_z3!(&ctx, tactic_z3!(&ctx, "fail"), par_or_z3!(&ctx, tactic_z3!(&ctx, "sat"), tactic_z3!(&ctx, "smt")));
+    let slv = slv_from_tactic_z3!(&ctx, &either);
+    slv_assert_z3!(&ctx, &slv, bool_var_z3!(&ctx, "p"));
+    assert_eq!(SatResultZ3::Sat, slv_check_z3!(&ctx, &slv));
+}
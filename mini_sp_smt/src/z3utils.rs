@@ -32,7 +32,7 @@ pub struct ModelGetConstDeclZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub model: Z3_model,
     pub index : ::std::os::raw::c_uint,
-    pub r: Z3_func_decl
+    pub r: FuncDecl<'ctx>
 }
 
 pub struct GetDeclNameZ3<'ctx> {
@@ -46,7 +46,7 @@ pub struct ModelGetConstInterpZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub model: Z3_model,
     pub decl : Z3_func_decl,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct GetSymbolStringZ3<'ctx> {
@@ -63,20 +63,36 @@ pub struct Z3StringToStringZ3 {
 pub struct Z3AstVectorToVectorAstZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub ast_vec: Z3_ast_vector,
-    pub r: Vec<Z3_ast>
+    pub r: Vec<Ast<'ctx>>
 }
 
 pub struct GetCnfVectorZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub cnf: Vec<Z3_ast>
+    pub cnf: Vec<Ast<'ctx>>
 }
 
 pub struct FreshModelZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub model: Z3_model
+    pub model: Model<'ctx>
 
 }
 
+pub struct ParseSmtLib2Z3<'ctx, 's> {
+    pub ctx: &'ctx ContextZ3,
+    pub script: &'s str,
+    pub sorts: Vec<Z3_sort>,
+    pub decls: Vec<Z3_func_decl>,
+    pub r: Vec<Ast<'ctx>>
+}
+
+pub struct ParseSmtLib2FileZ3<'ctx, 'f> {
+    pub ctx: &'ctx ContextZ3,
+    pub file: &'f str,
+    pub sorts: Vec<Z3_sort>,
+    pub decls: Vec<Z3_func_decl>,
+    pub r: Vec<Ast<'ctx>>
+}
+
 // pub trait IterOps<T, I>: IntoIterator<Item = T>
 //     where I: IntoIterator<Item = T>,
 //           T: PartialEq {
@@ -122,10 +138,11 @@ pub struct FreshModelZ3<'ctx> {
 
 impl<'ctx> FreshModelZ3<'ctx> {
     /// New empty model
-    pub fn new(ctx: &'ctx ContextZ3) -> Z3_model {
-        unsafe {
+    pub fn new(ctx: &'ctx ContextZ3) -> Model<'ctx> {
+        let z3 = unsafe {
             Z3_mk_model(ctx.r)
-        }
+        };
+        FreshModelZ3 {ctx, model: Model::new(ctx, z3)}.model
     }
 }
 
@@ -133,7 +150,8 @@ impl<'ctx> AstToStringZ3<'ctx> {
     /// AST to readable string
     /// 
     /// NOTE: See macro! `ast_to_string_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, what: Z3_ast) -> String {
+    pub fn new(ctx: &'ctx ContextZ3, what: impl AsAstZ3<'ctx>) -> String {
+        let what = what.as_ast();
         let z3 = unsafe {
             CStr::from_ptr(Z3_ast_to_string(ctx.r, what)).to_str().unwrap().to_owned()
         };
@@ -145,7 +163,8 @@ impl<'ctx> AstVectorToStringZ3<'ctx> {
     /// AST vector to readable string
     /// 
     /// NOTE: See macro! `ast_vector_to_string_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, what: Z3_ast_vector) -> String {
+    pub fn new(ctx: &'ctx ContextZ3, what: impl AsAstVectorZ3) -> String {
+        let what = what.as_ast_vector();
         let z3 = unsafe {
             CStr::from_ptr(Z3_ast_vector_to_string(ctx.r, what)).to_str().unwrap().to_owned()
         };
@@ -157,7 +176,8 @@ impl<'ctx> ModelToStringZ3<'ctx> {
     /// Model to readable string
     /// 
     /// NOTE: See macro! `model_to_string_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, what: Z3_model) -> String {
+    pub fn new(ctx: &'ctx ContextZ3, what: impl AsModelZ3) -> String {
+        let what = what.as_model();
         let z3 = unsafe {
             CStr::from_ptr(Z3_model_to_string(ctx.r, what)).to_str().unwrap().to_owned()
         };
@@ -169,7 +189,8 @@ impl<'ctx> ModelGetNumConstsZ3<'ctx> {
     /// Get the number of constants in a model
     /// 
     /// NOTE: See macro! `model_get_num_consts_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, model: Z3_model) -> ::std::os::raw::c_uint {
+    pub fn new(ctx: &'ctx ContextZ3, model: impl AsModelZ3) -> ::std::os::raw::c_uint {
+        let model = model.as_model();
         let z3 = unsafe {
             Z3_model_get_num_consts(ctx.r, model)
         };
@@ -181,11 +202,12 @@ impl<'ctx> ModelGetConstDeclZ3<'ctx> {
     /// Get declaration of the i-th const in a model
     /// 
     /// NOTE: See macro! `model_get_const_decl_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, model: Z3_model, index: ::std::os::raw::c_uint) -> Z3_func_decl {
+    pub fn new(ctx: &'ctx ContextZ3, model: impl AsModelZ3, index: ::std::os::raw::c_uint) -> FuncDecl<'ctx> {
+        let model = model.as_model();
         let z3 = unsafe {
             Z3_model_get_const_decl(ctx.r, model, index)
         };
-        ModelGetConstDeclZ3 {ctx, model, index, r: z3}.r
+        ModelGetConstDeclZ3 {ctx, model, index, r: FuncDecl::new(ctx, z3)}.r
     }
 }
 
@@ -193,7 +215,9 @@ impl<'ctx> GetDeclNameZ3<'ctx> {
     /// Get the name (symbol) of a declaration
     /// 
     /// NOTE: See macro! `get_decl_name_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, model: Z3_model, decl: Z3_func_decl) -> Z3_symbol {
+    pub fn new(ctx: &'ctx ContextZ3, model: impl AsModelZ3, decl: impl AsFuncDeclZ3) -> Z3_symbol {
+        let model = model.as_model();
+        let decl = decl.as_func_decl();
         let z3 = unsafe {
             Z3_get_decl_name(ctx.r, decl)
         };
@@ -205,11 +229,13 @@ impl<'ctx> ModelGetConstInterpZ3<'ctx> {
     /// Get interpretation of of a declaration
     /// 
     /// NOTE: See macro! `model_get_const_interp_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, model: Z3_model, decl: Z3_func_decl) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, model: impl AsModelZ3, decl: impl AsFuncDeclZ3) -> Ast<'ctx> {
+        let model = model.as_model();
+        let decl = decl.as_func_decl();
         let z3 = unsafe {
             Z3_model_get_const_interp(ctx.r, model, decl)
         };
-        ModelGetConstInterpZ3 {ctx, model, decl, r: z3}.r
+        ModelGetConstInterpZ3 {ctx, model, decl, r: Ast::new(ctx, z3)}.r
     }
 }
 
@@ -235,43 +261,111 @@ impl Z3StringToStringZ3 {
 
 impl <'ctx> Z3AstVectorToVectorAstZ3<'ctx> {
     /// transform to extract
-    pub fn new(ctx: &'ctx ContextZ3, ast_vec: Z3_ast_vector) -> Vec<Z3_ast> {
-        let mut vec: Vec<Z3_ast> = vec!();
+    pub fn new(ctx: &'ctx ContextZ3, ast_vec: impl AsAstVectorZ3) -> Vec<Ast<'ctx>> {
+        match Z3AstVectorToVectorAstZ3::try_new(ctx, ast_vec) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, ast_vec: impl AsAstVectorZ3) -> Result<Vec<Ast<'ctx>>, ErrorZ3> {
+        let ast_vec = ast_vec.as_ast_vector();
+        let mut vec: Vec<Ast<'ctx>> = vec!();
         unsafe {
             let size = Z3_ast_vector_size(ctx.r, ast_vec);
             for i in 0..size {
-                vec.push(Z3_ast_vector_get(ctx.r, ast_vec, i));
+                vec.push(Ast::new(ctx, Z3_ast_vector_get(ctx.r, ast_vec, i)));
             }
         }
-        vec
+        ctx.check_error()?;
+        Ok(Z3AstVectorToVectorAstZ3 {ctx, ast_vec, r: vec}.r)
     }
 }
 
 impl<'ctx> GetCnfVectorZ3<'ctx> {
     /// Get cnf. Will return false if unsat.
-    pub fn new(ctx: &'ctx ContextZ3, args: Vec<Z3_ast>) -> Vec<Z3_ast> {
-        let z3 = unsafe {
-            let goal = Z3_mk_goal(ctx.r, false, false, false);
-            for formula in args {
-                Z3_goal_assert(ctx.r, goal, formula);
-                Z3_goal_inc_ref(ctx.r, goal);
-            }
-            let tactic = Z3_mk_tactic(ctx.r, CString::new("tseitin-cnf".to_string()).unwrap().as_ptr());
-            Z3_tactic_inc_ref(ctx.r, tactic);
-            let applied = Z3_tactic_apply(ctx.r, tactic, goal);
-            let _nr_subgoals: u32 = Z3_apply_result_get_num_subgoals(ctx.r, applied);
-            let subgoal = Z3_apply_result_get_subgoal(ctx.r, applied, 0);
-            let subgoal_size = Z3_goal_size(ctx.r, subgoal);
-            let mut cnf_vec: Vec<Z3_ast> = vec!();
-            for i in 0..subgoal_size {
-                cnf_vec.push(Z3_goal_formula(ctx.r, subgoal, i));
-            }
-            cnf_vec
+    pub fn new(ctx: &'ctx ContextZ3, args: Vec<impl AsAstZ3<'ctx>>) -> Vec<Ast<'ctx>> {
+        let goal = GoalZ3::new(ctx, false, false, false);
+        for formula in args {
+            GoalAssertZ3::new(ctx, &goal, formula);
+        }
+        let tactic = TacticZ3::new(ctx, "tseitin-cnf");
+        let z3 = match TacticApplyZ3::new(ctx, &tactic, &goal).first() {
+            Some(subgoal) => GoalGetFormulasZ3::new(ctx, subgoal),
+            None => vec!()
         };
         GetCnfVectorZ3 {ctx, cnf: z3}.cnf
     }
 }
 
+// sorts and declarations are made visible to the parser under their own names
+fn parse_smtlib2_z3<'ctx>(ctx: &'ctx ContextZ3, source: &str, is_file: bool, sorts: &[Z3_sort], 
+    decls: &[Z3_func_decl]) -> Result<Vec<Ast<'ctx>>, ErrorZ3> {
+    let str_source = match CString::new(source) {
+        Ok(x) => x,
+        Err(_) => return Err(ErrorZ3 {code: Z3_error_code_Z3_INVALID_ARG, msg: "The script contains a nul byte.".to_string()})
+    };
+    unsafe {
+        let sort_names: Vec<Z3_symbol> = sorts.iter().map(|x| Z3_get_sort_name(ctx.r, *x)).collect();
+        let decl_names: Vec<Z3_symbol> = decls.iter().map(|x| Z3_get_decl_name(ctx.r, *x)).collect();
+        let parsed = match is_file {
+            true => Z3_parse_smtlib2_file(ctx.r, str_source.as_ptr(), sorts.len() as u32, sort_names.as_ptr(), 
+                sorts.as_ptr(), decls.len() as u32, decl_names.as_ptr(), decls.as_ptr()),
+            false => Z3_parse_smtlib2_string(ctx.r, str_source.as_ptr(), sorts.len() as u32, sort_names.as_ptr(), 
+                sorts.as_ptr(), decls.len() as u32, decl_names.as_ptr(), decls.as_ptr())
+        };
+        ctx.check_error()?;
+        let parsed = AstVector::new(ctx, parsed);
+        Z3AstVectorToVectorAstZ3::try_new(ctx, &parsed)
+    }
+}
+
+impl<'ctx, 's> ParseSmtLib2Z3<'ctx, 's> {
+    /// Parse an SMT-LIB2 script and return the asserted formulas.
+    ///
+    /// The `sorts` and `decls` are sorts and function declarations already created in 
+    /// the context that the script can refer to without declaring them, i.e. `&enumsort`
+    /// or the declaration of a variable from `GetAppDeclZ3`. Constructors of enum sorts 
+    /// are visible together with their sort.
+    ///
+    /// Parse errors, i.e. unknown symbols or sort mismatches, are returned as an 
+    /// `ErrorZ3` with the code `Z3_PARSER_ERROR` and the line and column in the message.
+    ///
+    /// NOTE: See macro! `parse_smtlib2_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, script: &'s str, sorts: Vec<impl AsSortZ3>, decls: Vec<impl AsFuncDeclZ3>) -> Vec<Ast<'ctx>> {
+        match ParseSmtLib2Z3::try_new(ctx, script, sorts, decls) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, script: &'s str, sorts: Vec<impl AsSortZ3>, decls: Vec<impl AsFuncDeclZ3>) -> Result<Vec<Ast<'ctx>>, ErrorZ3> {
+        let sorts: Vec<Z3_sort> = sorts.iter().map(|x| x.as_sort()).collect();
+        let decls: Vec<Z3_func_decl> = decls.iter().map(|x| x.as_func_decl()).collect();
+        let z3 = parse_smtlib2_z3(ctx, script, false, &sorts, &decls)?;
+        Ok(ParseSmtLib2Z3 {ctx, script, sorts, decls, r: z3}.r)
+    }
+}
+
+impl<'ctx, 'f> ParseSmtLib2FileZ3<'ctx, 'f> {
+    /// Same as `ParseSmtLib2Z3`, but the script is read from `file`.
+    ///
+    /// NOTE: See macro! `parse_smtlib2_file_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, file: &'f str, sorts: Vec<impl AsSortZ3>, decls: Vec<impl AsFuncDeclZ3>) -> Vec<Ast<'ctx>> {
+        match ParseSmtLib2FileZ3::try_new(ctx, file, sorts, decls) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, file: &'f str, sorts: Vec<impl AsSortZ3>, decls: Vec<impl AsFuncDeclZ3>) -> Result<Vec<Ast<'ctx>>, ErrorZ3> {
+        let sorts: Vec<Z3_sort> = sorts.iter().map(|x| x.as_sort()).collect();
+        let decls: Vec<Z3_func_decl> = decls.iter().map(|x| x.as_func_decl()).collect();
+        let z3 = parse_smtlib2_z3(ctx, file, true, &sorts, &decls)?;
+        Ok(ParseSmtLib2FileZ3 {ctx, file, sorts, decls, r: z3}.r)
+    }
+}
+
 /// abstract static tree to readable string
 #[macro_export]
 macro_rules! ast_to_string_z3 {
@@ -288,6 +382,48 @@ macro_rules! model_to_string_z3 {
     }
 }
 
+/// parse an SMT-LIB2 script into a vector of asserted formulas
+///
+/// Macro rule for:
+/// ```text
+/// z3utils::ParseSmtLib2Z3::new(&ctx, script, vec!(sort), vec!(decl))
+/// ```
+/// Using a specific context, without or with known sorts and declarations:
+/// ```text
+/// parse_smtlib2_z3!(&ctx, script)
+/// parse_smtlib2_z3!(&ctx, script, vec!(sort), vec!(decl))
+/// ```
+#[macro_export]
+macro_rules! parse_smtlib2_z3 {
+    ($ctx:expr, $s:expr) => {
+        ParseSmtLib2Z3::new($ctx, $s, Vec::<Sort>::new(), Vec::<FuncDecl>::new())
+    };
+    ($ctx:expr, $s:expr, $b:expr, $c:expr) => {
+        ParseSmtLib2Z3::new($ctx, $s, $b, $c)
+    };
+}
+
+/// parse an SMT-LIB2 file into a vector of asserted formulas
+///
+/// Macro rule for:
+/// ```text
+/// z3utils::ParseSmtLib2FileZ3::new(&ctx, file, vec!(sort), vec!(decl))
+/// ```
+/// Using a specific context, without or with known sorts and declarations:
+/// ```text
+/// parse_smtlib2_file_z3!(&ctx, file)
+/// parse_smtlib2_file_z3!(&ctx, file, vec!(sort), vec!(decl))
+/// ```
+#[macro_export]
+macro_rules! parse_smtlib2_file_z3 {
+    ($ctx:expr, $f:expr) => {
+        ParseSmtLib2FileZ3::new($ctx, $f, Vec::<Sort>::new(), Vec::<FuncDecl>::new())
+    };
+    ($ctx:expr, $f:expr, $b:expr, $c:expr) => {
+        ParseSmtLib2FileZ3::new($ctx, $f, $b, $c)
+    };
+}
+
 #[test]
 fn test_tseitin(){
     let conf = ConfigZ3::new();
@@ -298,13 +434,13 @@ fn test_tseitin(){
     let y = BoolVarZ3::new(&ctx, &sort, "y");
     let z = BoolVarZ3::new(&ctx, &sort, "z");
     
-    let asrt1 = NOTZ3::new(&ctx, x);
-    let _asrt2 = XORZ3::new(&ctx, x, y);
-    let asrt2 = XORZ3::new(&ctx, x, z);
+    let asrt1 = NOTZ3::new(&ctx, &x);
+    let _asrt2 = XORZ3::new(&ctx, &x, &y);
+    let asrt2 = XORZ3::new(&ctx, &x, z);
 
-    let asrt3 = IMPZ3::new(&ctx, y, x);
+    let asrt3 = IMPZ3::new(&ctx, &y, &x);
 
-    let asrt4 = DISTINCTZ3::new(&ctx, vec!(x, y));
+    let asrt4 = DISTINCTZ3::new(&ctx, vec!(&x, &y));
     let asrt5 = XORZ3::new(&ctx, x, y);
 
     let _tseit = GetCnfVectorZ3::new(&ctx, vec!(asrt1, asrt2, asrt3, asrt4, asrt5));
@@ -319,4 +455,21 @@ fn test_tseitin(){
 //   (or (not x) (not y))
 //   (or x y))
 // )", tseit);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_parse_smtlib2_1(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let x = BoolVarZ3::new(&ctx, &boolsort, "x");
+    let asserts = ParseSmtLib2Z3::new(&ctx, "(declare-const y Bool) (assert (or x y)) (assert (not y))", 
+        Vec::<Sort>::new(), vec!(GetAppDeclZ3::new(&ctx, &x)));
+
+    assert_eq!(2, asserts.len());
+    assert_eq!("(or x y)", ast_to_string_z3!(&ctx, &asserts[0]));
+    assert_eq!("(not y)", ast_to_string_z3!(&ctx, &asserts[1]));
+    assert_eq!(0, parse_smtlib2_z3!(&ctx, "(declare-const z Int)").len());
+    assert!(ParseSmtLib2Z3::try_new(&ctx, "(assert x)\0", Vec::<Sort>::new(), Vec::<FuncDecl>::new()).is_err());
+}
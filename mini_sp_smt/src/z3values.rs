@@ -7,39 +7,45 @@ use super::*;
 
 pub struct BoolZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct IntZ3<'ctx, 'isrt> {
     pub ctx: &'ctx ContextZ3,
     pub isrt: &'isrt IntSortZ3<'ctx>,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 pub struct RealZ3<'ctx, 'rsrt> {
     pub ctx: &'ctx ContextZ3,
     pub rsrt: &'rsrt RealSortZ3<'ctx>,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
+}
+
+pub struct BitVecZ3<'ctx, 'bvsrt> {
+    pub ctx: &'ctx ContextZ3,
+    pub bvsrt: &'bvsrt BitVecSortZ3<'ctx>,
+    pub r: Ast<'ctx>
 }
 
 pub struct StringZ3<'ctx, 'a> {
     pub ctx: &'ctx ContextZ3,
     pub val: &'a str,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
 }
 
 impl <'ctx> BoolZ3<'ctx> {
     /// Create an AST node representing `true` or 'false'.
     /// 
     /// NOTE: See macro! `bool_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, val: bool) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, val: bool) -> Ast<'ctx> {
         let z3 = if val == true { unsafe {
                 Z3_mk_true(ctx.r)
             }} else { unsafe { 
                 Z3_mk_false(ctx.r)
             }
         };
-        BoolZ3 {ctx, r: z3}.r
+        BoolZ3 {ctx, r: Ast::new(ctx, z3)}.r
     }
 }
 
@@ -51,11 +57,11 @@ impl <'ctx, 'isrt> IntZ3<'ctx, 'isrt> {
     /// - `val`: int to be realized.
     ///
     /// NOTE: See macro! `int_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, isrt: &'isrt IntSortZ3<'ctx>, val: i32) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, isrt: &'isrt IntSortZ3<'ctx>, val: i32) -> Ast<'ctx> {
         let z3 = unsafe {
             Z3_mk_int(ctx.r, val, isrt.r)
         };
-        IntZ3 {ctx, isrt, r: z3}.r
+        IntZ3 {ctx, isrt, r: Ast::new(ctx, z3)}.r
     }
 }
 
@@ -67,13 +73,61 @@ impl <'ctx, 'rsrt> RealZ3<'ctx, 'rsrt> {
     /// - `val`: float to be realized.
     /// 
     /// NOTE: See macro! `real_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, rsrt: &'rsrt RealSortZ3<'ctx>, val: f64) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, rsrt: &'rsrt RealSortZ3<'ctx>, val: f64) -> Ast<'ctx> {
         let num_string = val.to_string();
         let cstring = CString::new(num_string).unwrap();
         let z3 = unsafe {
             Z3_mk_numeral(ctx.r, cstring.as_ptr(), rsrt.r)
         };
-        RealZ3 {ctx, rsrt, r: z3}.r
+        RealZ3 {ctx, rsrt, r: Ast::new(ctx, z3)}.r
+    }
+}
+
+impl <'ctx, 'bvsrt> BitVecZ3<'ctx, 'bvsrt> {
+    /// Create a bit-vector constant from a rust i64.
+    ///
+    /// - `ctx`: logical context.
+    /// - `bvsrt`: bit-vector sort.
+    /// - `val`: int to be realized, negative values are stored in two's complement.
+    ///
+    /// NOTE: Values that don't fit in an i64, i.e. of sorts wider than 64 bits,
+    /// are made with `BitVecZ3::new_from_numeral`.
+    ///
+    /// NOTE: See macro! `bv_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, bvsrt: &'bvsrt BitVecSortZ3<'ctx>, val: i64) -> Ast<'ctx> {
+        match BitVecZ3::try_new(ctx, bvsrt, val) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    /// Create a bit-vector constant from a decimal numeral string of any size, i.e.
+    /// `"18446744073709551615"`. The value is taken modulo 2^size of the sort.
+    pub fn new_from_numeral(ctx: &'ctx ContextZ3, bvsrt: &'bvsrt BitVecSortZ3<'ctx>, val: &str) -> Ast<'ctx> {
+        match BitVecZ3::try_new_from_numeral(ctx, bvsrt, val) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, bvsrt: &'bvsrt BitVecSortZ3<'ctx>, val: i64) -> Result<Ast<'ctx>, ErrorZ3> {
+        let z3 = unsafe {
+            Z3_mk_int64(ctx.r, val, bvsrt.r)
+        };
+        ctx.check_error()?;
+        Ok(BitVecZ3 {ctx, bvsrt, r: Ast::new(ctx, z3)}.r)
+    }
+
+    pub fn try_new_from_numeral(ctx: &'ctx ContextZ3, bvsrt: &'bvsrt BitVecSortZ3<'ctx>, val: &str) -> Result<Ast<'ctx>, ErrorZ3> {
+        let cstring = match CString::new(val) {
+            Ok(x) => x,
+            Err(_) => return Err(ErrorZ3 {code: Z3_error_code_Z3_INVALID_ARG, msg: "The numeral contains a nul byte.".to_string()})
+        };
+        let z3 = unsafe {
+            Z3_mk_numeral(ctx.r, cstring.as_ptr(), bvsrt.r)
+        };
+        ctx.check_error()?;
+        Ok(BitVecZ3 {ctx, bvsrt, r: Ast::new(ctx, z3)}.r)
     }
 }
 
@@ -85,12 +139,12 @@ impl <'ctx, 'a> StringZ3<'ctx, 'a> {
     /// - `val`: string to be realized.
     /// 
     /// NOTE: See macro! `string_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, val: &'a str) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, val: &'a str) -> Ast<'ctx> {
         let string_val = CString::new(val.to_string()).unwrap();
         let z3 = unsafe {
             Z3_mk_string(ctx.r, string_val.as_ptr())
         };
-        StringZ3 {ctx, r: z3, val}.r
+        StringZ3 {ctx, r: Ast::new(ctx, z3), val}.r
     }
 }
 
@@ -145,6 +199,23 @@ macro_rules! real_z3 {
     }
 }
 
+/// create a bit-vector constant
+/// 
+/// Macro rule for:
+/// ```text
+/// z3values::BitVecZ3::new(&ctx, &BitVecSortZ3::new(&ctx, size), a)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bv_z3!(&ctx, size, a)
+/// ```
+#[macro_export]
+macro_rules! bv_z3 {
+    ($ctx:expr, $s:expr, $a:expr) => {
+        BitVecZ3::new($ctx, &BitVecSortZ3::new($ctx, $s), $a)
+    }
+}
+
 /// create a string constant
 /// 
 /// Macro rule for:
@@ -187,6 +258,31 @@ fn test_new_int(){
     assert_eq!("(- 1012390)", ast_to_string_z3!(&ctx, int2));
 }
 
+#[test]
+fn test_new_bv(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+
+    let bv1 = BitVecZ3::new(&ctx, &bvsort, 7);
+    let bv2 = BitVecZ3::new(&ctx, &bvsort, -1);
+
+    assert_eq!("#x07", ast_to_string_z3!(&ctx, bv1));
+    assert_eq!("#xff", ast_to_string_z3!(&ctx, bv2));
+}
+
+#[test]
+fn test_new_bv_from_numeral(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let bvsort = BitVecSortZ3::new(&ctx, 128);
+
+    let bv1 = BitVecZ3::new_from_numeral(&ctx, &bvsort, "340282366920938463463374607431768211455");
+
+    assert_eq!("#xffffffffffffffffffffffffffffffff", ast_to_string_z3!(&ctx, bv1));
+    assert!(BitVecZ3::try_new_from_numeral(&ctx, &bvsort, "seven").is_err());
+}
+
 #[test]
 fn test_new_string(){
     let conf = ConfigZ3::new();
@@ -202,7 +298,7 @@ fn test_bool_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let bool1 = bool_z3!(&ctx, true);
-    assert_eq!("true", ast_to_string_z3!(&ctx, bool1));
+    assert_eq!("true", ast_to_string_z3!(&ctx, &bool1));
     assert_eq!("Bool", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, bool1)));
 }
 
@@ -211,7 +307,7 @@ fn test_int_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let int1 = int_z3!(&ctx, 76);
-    assert_eq!("76", ast_to_string_z3!(&ctx, int1));
+    assert_eq!("76", ast_to_string_z3!(&ctx, &int1));
     assert_eq!("Int", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, int1)));
 }
 
@@ -220,7 +316,7 @@ fn test_real_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let real1 = real_z3!(&ctx, 76.456);
-    assert_eq!("(/ 9557.0 125.0)", ast_to_string_z3!(&ctx, real1));
+    assert_eq!("(/ 9557.0 125.0)", ast_to_string_z3!(&ctx, &real1));
     assert_eq!("Real", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, real1)));
 }
 
@@ -229,6 +325,6 @@ fn test_string_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let string1 = string_z3!(&ctx, "asdf_ASDF_!@#$");
-    assert_eq!("\"asdf_ASDF_!@#$\"", ast_to_string_z3!(&ctx, string1));
+    assert_eq!("\"asdf_ASDF_!@#$\"", ast_to_string_z3!(&ctx, &string1));
     assert_eq!("String", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, string1)));
 }
\ No newline at end of file
@@ -8,48 +8,62 @@ pub struct BoolVarZ3<'ctx, 'bsrt, 'a> {
     pub ctx: &'ctx ContextZ3,
     pub bsrt: &'bsrt BoolSortZ3<'ctx>,
     pub name: &'a str,
-    pub r: Z3_ast,
+    pub r: Ast<'ctx>,
 }
 
 pub struct IntVarZ3<'ctx, 'isrt, 'a> {
     pub ctx: &'ctx ContextZ3,
     pub isrt: &'isrt IntSortZ3<'ctx>,
     pub name: &'a str,
-    pub r: Z3_ast,
+    pub r: Ast<'ctx>,
 }
 
 pub struct RealVarZ3<'ctx, 'rsrt, 'a> {
     pub ctx: &'ctx ContextZ3,
     pub rsrt: &'rsrt RealSortZ3<'ctx>,
     pub name: &'a str,
-    pub r: Z3_ast,
+    pub r: Ast<'ctx>,
 }
 
 pub struct StringVarZ3<'ctx, 'ssrt, 'a> {
     pub ctx: &'ctx ContextZ3,
     pub ssrt: &'ssrt StringSortZ3<'ctx>,
     pub name: &'a str,
-    pub r: Z3_ast,
+    pub r: Ast<'ctx>,
+}
+
+pub struct BitVecVarZ3<'ctx, 'bvsrt, 'a> {
+    pub ctx: &'ctx ContextZ3,
+    pub bvsrt: &'bvsrt BitVecSortZ3<'ctx>,
+    pub name: &'a str,
+    pub r: Ast<'ctx>,
+}
+
+pub struct ArrayVarZ3<'ctx, 'asrt, 'a> {
+    pub ctx: &'ctx ContextZ3,
+    pub asrt: &'asrt ArraySortZ3<'ctx>,
+    pub name: &'a str,
+    pub r: Ast<'ctx>,
 }
 
 pub struct EnumVarZ3<'ctx, 'a> {
     pub ctx: &'ctx ContextZ3,
     pub esrt: Z3_sort,
     pub name: &'a str,
-    pub r: Z3_ast,
+    pub r: Ast<'ctx>,
 }
 
 impl <'ctx, 'bsrt, 'a> BoolVarZ3<'ctx, 'bsrt, 'a> {
     /// Declare and create a Boolean type variable.
     /// 
     /// NOTE: See macro! `bool_var_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, bsrt: &'bsrt BoolSortZ3<'ctx>, name: &'a str) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, bsrt: &'bsrt BoolSortZ3<'ctx>, name: &'a str) -> Ast<'ctx> {
         let bool_sort = bsrt.r;
         let str_name = CString::new(name).unwrap();
         let z3 = unsafe {
             Z3_mk_const(ctx.r, Z3_mk_string_symbol(ctx.r, str_name.as_ptr()), bool_sort)
         };
-        BoolVarZ3 {ctx, bsrt, name, r: z3}.r
+        BoolVarZ3 {ctx, bsrt, name, r: Ast::new(ctx, z3)}.r
     }
 }
 
@@ -57,13 +71,13 @@ impl <'ctx, 'isrt, 'a> IntVarZ3<'ctx, 'isrt, 'a> {
     /// Declare and create an Integer type variable.
     /// 
     /// NOTE: See macro! `int_var_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, isrt: &'isrt IntSortZ3<'ctx>, name: &'a str) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, isrt: &'isrt IntSortZ3<'ctx>, name: &'a str) -> Ast<'ctx> {
         let int_sort = isrt.r;
         let str_name = CString::new(name).unwrap();
         let z3 = unsafe {
             Z3_mk_const(ctx.r, Z3_mk_string_symbol(ctx.r, str_name.as_ptr()), int_sort)
         };
-        IntVarZ3 {ctx, isrt, name,r: z3}.r
+        IntVarZ3 {ctx, isrt, name,r: Ast::new(ctx, z3)}.r
     }
 }
 
@@ -71,13 +85,13 @@ impl <'ctx, 'rsrt, 'a> RealVarZ3<'ctx, 'rsrt, 'a> {
     /// Declare and create an Real type variable.
     /// 
     /// NOTE: See macro! `real_var_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, rsrt: &'rsrt RealSortZ3<'ctx>, name: &'a str) -> Z3_ast{
+    pub fn new(ctx: &'ctx ContextZ3, rsrt: &'rsrt RealSortZ3<'ctx>, name: &'a str) -> Ast<'ctx> {
         let real_sort = rsrt.r;
         let str_name = CString::new(name).unwrap();
         let z3 = unsafe {
             Z3_mk_const(ctx.r, Z3_mk_string_symbol(ctx.r, str_name.as_ptr()), real_sort)
         };
-        RealVarZ3 {ctx, rsrt, name, r: z3}.r
+        RealVarZ3 {ctx, rsrt, name, r: Ast::new(ctx, z3)}.r
     }
 }
 
@@ -85,13 +99,49 @@ impl <'ctx, 'ssrt, 'a> StringVarZ3<'ctx, 'ssrt, 'a> {
     /// Declare and create an String type variable
     /// 
     /// NOTE: See macro! `string_var_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, ssrt: &'ssrt StringSortZ3<'ctx>, name: &'a str) -> Z3_ast{
+    pub fn new(ctx: &'ctx ContextZ3, ssrt: &'ssrt StringSortZ3<'ctx>, name: &'a str) -> Ast<'ctx> {
         let string_sort = ssrt.r;
         let str_name = CString::new(name).unwrap();
         let z3 = unsafe {
             Z3_mk_const(ctx.r, Z3_mk_string_symbol(ctx.r, str_name.as_ptr()), string_sort)
         };
-        StringVarZ3 {ctx, ssrt, name, r: z3}.r
+        StringVarZ3 {ctx, ssrt, name, r: Ast::new(ctx, z3)}.r
+    }
+}
+
+impl <'ctx, 'bvsrt, 'a> BitVecVarZ3<'ctx, 'bvsrt, 'a> {
+    /// Declare and create a bit-vector type variable
+    /// 
+    /// NOTE: See macro! `bv_var_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, bvsrt: &'bvsrt BitVecSortZ3<'ctx>, name: &'a str) -> Ast<'ctx> {
+        match BitVecVarZ3::try_new(ctx, bvsrt, name) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, bvsrt: &'bvsrt BitVecSortZ3<'ctx>, name: &'a str) -> Result<Ast<'ctx>, ErrorZ3> {
+        let bv_sort = bvsrt.r;
+        let str_name = CString::new(name).unwrap();
+        let z3 = unsafe {
+            Z3_mk_const(ctx.r, Z3_mk_string_symbol(ctx.r, str_name.as_ptr()), bv_sort)
+        };
+        ctx.check_error()?;
+        Ok(BitVecVarZ3 {ctx, bvsrt, name, r: Ast::new(ctx, z3)}.r)
+    }
+}
+
+impl <'ctx, 'asrt, 'a> ArrayVarZ3<'ctx, 'asrt, 'a> {
+    /// Declare and create an array type variable
+    /// 
+    /// NOTE: See macro! `array_var_z3!`
+    pub fn new(ctx: &'ctx ContextZ3, asrt: &'asrt ArraySortZ3<'ctx>, name: &'a str) -> Ast<'ctx> {
+        let array_sort = asrt.r;
+        let str_name = CString::new(name).unwrap();
+        let z3 = unsafe {
+            Z3_mk_const(ctx.r, Z3_mk_string_symbol(ctx.r, str_name.as_ptr()), array_sort)
+        };
+        ArrayVarZ3 {ctx, asrt, name, r: Ast::new(ctx, z3)}.r
     }
 }
 
@@ -99,12 +149,13 @@ impl <'ctx, 'a> EnumVarZ3<'ctx, 'a> {
     /// Declare and create an Enum type variable
     /// 
     /// NOTE: See macro! `enum_var_z3!`
-    pub fn new(ctx: &'ctx ContextZ3, esrt: Z3_sort, name: &'a str) -> Z3_ast{
+    pub fn new(ctx: &'ctx ContextZ3, esrt: impl AsSortZ3, name: &'a str) -> Ast<'ctx> {
+        let esrt = esrt.as_sort();
         let str_name = CString::new(name).unwrap();
         let z3 = unsafe {
             Z3_mk_const(ctx.r, Z3_mk_string_symbol(ctx.r, str_name.as_ptr()), esrt)
         };
-        EnumVarZ3 {ctx, esrt, name, r: z3}.r
+        EnumVarZ3 {ctx, esrt, name, r: Ast::new(ctx, z3)}.r
     }
 }
 
@@ -176,6 +227,40 @@ macro_rules! string_var_z3 {
     }
 }
 
+/// create a bit-vector variable
+/// 
+/// Macro rule for:
+/// ```text
+/// z3variables::BitVecVarZ3::new(&ctx, &BitVecSortZ3::new(&ctx, size), a)
+/// ```
+/// Using a specific context:
+/// ```text
+/// bv_var_z3!(&ctx, size, a)
+/// ```
+#[macro_export]
+macro_rules! bv_var_z3 {
+    ($ctx:expr, $s:expr, $a:expr) => {
+        BitVecVarZ3::new($ctx, &BitVecSortZ3::new($ctx, $s), $a)
+    }
+}
+
+/// create an array variable
+/// 
+/// Macro rule for:
+/// ```text
+/// z3variables::ArrayVarZ3::new(&ctx, &ArraySortZ3::new(&ctx, domain, range), a)
+/// ```
+/// Using a specific context:
+/// ```text
+/// array_var_z3!(&ctx, domain, range, a)
+/// ```
+#[macro_export]
+macro_rules! array_var_z3 {
+    ($ctx:expr, $d:expr, $r:expr, $a:expr) => {
+        ArrayVarZ3::new($ctx, &ArraySortZ3::new($ctx, $d, $r), $a)
+    }
+}
+
 /// create an enumeration variable
 /// 
 /// Macro rule for:
@@ -248,7 +333,7 @@ fn test_bool_var_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let bool1 = bool_var_z3!(&ctx, "x");
-    assert_eq!("x", ast_to_string_z3!(&ctx, bool1));
+    assert_eq!("x", ast_to_string_z3!(&ctx, &bool1));
     assert_eq!("Bool", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, bool1)));
 }
 
@@ -257,7 +342,7 @@ fn test_int_var_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let int1 = int_var_z3!(&ctx, "x");
-    assert_eq!("x", ast_to_string_z3!(&ctx, int1));
+    assert_eq!("x", ast_to_string_z3!(&ctx, &int1));
     assert_eq!("Int", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, int1)));
 }
 
@@ -266,7 +351,7 @@ fn test_real_var_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let real1 = real_var_z3!(&ctx, "x");
-    assert_eq!("x", ast_to_string_z3!(&ctx, real1));
+    assert_eq!("x", ast_to_string_z3!(&ctx, &real1));
     assert_eq!("Real", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, real1)));
 }
 
@@ -275,6 +360,28 @@ fn test_string_var_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let string1 = string_var_z3!(&ctx, "x");
-    assert_eq!("x", ast_to_string_z3!(&ctx, string1));
+    assert_eq!("x", ast_to_string_z3!(&ctx, &string1));
     assert_eq!("String", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, string1)));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_bv_var_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let bv1 = bv_var_z3!(&ctx, 32, "x");
+    assert_eq!("x", ast_to_string_z3!(&ctx, &bv1));
+    assert_eq!("(_ BitVec 32)", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, bv1)));
+}
+
+#[test]
+fn test_new_array_var(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let sort = ArraySortZ3::new(&ctx, &intsort, &intsort);
+
+    let x = ArrayVarZ3::new(&ctx, &sort, "x");
+
+    assert_eq!("x", ast_to_string_z3!(&ctx, &x));
+    assert_eq!("(Array Int Int)", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, x)));
+}
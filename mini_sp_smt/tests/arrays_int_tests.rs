@@ -0,0 +1,106 @@
+use mini_sp_smt::*;
+
+#[test]
+fn test_new_select_store(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let sort = ArraySortZ3::new(&ctx, &intsort, &intsort);
+
+    let a = ArrayVarZ3::new(&ctx, &sort, "a");
+    let store = StoreZ3::new(&ctx, &a, IntZ3::new(&ctx, &intsort, 1), IntZ3::new(&ctx, &intsort, 5));
+    let select = SelectZ3::new(&ctx, &store, IntZ3::new(&ctx, &intsort, 1));
+
+    assert_eq!("(store a 1 5)", ast_to_string_z3!(&ctx, &store));
+    assert_eq!("(select (store a 1 5) 1)", ast_to_string_z3!(&ctx, &select));
+    assert_eq!("Int", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, select)));
+}
+
+#[test]
+fn test_new_const_array(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let zeros = ConstArrayZ3::new(&ctx, &intsort, IntZ3::new(&ctx, &intsort, 0));
+
+    assert_eq!("((as const (Array Int Int)) 0)", ast_to_string_z3!(&ctx, &zeros));
+}
+
+#[test]
+fn test_array_sort_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let sort = ArraySortZ3::new(&ctx, &intsort, &intsort);
+
+    let a = ArrayVarZ3::new(&ctx, &sort, "a");
+
+    assert!(SelectZ3::try_new(&ctx, &a, BoolZ3::new(&ctx, true)).is_err());
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_array_slot_map(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("none", "red", "blue"));
+    let sort = ArraySortZ3::new(&ctx, &intsort, &balls);
+
+    let slots = ArrayVarZ3::new(&ctx, &sort, "slots");
+    let empty = ConstArrayZ3::new(&ctx, &intsort, &balls.enum_asts[0]);
+    let filled = StoreZ3::new(&ctx, 
+        StoreZ3::new(&ctx, empty, IntZ3::new(&ctx, &intsort, 1), &balls.enum_asts[1]),
+        IntZ3::new(&ctx, &intsort, 2), &balls.enum_asts[2]);
+
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &slots, filled));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let interp = ModelGetArrayInterpZ3::new(&ctx, &model, &slots);
+
+    assert_eq!(Some(&ModelValueZ3::Enum("red".to_string())), interp.value(&ModelValueZ3::Int(1)));
+    assert_eq!(Some(&ModelValueZ3::Enum("blue".to_string())), interp.value(&ModelValueZ3::Int(2)));
+    assert_eq!(Some(&ModelValueZ3::Enum("none".to_string())), interp.value(&ModelValueZ3::Int(3)));
+}
+
+#[test]
+fn test_array_const_interp(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let sort = ArraySortZ3::new(&ctx, &intsort, &intsort);
+
+    let a = ArrayVarZ3::new(&ctx, &sort, "a");
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, SelectZ3::new(&ctx, &a, IntZ3::new(&ctx, &intsort, 1)), IntZ3::new(&ctx, &intsort, 5)));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, SelectZ3::new(&ctx, &a, IntZ3::new(&ctx, &intsort, 2)), IntZ3::new(&ctx, &intsort, 7)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let decl = ModelGetConstDeclZ3::new(&ctx, &model, 0);
+    let value = ModelGetConstInterpZ3::new(&ctx, &model, &decl);
+    let interp = ModelGetArrayInterpZ3::new(&ctx, &model, &value);
+
+    assert_eq!("a", Z3StringToStringZ3::new(GetSymbolStringZ3::new(&ctx, GetDeclNameZ3::new(&ctx, &model, &decl))));
+    assert_eq!(Some(&ModelValueZ3::Int(5)), interp.value(&ModelValueZ3::Int(1)));
+    assert_eq!(Some(&ModelValueZ3::Int(7)), interp.value(&ModelValueZ3::Int(2)));
+}
+
+#[test]
+fn test_array_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let intsort = int_sort_z3!(&ctx);
+    let boolsort = bool_sort_z3!(&ctx);
+
+    let a = array_var_z3!(&ctx, &intsort, &boolsort, "a");
+    let t = const_array_z3!(&ctx, &intsort, bool_z3!(&ctx, true));
+
+    assert_eq!("(Array Int Bool)", sort_to_string_z3!(&ctx, array_sort_z3!(&ctx, &intsort, &boolsort)));
+    assert_eq!("(store a 3 false)", ast_to_string_z3!(&ctx, store_z3!(&ctx, &a, int_z3!(&ctx, 3), bool_z3!(&ctx, false))));
+    assert_eq!("(select a 3)", ast_to_string_z3!(&ctx, select_z3!(&ctx, &a, int_z3!(&ctx, 3))));
+    assert_eq!("((as const (Array Int Bool)) true)", ast_to_string_z3!(&ctx, &t));
+}
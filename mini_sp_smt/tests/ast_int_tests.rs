@@ -0,0 +1,77 @@
+use mini_sp_smt::*;
+
+#[test]
+fn test_ast_clone_and_display(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let y = x.clone();
+    drop(x);
+
+    assert_eq!("x", format!("{}", y));
+    assert_eq!("(= x 7)", format!("{}", EQZ3::new(&ctx, &y, IntZ3::new(&ctx, &intsort, 7))));
+}
+
+#[test]
+fn test_sort_and_model_display(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 7)));
+    SlvCheckZ3::new(&ctx, &slv);
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let decl = ModelGetConstDeclZ3::new(&ctx, &model, 0);
+
+    assert_eq!("Int", format!("{}", GetSortZ3::new(&ctx, &x).r));
+    assert_eq!("x -> 7\n", format!("{}", model.clone()));
+    assert_eq!("(declare-fun x () Int)", format!("{}", decl));
+}
+
+#[test]
+fn test_ast_outlives_solver(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let mut kept = vec!();
+    for i in 0..3 {
+        let slv = SolverZ3::new(&ctx);
+        let x = IntVarZ3::new(&ctx, &intsort, "x");
+        let cst = GTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, i));
+        SlvAssertZ3::new(&ctx, &slv, &cst);
+        assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+        kept.push(cst);
+    }
+
+    let slv = SolverZ3::new(&ctx);
+    SlvAssertZ3::new(&ctx, &slv, ANDZ3::new(&ctx, kept.clone()));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+    assert_eq!("(> x 2)", format!("{}", kept[2]));
+}
+
+#[test]
+fn test_handles_outlive_later_calls(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("red", "blue"));
+    let b = EnumVarZ3::new(&ctx, &balls, "b");
+    let p = BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), "p");
+    let q = BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), "q");
+
+    let equiv = z3logics::EQUIVZ3::new(&ctx, &p, &q);
+    let red = EQZ3::new(&ctx, &b, &balls.enum_asts[0]);
+    SlvAssertZ3::new(&ctx, &slv, and_z3!(&ctx, &equiv, NOTZ3::new(&ctx, &red), ORZ3::new(&ctx, vec!(&p, &q))));
+
+    assert_eq!("(and (=> p q) (=> q p))", format!("{}", equiv));
+    assert_eq!("red", format!("{}", balls.enum_asts[0]));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!(ModelValueZ3::Enum("blue".to_string()), ModelEvalZ3::new(&ctx, &model, &b, true));
+}
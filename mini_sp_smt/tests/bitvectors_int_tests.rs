@@ -0,0 +1,131 @@
+use mini_sp_smt::*;
+
+#[test]
+fn test_new_bv_arith(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+
+    let x = BitVecVarZ3::new(&ctx, &bvsort, "x");
+    let y = BitVecVarZ3::new(&ctx, &bvsort, "y");
+    let bv1 = BitVecZ3::new(&ctx, &bvsort, 3);
+
+    assert_eq!("(bvadd x #x03)", ast_to_string_z3!(&ctx, BVADDZ3::new(&ctx, &x, &bv1)));
+    assert_eq!("(bvsub x y)", ast_to_string_z3!(&ctx, BVSUBZ3::new(&ctx, &x, &y)));
+    assert_eq!("(bvmul x y)", ast_to_string_z3!(&ctx, BVMULZ3::new(&ctx, &x, &y)));
+    assert_eq!("(bvudiv x #x03)", ast_to_string_z3!(&ctx, BVUDIVZ3::new(&ctx, &x, &bv1)));
+    assert_eq!("(bvsdiv x #x03)", ast_to_string_z3!(&ctx, BVSDIVZ3::new(&ctx, &x, &bv1)));
+    assert_eq!("(bvurem x y)", ast_to_string_z3!(&ctx, BVUREMZ3::new(&ctx, &x, &y)));
+    assert_eq!("(bvsrem x y)", ast_to_string_z3!(&ctx, BVSREMZ3::new(&ctx, &x, &y)));
+    assert_eq!("(bvneg x)", ast_to_string_z3!(&ctx, BVNEGZ3::new(&ctx, &x)));
+}
+
+#[test]
+fn test_new_bv_bitwise(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+
+    let x = BitVecVarZ3::new(&ctx, &bvsort, "x");
+    let mask = BitVecZ3::new(&ctx, &bvsort, 15);
+    let shift = BitVecZ3::new(&ctx, &bvsort, 4);
+
+    assert_eq!("(bvand x #x0f)", ast_to_string_z3!(&ctx, BVANDZ3::new(&ctx, &x, &mask)));
+    assert_eq!("(bvor x #x0f)", ast_to_string_z3!(&ctx, BVORZ3::new(&ctx, &x, &mask)));
+    assert_eq!("(bvxor x #x0f)", ast_to_string_z3!(&ctx, BVXORZ3::new(&ctx, &x, &mask)));
+    assert_eq!("(bvnot x)", ast_to_string_z3!(&ctx, BVNOTZ3::new(&ctx, &x)));
+    assert_eq!("(bvshl x #x04)", ast_to_string_z3!(&ctx, BVSHLZ3::new(&ctx, &x, &shift)));
+    assert_eq!("(bvlshr x #x04)", ast_to_string_z3!(&ctx, BVLSHRZ3::new(&ctx, &x, &shift)));
+    assert_eq!("(bvashr x #x04)", ast_to_string_z3!(&ctx, BVASHRZ3::new(&ctx, &x, &shift)));
+}
+
+#[test]
+fn test_new_bv_compare(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+
+    let minus_one = BitVecZ3::new(&ctx, &bvsort, -1);
+    let one = BitVecZ3::new(&ctx, &bvsort, 1);
+
+    SlvAssertZ3::new(&ctx, &slv, BVSLTZ3::new(&ctx, &minus_one, &one));
+    SlvAssertZ3::new(&ctx, &slv, BVSLEZ3::new(&ctx, &minus_one, &one));
+    SlvAssertZ3::new(&ctx, &slv, BVUGTZ3::new(&ctx, &minus_one, &one));
+    SlvAssertZ3::new(&ctx, &slv, BVUGEZ3::new(&ctx, &minus_one, &one));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    SlvAssertZ3::new(&ctx, &slv, BVULTZ3::new(&ctx, &minus_one, &one));
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+
+    assert_eq!("(bvule #xff #x01)", ast_to_string_z3!(&ctx, BVULEZ3::new(&ctx, &minus_one, &one)));
+    assert_eq!("(bvsgt #xff #x01)", ast_to_string_z3!(&ctx, BVSGTZ3::new(&ctx, &minus_one, &one)));
+    assert_eq!("(bvsge #xff #x01)", ast_to_string_z3!(&ctx, BVSGEZ3::new(&ctx, &minus_one, &one)));
+}
+
+#[test]
+fn test_new_bv_extract_concat(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+
+    let x = BitVecVarZ3::new(&ctx, &bvsort, "x");
+    let y = BitVecVarZ3::new(&ctx, &bvsort, "y");
+
+    let high = BVEXTRACTZ3::new(&ctx, 7, 4, &x);
+    let word = BVCONCATZ3::new(&ctx, &x, &y);
+
+    assert_eq!("((_ extract 7 4) x)", ast_to_string_z3!(&ctx, &high));
+    assert_eq!("(concat x y)", ast_to_string_z3!(&ctx, &word));
+    assert_eq!("(_ BitVec 4)", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, high)));
+    assert_eq!("(_ BitVec 16)", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, word)));
+}
+
+#[test]
+fn test_bv_register_mask(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+
+    let x = BitVecVarZ3::new(&ctx, &bvsort, "x");
+    let low = BVANDZ3::new(&ctx, &x, BitVecZ3::new(&ctx, &bvsort, 15));
+    let high = BVLSHRZ3::new(&ctx, &x, BitVecZ3::new(&ctx, &bvsort, 4));
+
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, low, BitVecZ3::new(&ctx, &bvsort, 5)));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, high, BitVecZ3::new(&ctx, &bvsort, 10)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!("x -> #xa5\n", model_to_string_z3!(&ctx, model));
+}
+
+#[test]
+fn test_bv_sort_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+
+    let x = BitVecVarZ3::new(&ctx, &BitVecSortZ3::new(&ctx, 8), "x");
+    let y = BitVecVarZ3::new(&ctx, &BitVecSortZ3::new(&ctx, 16), "y");
+
+    assert!(BVADDZ3::try_new(&ctx, &x, &y).is_err());
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_bv_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+
+    let x = bv_var_z3!(&ctx, 8, "x");
+    let y = bv_var_z3!(&ctx, 8, "y");
+    let mask = bv_z3!(&ctx, 8, 240);
+
+    assert_eq!("(bvadd x y)", ast_to_string_z3!(&ctx, bvadd_z3!(&ctx, &x, &y)));
+    assert_eq!("(bvand x #xf0)", ast_to_string_z3!(&ctx, bvand_z3!(&ctx, &x, &mask)));
+    assert_eq!("(bvnot x)", ast_to_string_z3!(&ctx, bvnot_z3!(&ctx, &x)));
+    assert_eq!("(bvslt x y)", ast_to_string_z3!(&ctx, bvslt_z3!(&ctx, &x, &y)));
+    assert_eq!("(bvuge x y)", ast_to_string_z3!(&ctx, bvuge_z3!(&ctx, &x, &y)));
+    assert_eq!("((_ extract 3 0) x)", ast_to_string_z3!(&ctx, bvextract_z3!(&ctx, 3, 0, &x)));
+    assert_eq!("(concat x y)", ast_to_string_z3!(&ctx, bvconcat_z3!(&ctx, x, y)));
+}
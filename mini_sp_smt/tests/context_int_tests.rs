@@ -1,4 +1,5 @@
 use mini_sp_smt::*;
+use z3_sys::*;
 
 #[test]
 fn test_ctx(){
@@ -11,6 +12,25 @@ fn test_default_ctx(){
     ContextZ3::default();
 }
 
+#[test]
+fn test_ctx_check_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_ctx_check_error_latest(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    unsafe {
+        Z3_mk_bv_sort(ctx.r, 0);
+        Z3_get_numeral_string(ctx.r, Z3_mk_true(ctx.r));
+    }
+    assert_eq!(Z3_error_code_Z3_INVALID_ARG, ctx.check_error().unwrap_err().code);
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
 #[test]
 fn test_ctx_macro(){
     let cfg = cfg_z3!();
@@ -0,0 +1,102 @@
+use mini_sp_smt::*;
+
+#[test]
+fn test_model_eval_bool_int(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let b = BoolVarZ3::new(&ctx, &boolsort, "b");
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, -7)));
+    SlvAssertZ3::new(&ctx, &slv, NOTZ3::new(&ctx, &b));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!(ModelValueZ3::Int(-7), ModelEvalZ3::new(&ctx, &model, &x, true));
+    assert_eq!(ModelValueZ3::Bool(false), ModelEvalZ3::new(&ctx, &model, &b, true));
+    assert_eq!(ModelValueZ3::Int(-14), ModelEvalZ3::new(&ctx, &model, ADDZ3::new(&ctx, vec!(&x, &x)), true));
+    assert_eq!(Some(-7), ModelEvalZ3::new(&ctx, &model, &x, true).as_i64());
+}
+
+#[test]
+fn test_model_eval_completion(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let y = IntVarZ3::new(&ctx, &intsort, "y");
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 1)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!(ModelValueZ3::Other("y".to_string()), ModelEvalZ3::new(&ctx, &model, &y, false));
+    assert_eq!(ModelValueZ3::Int(0), ModelEvalZ3::new(&ctx, &model, &y, true));
+}
+
+#[test]
+fn test_model_eval_big_int_and_real(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let realsort = RealSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let r = RealVarZ3::new(&ctx, &realsort, "r");
+    let big = MULZ3::new(&ctx, vec!(IntZ3::new(&ctx, &intsort, 2000000000), IntZ3::new(&ctx, &intsort, 2000000000),
+        IntZ3::new(&ctx, &intsort, 2000000000)));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &x, big));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, MULZ3::new(&ctx, vec!(&r, &RealZ3::new(&ctx, &realsort, 3.0))),
+        RealZ3::new(&ctx, &realsort, 1.0)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!(ModelValueZ3::BigInt("8000000000000000000000000000".to_string()), ModelEvalZ3::new(&ctx, &model, &x, true));
+    assert_eq!(ModelValueZ3::Rational(1, 3), ModelEvalZ3::new(&ctx, &model, &r, true));
+    assert_eq!("1/3", format!("{}", ModelEvalZ3::new(&ctx, &model, &r, true)));
+}
+
+#[test]
+fn test_model_eval_string_enum_bv(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let stringsort = StringSortZ3::new(&ctx);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("red", "blue"));
+
+    let s = StringVarZ3::new(&ctx, &stringsort, "s");
+    let v = BitVecVarZ3::new(&ctx, &bvsort, "v");
+    let b = EnumVarZ3::new(&ctx, &balls, "b");
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &s, StringZ3::new(&ctx, "hello")));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &v, BitVecZ3::new(&ctx, &bvsort, 200)));
+    SlvAssertZ3::new(&ctx, &slv, NOTZ3::new(&ctx, EQZ3::new(&ctx, &b, &balls.enum_asts[0])));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!(ModelValueZ3::String("hello".to_string()), ModelEvalZ3::new(&ctx, &model, &s, true));
+    assert_eq!(ModelValueZ3::Int(200), ModelEvalZ3::new(&ctx, &model, &v, true));
+    assert_eq!(ModelValueZ3::Enum("blue".to_string()), ModelEvalZ3::new(&ctx, &model, &b, true));
+    assert_eq!(Some("blue"), ModelEvalZ3::new(&ctx, &model, &b, true).as_str());
+}
+
+#[test]
+fn test_model_eval_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    let x = int_var_z3!(&ctx, "x");
+    let y = int_var_z3!(&ctx, "y");
+    slv_assert_z3!(&ctx, &slv, eq_z3!(&ctx, &x, int_z3!(&ctx, 3)));
+    slv_check_z3!(&ctx, &slv);
+
+    let model = slv_get_model_z3!(&ctx, &slv);
+    assert_eq!(ModelValueZ3::Int(3), model_eval_z3!(&ctx, &model, &x));
+    assert_eq!(ModelValueZ3::Other("y".to_string()), model_eval_z3!(&ctx, &model, &y, false));
+}
@@ -0,0 +1,112 @@
+use mini_sp_smt::*;
+
+#[test]
+fn test_new_func_decl_and_app(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let f = FuncDeclZ3::new(&ctx, "f", vec!(&intsort, &intsort), &boolsort);
+    let app = AppZ3::new(&ctx, &f, vec!(IntZ3::new(&ctx, &intsort, 1), IntVarZ3::new(&ctx, &intsort, "x")));
+
+    assert_eq!("(declare-fun f (Int Int) Bool)", format!("{}", f));
+    assert_eq!("(f 1 x)", ast_to_string_z3!(&ctx, &app));
+    assert_eq!("Bool", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, app)));
+}
+
+#[test]
+fn test_app_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let f = FuncDeclZ3::new(&ctx, "f", vec!(&intsort), &intsort);
+
+    assert!(AppZ3::try_new(&ctx, &f, vec!(BoolZ3::new(&ctx, true))).is_err());
+    assert!(AppZ3::try_new(&ctx, &f, Vec::<Ast>::new()).is_err());
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_func_interp_relation(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("ball1", "ball2"));
+    let rooms = EnumSortZ3::new(&ctx, "rooms", vec!("room_a", "room_b"));
+
+    let at = FuncDeclZ3::new(&ctx, "at", vec!(&balls, &rooms), &boolsort);
+    SlvAssertZ3::new(&ctx, &slv, AppZ3::new(&ctx, &at, vec!(&balls.enum_asts[0], &rooms.enum_asts[0])));
+    SlvAssertZ3::new(&ctx, &slv, NOTZ3::new(&ctx, AppZ3::new(&ctx, &at, vec!(&balls.enum_asts[0], &rooms.enum_asts[1]))));
+    SlvAssertZ3::new(&ctx, &slv, AppZ3::new(&ctx, &at, vec!(&balls.enum_asts[1], &rooms.enum_asts[1])));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let interp = ModelGetFuncInterpZ3::new(&ctx, &model, &at);
+
+    assert_eq!(Some(ModelValueZ3::Bool(true)), interp.value(vec!(&balls.enum_asts[0], &rooms.enum_asts[0])).unwrap());
+    assert_eq!(Some(ModelValueZ3::Bool(false)), interp.value(vec!(&balls.enum_asts[0], &rooms.enum_asts[1])).unwrap());
+    assert_eq!(Some(ModelValueZ3::Bool(true)), interp.value(vec!(&balls.enum_asts[1], &rooms.enum_asts[1])).unwrap());
+}
+
+#[test]
+fn test_func_interp_constant(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let c = FuncDeclZ3::new(&ctx, "c", Vec::<Sort>::new(), &intsort);
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, AppZ3::new(&ctx, &c, Vec::<Ast>::new()), IntZ3::new(&ctx, &intsort, 3)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let interp = ModelGetFuncInterpZ3::new(&ctx, &model, &c);
+
+    assert!(interp.entries.is_empty());
+    assert_eq!(Some(ModelValueZ3::Int(3)), interp.value(Vec::<Ast>::new()).unwrap());
+}
+
+#[test]
+fn test_func_interp_default_term(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let f = FuncDeclZ3::new(&ctx, "f", vec!(&intsort), &intsort);
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let succ = EQZ3::new(&ctx, AppZ3::new(&ctx, &f, vec!(&x)), ADDZ3::new(&ctx, vec!(&x, &IntZ3::new(&ctx, &intsort, 1))));
+    SlvAssertZ3::new(&ctx, &slv, ForallZ3::new(&ctx, vec!(&x), succ));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let interp = ModelGetFuncInterpZ3::new(&ctx, &model, &f);
+
+    assert!(interp.default.is_some());
+    assert_eq!(Some(ModelValueZ3::Int(6)), interp.value(vec!(IntZ3::new(&ctx, &intsort, 5))).unwrap());
+    assert_eq!(Some(ModelValueZ3::Int(-41)), interp.value(vec!(IntZ3::new(&ctx, &intsort, -42))).unwrap());
+}
+
+#[test]
+fn test_func_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let f = func_decl_z3!(&ctx, "f", vec!(&intsort), &intsort);
+    let x = int_var_z3!(&ctx, "x");
+    slv_assert_z3!(&ctx, &slv, eq_z3!(&ctx, app_z3!(&ctx, &f, int_z3!(&ctx, 1)), int_z3!(&ctx, 10)));
+    slv_assert_z3!(&ctx, &slv, eq_z3!(&ctx, app_z3!(&ctx, &f, &x), int_z3!(&ctx, 20)));
+    slv_assert_z3!(&ctx, &slv, eq_z3!(&ctx, &x, int_z3!(&ctx, 2)));
+    assert_eq!(SatResultZ3::Sat, slv_check_z3!(&ctx, &slv));
+
+    let model = slv_get_model_z3!(&ctx, &slv);
+    let interp = model_get_func_interp_z3!(&ctx, &model, &f);
+
+    assert_eq!(Some(ModelValueZ3::Int(10)), interp.value(vec!(int_z3!(&ctx, 1))).unwrap());
+    assert_eq!(Some(ModelValueZ3::Int(20)), interp.value(vec!(int_z3!(&ctx, 2))).unwrap());
+}
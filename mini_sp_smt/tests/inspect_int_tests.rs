@@ -0,0 +1,177 @@
+use mini_sp_smt::*;
+use z3_sys::*;
+
+// collects the names of the uninterpreted constants of a term
+struct ConstNames;
+
+impl <'ctx> AstFoldZ3<'ctx> for ConstNames {
+    type Result = Vec<String>;
+
+    fn numeral(&mut self, _ast: &Ast<'ctx>, _value: ModelValueZ3) -> Vec<String> {
+        vec!()
+    }
+
+    fn app(&mut self, _ast: &Ast<'ctx>, kind: Z3_decl_kind, name: &str, args: Vec<Vec<String>>) -> Vec<String> {
+        let mut names: Vec<String> = args.concat();
+        if kind == Z3_decl_kind_Z3_OP_UNINTERPRETED && !names.contains(&name.to_string()) {
+            names.push(name.to_string());
+        }
+        names
+    }
+
+    fn var(&mut self, _ast: &Ast<'ctx>, _index: u32) -> Vec<String> {
+        vec!()
+    }
+
+    fn quantifier(&mut self, _ast: &Ast<'ctx>, _kind: QuantifierKindZ3, _bound: Vec<String>, body: Vec<String>) -> Vec<String> {
+        body
+    }
+}
+
+// counts the folded nodes, the result is the depth of the term
+struct Depth {
+    nodes: u32
+}
+
+impl <'ctx> AstFoldZ3<'ctx> for Depth {
+    type Result = u32;
+
+    fn numeral(&mut self, _ast: &Ast<'ctx>, _value: ModelValueZ3) -> u32 {
+        self.nodes += 1;
+        1
+    }
+
+    fn app(&mut self, _ast: &Ast<'ctx>, _kind: Z3_decl_kind, _name: &str, args: Vec<u32>) -> u32 {
+        self.nodes += 1;
+        1 + args.into_iter().max().unwrap_or(0)
+    }
+
+    fn var(&mut self, _ast: &Ast<'ctx>, _index: u32) -> u32 {
+        self.nodes += 1;
+        1
+    }
+
+    fn quantifier(&mut self, _ast: &Ast<'ctx>, _kind: QuantifierKindZ3, _bound: Vec<String>, body: u32) -> u32 {
+        self.nodes += 1;
+        1 + body
+    }
+}
+
+#[test]
+fn test_simplify(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let sum = ADDZ3::new(&ctx, vec!(&x, &IntZ3::new(&ctx, &intsort, 1), &IntZ3::new(&ctx, &intsort, 2)));
+    assert_eq!("(+ 3 x)", AstToStringZ3::new(&ctx, SimplifyZ3::new(&ctx, &sum, vec!())));
+
+    let b = BoolVarZ3::new(&ctx, &boolsort, "b");
+    let c = BoolVarZ3::new(&ctx, &boolsort, "c");
+    let and = ANDZ3::new(&ctx, vec!(&b, &c));
+    assert_eq!("(and b c)", AstToStringZ3::new(&ctx, SimplifyZ3::new(&ctx, &and, vec!())));
+    assert_eq!("(not (or (not b) (not c)))", AstToStringZ3::new(&ctx, SimplifyZ3::new(&ctx, &and, vec!(("elim_and", "true")))));
+    assert!(SimplifyZ3::try_new(&ctx, &and, vec!(("elim_and", "1"))).is_err());
+    assert!(SimplifyZ3::try_new(&ctx, &and, vec!(("no_such_param", "true"))).is_err());
+}
+
+#[test]
+fn test_ast_decompose(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let b = BoolVarZ3::new(&ctx, &boolsort, "b");
+    let five = IntZ3::new(&ctx, &intsort, 5);
+    let gt = GTZ3::new(&ctx, &x, &five);
+
+    assert_eq!(AstNodeZ3::App {kind: Z3_decl_kind_Z3_OP_GT, name: ">".to_string(), args: vec!(x.clone(), five.clone())},
+        AstDecomposeZ3::new(&ctx, &gt));
+    assert_eq!(AstNodeZ3::App {kind: Z3_decl_kind_Z3_OP_UNINTERPRETED, name: "b".to_string(), args: vec!()},
+        AstDecomposeZ3::new(&ctx, &b));
+    assert_eq!(AstNodeZ3::Numeral(ModelValueZ3::Int(5)), AstDecomposeZ3::new(&ctx, &five));
+
+    let forall = ForallZ3::new(&ctx, vec!(&x), GEZ3::new(&ctx, MULZ3::new(&ctx, vec!(&x, &x)), IntZ3::new(&ctx, &intsort, 0)));
+    match AstDecomposeZ3::new(&ctx, &forall) {
+        AstNodeZ3::Quantifier {kind, bound, body} => {
+            assert_eq!(QuantifierKindZ3::Forall, kind);
+            assert_eq!(vec!("x"), bound);
+            assert_eq!("(>= (* (:var 0) (:var 0)) 0)", AstToStringZ3::new(&ctx, &body));
+        },
+        x => panic!("expected a quantifier, got {:?}", x)
+    };
+}
+
+#[test]
+fn test_fold_cnf(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let sort = BoolSortZ3::new(&ctx);
+
+    let x = BoolVarZ3::new(&ctx, &sort, "x");
+    let y = BoolVarZ3::new(&ctx, &sort, "y");
+    let z = BoolVarZ3::new(&ctx, &sort, "z");
+    let cnf = GetCnfVectorZ3::new(&ctx, vec!(IMPZ3::new(&ctx, &y, &x), XORZ3::new(&ctx, &x, &z)));
+
+    // every clause is a disjunction of literals or a single literal
+    for clause in &cnf {
+        let literals = match AstDecomposeZ3::new(&ctx, clause) {
+            AstNodeZ3::App {kind, args, ..} if kind == Z3_decl_kind_Z3_OP_OR => args,
+            _ => vec!(clause.clone())
+        };
+        for literal in literals {
+            match AstDecomposeZ3::new(&ctx, &literal) {
+                AstNodeZ3::App {kind, args, ..} if kind == Z3_decl_kind_Z3_OP_NOT =>
+                    assert!(matches!(AstDecomposeZ3::new(&ctx, &args[0]), AstNodeZ3::App {kind, ..} if kind == Z3_decl_kind_Z3_OP_UNINTERPRETED)),
+                node => assert!(matches!(node, AstNodeZ3::App {kind, ..} if kind == Z3_decl_kind_Z3_OP_UNINTERPRETED))
+            }
+        }
+    }
+
+    let mut names: Vec<String> = cnf.iter().flat_map(|c| FoldAstZ3::new(&ctx, c, &mut ConstNames)).collect();
+    names.sort();
+    names.dedup();
+    assert_eq!(vec!("x", "y", "z"), names);
+}
+
+#[test]
+fn test_fold_shared_and_deep(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    // x occurs 2^50 times, but there are only 51 different subterms
+    let mut shared = IntVarZ3::new(&ctx, &intsort, "x");
+    for _ in 0..50 {
+        shared = ADDZ3::new(&ctx, vec!(&shared, &shared));
+    }
+    let mut depth = Depth {nodes: 0};
+    assert_eq!(51, FoldAstZ3::new(&ctx, &shared, &mut depth));
+    assert_eq!(51, depth.nodes);
+
+    let mut deep = BoolVarZ3::new(&ctx, &boolsort, "b");
+    for _ in 0..100000 {
+        deep = NOTZ3::new(&ctx, &deep);
+    }
+    assert_eq!(100001, FoldAstZ3::new(&ctx, &deep, &mut Depth {nodes: 0}));
+}
+
+#[test]
+fn test_inspect_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+
+    let x = int_var_z3!(&ctx, "x");
+    let y = int_var_z3!(&ctx, "y");
+    let t = add_z3!(&ctx, mul_z3!(&ctx, int_z3!(&ctx, 0), &x), &y);
+
+    let s = simplify_z3!(&ctx, &t);
+    assert_eq!("y", ast_to_string_z3!(&ctx, &s));
+    assert_eq!(AstNodeZ3::App {kind: Z3_decl_kind_Z3_OP_UNINTERPRETED, name: "y".to_string(), args: vec!()}, ast_decompose_z3!(&ctx, &s));
+    assert_eq!(vec!("x", "y"), fold_ast_z3!(&ctx, &t, &mut ConstNames));
+}
@@ -61,7 +61,7 @@ fn test_new_not(){
 
     let not1 = NOTZ3::new(&ctx, bool1);
     let not2 = NOTZ3::new(&ctx, x1);
-    let not3 = NOTZ3::new(&ctx, not2);
+    let not3 = NOTZ3::new(&ctx, &not2);
 
     assert_eq!("(not true)", ast_to_string_z3!(&ctx, not1));
     assert_eq!("(not x1)", ast_to_string_z3!(&ctx, not2));
@@ -80,7 +80,7 @@ fn test_new_ite(){
     let int1 = IntZ3::new(&ctx, &intsort, 3);
     let int2 = IntZ3::new(&ctx, &intsort, 7);
 
-    let ite1 = ITEZ3::new(&ctx, bool1, int1, int2);
+    let ite1 = ITEZ3::new(&ctx, bool1, &int1, &int2);
     let ite2 = ITEZ3::new(&ctx, bool2, int1, int2);
     
     assert_eq!("(ite true 3 7)", ast_to_string_z3!(&ctx, ite1));
@@ -99,9 +99,9 @@ fn test_new_iff(){
     let x1 = BoolVarZ3::new(&ctx, &boolsort, "x1");
     let x2 = BoolVarZ3::new(&ctx, &boolsort, "x2");
 
-    let iff1 = IFFZ3::new(&ctx, bool1, bool2);
-    let iff2 = IFFZ3::new(&ctx, bool1, x1);
-    let iff3 = IFFZ3::new(&ctx, x2, bool2);
+    let iff1 = IFFZ3::new(&ctx, &bool1, &bool2);
+    let iff2 = IFFZ3::new(&ctx, bool1, &x1);
+    let iff3 = IFFZ3::new(&ctx, &x2, bool2);
     let iff4 = IFFZ3::new(&ctx, x1, x2);
     
     assert_eq!("(= true false)", ast_to_string_z3!(&ctx, iff1));
@@ -122,9 +122,9 @@ fn test_new_imp(){
     let x1 = BoolVarZ3::new(&ctx, &boolsort, "x1");
     let x2 = BoolVarZ3::new(&ctx, &boolsort, "x2");
 
-    let imp1 = IMPZ3::new(&ctx, bool1, bool2);
-    let imp2 = IMPZ3::new(&ctx, bool1, x1);
-    let imp3 = IMPZ3::new(&ctx, x2, bool2);
+    let imp1 = IMPZ3::new(&ctx, &bool1, &bool2);
+    let imp2 = IMPZ3::new(&ctx, bool1, &x1);
+    let imp3 = IMPZ3::new(&ctx, &x2, bool2);
     let imp4 = IMPZ3::new(&ctx, x1, x2);
     
     assert_eq!("(=> true false)", ast_to_string_z3!(&ctx, imp1));
@@ -145,9 +145,9 @@ fn test_new_xor(){
     let x1 = BoolVarZ3::new(&ctx, &boolsort, "x1");
     let x2 = BoolVarZ3::new(&ctx, &boolsort, "x2");
 
-    let xor1 = XORZ3::new(&ctx, bool1, bool2);
-    let xor2 = XORZ3::new(&ctx, bool1, x1);
-    let xor3 = XORZ3::new(&ctx, x2, bool2);
+    let xor1 = XORZ3::new(&ctx, &bool1, &bool2);
+    let xor2 = XORZ3::new(&ctx, bool1, &x1);
+    let xor3 = XORZ3::new(&ctx, &x2, bool2);
     let xor4 = XORZ3::new(&ctx, x1, x2);
     
     assert_eq!("(xor true false)", ast_to_string_z3!(&ctx, xor1));
@@ -245,7 +245,7 @@ fn test_distinct_macro_3(){
     slv_assert_z3!(&ctx, &slv, dist1);
     slv_check_z3!(&ctx, &slv);
     let model = slv_get_model_z3!(&ctx, &slv);
-    println!("{}", model_to_string_z3!(&ctx, model));
+    println!("{}", model_to_string_z3!(&ctx, &model));
     assert_eq!("z -> (- 3)
 y -> (- 4)
 x -> (- 5)
@@ -306,4 +306,103 @@ fn test_xor_macro_1(){
         bool_var_z3!(&ctx, "y")
     );
     assert_eq!("(xor x y)", ast_to_string_z3!(&ctx, xor1));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_forall_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let intsort = IntSortZ3::new(&ctx);
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let one = IntZ3::new(&ctx, &intsort, 1);
+    let forall1 = ForallZ3::new(&ctx, vec!(&x), GTZ3::new(&ctx, ADDZ3::new(&ctx, vec!(&x, &one)), &x));
+    assert_eq!("(forall ((x Int)) (> (+ x 1) x))", ast_to_string_z3!(&ctx, forall1));
+}
+
+#[test]
+fn test_exists_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let intsort = IntSortZ3::new(&ctx);
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let y = IntVarZ3::new(&ctx, &intsort, "y");
+    let exists1 = ExistsZ3::new(&ctx, vec!(&x, &y), EQZ3::new(&ctx, &x, &y));
+    assert_eq!("(exists ((x Int) (y Int)) (= x y))", ast_to_string_z3!(&ctx, exists1));
+}
+
+#[test]
+fn test_forall_with_patterns_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let intsort = IntSortZ3::new(&ctx);
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let two = IntZ3::new(&ctx, &intsort, 2);
+    let pattern = PatternZ3::new(&ctx, vec!(MULZ3::new(&ctx, vec!(&two, &x))));
+    let forall1 = ForallZ3::new_with_patterns(&ctx, vec!(&x), vec!(pattern), 
+        GEZ3::new(&ctx, MULZ3::new(&ctx, vec!(&two, &x)), &x));
+    assert_eq!("(forall ((x Int)) (! (>= (* 2 x) x) :pattern ((* 2 x))))", ast_to_string_z3!(&ctx, forall1));
+}
+
+#[test]
+fn test_forall_sat_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+    let x = int_var_z3!(&ctx, "x");
+    let y = int_var_z3!(&ctx, "y");
+    slv_assert_z3!(&ctx, &slv, forall_z3!(&ctx, vec!(&x), ge_z3!(&ctx, mul_z3!(&ctx, &x, &x), &y)));
+    assert_eq!(SatResultZ3::Sat, slv_check_z3!(&ctx, &slv));
+}
+
+#[test]
+fn test_forall_unsat_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+    let x = int_var_z3!(&ctx, "x");
+    slv_assert_z3!(&ctx, &slv, forall_z3!(&ctx, vec!(&x), gt_z3!(&ctx, &x, int_z3!(&ctx, 0))));
+    assert_eq!(SatResultZ3::Unsat, slv_check_z3!(&ctx, &slv));
+}
+
+#[test]
+fn test_forall_unknown_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+    let x = int_var_z3!(&ctx, "x");
+    let y = int_var_z3!(&ctx, "y");
+    let z = int_var_z3!(&ctx, "z");
+    let positive = and_z3!(&ctx, 
+        gt_z3!(&ctx, &x, int_z3!(&ctx, 0)), 
+        gt_z3!(&ctx, &y, int_z3!(&ctx, 0)), 
+        gt_z3!(&ctx, &z, int_z3!(&ctx, 0)));
+    let cubes = eq_z3!(&ctx, 
+        add_z3!(&ctx, mul_z3!(&ctx, &x, &x, &x), mul_z3!(&ctx, &y, &y, &y)), 
+        mul_z3!(&ctx, &z, &z, &z));
+    slv_assert_z3!(&ctx, &slv, forall_z3!(&ctx, vec!(&x, &y, &z), imp_z3!(&ctx, positive, not_z3!(&ctx, cubes))));
+    slv_set_rlimit_z3!(&ctx, &slv, 1000);
+    assert_eq!(SatResultZ3::Unknown("max. resource limit exceeded".to_string()), slv_check_z3!(&ctx, &slv));
+}
+
+#[test]
+fn test_exists_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+    let x = int_var_z3!(&ctx, "x");
+    let exists1 = exists_z3!(&ctx, vec!(&x), 
+        and_z3!(&ctx, gt_z3!(&ctx, &x, int_z3!(&ctx, 3)), lt_z3!(&ctx, &x, int_z3!(&ctx, 5))));
+    slv_assert_z3!(&ctx, &slv, &exists1);
+    assert_eq!(SatResultZ3::Sat, slv_check_z3!(&ctx, &slv));
+    assert_eq!("(exists ((x Int)) (and (> x 3) (< x 5)))", ast_to_string_z3!(&ctx, exists1));
+}
+
+#[test]
+fn test_forall_macro_with_patterns_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let x = int_var_z3!(&ctx, "x");
+    let forall1 = forall_z3!(&ctx, vec!(&x), vec!(pattern_z3!(&ctx, add_z3!(&ctx, &x, int_z3!(&ctx, 1)))), 
+        gt_z3!(&ctx, add_z3!(&ctx, &x, int_z3!(&ctx, 1)), &x));
+    assert_eq!("(forall ((x Int)) (! (> (+ x 1) x) :pattern ((+ x 1))))", ast_to_string_z3!(&ctx, forall1));
+}
@@ -44,10 +44,10 @@ fn test_new_div(){
     let x1 = IntVarZ3::new(&ctx, &intsort, "x1");
     let x3 = RealVarZ3::new(&ctx, &realsort, "x3");
 
-    let div1 = DIVZ3::new(&ctx, int1, int2);
-    let div2 = DIVZ3::new(&ctx, int1, real2);
+    let div1 = DIVZ3::new(&ctx, &int1, int2);
+    let div2 = DIVZ3::new(&ctx, &int1, &real2);
     let div3 = DIVZ3::new(&ctx, x1, real2);
-    let div4 = DIVZ3::new(&ctx, x3, int1);
+    let div4 = DIVZ3::new(&ctx, &x3, int1);
     let div5 = DIVZ3::new(&ctx, x3, real1);
 
     assert_eq!("(div 7 (- 1012))", ast_to_string_z3!(&ctx, div1));
@@ -69,8 +69,8 @@ fn test_new_mod(){
     let x1 = IntVarZ3::new(&ctx, &intsort, "x1");
     let x2 = IntVarZ3::new(&ctx, &intsort, "x2");
 
-    let mod1 = MODZ3::new(&ctx, int1, int2);
-    let mod2 = MODZ3::new(&ctx, x1, x2);
+    let mod1 = MODZ3::new(&ctx, &int1, &int2);
+    let mod2 = MODZ3::new(&ctx, &x1, &x2);
     let mod3 = MODZ3::new(&ctx, x1, int1);
     let mod4 = MODZ3::new(&ctx, int2, x2);
 
@@ -92,8 +92,8 @@ fn test_new_rem(){
     let x1 = IntVarZ3::new(&ctx, &intsort, "x1");
     let x2 = IntVarZ3::new(&ctx, &intsort, "x2");
 
-    let rem1 = REMZ3::new(&ctx, int1, int2);
-    let rem2 = REMZ3::new(&ctx, x1, x2);
+    let rem1 = REMZ3::new(&ctx, &int1, &int2);
+    let rem2 = REMZ3::new(&ctx, &x1, &x2);
     let rem3 = REMZ3::new(&ctx, x1, int1);
     let rem4 = REMZ3::new(&ctx, int2, x2);
 
@@ -195,8 +195,8 @@ fn test_new_pow(){
     let x1 = IntVarZ3::new(&ctx, &intsort, "x1");
     let x2 = IntVarZ3::new(&ctx, &intsort, "x2");
 
-    let pow1 = POWZ3::new(&ctx, int1, int2);
-    let pow2 = POWZ3::new(&ctx, x1, x2);
+    let pow1 = POWZ3::new(&ctx, &int1, &int2);
+    let pow2 = POWZ3::new(&ctx, &x1, &x2);
     let pow3 = POWZ3::new(&ctx, x1, int1);
     let pow4 = POWZ3::new(&ctx, int2, x2);
 
@@ -32,6 +32,17 @@ fn test_new_oassert(){
     assert_eq!("", model_to_string_z3!(&ctx, model));
 }
 
+#[test]
+fn test_oassert_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+
+    let intsort = IntSortZ3::new(&ctx);
+    assert!(OptAssertZ3::try_new(&ctx, &opt, IntZ3::new(&ctx, &intsort, 1)).is_err());
+    assert_eq!(Ok("(check-sat)\n".to_string()), OptGetStringZ3::try_new(&ctx, &opt));
+}
+
 #[test]
 fn test_new_ocheck(){
     let conf = ConfigZ3::new();
@@ -54,9 +65,9 @@ fn test_new_ocheck(){
 (assert (= y (- (/ 271549371.0 500000.0))))
 (check-sat)\n", opt_to_string_z3!(&ctx, &opt));
 
-    let res1 = OptCheckZ3::new(&ctx, &opt, vec!());
+    let res1 = OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
     println!("This is the return of the check:");
-    println!("{}", res1);
+    println!("{:?}", res1);
 
     println!("This is the opt context with an assertion after the check:");
     assert_eq!("(declare-fun y () Real)
@@ -78,11 +89,11 @@ fn test_new_maximize(){
     let x = IntVarZ3::new(&ctx, &intsort, "x");
     let int1 = IntZ3::new(&ctx, &intsort, 100);
 
-    let lt1 = LTZ3::new(&ctx, x, int1);
+    let lt1 = LTZ3::new(&ctx, &x, int1);
 
     OptAssertZ3::new(&ctx, &opt, lt1);
     OptMaximizeZ3::new(&ctx, &opt, x);
-    OptCheckZ3::new(&ctx, &opt, vec!());
+    OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
 
     let model = opt_get_model_z3!(&ctx, &opt);
     assert_eq!("x -> 99\n", model_to_string_z3!(&ctx, model));
@@ -98,16 +109,30 @@ fn test_new_minimize(){
     let x = IntVarZ3::new(&ctx, &intsort, "x");
     let int1 = IntZ3::new(&ctx, &intsort, 11);
 
-    let gt1 = GTZ3::new(&ctx, x, int1);
+    let gt1 = GTZ3::new(&ctx, &x, int1);
 
     OptAssertZ3::new(&ctx, &opt, gt1);
     OptMinimizeZ3::new(&ctx, &opt, x);
-    OptCheckZ3::new(&ctx, &opt, vec!());
+    OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
 
     let model = opt_get_model_z3!(&ctx, &opt);
     assert_eq!("x -> 12\n", model_to_string_z3!(&ctx, model));
 }
 
+#[test]
+fn test_maximize_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+
+    let boolsort = BoolSortZ3::new(&ctx);
+    let b = BoolVarZ3::new(&ctx, &boolsort, "b");
+
+    assert!(OptMaximizeZ3::try_new(&ctx, &opt, &b).is_err());
+    assert!(OptMinimizeZ3::try_new(&ctx, &opt, &b).is_err());
+    assert!(opt.objectives.borrow().is_empty());
+}
+
 #[test]
 fn test_opt_macro_1(){
     let cfg = cfg_z3!();
@@ -152,7 +177,7 @@ fn test_ocheck_macro_1(){
 (check-sat)\n", opt_to_string_z3!(&ctx, &opt));
 
     let res1 = opt_check_z3!(&ctx, &opt, );
-    println!("{}", res1);
+    println!("{:?}", res1);
 }
 
 #[test]
@@ -175,7 +200,7 @@ fn test_omax_macro_1(){
 (check-sat)\n", opt_to_string_z3!(&ctx, &opt));
 
     let res1 = opt_check_z3!(&ctx, &opt, );
-    println!("{}", res1);
+    println!("{:?}", res1);
 
     let model = opt_get_model_z3!(&ctx, &opt);
     assert_eq!("y -> 99.0\n", model_to_string_z3!(&ctx, model));
@@ -201,8 +226,231 @@ fn test_omin_macro_1(){
 (check-sat)\n", opt_to_string_z3!(&ctx, &opt));
 
     let res1 = opt_check_z3!(&ctx, &opt, );
-    println!("{}", res1);
+    println!("{:?}", res1);
 
     let model = opt_get_model_z3!(&ctx, &opt);
     assert_eq!("y -> 12.0\n", model_to_string_z3!(&ctx, model));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_opt_from_smtlib2_1(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    OptFromSmtLib2Z3::new(&ctx, &opt, "(assert (< x 10)) (assert (> x 2))", 
+        Vec::<Sort>::new(), vec!(GetAppDeclZ3::new(&ctx, &x)));
+    OptMaximizeZ3::new(&ctx, &opt, &x);
+    assert_eq!(SatResultZ3::Sat, OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new()));
+
+    let model = OptGetModelZ3::new(&ctx, &opt);
+    assert_eq!("x -> 9\n", model_to_string_z3!(&ctx, model));
+    assert!(OptFromSmtLib2Z3::try_new(&ctx, &opt, "(assert (< z 10))", Vec::<Sort>::new(), Vec::<FuncDecl>::new()).is_err());
+}
+
+#[test]
+fn test_opt_from_smtlib2_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let opt = opt_z3!(&ctx);
+
+    let y = int_var_z3!(&ctx, "y");
+    opt_from_smtlib2_z3!(&ctx, &opt, "(assert (> y 11))", Vec::<Sort>::new(), vec!(get_app_decl_z3!(&ctx, &y)));
+    opt_minimize_z3!(&ctx, &opt, &y);
+    opt_check_z3!(&ctx, &opt, );
+
+    let model = opt_get_model_z3!(&ctx, &opt);
+    assert_eq!("y -> 12\n", model_to_string_z3!(&ctx, model));
+}
+
+#[test]
+fn test_opt_assert_soft(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    // a path from room a to room c goes through room b or room d
+    let via_b = BoolVarZ3::new(&ctx, &boolsort, "via_b");
+    let via_d = BoolVarZ3::new(&ctx, &boolsort, "via_d");
+    OptAssertZ3::new(&ctx, &opt, ORZ3::new(&ctx, vec!(&via_b, &via_d)));
+
+    assert_eq!(0, OptAssertSoftZ3::new(&ctx, &opt, NOTZ3::new(&ctx, &via_b), 5, "rooms"));
+    assert_eq!(0, OptAssertSoftZ3::new(&ctx, &opt, NOTZ3::new(&ctx, &via_d), 2, "rooms"));
+
+    // prefer the left gripper, but it is broken
+    let left = BoolVarZ3::new(&ctx, &boolsort, "left");
+    OptAssertZ3::new(&ctx, &opt, NOTZ3::new(&ctx, &left));
+    assert_eq!(1, OptAssertSoftZ3::new(&ctx, &opt, &left, 1, "grippers"));
+
+    assert_eq!(SatResultZ3::Sat, OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new()));
+
+    let violated = OptGetViolatedSoftZ3::new(&ctx, &opt);
+    assert_eq!(vec!("(not via_d)", "left"), violated.iter().map(|x| x.cst.to_string()).collect::<Vec<String>>());
+    assert_eq!(vec!("rooms", "grippers"), violated.iter().map(|x| x.group.as_str()).collect::<Vec<&str>>());
+    assert_eq!(3, OptGetPenaltyZ3::new(&ctx, &opt));
+}
+
+#[test]
+fn test_opt_assert_soft_none_violated(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    OptAssertSoftZ3::new(&ctx, &opt, GTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 3)), 4, "");
+    OptAssertSoftZ3::new(&ctx, &opt, LTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 5)), 4, "");
+    OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
+
+    assert!(OptGetViolatedSoftZ3::new(&ctx, &opt).is_empty());
+    assert_eq!(0, OptGetPenaltyZ3::new(&ctx, &opt));
+    assert_eq!("x -> 4\n", model_to_string_z3!(&ctx, opt_get_model_z3!(&ctx, &opt)));
+}
+
+#[test]
+fn test_opt_assert_soft_errors(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let x = BoolVarZ3::new(&ctx, &boolsort, "x");
+    let y = BoolVarZ3::new(&ctx, &boolsort, "y");
+    let err = OptAssertSoftZ3::try_new(&ctx, &opt, &x, 1, "gro\0up").unwrap_err();
+    assert_eq!(z3_sys::Z3_error_code_Z3_INVALID_ARG, err.code);
+    assert_eq!("The group name contains a nul byte.", err.msg);
+
+    // both heavy soft constraints are violated, their penalty overflows
+    OptAssertZ3::new(&ctx, &opt, NOTZ3::new(&ctx, &x));
+    OptAssertZ3::new(&ctx, &opt, NOTZ3::new(&ctx, &y));
+    OptAssertSoftZ3::new(&ctx, &opt, &x, u32::MAX, "heavy");
+    OptAssertSoftZ3::new(&ctx, &opt, &y, u32::MAX, "heavy");
+    OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
+
+    assert_eq!(2, OptGetViolatedSoftZ3::new(&ctx, &opt).len());
+    assert!(OptGetPenaltyZ3::try_new(&ctx, &opt).is_err());
+}
+
+#[test]
+fn test_opt_assert_soft_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let opt = opt_z3!(&ctx);
+
+    let x = bool_var_z3!(&ctx, "x");
+    opt_assert_z3!(&ctx, &opt, not_z3!(&ctx, &x));
+    opt_assert_soft_z3!(&ctx, &opt, &x, 7);
+    opt_assert_soft_z3!(&ctx, &opt, not_z3!(&ctx, &x), 1, "other");
+    opt_check_z3!(&ctx, &opt, Vec::<Ast>::new());
+
+    assert_eq!(1, opt_get_violated_soft_z3!(&ctx, &opt).len());
+    assert_eq!(7, opt_get_penalty_z3!(&ctx, &opt));
+}
+
+#[test]
+fn test_opt_priority_lex_and_box(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+
+    // shorter plans cost more energy
+    let len = IntVarZ3::new(&ctx, &intsort, "len");
+    let energy = IntVarZ3::new(&ctx, &intsort, "energy");
+    let constraints = vec!(
+        GEZ3::new(&ctx, &len, IntZ3::new(&ctx, &intsort, 2)),
+        LEZ3::new(&ctx, &len, IntZ3::new(&ctx, &intsort, 8)),
+        GEZ3::new(&ctx, ADDZ3::new(&ctx, vec!(&len, &energy)), IntZ3::new(&ctx, &intsort, 10))
+    );
+
+    let lex = OptimizerZ3::new(&ctx);
+    for cst in &constraints {
+        OptAssertZ3::new(&ctx, &lex, cst);
+    }
+    OptSetPriorityZ3::new(&ctx, &lex, OptPriorityZ3::Lex);
+    assert_eq!(0, OptMinimizeZ3::new(&ctx, &lex, &len));
+    assert_eq!(1, OptMinimizeZ3::new(&ctx, &lex, &energy));
+    assert_eq!(SatResultZ3::Sat, OptCheckZ3::new(&ctx, &lex, Vec::<Ast>::new()));
+    assert_eq!((ModelValueZ3::Int(2), ModelValueZ3::Int(2)), (OptGetLowerZ3::new(&ctx, &lex, 0), OptGetUpperZ3::new(&ctx, &lex, 0)));
+    assert_eq!(ModelValueZ3::Int(2), OptGetObjectiveValueZ3::new(&ctx, &lex, 0));
+    assert_eq!(ModelValueZ3::Int(8), OptGetObjectiveValueZ3::new(&ctx, &lex, 1));
+
+    let boxed = OptimizerZ3::new(&ctx);
+    for cst in &constraints {
+        OptAssertZ3::new(&ctx, &boxed, cst);
+    }
+    OptSetPriorityZ3::new(&ctx, &boxed, OptPriorityZ3::Box);
+    OptMinimizeZ3::new(&ctx, &boxed, &len);
+    OptMinimizeZ3::new(&ctx, &boxed, &energy);
+    assert_eq!(SatResultZ3::Sat, OptCheckZ3::new(&ctx, &boxed, Vec::<Ast>::new()));
+    assert_eq!(ModelValueZ3::Int(2), OptGetLowerZ3::new(&ctx, &boxed, 0));
+    assert_eq!(ModelValueZ3::Int(2), OptGetLowerZ3::new(&ctx, &boxed, 1));
+}
+
+#[test]
+fn test_opt_pareto(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let len = IntVarZ3::new(&ctx, &intsort, "len");
+    let energy = IntVarZ3::new(&ctx, &intsort, "energy");
+    OptAssertZ3::new(&ctx, &opt, GEZ3::new(&ctx, &len, IntZ3::new(&ctx, &intsort, 2)));
+    OptAssertZ3::new(&ctx, &opt, LEZ3::new(&ctx, &len, IntZ3::new(&ctx, &intsort, 5)));
+    OptAssertZ3::new(&ctx, &opt, GEZ3::new(&ctx, ADDZ3::new(&ctx, vec!(&len, &energy)), IntZ3::new(&ctx, &intsort, 10)));
+    OptMinimizeZ3::new(&ctx, &opt, &len);
+    OptMinimizeZ3::new(&ctx, &opt, &energy);
+
+    let mut front: Vec<Vec<ModelValueZ3>> = OptParetoZ3::new(&ctx, &opt).map(|x| x.unwrap().values).collect();
+    front.sort();
+    let point = |l, e| vec!(ModelValueZ3::Int(l), ModelValueZ3::Int(e));
+    assert_eq!(vec!(point(2, 8), point(3, 7), point(4, 6), point(5, 5)), front);
+
+    OptAssertZ3::new(&ctx, &opt, LEZ3::new(&ctx, &len, IntZ3::new(&ctx, &intsort, 1)));
+    assert_eq!(0, OptParetoZ3::new(&ctx, &opt).count());
+}
+
+#[test]
+fn test_opt_unbounded_and_bounded(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let realsort = RealSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let max_x = OptMaximizeZ3::new(&ctx, &opt, &x);
+    OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
+    assert_eq!(ModelValueZ3::Other("oo".to_string()), OptGetUpperZ3::new(&ctx, &opt, max_x));
+    assert!(OptGetLowerZ3::try_new(&ctx, &opt, 7).is_err());
+
+    let bounded = OptimizerZ3::new(&ctx);
+    let y = RealVarZ3::new(&ctx, &realsort, "y");
+    OptAssertZ3::new(&ctx, &bounded, LEZ3::new(&ctx, &y, RealZ3::new(&ctx, &realsort, 2.5)));
+    let max_y = OptMaximizeZ3::new(&ctx, &bounded, &y);
+    OptCheckZ3::new(&ctx, &bounded, Vec::<Ast>::new());
+    assert_eq!(ModelValueZ3::Rational(5, 2), OptGetUpperZ3::new(&ctx, &bounded, max_y));
+    assert_eq!(ModelValueZ3::Rational(5, 2), OptGetObjectiveValueZ3::new(&ctx, &bounded, max_y));
+}
+
+#[test]
+fn test_opt_objectives_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let opt = opt_z3!(&ctx);
+
+    let x = int_var_z3!(&ctx, "x");
+    opt_assert_z3!(&ctx, &opt, le_z3!(&ctx, &x, int_z3!(&ctx, 3)));
+    opt_set_priority_z3!(&ctx, &opt, OptPriorityZ3::Pareto);
+    let idx = opt_maximize_z3!(&ctx, &opt, &x);
+
+    let points: Vec<ParetoPointZ3> = opt_pareto_z3!(&ctx, &opt).collect::<Result<_, _>>().unwrap();
+    assert_eq!(1, points.len());
+    assert_eq!(vec!(ModelValueZ3::Int(3)), points[0].values);
+    assert_eq!(ModelValueZ3::Int(3), opt_get_lower_z3!(&ctx, &opt, idx));
+    assert_eq!(ModelValueZ3::Int(3), opt_get_upper_z3!(&ctx, &opt, idx));
+    assert_eq!(ModelValueZ3::Int(3), opt_get_objective_value_z3!(&ctx, &opt, idx));
+}
@@ -12,8 +12,8 @@ fn test_new_eq(){
     let int1 = IntZ3::new(&ctx, &intsort, 7);
     let real1 = RealZ3::new(&ctx, &realsort, -543.098742);
 
-    let rel1 = EQZ3::new(&ctx, x, int1);
-    let rel2 = EQZ3::new(&ctx, y, real1);
+    let rel1 = EQZ3::new(&ctx, &x, &int1);
+    let rel2 = EQZ3::new(&ctx, &y, &real1);
     let rel3 = EQZ3::new(&ctx, y, x);
     let rel4 = EQZ3::new(&ctx, int1, real1);
 
@@ -23,6 +23,21 @@ fn test_new_eq(){
     assert_eq!("(= (to_real 7) (- (/ 271549371.0 500000.0)))", ast_to_string_z3!(&ctx, rel4));
 }
 
+#[test]
+fn test_eq_sort_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let boolsort = BoolSortZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = BoolVarZ3::new(&ctx, &boolsort, "x");
+    let int1 = IntZ3::new(&ctx, &intsort, 7);
+
+    let err = EQZ3::try_new(&ctx, x, int1).unwrap_err();
+    assert_eq!("Sorts Bool and Int are incompatible", err.msg);
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
 #[test]
 fn test_new_le(){
     let conf = ConfigZ3::new();
@@ -35,8 +50,8 @@ fn test_new_le(){
     let int1 = IntZ3::new(&ctx, &intsort, 7);
     let real1 = RealZ3::new(&ctx, &realsort, -543.098742);
 
-    let rel1 = LEZ3::new(&ctx, x, int1);
-    let rel2 = LEZ3::new(&ctx, y, real1);
+    let rel1 = LEZ3::new(&ctx, &x, &int1);
+    let rel2 = LEZ3::new(&ctx, &y, &real1);
     let rel3 = LEZ3::new(&ctx, y, x);
     let rel4 = LEZ3::new(&ctx, int1, real1);
 
@@ -58,8 +73,8 @@ fn test_new_lt(){
     let int1 = IntZ3::new(&ctx, &intsort, 7);
     let real1 = RealZ3::new(&ctx, &realsort, -543.098742);
 
-    let rel1 = LTZ3::new(&ctx, x, int1);
-    let rel2 = LTZ3::new(&ctx, y, real1);
+    let rel1 = LTZ3::new(&ctx, &x, &int1);
+    let rel2 = LTZ3::new(&ctx, &y, &real1);
     let rel3 = LTZ3::new(&ctx, y, x);
     let rel4 = LTZ3::new(&ctx, int1, real1);
 
@@ -81,8 +96,8 @@ fn test_new_ge(){
     let int1 = IntZ3::new(&ctx, &intsort, 7);
     let real1 = RealZ3::new(&ctx, &realsort, -543.098742);
 
-    let rel1 = GEZ3::new(&ctx, x, int1);
-    let rel2 = GEZ3::new(&ctx, y, real1);
+    let rel1 = GEZ3::new(&ctx, &x, &int1);
+    let rel2 = GEZ3::new(&ctx, &y, &real1);
     let rel3 = GEZ3::new(&ctx, y, x);
     let rel4 = GEZ3::new(&ctx, int1, real1);
 
@@ -104,8 +119,8 @@ fn test_new_gt(){
     let int1 = IntZ3::new(&ctx, &intsort, 7);
     let real1 = RealZ3::new(&ctx, &realsort, -543.098742);
 
-    let rel1 = GTZ3::new(&ctx, x, int1);
-    let rel2 = GTZ3::new(&ctx, y, real1);
+    let rel1 = GTZ3::new(&ctx, &x, &int1);
+    let rel2 = GTZ3::new(&ctx, &y, &real1);
     let rel3 = GTZ3::new(&ctx, y, x);
     let rel4 = GTZ3::new(&ctx, int1, real1);
 
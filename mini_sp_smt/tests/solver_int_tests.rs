@@ -64,7 +64,7 @@ fn test_new_scheck(){
 
     let res1 = SlvCheckZ3::new(&ctx, &solv);
 
-    assert_eq!(1, res1);
+    assert_eq!(SatResultZ3::Sat, res1);
 
     assert_eq!("(declare-fun y () Real)
 (assert (= y (- (/ 271549371.0 500000.0))))
@@ -76,6 +76,23 @@ y -> (- (/ 271549371.0 500000.0))
     assert_eq!("y -> (- (/ 271549371.0 500000.0))\n", model_to_string_z3!(&ctx, model));
 }
 
+#[test]
+fn test_get_reason_unknown(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let solv = SolverZ3::new(&ctx);
+
+    let intsort = IntSortZ3::new(&ctx);
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let int1 = IntZ3::new(&ctx, &intsort, 7);
+
+    SlvAssertZ3::new(&ctx, &solv, EQZ3::new(&ctx, x, int1));
+
+    assert_eq!(SatResultZ3::Sat, slv_check_z3!(&ctx, &solv));
+    // no reason is given when the check is not unknown
+    assert_eq!("", slv_get_reason_unknown_z3!(&ctx, &solv));
+}
+
 #[test]
 fn test_new_solver_get_param_descr(){
     let conf = ConfigZ3::new();
@@ -97,7 +114,7 @@ fn get_proof_test() {
     let three = IntZ3::new(&ctx, &sort, 3);
     let two = IntZ3::new(&ctx, &sort, 2);
 
-    SlvAssertZ3::new(&ctx, &slv, ANDZ3::new(&ctx, vec!(GTZ3::new(&ctx, x, three), LTZ3::new(&ctx, x, two))));
+    SlvAssertZ3::new(&ctx, &slv, ANDZ3::new(&ctx, vec!(GTZ3::new(&ctx, &x, three), LTZ3::new(&ctx, x, two))));
 
     SlvCheckZ3::new(&ctx, &slv);
     let proof = SlvGetProofZ3::new(&ctx, &slv);
@@ -119,6 +136,77 @@ fn get_proof_test() {
 
 }
 
+#[test]
+fn test_slv_scope_pops_on_drop() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+    let x = BoolVarZ3::new(&ctx, &boolsort, "x");
+
+    SlvAssertZ3::new(&ctx, &slv, &x);
+    {
+        let scope = slv.push();
+        assert_eq!(1, scope.level);
+        SlvAssertZ3::new(&ctx, &slv, NOTZ3::new(&ctx, &x));
+        assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+    }
+    assert_eq!(0, SlvGetPopPointsZ3::new(&ctx, &slv));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_slv_scope_commit_and_discard() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+    let x = BoolVarZ3::new(&ctx, &boolsort, "x");
+
+    let outer = slv.push();
+    let inner = slv.push();
+    assert_eq!(2, inner.level);
+    SlvAssertZ3::new(&ctx, &slv, &x);
+    inner.commit();
+    assert_eq!(2, SlvGetPopPointsZ3::new(&ctx, &slv));
+
+    // the outer scope also removes the committed inner one
+    SlvAssertZ3::new(&ctx, &slv, NOTZ3::new(&ctx, &x));
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+    outer.discard();
+    assert_eq!(0, SlvGetPopPointsZ3::new(&ctx, &slv));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_slv_scope_stale() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+    let x = BoolVarZ3::new(&ctx, &boolsort, "x");
+
+    // the outer scope also pops the inner ones
+    let outer = slv.push();
+    let inner = slv.push();
+    let dropped = slv.push();
+    outer.discard();
+    assert_eq!(0, SlvGetPopPointsZ3::new(&ctx, &slv));
+
+    // the stale guards don't pop the newer scopes at their levels
+    let newer = slv.push();
+    let newest = slv.push();
+    SlvAssertZ3::new(&ctx, &slv, &x);
+    assert!(inner.try_discard().is_err());
+    drop(dropped);
+    assert_eq!(2, SlvGetPopPointsZ3::new(&ctx, &slv));
+    assert_eq!(1, Z3AstVectorToVectorAstZ3::new(&ctx, SlvGetAssertsZ3::new(&ctx, &slv)).len());
+
+    newest.discard();
+    newer.discard();
+    assert_eq!(0, SlvGetPopPointsZ3::new(&ctx, &slv));
+}
+
 #[test]
 fn get_unsat_core_test() {
     let cfg = ConfigZ3::new();
@@ -133,8 +221,8 @@ fn get_unsat_core_test() {
     let two = IntZ3::new(&ctx, &sort, 2);
     let one = IntZ3::new(&ctx, &sort, 1);
 
-    SlvAssertAndTrackZ3::new(&ctx, &slv, GTZ3::new(&ctx, x, three), "a1");
-    SlvAssertAndTrackZ3::new(&ctx, &slv, LTZ3::new(&ctx, x, two), "a2");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, GTZ3::new(&ctx, &x, three), "a1");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LTZ3::new(&ctx, &x, two), "a2");
     SlvAssertAndTrackZ3::new(&ctx, &slv, EQZ3::new(&ctx, x, one), "a3");
 
     SlvCheckZ3::new(&ctx, &slv);
@@ -144,6 +232,227 @@ fn get_unsat_core_test() {
   a2)", slv_unsat_core_to_string_z3!(&ctx, unsat_core));
 }
 
+#[test]
+fn test_get_unsat_core_names() {
+    let cfg = ConfigZ3::new();
+    SetParamZ3::new(&cfg, "unsat_core", "true");
+    let ctx = ContextZ3::new(&cfg);
+    let slv = SolverZ3::new(&ctx);
+
+    let sort = IntSortZ3::new(&ctx);
+    let x = IntVarZ3::new(&ctx, &sort, "x");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, GTZ3::new(&ctx, &x, IntZ3::new(&ctx, &sort, 3)), "goal_1");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LTZ3::new(&ctx, &x, IntZ3::new(&ctx, &sort, 2)), "spec_1");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LTZ3::new(&ctx, &x, IntZ3::new(&ctx, &sort, 10)), "spec_2");
+
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+    let mut core = SlvGetUnsatCoreNamesZ3::new(&ctx, &slv);
+    core.sort();
+    assert_eq!(vec!("goal_1", "spec_1"), core);
+}
+
+#[test]
+fn test_get_unsat_core_names_minimized() {
+    let cfg = ConfigZ3::new();
+    SetParamZ3::new(&cfg, "unsat_core", "true");
+    let ctx = ContextZ3::new(&cfg);
+    let slv = SolverZ3::new(&ctx);
+    SlvSetCoreMinimizeZ3::new(&ctx, &slv, true);
+
+    let sort = IntSortZ3::new(&ctx);
+    let x = IntVarZ3::new(&ctx, &sort, "x");
+    let y = IntVarZ3::new(&ctx, &sort, "y");
+    let z = IntVarZ3::new(&ctx, &sort, "z");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LEZ3::new(&ctx, &x, IntZ3::new(&ctx, &sort, 4)), "t1");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LEZ3::new(&ctx, &y, IntZ3::new(&ctx, &sort, 6)), "t2");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LEZ3::new(&ctx, &z, IntZ3::new(&ctx, &sort, 6)), "t3");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, GEZ3::new(&ctx, ADDZ3::new(&ctx, vec!(&x, &y, &z)), IntZ3::new(&ctx, &sort, 20)), "t4");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LEZ3::new(&ctx, &x, IntZ3::new(&ctx, &sort, 1)), "t5");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LEZ3::new(&ctx, &y, IntZ3::new(&ctx, &sort, 1)), "t6");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LEZ3::new(&ctx, &z, IntZ3::new(&ctx, &sort, 1)), "t7");
+
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+    let core = SlvGetUnsatCoreNamesZ3::new(&ctx, &slv);
+    assert_eq!(4, core.len());
+    assert!(core.contains(&"t4".to_string()));
+}
+
+#[test]
+fn test_get_unsat_core_names_macro_1() {
+    let cfg = cfg_z3!();
+    SetParamZ3::new(&cfg, "unsat_core", "true");
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+    slv_set_core_minimize_z3!(&ctx, &slv, true);
+
+    let x = bool_var_z3!(&ctx, "x");
+    slv_assert_and_track_z3!(&ctx, &slv, x.clone(), "a1");
+    slv_assert_and_track_z3!(&ctx, &slv, not_z3!(&ctx, x), "a2");
+    slv_check_z3!(&ctx, &slv);
+
+    let mut core = slv_get_unsat_core_names_z3!(&ctx, &slv);
+    core.sort();
+    assert_eq!(vec!("a1", "a2"), core);
+}
+
+#[test]
+fn test_slv_models_projection() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let aux = BoolVarZ3::new(&ctx, &boolsort, "aux");
+    SlvAssertZ3::new(&ctx, &slv, GEZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 1)));
+    SlvAssertZ3::new(&ctx, &slv, LEZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 3)));
+    SlvAssertZ3::new(&ctx, &slv, ORZ3::new(&ctx, vec!(&aux, &NOTZ3::new(&ctx, &aux))));
+
+    let mut values: Vec<ModelValueZ3> = SlvModelsZ3::new(&ctx, &slv, vec!(&x), Vec::<Ast>::new())
+        .map(|m| m.unwrap().values[0].clone()).collect();
+    values.sort();
+    assert_eq!(vec!(ModelValueZ3::Int(1), ModelValueZ3::Int(2), ModelValueZ3::Int(3)), values);
+
+    // the blocking constraints are gone when the iterator is dropped
+    assert_eq!(3, SlvModelsZ3::new(&ctx, &slv, vec!(&x), Vec::<Ast>::new()).count());
+    assert_eq!(1, SlvModelsZ3::new(&ctx, &slv, Vec::<Ast>::new(), Vec::<Ast>::new()).count());
+}
+
+#[test]
+fn test_slv_models_assumptions() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("red", "blue", "green"));
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let b = EnumVarZ3::new(&ctx, &balls, "b");
+    let no_red = NOTZ3::new(&ctx, EQZ3::new(&ctx, &b, &balls.enum_asts[0]));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 5)));
+
+    let mut models = SlvModelsZ3::new(&ctx, &slv, vec!(&b, &x), vec!(&no_red));
+    let first = models.next().unwrap().unwrap();
+    assert_eq!(ModelValueZ3::Int(5), first.values[1]);
+    assert!(first.values[0] != ModelValueZ3::Enum("red".to_string()));
+    assert_eq!(1, models.count());
+}
+
+#[test]
+fn test_slv_models_unknown() {
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    let x = int_var_z3!(&ctx, "x");
+    let y = int_var_z3!(&ctx, "y");
+    let z = int_var_z3!(&ctx, "z");
+    let positive = and_z3!(&ctx, 
+        gt_z3!(&ctx, &x, int_z3!(&ctx, 0)), 
+        gt_z3!(&ctx, &y, int_z3!(&ctx, 0)), 
+        gt_z3!(&ctx, &z, int_z3!(&ctx, 0)));
+    let cubes = eq_z3!(&ctx, 
+        add_z3!(&ctx, mul_z3!(&ctx, &x, &x, &x), mul_z3!(&ctx, &y, &y, &y)), 
+        mul_z3!(&ctx, &z, &z, &z));
+    slv_assert_z3!(&ctx, &slv, forall_z3!(&ctx, vec!(&x, &y, &z), imp_z3!(&ctx, positive, not_z3!(&ctx, cubes))));
+    slv_set_rlimit_z3!(&ctx, &slv, 1000);
+
+    // an unknown check is not the end of the models
+    let models: Vec<_> = slv_models_z3!(&ctx, &slv, vec!(&x)).collect();
+    assert_eq!(1, models.len());
+    assert_eq!("The solver returned unknown: (incomplete quantifiers)", models[0].as_ref().err().unwrap().msg);
+}
+
+#[test]
+fn test_slv_models_macro_1() {
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    let x = bool_var_z3!(&ctx, "x");
+    let y = bool_var_z3!(&ctx, "y");
+    slv_assert_z3!(&ctx, &slv, or_z3!(&ctx, vec!(&x, &y)));
+
+    assert_eq!(2, slv_models_z3!(&ctx, &slv, vec!(&x)).count());
+    assert_eq!(1, slv_models_z3!(&ctx, &slv, vec!(&x), vec!(&x)).count());
+    assert_eq!(3, slv_models_z3!(&ctx, &slv, vec!(&x, &y)).count());
+}
+
+#[test]
+fn test_slv_check_assumptions() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let goal_1 = BoolVarZ3::new(&ctx, &boolsort, "goal_1");
+    let goal_2 = BoolVarZ3::new(&ctx, &boolsort, "goal_2");
+    SlvAssertZ3::new(&ctx, &slv, IMPZ3::new(&ctx, &goal_1, GTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 5))));
+    SlvAssertZ3::new(&ctx, &slv, IMPZ3::new(&ctx, &goal_2, LTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 3))));
+
+    assert_eq!(AssumptionsResultZ3::Sat, SlvCheckAssumptionsZ3::new(&ctx, &slv, vec!(&goal_1)));
+    assert_eq!(AssumptionsResultZ3::Sat, SlvCheckAssumptionsZ3::new(&ctx, &slv, vec!(&goal_2)));
+    match SlvCheckAssumptionsZ3::new(&ctx, &slv, vec!(&goal_1, &goal_2)) {
+        AssumptionsResultZ3::Unsat(core) => {
+            assert_eq!(2, core.len());
+            assert!(core.contains(&goal_1) && core.contains(&goal_2));
+        },
+        x => panic!("expected unsat, got {:?}", x)
+    }
+    // the assumptions don't stay in the solver
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_slv_get_consequences() {
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let a = BoolVarZ3::new(&ctx, &boolsort, "a");
+    let p = BoolVarZ3::new(&ctx, &boolsort, "p");
+    let q = BoolVarZ3::new(&ctx, &boolsort, "q");
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let y = IntVarZ3::new(&ctx, &intsort, "y");
+    SlvAssertZ3::new(&ctx, &slv, IMPZ3::new(&ctx, &a, EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 3))));
+    SlvAssertZ3::new(&ctx, &slv, IMPZ3::new(&ctx, &a, NOTZ3::new(&ctx, &p)));
+    SlvAssertZ3::new(&ctx, &slv, &q);
+
+    match SlvGetConsequencesZ3::new(&ctx, &slv, vec!(&a), vec!(&p, &q, &x, &y)) {
+        ConsequencesResultZ3::Sat(cons) => {
+            assert_eq!(3, cons.len());
+            assert!(cons.contains(&ConsequenceZ3 {assumptions: vec!(), var: q.clone(), value: ModelValueZ3::Bool(true)}));
+            assert!(cons.contains(&ConsequenceZ3 {assumptions: vec!(a.clone()), var: p.clone(), value: ModelValueZ3::Bool(false)}));
+            assert!(cons.contains(&ConsequenceZ3 {assumptions: vec!(a.clone()), var: x.clone(), value: ModelValueZ3::Int(3)}));
+        },
+        x => panic!("expected sat, got {:?}", x)
+    }
+
+    SlvAssertZ3::new(&ctx, &slv, &p);
+    assert_eq!(ConsequencesResultZ3::Unsat(vec!(a.clone())), SlvGetConsequencesZ3::new(&ctx, &slv, vec!(&a), vec!(&x)));
+}
+
+#[test]
+fn test_slv_check_assumptions_macro_1() {
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    let x = bool_var_z3!(&ctx, "x");
+    let y = bool_var_z3!(&ctx, "y");
+    slv_assert_z3!(&ctx, &slv, iff_z3!(&ctx, &x, not_z3!(&ctx, &y)));
+
+    assert_eq!(AssumptionsResultZ3::Sat, slv_check_assumptions_z3!(&ctx, &slv, vec!(&x)));
+    assert!(matches!(slv_check_assumptions_z3!(&ctx, &slv, vec!(&x, &y)), AssumptionsResultZ3::Unsat(_)));
+    assert_eq!(ConsequencesResultZ3::Sat(vec!(ConsequenceZ3 {assumptions: vec!(x.clone()), var: y.clone(), value: ModelValueZ3::Bool(false)})),
+        slv_get_consequences_z3!(&ctx, &slv, vec!(&x), vec!(&y)));
+}
+
 #[test]
 fn test_slv_macro_1(){
     let cfg = cfg_z3!();
@@ -203,7 +512,7 @@ fn test_slv_check_macro_1(){
 
     let res1 = slv_check_z3!(&ctx, &slv);
 
-    assert_eq!(1, res1);
+    assert_eq!(SatResultZ3::Sat, res1);
 
     assert_eq!("(declare-fun y () Real)
 (assert (= y (- (/ 271549371.0 500000.0))))
@@ -235,7 +544,7 @@ fn test_get_proof_macro_1(){
     let three = IntZ3::new(&ctx, &sort, 3);
     let two = IntZ3::new(&ctx, &sort, 2);
 
-    SlvAssertZ3::new(&ctx, &slv, ANDZ3::new(&ctx, vec!(GTZ3::new(&ctx, x, three), LTZ3::new(&ctx, x, two))));
+    SlvAssertZ3::new(&ctx, &slv, ANDZ3::new(&ctx, vec!(GTZ3::new(&ctx, &x, three), LTZ3::new(&ctx, x, two))));
 
     SlvCheckZ3::new(&ctx, &slv);
     let proof = slv_get_proof_z3!(&ctx, &slv);
@@ -274,7 +583,7 @@ fn test_get_proof_macro_1(){
     );
 
     let res1 = slv_check_z3!(&ctx, &slv);
-    assert_eq!(-1, res1);
+    assert_eq!(SatResultZ3::Unsat, res1);
 
     let proof = slv_get_proof_z3!(&ctx, &slv);
     assert_eq!("(mp (and-elim (mp (asserted (and (= y (- 543)) (< y (- 600)) (> y 300)))
@@ -359,8 +668,8 @@ fn test_get_unsat_core_macro_1() {
     let two = IntZ3::new(&ctx, &sort, 2);
     let one = IntZ3::new(&ctx, &sort, 1);
 
-    SlvAssertAndTrackZ3::new(&ctx, &slv, GTZ3::new(&ctx, x, three), "a1");
-    SlvAssertAndTrackZ3::new(&ctx, &slv, LTZ3::new(&ctx, x, two), "a2");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, GTZ3::new(&ctx, &x, three), "a1");
+    SlvAssertAndTrackZ3::new(&ctx, &slv, LTZ3::new(&ctx, &x, two), "a2");
     SlvAssertAndTrackZ3::new(&ctx, &slv, EQZ3::new(&ctx, x, one), "a3");
 
     SlvCheckZ3::new(&ctx, &slv);
@@ -368,4 +677,206 @@ fn test_get_unsat_core_macro_1() {
     assert_eq!("(ast-vector
   a1
   a2)", slv_unsat_core_to_string_z3!(&ctx, unsat_core));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_slv_from_smtlib2_1(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("red", "blue"));
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let b = EnumVarZ3::new(&ctx, &balls, "b");
+    SlvFromSmtLib2Z3::new(&ctx, &slv, "(assert (> x 5)) (assert (< x 7)) (assert (not (= b red)))", 
+        vec!(&balls), vec!(GetAppDeclZ3::new(&ctx, &x), GetAppDeclZ3::new(&ctx, &b)));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 6)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!("b -> blue\nx -> 6\n", model_to_string_z3!(&ctx, model));
+}
+
+#[test]
+fn test_slv_from_smtlib2_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+
+    let err = SlvFromSmtLib2Z3::try_new(&ctx, &slv, "(assert (> y 5))", Vec::<Sort>::new(), Vec::<FuncDecl>::new()).unwrap_err();
+    assert_eq!(z3_sys::Z3_error_code_Z3_PARSER_ERROR, err.code);
+    assert_eq!("(error \"line 1 column 12: unknown constant y\")\n", err.msg);
+
+    let err = SlvFromSmtLib2Z3::try_new(&ctx, &slv, "(assert (> 1 true))", Vec::<Sort>::new(), Vec::<FuncDecl>::new()).unwrap_err();
+    assert_eq!(z3_sys::Z3_error_code_Z3_PARSER_ERROR, err.code);
+    assert_eq!("", SlvToStringZ3::new(&ctx, &slv));
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_slv_from_smtlib2_file_1(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+
+    let path = std::env::temp_dir().join("mini_sp_smt_slv_from_smtlib2_file_1.smt2");
+    std::fs::write(&path, "(declare-const y Int)\n(assert (> y 2))\n(assert (< y 2))\n").unwrap();
+    SlvFromSmtLib2FileZ3::new(&ctx, &slv, path.to_str().unwrap(), Vec::<Sort>::new(), Vec::<FuncDecl>::new());
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+    assert!(SlvFromSmtLib2FileZ3::try_new(&ctx, &slv, path.to_str().unwrap(), Vec::<Sort>::new(), Vec::<FuncDecl>::new()).is_err());
+}
+
+#[test]
+fn test_slv_from_smtlib2_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    let x = int_var_z3!(&ctx, "x");
+    slv_from_smtlib2_z3!(&ctx, &slv, "(declare-const y Int) (assert (= y 3))");
+    slv_from_smtlib2_z3!(&ctx, &slv, "(assert (= x (* 2 3)))", Vec::<Sort>::new(), vec!(get_app_decl_z3!(&ctx, &x)));
+    assert_eq!(SatResultZ3::Sat, slv_check_z3!(&ctx, &slv));
+
+    slv_assert_z3!(&ctx, &slv, not_z3!(&ctx, eq_z3!(&ctx, &x, int_z3!(&ctx, 6))));
+    assert_eq!(SatResultZ3::Unsat, slv_check_z3!(&ctx, &slv));
+}
+
+#[test]
+fn test_slv_to_smtlib2_1(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let balls = EnumSortZ3::new(&ctx, "balls", vec!("red", "blue"));
+
+    let b = EnumVarZ3::new(&ctx, &balls, "b");
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &b, &balls.enum_asts[0]));
+    SlvAssertZ3::new(&ctx, &slv, GTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 2)));
+
+    let script = SlvToSmtLib2Z3::new(&ctx, &slv, "test");
+    assert_eq!("; test
+(set-info :status unknown)
+(declare-datatypes ((balls 0)) (((red) (blue))))
+ (declare-fun b () balls)
+(declare-fun x () Int)
+(assert
+ (= b red))
+(assert
+ (> x 2))
+(check-sat)
+", script);
+
+    // the script is standalone, a fresh context can load it without knowing the sorts
+    let conf2 = ConfigZ3::new();
+    let ctx2 = ContextZ3::new(&conf2);
+    let slv2 = SolverZ3::new(&ctx2);
+    SlvFromSmtLib2Z3::new(&ctx2, &slv2, &script, Vec::<Sort>::new(), Vec::<FuncDecl>::new());
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx2, &slv2));
+}
+
+#[test]
+fn test_slv_to_smtlib2_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+
+    let err = SlvToSmtLib2Z3::try_new(&ctx, &slv, "te\0st").unwrap_err();
+    assert_eq!(z3_sys::Z3_error_code_Z3_INVALID_ARG, err.code);
+    assert_eq!("The benchmark name contains a nul byte.", err.msg);
+}
+
+#[test]
+fn test_slv_to_smtlib2_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    assert_eq!("; empty\n(set-info :status unknown)\n(check-sat)\n", slv_to_smtlib2_z3!(&ctx, &slv, "empty"));
+}
+
+// pigeonhole problems are hard for the solver, so the checks run long enough to be stopped
+fn pigeonhole_z3<'ctx>(ctx: &'ctx ContextZ3, slv: &SolverZ3<'ctx>, holes: u32) {
+    let p = |i: u32, h: u32| BoolVarZ3::new(ctx, &BoolSortZ3::new(ctx), &format!("p_{}_{}", i, h));
+    for i in 0..holes + 1 {
+        SlvAssertZ3::new(ctx, slv, ORZ3::new(ctx, (0..holes).map(|h| p(i, h)).collect()));
+    }
+    for h in 0..holes {
+        for i in 0..holes + 1 {
+            for j in i + 1..holes + 1 {
+                SlvAssertZ3::new(ctx, slv, NOTZ3::new(ctx, ANDZ3::new(ctx, vec!(p(i, h), p(j, h)))));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_slv_set_timeout(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+
+    pigeonhole_z3(&ctx, &slv, 10);
+    SlvSetTimeoutZ3::new(&ctx, &slv, 20);
+    assert_eq!(SatResultZ3::Unknown("timeout".to_string()), SlvCheckZ3::new(&ctx, &slv));
+    assert_eq!("timeout", SlvGetReasonUnknownZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_slv_set_rlimit(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+
+    pigeonhole_z3(&ctx, &slv, 10);
+    SlvSetRlimitZ3::new(&ctx, &slv, 1000);
+    assert_eq!(SatResultZ3::Unknown("max. resource limit exceeded".to_string()), SlvCheckZ3::new(&ctx, &slv));
+
+    SlvResetZ3::new(&ctx, &slv);
+    SlvSetRlimitZ3::new(&ctx, &slv, 0);
+    let boolsort = BoolSortZ3::new(&ctx);
+    SlvAssertZ3::new(&ctx, &slv, BoolVarZ3::new(&ctx, &boolsort, "x"));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_interrupt_from_other_thread(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+
+    pigeonhole_z3(&ctx, &slv, 10);
+    let handle = InterruptHandleZ3::new(&ctx);
+    let res = crossbeam::scope(|s| {
+        s.spawn(move |_| {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            handle.interrupt();
+        });
+        SlvCheckZ3::new(&ctx, &slv)
+    }).unwrap();
+    assert_eq!(SatResultZ3::Unknown("interrupted".to_string()), res);
+
+    // the context can be used again after the interrupt
+    let slv2 = SolverZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+    SlvAssertZ3::new(&ctx, &slv2, BoolVarZ3::new(&ctx, &boolsort, "x"));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv2));
+}
+
+#[test]
+fn test_limits_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    pigeonhole_z3(&ctx, &slv, 10);
+    slv_set_rlimit_z3!(&ctx, &slv, 1000);
+    slv_set_timeout_z3!(&ctx, &slv, 60000);
+    assert!(matches!(slv_check_z3!(&ctx, &slv), SatResultZ3::Unknown(_)));
+
+    let handle = interrupt_handle_z3!(&ctx);
+    handle.interrupt();
+}
@@ -5,7 +5,7 @@ fn test_bool_sort(){
     let conf = ConfigZ3::new();
     let ctx = ContextZ3::new(&conf);
     let sort = BoolSortZ3::new(&ctx);
-    assert_eq!("Bool", sort_to_string_z3!(&ctx, sort.r));
+    assert_eq!("Bool", sort_to_string_z3!(&ctx, &sort));
 }
 
 #[test]
@@ -13,7 +13,7 @@ fn test_int_sort(){
     let conf = ConfigZ3::new();
     let ctx = ContextZ3::new(&conf);
     let sort = IntSortZ3::new(&ctx);
-    assert_eq!("Int", sort_to_string_z3!(&ctx, sort.r));
+    assert_eq!("Int", sort_to_string_z3!(&ctx, &sort));
 }
 
 #[test]
@@ -21,7 +21,7 @@ fn test_real_sort(){
     let conf = ConfigZ3::new();
     let ctx = ContextZ3::new(&conf);
     let sort = RealSortZ3::new(&ctx);
-    assert_eq!("Real", sort_to_string_z3!(&ctx, sort.r));
+    assert_eq!("Real", sort_to_string_z3!(&ctx, &sort));
 }
 
 #[test]
@@ -29,7 +29,26 @@ fn test_string_sort(){
     let conf = ConfigZ3::new();
     let ctx = ContextZ3::new(&conf);
     let sort = StringSortZ3::new(&ctx);
-    assert_eq!("String", sort_to_string_z3!(&ctx, sort.r));
+    assert_eq!("String", sort_to_string_z3!(&ctx, &sort));
+}
+
+#[test]
+fn test_bv_sort(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let sort = BitVecSortZ3::new(&ctx, 16);
+    assert_eq!("(_ BitVec 16)", sort_to_string_z3!(&ctx, &sort));
+    assert!(BitVecSortZ3::try_new(&ctx, 0).is_err());
+}
+
+#[test]
+fn test_array_sort(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let boolsort = BoolSortZ3::new(&ctx);
+    let sort = ArraySortZ3::new(&ctx, &intsort, &boolsort);
+    assert_eq!("(Array Int Bool)", sort_to_string_z3!(&ctx, &sort));
 }
 
 
@@ -38,7 +57,7 @@ fn test_bool_sort_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let bsrt = bool_sort_z3!(&ctx);
-    assert_eq!("Bool", sort_to_string_z3!(&ctx, bsrt.r));
+    assert_eq!("Bool", sort_to_string_z3!(&ctx, &bsrt));
 }
 
 #[test]
@@ -46,7 +65,7 @@ fn test_int_sort_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let isrt = int_sort_z3!(&ctx);
-    assert_eq!("Int", sort_to_string_z3!(&ctx, isrt.r));
+    assert_eq!("Int", sort_to_string_z3!(&ctx, &isrt));
 }
 
 #[test]
@@ -54,7 +73,7 @@ fn test_real_sort_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let rsrt = real_sort_z3!(&ctx);
-    assert_eq!("Real", sort_to_string_z3!(&ctx, rsrt.r));
+    assert_eq!("Real", sort_to_string_z3!(&ctx, &rsrt));
 }
 
 #[test]
@@ -62,5 +81,5 @@ fn test_string_sort_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let ssrt = string_sort_z3!(&ctx);
-    assert_eq!("String", sort_to_string_z3!(&ctx, ssrt.r));
+    assert_eq!("String", sort_to_string_z3!(&ctx, &ssrt));
 }
\ No newline at end of file
@@ -0,0 +1,61 @@
+use mini_sp_smt::*;
+
+#[test]
+fn test_slv_get_statistics(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let y = IntVarZ3::new(&ctx, &intsort, "y");
+    SlvAssertZ3::new(&ctx, &slv, GTZ3::new(&ctx, &x, &y));
+    SlvAssertZ3::new(&ctx, &slv, ORZ3::new(&ctx, vec!(EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 1)),
+        EQZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 2)))));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let stats = SlvGetStatisticsZ3::new(&ctx, &slv);
+    match stats.get("memory") {
+        Some(StatValueZ3::Double(x)) => assert!(*x > 0.0),
+        _ => panic!("memory is reported as a double")
+    }
+    assert!(stats.value("max memory") >= stats.value("memory"));
+    assert_eq!(0.0, stats.value("no such statistic"));
+}
+
+#[test]
+fn test_opt_get_statistics(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let opt = OptimizerZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    OptAssertZ3::new(&ctx, &opt, LTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 10)));
+    OptMaximizeZ3::new(&ctx, &opt, &x);
+    OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
+
+    let stats = OptGetStatisticsZ3::new(&ctx, &opt);
+    assert!(stats.get("memory").is_some());
+}
+
+#[test]
+fn test_stat_value_order(){
+    assert_eq!(StatValueZ3::Double(1.5), StatValueZ3::Double(1.5));
+    assert!(StatValueZ3::UInt(7) < StatValueZ3::Double(0.5));
+    assert_eq!(3.0, StatValueZ3::UInt(3).as_f64());
+    assert_eq!("3", format!("{}", StatValueZ3::UInt(3)));
+}
+
+#[test]
+fn test_get_statistics_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+    let slv = slv_z3!(&ctx);
+
+    slv_assert_z3!(&ctx, &slv, bool_var_z3!(&ctx, "x"));
+    slv_check_z3!(&ctx, &slv);
+
+    let stats = slv_get_statistics_z3!(&ctx, &slv);
+    assert!(!stats.entries.is_empty());
+}
@@ -0,0 +1,142 @@
+use mini_sp_smt::*;
+
+#[test]
+fn test_new_str_ops(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let stringsort = StringSortZ3::new(&ctx);
+
+    let x = StringVarZ3::new(&ctx, &stringsort, "x");
+    let y = StringVarZ3::new(&ctx, &stringsort, "y");
+    let zero = IntZ3::new(&ctx, &IntSortZ3::new(&ctx), 0);
+    let three = IntZ3::new(&ctx, &IntSortZ3::new(&ctx), 3);
+
+    assert_eq!("(str.++ x y)", ast_to_string_z3!(&ctx, STRCONCATZ3::new(&ctx, vec!(&x, &y))));
+    assert_eq!("(str.len x)", ast_to_string_z3!(&ctx, STRLENZ3::new(&ctx, &x)));
+    assert_eq!("(str.substr x 0 3)", ast_to_string_z3!(&ctx, SUBSTRZ3::new(&ctx, &x, &zero, &three)));
+    assert_eq!("(str.contains x y)", ast_to_string_z3!(&ctx, STRCONTAINSZ3::new(&ctx, &x, &y)));
+    assert_eq!("(str.prefixof y x)", ast_to_string_z3!(&ctx, STRPREFIXOFZ3::new(&ctx, &y, &x)));
+    assert_eq!("(str.suffixof y x)", ast_to_string_z3!(&ctx, STRSUFFIXOFZ3::new(&ctx, &y, &x)));
+    assert_eq!("(str.indexof x y 0)", ast_to_string_z3!(&ctx, STRINDEXOFZ3::new(&ctx, &x, &y, &zero)));
+    assert_eq!("Int", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, STRLENZ3::new(&ctx, &x))));
+}
+
+#[test]
+fn test_new_str_conversions(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let stringsort = StringSortZ3::new(&ctx);
+
+    let s = StringVarZ3::new(&ctx, &stringsort, "s");
+    let n = IntVarZ3::new(&ctx, &intsort, "n");
+
+    assert_eq!("(str.to_int s)", ast_to_string_z3!(&ctx, STRTOINTZ3::new(&ctx, &s)));
+    assert_eq!("(str.from_int n)", ast_to_string_z3!(&ctx, INTTOSTRZ3::new(&ctx, &n)));
+
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &s, INTTOSTRZ3::new(&ctx, IntZ3::new(&ctx, &intsort, 42))));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &n, STRTOINTZ3::new(&ctx, StringZ3::new(&ctx, "017"))));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    assert_eq!(ModelValueZ3::String("42".to_string()), ModelEvalZ3::new(&ctx, &model, &s, true));
+    assert_eq!(ModelValueZ3::Int(17), ModelEvalZ3::new(&ctx, &model, &n, true));
+}
+
+#[test]
+fn test_new_re_ops(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let stringsort = StringSortZ3::new(&ctx);
+
+    let x = StringVarZ3::new(&ctx, &stringsort, "x");
+    let ab = STRTOREZ3::new(&ctx, StringZ3::new(&ctx, "ab"));
+    let digit = RERANGEZ3::new(&ctx, StringZ3::new(&ctx, "0"), StringZ3::new(&ctx, "9"));
+
+    assert_eq!("(str.to_re \"ab\")", ast_to_string_z3!(&ctx, &ab));
+    assert_eq!("(re.range \"0\" \"9\")", ast_to_string_z3!(&ctx, &digit));
+    assert_eq!("(re.+ (str.to_re \"ab\"))", ast_to_string_z3!(&ctx, REPLUSZ3::new(&ctx, &ab)));
+    assert_eq!("(re.* (str.to_re \"ab\"))", ast_to_string_z3!(&ctx, RESTARZ3::new(&ctx, &ab)));
+    assert_eq!("(re.opt (str.to_re \"ab\"))", ast_to_string_z3!(&ctx, REOPTIONZ3::new(&ctx, &ab)));
+    assert_eq!("(re.union (str.to_re \"ab\") (re.range \"0\" \"9\"))",
+        ast_to_string_z3!(&ctx, REUNIONZ3::new(&ctx, vec!(&ab, &digit))));
+    assert_eq!("(re.++ (str.to_re \"ab\") (re.range \"0\" \"9\"))",
+        ast_to_string_z3!(&ctx, RECONCATZ3::new(&ctx, vec!(&ab, &digit))));
+    assert_eq!("(str.in_re x (str.to_re \"ab\"))", ast_to_string_z3!(&ctx, INREZ3::new(&ctx, &x, &ab)));
+    assert_eq!("(RegEx String)", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, ab)));
+}
+
+#[test]
+fn test_str_batch_id_guard(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let slv = SolverZ3::new(&ctx);
+    let intsort = IntSortZ3::new(&ctx);
+    let stringsort = StringSortZ3::new(&ctx);
+
+    let id = StringVarZ3::new(&ctx, &stringsort, "id");
+    let digits = REPLUSZ3::new(&ctx, RERANGEZ3::new(&ctx, StringZ3::new(&ctx, "0"), StringZ3::new(&ctx, "9")));
+    let format = RECONCATZ3::new(&ctx, vec!(STRTOREZ3::new(&ctx, StringZ3::new(&ctx, "PRD-")), digits));
+
+    SlvAssertZ3::new(&ctx, &slv, INREZ3::new(&ctx, &id, &format));
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, STRLENZ3::new(&ctx, &id), IntZ3::new(&ctx, &intsort, 8)));
+    SlvAssertZ3::new(&ctx, &slv, STRSUFFIXOFZ3::new(&ctx, StringZ3::new(&ctx, "7"), &id));
+    SlvAssertZ3::new(&ctx, &slv, GTZ3::new(&ctx,
+        STRTOINTZ3::new(&ctx, SUBSTRZ3::new(&ctx, &id, IntZ3::new(&ctx, &intsort, 4), IntZ3::new(&ctx, &intsort, 4))),
+        IntZ3::new(&ctx, &intsort, 9000)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let value = ModelEvalZ3::new(&ctx, &model, &id, true);
+    let value = value.as_str().unwrap();
+    assert!(value.starts_with("PRD-"));
+    assert!(value.ends_with('7'));
+    assert!(value[4..].parse::<u32>().unwrap() > 9000);
+
+    SlvAssertZ3::new(&ctx, &slv, STRCONTAINSZ3::new(&ctx, &id, StringZ3::new(&ctx, "X")));
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_str_sort_error(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+
+    let s = StringVarZ3::new(&ctx, &StringSortZ3::new(&ctx), "s");
+    let n = IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), "n");
+
+    assert!(STRLENZ3::try_new(&ctx, &n).is_err());
+    assert!(STRCONTAINSZ3::try_new(&ctx, &s, &n).is_err());
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_str_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+
+    let x = string_var_z3!(&ctx, "x");
+    let y = string_var_z3!(&ctx, "y");
+    let n = int_var_z3!(&ctx, "n");
+
+    assert_eq!("(str.++ x \"-\" y)", ast_to_string_z3!(&ctx, str_concat_z3!(&ctx, &x, string_z3!(&ctx, "-"), &y)));
+    assert_eq!("(str.len x)", ast_to_string_z3!(&ctx, str_len_z3!(&ctx, &x)));
+    assert_eq!("(str.substr x n 2)", ast_to_string_z3!(&ctx, substr_z3!(&ctx, &x, &n, int_z3!(&ctx, 2))));
+    assert_eq!("(str.contains x y)", ast_to_string_z3!(&ctx, str_contains_z3!(&ctx, &x, &y)));
+    assert_eq!("(str.prefixof y x)", ast_to_string_z3!(&ctx, str_prefix_of_z3!(&ctx, &y, &x)));
+    assert_eq!("(str.suffixof y x)", ast_to_string_z3!(&ctx, str_suffix_of_z3!(&ctx, &y, &x)));
+    assert_eq!("(str.indexof x y n)", ast_to_string_z3!(&ctx, str_index_of_z3!(&ctx, &x, &y, &n)));
+    assert_eq!("(str.to_int x)", ast_to_string_z3!(&ctx, str_to_int_z3!(&ctx, &x)));
+    assert_eq!("(str.from_int n)", ast_to_string_z3!(&ctx, int_to_str_z3!(&ctx, &n)));
+
+    let lower = re_star_z3!(&ctx, re_range_z3!(&ctx, string_z3!(&ctx, "a"), string_z3!(&ctx, "z")));
+    let zeros = re_option_z3!(&ctx, re_plus_z3!(&ctx, str_to_re_z3!(&ctx, string_z3!(&ctx, "0"))));
+    assert_eq!("(re.* (re.range \"a\" \"z\"))", ast_to_string_z3!(&ctx, &lower));
+    assert_eq!("(re.opt (re.+ (str.to_re \"0\")))", ast_to_string_z3!(&ctx, &zeros));
+    assert_eq!("(re.++ (str.to_re y) (re.* (re.range \"a\" \"z\")))",
+        ast_to_string_z3!(&ctx, re_concat_z3!(&ctx, str_to_re_z3!(&ctx, &y), &lower)));
+    assert_eq!("(re.union (re.* (re.range \"a\" \"z\")) (re.opt (re.+ (str.to_re \"0\"))))",
+        ast_to_string_z3!(&ctx, re_union_z3!(&ctx, &lower, &zeros)));
+    assert_eq!("(str.in_re x (re.* (re.range \"a\" \"z\")))", ast_to_string_z3!(&ctx, in_re_z3!(&ctx, x, lower)));
+}
@@ -0,0 +1,114 @@
+use mini_sp_smt::*;
+
+#[test]
+fn test_new_tactic_and_goal(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+
+    let x = IntVarZ3::new(&ctx, &intsort, "x");
+    let y = IntVarZ3::new(&ctx, &intsort, "y");
+    let goal = GoalZ3::new(&ctx, true, false, false);
+    GoalAssertZ3::new(&ctx, &goal, EQZ3::new(&ctx, &x, ADDZ3::new(&ctx, vec!(&y, &IntZ3::new(&ctx, &intsort, 1)))));
+    GoalAssertZ3::new(&ctx, &goal, GTZ3::new(&ctx, &x, IntZ3::new(&ctx, &intsort, 2)));
+
+    let pipeline = TacticAndThenZ3::new(&ctx, &TacticZ3::new(&ctx, "solve-eqs"), &TacticZ3::new(&ctx, "simplify"));
+    let subgoals = TacticApplyZ3::new(&ctx, &pipeline, &goal);
+
+    assert_eq!(1, subgoals.len());
+    assert_eq!(vec!("(not (<= y 1))"), GoalGetFormulasZ3::new(&ctx, &subgoals[0]).iter().map(|x| x.to_string()).collect::<Vec<String>>());
+    assert_eq!(2, GoalGetFormulasZ3::new(&ctx, &goal).len());
+}
+
+#[test]
+fn test_unknown_tactic(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+
+    assert!(TacticZ3::try_new(&ctx, "no-such-tactic").is_err());
+    assert_eq!(Ok(()), ctx.check_error());
+}
+
+#[test]
+fn test_goal_and_combinator_errors(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let simplify = TacticZ3::new(&ctx, "simplify");
+    let smt = TacticZ3::new(&ctx, "smt");
+
+    // proofs are not enabled in the context
+    assert!(GoalZ3::try_new(&ctx, true, false, true).is_err());
+    assert_eq!(Ok(()), ctx.check_error());
+
+    let goal = GoalZ3::try_new(&ctx, true, false, false).unwrap();
+    assert_eq!(0, GoalGetFormulasZ3::try_new(&ctx, &goal).unwrap().len());
+    assert!(TacticAndThenZ3::try_new(&ctx, &simplify, &smt).is_ok());
+    assert!(TacticOrElseZ3::try_new(&ctx, &simplify, &smt).is_ok());
+    assert!(TacticRepeatZ3::try_new(&ctx, &simplify, 3).is_ok());
+    assert!(TacticParOrZ3::try_new(&ctx, vec!(&simplify, &smt)).is_ok());
+    assert!(SolverFromTacticZ3::try_new(&ctx, &smt).is_ok());
+}
+
+#[test]
+fn test_tactic_combinators(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let a = BoolVarZ3::new(&ctx, &boolsort, "a");
+    let b = BoolVarZ3::new(&ctx, &boolsort, "b");
+    let goal = GoalZ3::new(&ctx, true, false, false);
+    GoalAssertZ3::new(&ctx, &goal, ORZ3::new(&ctx, vec!(&a, &b)));
+    GoalAssertZ3::new(&ctx, &goal, NOTZ3::new(&ctx, &a));
+    GoalAssertZ3::new(&ctx, &goal, NOTZ3::new(&ctx, &b));
+
+    let simplify = TacticZ3::new(&ctx, "simplify");
+    let pipeline = TacticAndThenZ3::new(&ctx, &TacticRepeatZ3::new(&ctx, &simplify, 5),
+        &TacticOrElseZ3::new(&ctx, &TacticZ3::new(&ctx, "fail"), &TacticZ3::new(&ctx, "sat")));
+    let subgoals = TacticApplyZ3::new(&ctx, &pipeline, &goal);
+
+    assert_eq!(1, subgoals.len());
+    assert_eq!("(goal\n  false)", subgoals[0].to_string());
+
+    let par = TacticParOrZ3::new(&ctx, vec!(&TacticZ3::new(&ctx, "sat"), &TacticZ3::new(&ctx, "smt")));
+    let subgoals = TacticApplyZ3::new(&ctx, &par, &goal);
+    assert_eq!(vec!("false"), GoalGetFormulasZ3::new(&ctx, &subgoals[0]).iter().map(|x| x.to_string()).collect::<Vec<String>>());
+}
+
+#[test]
+fn test_solver_from_tactic(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let boolsort = BoolSortZ3::new(&ctx);
+
+    let tactic = TacticAndThenZ3::new(&ctx, &TacticZ3::new(&ctx, "simplify"), &TacticZ3::new(&ctx, "smt"));
+    let slv = SolverFromTacticZ3::new(&ctx, &tactic);
+
+    let a = BoolVarZ3::new(&ctx, &boolsort, "a");
+    SlvAssertZ3::new(&ctx, &slv, &a);
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+    SlvAssertZ3::new(&ctx, &slv, NOTZ3::new(&ctx, &a));
+    assert_eq!(SatResultZ3::Unsat, SlvCheckZ3::new(&ctx, &slv));
+}
+
+#[test]
+fn test_tactic_macro_1(){
+    let cfg = cfg_z3!();
+    let ctx = ctx_z3!(&cfg);
+
+    let goal = goal_z3!(&ctx);
+    let x = bv_var_z3!(&ctx, 4, "x");
+    goal_assert_z3!(&ctx, &goal,
+        eq_z3!(&ctx, bvadd_z3!(&ctx, &x, bv_z3!(&ctx, 4, 1)), bv_z3!(&ctx, 4, 3)),
+        bool_var_z3!(&ctx, "p"));
+
+    let tactic = and_then_z3!(&ctx, tactic_z3!(&ctx, "simplify"), tactic_z3!(&ctx, "bit-blast"),
+        repeat_z3!(&ctx, tactic_z3!(&ctx, "solve-eqs"), 2));
+    let subgoals = tactic_apply_z3!(&ctx, &tactic, &goal);
+    assert_eq!(1, subgoals.len());
+
+    let either = or_else_z3!(&ctx, tactic_z3!(&ctx, "fail"), par_or_z3!(&ctx, tactic_z3!(&ctx, "sat"), tactic_z3!(&ctx, "smt")));
+    let slv = slv_from_tactic_z3!(&ctx, &either);
+    slv_assert_z3!(&ctx, &slv, bool_var_z3!(&ctx, "p"));
+    assert_eq!(SatResultZ3::Sat, slv_check_z3!(&ctx, &slv));
+}
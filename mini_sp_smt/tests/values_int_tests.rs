@@ -25,6 +25,31 @@ fn test_new_int(){
     assert_eq!("(- 1012390)", ast_to_string_z3!(&ctx, int2));
 }
 
+#[test]
+fn test_new_bv(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let bvsort = BitVecSortZ3::new(&ctx, 8);
+
+    let bv1 = BitVecZ3::new(&ctx, &bvsort, 7);
+    let bv2 = BitVecZ3::new(&ctx, &bvsort, -1);
+
+    assert_eq!("#x07", ast_to_string_z3!(&ctx, bv1));
+    assert_eq!("#xff", ast_to_string_z3!(&ctx, bv2));
+}
+
+#[test]
+fn test_new_bv_from_numeral(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let bvsort = BitVecSortZ3::new(&ctx, 128);
+
+    let bv1 = BitVecZ3::new_from_numeral(&ctx, &bvsort, "340282366920938463463374607431768211455");
+
+    assert_eq!("#xffffffffffffffffffffffffffffffff", ast_to_string_z3!(&ctx, bv1));
+    assert!(BitVecZ3::try_new_from_numeral(&ctx, &bvsort, "seven").is_err());
+}
+
 #[test]
 fn test_new_string(){
     let conf = ConfigZ3::new();
@@ -40,7 +65,7 @@ fn test_bool_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let bool1 = bool_z3!(&ctx, true);
-    assert_eq!("true", ast_to_string_z3!(&ctx, bool1));
+    assert_eq!("true", ast_to_string_z3!(&ctx, &bool1));
     assert_eq!("Bool", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, bool1)));
 }
 
@@ -49,7 +74,7 @@ fn test_int_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let int1 = int_z3!(&ctx, 76);
-    assert_eq!("76", ast_to_string_z3!(&ctx, int1));
+    assert_eq!("76", ast_to_string_z3!(&ctx, &int1));
     assert_eq!("Int", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, int1)));
 }
 
@@ -58,7 +83,7 @@ fn test_real_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let real1 = real_z3!(&ctx, 76.456);
-    assert_eq!("(/ 9557.0 125.0)", ast_to_string_z3!(&ctx, real1));
+    assert_eq!("(/ 9557.0 125.0)", ast_to_string_z3!(&ctx, &real1));
     assert_eq!("Real", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, real1)));
 }
 
@@ -67,6 +92,6 @@ fn test_string_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let string1 = string_z3!(&ctx, "asdf_ASDF_!@#$");
-    assert_eq!("\"asdf_ASDF_!@#$\"", ast_to_string_z3!(&ctx, string1));
+    assert_eq!("\"asdf_ASDF_!@#$\"", ast_to_string_z3!(&ctx, &string1));
     assert_eq!("String", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, string1)));
 }
\ No newline at end of file
@@ -26,6 +26,31 @@ fn test_new_int_var(){
     assert_eq!("y", ast_to_string_z3!(&ctx, y));
 }
 
+#[test]
+fn test_new_bv_var(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let sort = BitVecSortZ3::new(&ctx, 16);
+
+    let x = BitVecVarZ3::new(&ctx, &sort, "x");
+
+    assert_eq!("x", ast_to_string_z3!(&ctx, &x));
+    assert_eq!("(_ BitVec 16)", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, x)));
+}
+
+#[test]
+fn test_new_array_var(){
+    let conf = ConfigZ3::new();
+    let ctx = ContextZ3::new(&conf);
+    let intsort = IntSortZ3::new(&ctx);
+    let sort = ArraySortZ3::new(&ctx, &intsort, &intsort);
+
+    let x = ArrayVarZ3::new(&ctx, &sort, "x");
+
+    assert_eq!("x", ast_to_string_z3!(&ctx, &x));
+    assert_eq!("(Array Int Int)", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, x)));
+}
+
 #[test]
 fn test_new_real_var(){
         let conf = ConfigZ3::new();
@@ -44,7 +69,7 @@ fn test_bool_var_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let bool1 = bool_var_z3!(&ctx, "x");
-    assert_eq!("x", ast_to_string_z3!(&ctx, bool1));
+    assert_eq!("x", ast_to_string_z3!(&ctx, &bool1));
     assert_eq!("Bool", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, bool1)));
 }
 
@@ -53,7 +78,7 @@ fn test_int_var_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let int1 = int_var_z3!(&ctx, "x");
-    assert_eq!("x", ast_to_string_z3!(&ctx, int1));
+    assert_eq!("x", ast_to_string_z3!(&ctx, &int1));
     assert_eq!("Int", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, int1)));
 }
 
@@ -62,6 +87,6 @@ fn test_real_var_macro_1(){
     let cfg = cfg_z3!();
     let ctx = ctx_z3!(&cfg);
     let real1 = real_var_z3!(&ctx, "x");
-    assert_eq!("x", ast_to_string_z3!(&ctx, real1));
+    assert_eq!("x", ast_to_string_z3!(&ctx, &real1));
     assert_eq!("Real", sort_to_string_z3!(&ctx, get_sort_z3!(&ctx, real1)));
 }
\ No newline at end of file
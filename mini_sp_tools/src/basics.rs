@@ -1,3 +1,6 @@
+use std::fmt;
+use super::*;
+
 #[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord)]
 pub struct Parameter {
     pub name: String,
@@ -26,29 +29,54 @@ pub struct EnumVariable {
     pub param: Parameter
 }
 
-// #[derive(Eq, Debug, PartialEq, Clone, PartialOrd, Ord)]
-// pub struct EnumAssignment {
-//     pub var: EnumVariable,
-//     pub val: String
-// }
+#[derive(Eq, Debug, PartialEq, Clone, PartialOrd, Ord)]
+pub enum Variable {
+    Bool(BoolVariable),
+    Int(IntVariable),
+    Enum(EnumVariable)
+}
+
+#[derive(Eq, Debug, PartialEq, Clone, PartialOrd, Ord)]
+pub enum Value {
+    Bool(bool),
+    Int(i32),
+    Enum(String)
+}
 
-// for now, later have to figure out how to add bool and int
-// #[derive(Eq, Debug, PartialEq, Clone, PartialOrd, Ord)]
-// pub struct State {
-//     pub variables: Vec<EnumVariable>,
-//     pub asssignments: Vec<EnumAssignment>,
-//     pub partial: bool
-// }
+#[derive(Eq, Debug, PartialEq, Clone, PartialOrd, Ord)]
+pub struct Assignment {
+    pub var: Variable,
+    pub val: Value
+}
+
+// variables are all the variables known to the state, partial is true if some of them are unassigned
+#[derive(Eq, Debug, PartialEq, Clone, PartialOrd, Ord, Default)]
+pub struct State {
+    pub variables: Vec<Variable>,
+    pub assignments: Vec<Assignment>,
+    pub partial: bool
+}
 
 impl Parameter {
     pub fn new(name: &str, value: &bool) -> Parameter {
+        match Parameter::try_new(name, value) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(name: &str, value: &bool) -> SPResult<Parameter> {
         match name == "TRUE" {
-            true => panic!("Error 5b376941-3c6e-4b52-bec3-49eb8d9991bb: Parameter name 'TRUE' is reserved."),
+            true => Err(SPError::ReservedName {
+                code: "5b376941-3c6e-4b52-bec3-49eb8d9991bb",
+                kind: "Parameter".to_string(),
+                name: name.to_string()
+            }),
             false => {
-                Parameter {
+                Ok(Parameter {
                     name: name.to_string(),
                     value: *value
-                }
+                })
             }
         }
     }
@@ -91,21 +119,115 @@ impl IntVariable {
 
 impl EnumVariable{
     pub fn new(name: &str, r#type: &str, domain: &Vec<&str>, param: Option<&Parameter>) -> EnumVariable {
-        EnumVariable { 
+        match EnumVariable::try_new(name, r#type, domain, param) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(name: &str, r#type: &str, domain: &Vec<&str>, param: Option<&Parameter>) -> SPResult<EnumVariable> {
+        Ok(EnumVariable { 
             param: match param {
                 Some(x) => x.to_owned(),
                 None => Parameter::default()
             },
             name: match name == "EMPTY" {
-                true => panic!("Error 69e2abf9-498b-4d5c-88c7-30ea70ed27fb: EnumVariable name 'EMPTY' is reserved."),
+                true => return Err(SPError::ReservedName {
+                    code: "69e2abf9-498b-4d5c-88c7-30ea70ed27fb",
+                    kind: "EnumVariable".to_string(),
+                    name: name.to_string()
+                }),
                 false => name.to_string()
             },
             r#type: r#type.to_string(),
             domain: domain.iter().map(|x| x.to_string()).collect::<Vec<String>>()
+        })
+    }
+}
+
+impl Variable {
+    pub fn name(&self) -> String {
+        match self {
+            Variable::Bool(x) => x.name.to_string(),
+            Variable::Int(x) => x.name.to_string(),
+            Variable::Enum(x) => x.name.to_string()
+        }
+    }
+
+    pub fn param(&self) -> Parameter {
+        match self {
+            Variable::Bool(x) => x.param.to_owned(),
+            Variable::Int(x) => x.param.to_owned(),
+            Variable::Enum(x) => x.param.to_owned()
+        }
+    }
+}
+
+impl Assignment {
+    pub fn new(var: &Variable, val: &Value) -> Assignment {
+        match Assignment::try_new(var, val) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(var: &Variable, val: &Value) -> SPResult<Assignment> {
+        let in_domain = match (var, val) {
+            (Variable::Bool(_), Value::Bool(_)) => true,
+            (Variable::Int(x), Value::Int(y)) => x.domain.contains(y),
+            (Variable::Enum(x), Value::Enum(y)) => x.domain.contains(y),
+            _ => return Err(SPError::WrongType {
+                code: "a61d4b8e-3f2c-4e7a-b9d0-6e5c1f8a2d37",
+                value: val.to_string(),
+                var: var.name()
+            })
+        };
+        match in_domain {
+            true => Ok(Assignment {
+                var: var.to_owned(),
+                val: val.to_owned()
+            }),
+            false => Err(SPError::NotInDomain {
+                code: "2c7f9e31-4a8d-4b6e-9f15-8d3a6c0e7b49",
+                value: val.to_string(),
+                var: var.name()
+            })
         }
     }
 }
 
+impl State {
+    pub fn new(variables: &Vec<Variable>, assignments: &Vec<Assignment>) -> State {
+        let mut vars = variables.to_owned();
+        let mut asgns = assignments.to_owned();
+        vars.sort();
+        vars.dedup();
+        asgns.sort();
+        asgns.dedup();
+        State {
+            partial: vars.iter().any(|x| !asgns.iter().any(|y| y.var == *x)),
+            variables: vars,
+            assignments: asgns
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Bool(x) => write!(f, "{}", x),
+            Value::Int(x) => write!(f, "{}", x),
+            Value::Enum(x) => write!(f, "{}", x)
+        }
+    }
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} -> {}", self.var.name(), self.val)
+    }
+}
+
 impl Default for EnumVariable {
     fn default() -> Self {
         EnumVariable {
@@ -125,4 +247,51 @@ fn test_new_enum_variable(){
     assert_eq!("EnumVariable { name: \"z\", type: \"letters\", domain: [\"a\", \"b\", \"c\", \"d\"], param: Parameter { name: \"param1\", value: false } }", 
         &format!("{:?}", EnumVariable::new("z", "letters", &vec!("a", "b", "c", "d"), Some(&param))));
 
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_variable_name_and_param(){
+    let param = Parameter::new("param1", &false);
+    let x = Variable::Bool(BoolVariable::new("x", Some(&param)));
+    let y = Variable::Int(IntVariable::new("y", &vec!(&0, &1, &2), None));
+    assert_eq!("x", x.name());
+    assert_eq!(param, x.param());
+    assert_eq!("y", y.name());
+    assert_eq!(Parameter::default(), y.param());
+}
+
+#[test]
+fn test_new_state(){
+    let x = Variable::Enum(EnumVariable::new("x", "letters", &vec!("a", "b"), None));
+    let y = Variable::Bool(BoolVariable::new("y", None));
+    let z = Variable::Int(IntVariable::new("z", &vec!(&1, &2), None));
+    let full = State::new(&vec!(z.clone(), y.clone(), x.clone()), &vec!(
+        Assignment::new(&z, &Value::Int(2)), 
+        Assignment::new(&x, &Value::Enum("a".to_string())), 
+        Assignment::new(&y, &Value::Bool(true))));
+    let partial = State::new(&vec!(x.clone(), y.clone(), z.clone()), &vec!(Assignment::new(&x, &Value::Enum("b".to_string()))));
+    assert!(!full.partial);
+    assert!(partial.partial);
+    assert_eq!(vec!("y -> true", "z -> 2", "x -> a"), full.assignments.iter().map(|a| a.to_string()).collect::<Vec<String>>());
+}
+
+#[test]
+#[should_panic(expected = "Error 2c7f9e31-4a8d-4b6e-9f15-8d3a6c0e7b49: Value 'c' not in the domain of variable 'x'.")]
+fn test_new_assignment_panic_1(){
+    let x = Variable::Enum(EnumVariable::new("x", "letters", &vec!("a", "b"), None));
+    Assignment::new(&x, &Value::Enum("c".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "Error a61d4b8e-3f2c-4e7a-b9d0-6e5c1f8a2d37: Value '1' has the wrong type for variable 'y'.")]
+fn test_new_assignment_panic_2(){
+    let y = Variable::Bool(BoolVariable::new("y", None));
+    Assignment::new(&y, &Value::Int(1));
+}
+
+#[test]
+fn test_try_new_reserved_names(){
+    assert_eq!("5b376941-3c6e-4b52-bec3-49eb8d9991bb", Parameter::try_new("TRUE", &true).unwrap_err().code());
+    assert_eq!("69e2abf9-498b-4d5c-88c7-30ea70ed27fb", EnumVariable::try_new("EMPTY", "letters", &vec!("a"), None).unwrap_err().code());
+    assert!(Parameter::try_new("param1", &true).is_ok());
+}
@@ -5,8 +5,7 @@ pub struct Activate {
 }
 
 pub struct StateToParamPredicate {
-    pub state: Vec<String>,
-    pub prob: ParamPlanningProblem,
+    pub state: State,
     pub ppred: ParamPredicate
 }
 
@@ -26,10 +25,17 @@ pub struct Compositional {
 
 impl Activate {
     pub fn new(params: &Vec<&Parameter>) -> Vec<Parameter> {
+        match Activate::try_new(params) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(params: &Vec<&Parameter>) -> SPResult<Vec<Parameter>> {
         let mut new_params: Vec<Parameter> = vec!();
         let mut activated_ff: bool = false;
         match params.iter().all(|x| x.value) {
-            true => panic!("Error 830d4128-68b0-42f2-9ec6-64717fd17b74: All parameters active in Activate."),
+            true => return Err(SPError::AllParamsActive { code: "830d4128-68b0-42f2-9ec6-64717fd17b74" }),
             false => {
                 for param in params {
                     if param.value | activated_ff {
@@ -42,35 +48,37 @@ impl Activate {
                 }
             }
         }
-        new_params
+        Ok(new_params)
     }
 }
 
 impl StateToParamPredicate {
-    pub fn new(state: &Vec<&str>, prob: &ParamPlanningProblem) -> ParamPredicate {
-        let mut pred_vec: Vec<Predicate> = vec!();
-        let prob_vars = GetParamProblemVars::new(prob);
-        for s in state {
-            let sep: Vec<&str> = s.split(" -> ").collect();
-            let mut var: EnumVariable = EnumVariable::default();
-            for v in &prob_vars {
-                match v.name == sep[0] {
-                    true => var = v.to_owned(),
-                    false => ()
-                };
-            };
-            pred_vec.push(Predicate::EQRL(var, String::from(sep[1])));
-        } 
-        ParamPredicate::new(&pred_vec.iter().map(|x| x).collect())
+    pub fn new(state: &State) -> ParamPredicate {
+        match StateToParamPredicate::try_new(state) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(state: &State) -> SPResult<ParamPredicate> {
+        let pred_vec: Vec<Predicate> = state.assignments.iter().map(|x| AssignmentToPredicate::try_new(x)).collect::<SPResult<Vec<Predicate>>>()?;
+        Ok(ParamPredicate::new(&pred_vec.iter().map(|x| x).collect()))
     }
 }
 
 impl Concatenate {
     pub fn new(results: &Vec<&ParamPlanningResult>) -> ParamPlanningResult {
+        match Concatenate::try_new(results) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(results: &Vec<&ParamPlanningResult>) -> SPResult<ParamPlanningResult> {
         match results.len() == 0 {
-            true => panic!("Error e1146f6a-9f3e-46df-8ec1-14f9b2bdd820: No results to concatenate."),
+            true => Err(SPError::NoResults { code: "e1146f6a-9f3e-46df-8ec1-14f9b2bdd820" }),
             false => {
-                ParamPlanningResult {
+                Ok(ParamPlanningResult {
                     plan_found: results.iter().all(|x| x.plan_found),
                     plan_length: results.iter().map(|x| x.plan_length).sum(),
                     level: results[0].level,
@@ -94,37 +102,35 @@ impl Concatenate {
                         conc_plan_trace
                     },
                     time_to_solve: results.iter().map(|x| x.time_to_solve).sum()
-                }
+                })
             }
         }
     }
 }
 
-// messy, will need to make state and assignment structs... also have to be tested
-// can we avoid making loops in the first place?
+// have to be tested, can we avoid making loops in the first place?
 impl RemoveLoops {
     pub fn new(result: &ParamPlanningResult) -> ParamPlanningResult {
         let mut duplicates: Vec<(PlanningFrame, usize, usize)> = vec!();
         let mut sorted_trace: Vec<PlanningFrame> = vec!();
 
         for r in &result.trace {
-            let mut sorted_state = r.state.clone();
-            sorted_state.sort();
-            let frame: PlanningFrame = PlanningFrame::new(&sorted_state.iter().map(|x| x.as_str()).collect(), &r.trans);
+            let sorted_state = State::new(&r.state.variables, &r.state.assignments);
+            let frame: PlanningFrame = PlanningFrame::new(&sorted_state, &r.trans);
             sorted_trace.push(frame);
         };
 
         for tr in &sorted_trace {
-            let start = match sorted_trace.iter().position(|x| x.state == tr.state) {
+            let start = match sorted_trace.iter().position(|x| x.state.assignments == tr.state.assignments) {
                 Some(y) => y as usize,
                 None => 666
             };
-            let finish = match sorted_trace.iter().rposition(|x| x.state == tr.state) {
+            let finish = match sorted_trace.iter().rposition(|x| x.state.assignments == tr.state.assignments) {
                 Some(y) => y as usize,
                 None => 666
             };
             if start != finish && start != 666 && finish != 666 {
-                if !duplicates.iter().any(|x| x.0.state == tr.state) {
+                if !duplicates.iter().any(|x| x.0.state.assignments == tr.state.assignments) {
                     duplicates.push((tr.to_owned(), start, finish))
                 }   
             }
@@ -139,11 +145,11 @@ impl RemoveLoops {
                 sorted_trace.drain(duplicates[0].1 + 1..duplicates[0].2 + 1).for_each(drop);
                 duplicates.remove(0);
                 if duplicates.len() != 0 {
-                    duplicates[0].1 = match sorted_trace.iter().position(|x| x.state == duplicates[0].0.state) {
+                    duplicates[0].1 = match sorted_trace.iter().position(|x| x.state.assignments == duplicates[0].0.state.assignments) {
                         Some(y) => y as usize,
                         None => 123456789
                     };
-                    duplicates[0].2 = match sorted_trace.iter().rposition(|x| x.state == duplicates[0].0.state) {
+                    duplicates[0].2 = match sorted_trace.iter().rposition(|x| x.state.assignments == duplicates[0].0.state.assignments) {
                         Some(y) => y as usize,
                         None => 123456789
                     };
@@ -151,11 +157,11 @@ impl RemoveLoops {
             } else {
                 duplicates.remove(0);
                 if duplicates.len() != 0 {
-                    duplicates[0].1 = match sorted_trace.iter().position(|x| x.state == duplicates[0].0.state) {
+                    duplicates[0].1 = match sorted_trace.iter().position(|x| x.state.assignments == duplicates[0].0.state.assignments) {
                         Some(y) => y as usize,
                         None => 123456789
                     };
-                    duplicates[0].2 = match sorted_trace.iter().rposition(|x| x.state == duplicates[0].0.state) {
+                    duplicates[0].2 = match sorted_trace.iter().rposition(|x| x.state.assignments == duplicates[0].0.state.assignments) {
                         Some(y) => y as usize,
                         None => 123456789
                     };
@@ -176,26 +182,33 @@ impl RemoveLoops {
 
 impl Compositional {
     pub fn new(prob: &ParamPlanningProblem, params: &Vec<&Parameter>) -> ParamPlanningResult {
+        match Compositional::try_new(prob, params) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(prob: &ParamPlanningProblem, params: &Vec<&Parameter>) -> SPResult<ParamPlanningResult> {
         let return_result = match params.iter().all(|x| !x.value) {
             true => {
-                let first_params = Activate::new(params);
-                let first_result = ParamIncremental::new(&prob, &first_params.iter().map(|x| x).collect(), &0, &0);
-                recursive_subfn(&first_result, &prob, &params, &0)
+                let first_params = Activate::try_new(params)?;
+                let first_result = ParamIncremental::try_new(&prob, &first_params.iter().map(|x| x).collect(), &0, &0)?;
+                recursive_subfn(&first_result, &prob, &params, &0)?
             },
             false => {
-                let first_result = ParamIncremental::new(&prob, &params.iter().map(|&x| x).collect(), &0, &0);
-                recursive_subfn(&first_result, &prob, &params, &0)
+                let first_result = ParamIncremental::try_new(&prob, &params.iter().map(|&x| x).collect(), &0, &0)?;
+                recursive_subfn(&first_result, &prob, &params, &0)?
             }
         };
 
-        fn recursive_subfn(result: &ParamPlanningResult, prob: &ParamPlanningProblem, params: &Vec<&Parameter>, level: &u32) -> ParamPlanningResult {
+        fn recursive_subfn(result: &ParamPlanningResult, prob: &ParamPlanningProblem, params: &Vec<&Parameter>, level: &u32) -> SPResult<ParamPlanningResult> {
             let level = level + 1;
             let mut final_result: ParamPlanningResult = result.to_owned();
             if !params.iter().all(|x| x.value) {
                 if result.plan_found {
-                    let mut inheritance: Vec<String> = vec!() ;
+                    let mut inheritance: State = State::default();
                     let mut level_subresults = vec!();
-                    let activated_params = Activate::new(&params);
+                    let activated_params = Activate::try_new(&params)?;
                     let mut concat: u32 = 0;
                     if result.plan_length != 0 {
                         for i in 0..=result.trace.len() - 1 {
@@ -204,78 +217,78 @@ impl Compositional {
                                     &format!("problem_l{:?}_c{:?}", level, concat),
                                     params,
                                     &prob.init,
-                                    &StateToParamPredicate::new(&result.trace[i + 1].state.iter().map(|x| x.as_str()).collect(), &prob),
+                                    &StateToParamPredicate::try_new(&result.trace[i + 1].state)?,
                                     &prob.trans,
                                     &prob.ltl_specs,
                                     &prob.max_steps
                                 );
-                                let next_result = ParamIncremental::new(&next_prob, &activated_params.iter().map(|x| x).collect(), &level, &concat);
+                                let next_result = ParamIncremental::try_new(&next_prob, &activated_params.iter().map(|x| x).collect(), &level, &concat)?;
                                 if next_result.plan_found {
                                     level_subresults.push(next_result.to_owned());
                                     match next_result.trace.last() {
                                         Some(x) => inheritance = x.state.clone(),
-                                        None => panic!("Error cb10dd80-f6dd-4ae1-9119-116d8ba09dfa: No tail in the plan.")
+                                        None => return Err(SPError::NoTail { code: "cb10dd80-f6dd-4ae1-9119-116d8ba09dfa" })
                                     }
                                 } else {
-                                    panic!("Error 66a7001a-67f1-4876-9928-b90b6aa55936: No plan found.")
+                                    return Err(SPError::NoPlanFound { code: "66a7001a-67f1-4876-9928-b90b6aa55936" })
                                 }
                                 concat = concat + 1;                       
                             } else if i == result.trace.len() - 1 {
                                 let next_prob = ParamPlanningProblem::new(
                                     &format!("problem_l{:?}_c{:?}", level, concat),
                                     params,
-                                    &StateToParamPredicate::new(&inheritance.iter().map(|x| x.as_str()).collect(), &prob),
+                                    &StateToParamPredicate::try_new(&inheritance)?,
                                     &prob.goal,
                                     &prob.trans,
                                     &prob.ltl_specs,
                                     &prob.max_steps
                                 );
-                                let next_result = ParamIncremental::new(&next_prob, &activated_params.iter().map(|x| x).collect(), &level, &concat);
+                                let next_result = ParamIncremental::try_new(&next_prob, &activated_params.iter().map(|x| x).collect(), &level, &concat)?;
                                 
                                 if next_result.plan_found {
                                     level_subresults.push(next_result.clone());
                                 } else {
-                                    panic!("Error b22dd6ed-cded-4424-89d6-b828c62aa0a1: No plan found.")
+                                    return Err(SPError::NoPlanFound { code: "b22dd6ed-cded-4424-89d6-b828c62aa0a1" })
                                 }
                                 concat = concat + 1;
                             } else {
                                 let next_prob = ParamPlanningProblem::new(
                                     &format!("problem_l{:?}_c{:?}", level, concat),
                                     params,
-                                    &StateToParamPredicate::new(&inheritance.iter().map(|x| x.as_str()).collect(), &prob),
-                                    &StateToParamPredicate::new(&result.trace[i + 1].state.iter().map(|x| x.as_str()).collect(), &prob),
+                                    &StateToParamPredicate::try_new(&inheritance)?,
+                                    &StateToParamPredicate::try_new(&result.trace[i + 1].state)?,
                                     &prob.trans,
                                     &prob.ltl_specs,
                                     &prob.max_steps
                                 );
-                                let next_result = ParamIncremental::new(&next_prob, &activated_params.iter().map(|x| x).collect(), &level, &concat);
+                                let next_result = ParamIncremental::try_new(&next_prob, &activated_params.iter().map(|x| x).collect(), &level, &concat)?;
                                 if next_result.plan_found {
                                     level_subresults.push(next_result.to_owned());
                                     match next_result.trace.last() {
                                         Some(x) => inheritance = x.state.clone(),
-                                        None => panic!("Error 5959aa62-0505-477e-a982-a826c4e25022: No tail in the plan.")
+                                        None => return Err(SPError::NoTail { code: "5959aa62-0505-477e-a982-a826c4e25022" })
                                     }
                                 } else {
-                                    panic!("Error 357bd649-0ffe-4e62-8b65-c2dac34f786f: No plan found.")
+                                    return Err(SPError::NoPlanFound { code: "357bd649-0ffe-4e62-8b65-c2dac34f786f" })
                                 }
                                 concat = concat + 1;   
                             }
                         } 
                     } else {
                         // have to investigate this step more... now it feels like a hack
-                        let activated_params = Activate::new(&params);
+                        let activated_params = Activate::try_new(&params)?;
                         let next_prob = ParamPlanningProblem::new(
                             &format!("problem_l{:?}_c{:?}", level, concat),
                             params,
                             &prob.init,
                             &prob.goal,
-                            // &StateToParamPredicate::new(&result.trace[0].state.iter().map(|x| x.as_str()).collect(), &prob),
-                            // &StateToParamPredicate::new(&result.trace[0].state.iter().map(|x| x.as_str()).collect(), &prob),
+                            // &StateToParamPredicate::new(&result.trace[0].state),
+                            // &StateToParamPredicate::new(&result.trace[0].state),
                             &prob.trans,
                             &prob.ltl_specs,
                             &prob.max_steps
                         );
-                        let next_result = ParamIncremental::new(&next_prob, &activated_params.iter().map(|x| x).collect(), &level, &concat);
+                        let next_result = ParamIncremental::try_new(&next_prob, &activated_params.iter().map(|x| x).collect(), &level, &concat)?;
                         if next_result.plan_found {
                             level_subresults.push(next_result.to_owned());
                             // match next_result.trace.last() {
@@ -283,11 +296,11 @@ impl Compositional {
                             //     None => panic!("No tail in the plan! 3")
                             // }
                         } else {
-                            panic!("Error 6e797cad-58f4-423d-8837-10521a986cfb: No plan found.")
+                            return Err(SPError::NoPlanFound { code: "6e797cad-58f4-423d-8837-10521a986cfb" })
                         }
                         // concat = concat + 1;   
                     }
-                    let level_result = Concatenate::new(&level_subresults.iter().map(|x| x).collect());
+                    let level_result = Concatenate::try_new(&level_subresults.iter().map(|x| x).collect())?;
                     for t in 0..level_result.trace.len() {
  
                         // println!("only_concat: {:?} : {:?}", t, level_result.trace[t].state);
@@ -302,12 +315,12 @@ impl Compositional {
                         // println!("delooped: {:?} : {:?}", t, delooped_and_sorted.trace[t].trans);
                         // println!("=========================");
                     }
-                    final_result = recursive_subfn(&delooped_and_sorted, &prob, &activated_params.iter().map(|x| x).collect(), &level);
+                    final_result = recursive_subfn(&delooped_and_sorted, &prob, &activated_params.iter().map(|x| x).collect(), &level)?;
                 }
             }
-            final_result
+            Ok(final_result)
         }
-        return_result
+        Ok(return_result)
     }
 }
   
@@ -332,6 +345,20 @@ fn test_activate_panic() {
     Activate::new(&params);
 }
 
+#[test]
+fn test_activate_try_new() {
+    let param_a = Parameter::new("a", &true);
+    let param_b = Parameter::new("b", &true);
+    let params = vec!(&param_a, &param_b);
+    assert_eq!(Err(SPError::AllParamsActive { code: "830d4128-68b0-42f2-9ec6-64717fd17b74" }), Activate::try_new(&params));
+}
+
+#[test]
+fn test_concatenate_try_new() {
+    let err = Concatenate::try_new(&vec!()).unwrap_err();
+    assert_eq!("Error e1146f6a-9f3e-46df-8ec1-14f9b2bdd820: No results to concatenate.", format!("{}", err));
+}
+
 #[test]
 fn test_compositional_1(){
 
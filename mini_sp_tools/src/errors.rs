@@ -0,0 +1,82 @@
+use std::fmt;
+
+/// Errors of the SP tools, the code is the same uuid that is printed when the
+/// panicking constructors fail, so it can be used as a stable error code.
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord)]
+pub enum SPError {
+    ReservedName { code: &'static str, kind: String, name: String },
+    NotInDomain { code: &'static str, value: String, var: String },
+    WrongType { code: &'static str, value: String, var: String },
+    SortMismatch { code: &'static str, left: String, right: String },
+    PredicateType { code: &'static str, r#type: String },
+    SelfReference { code: &'static str, var: String },
+    InvalidStep { code: &'static str, ltlf: String },
+    AllParamsActive { code: &'static str },
+    NoResults { code: &'static str },
+    NoTail { code: &'static str },
+    NoPlanFound { code: &'static str },
+    SolverUnknown { code: &'static str, reason: String },
+    Z3Error { code: &'static str, reason: String },
+    PlanningStopped { code: &'static str },
+    FileAccess { code: &'static str, path: String, reason: String }
+}
+
+pub type SPResult<T> = Result<T, SPError>;
+
+impl SPError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            SPError::ReservedName { code, .. } => code,
+            SPError::NotInDomain { code, .. } => code,
+            SPError::WrongType { code, .. } => code,
+            SPError::SortMismatch { code, .. } => code,
+            SPError::PredicateType { code, .. } => code,
+            SPError::SelfReference { code, .. } => code,
+            SPError::InvalidStep { code, .. } => code,
+            SPError::AllParamsActive { code } => code,
+            SPError::NoResults { code } => code,
+            SPError::NoTail { code } => code,
+            SPError::NoPlanFound { code } => code,
+            SPError::SolverUnknown { code, .. } => code,
+            SPError::Z3Error { code, .. } => code,
+            SPError::PlanningStopped { code } => code,
+            SPError::FileAccess { code, .. } => code
+        }
+    }
+}
+
+impl fmt::Display for SPError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Error {}: ", self.code())?;
+        match self {
+            SPError::ReservedName { kind, name, .. } => write!(f, "{} name '{}' is reserved.", kind, name),
+            SPError::NotInDomain { value, var, .. } => write!(f, "Value '{}' not in the domain of variable '{}'.", value, var),
+            SPError::WrongType { value, var, .. } => write!(f, "Value '{}' has the wrong type for variable '{}'.", value, var),
+            SPError::SortMismatch { left, right, .. } => write!(f, "Sorts '{}' and '{}' are incompatible.", left, right),
+            SPError::PredicateType { r#type, .. } => write!(f, "Predicate type '{}' is not allowed.", r#type),
+            SPError::SelfReference { var, .. } => write!(f, "Variable '{}' refers to itself outside of an update.", var),
+            SPError::InvalidStep { ltlf, .. } => write!(f, "from_step > until_step in {} ltlf.", ltlf),
+            SPError::AllParamsActive { .. } => write!(f, "All parameters active in Activate."),
+            SPError::NoResults { .. } => write!(f, "No results to concatenate."),
+            SPError::NoTail { .. } => write!(f, "No tail in the plan."),
+            SPError::NoPlanFound { .. } => write!(f, "No plan found."),
+            SPError::SolverUnknown { reason, .. } => write!(f, "Solver returned unknown, reason: '{}'.", reason),
+            SPError::Z3Error { reason, .. } => write!(f, "Z3 failed, reason: '{}'.", reason),
+            SPError::PlanningStopped { .. } => write!(f, "Planning stopped."),
+            SPError::FileAccess { path, reason, .. } => write!(f, "Can't write file '{}', reason: '{}'.", path, reason)
+        }
+    }
+}
+
+impl std::error::Error for SPError {}
+
+#[test]
+fn test_error_display_and_code(){
+    let err = SPError::NotInDomain {
+        code: "6f789b86-7f6c-4426-ab0f-6b5b72dd2c55",
+        value: "e".to_string(),
+        var: "x".to_string()
+    };
+    assert_eq!("6f789b86-7f6c-4426-ab0f-6b5b72dd2c55", err.code());
+    assert_eq!("Error 6f789b86-7f6c-4426-ab0f-6b5b72dd2c55: Value 'e' not in the domain of variable 'x'.", format!("{}", err));
+}
@@ -1,10 +1,22 @@
 use std::time::{Instant};
-use z3_sys::*;
 use mini_sp_smt::*;
 use super::*;
 
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fs::File;
 use std::io::prelude::*;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use crossbeam::channel::{self, Receiver, Sender};
+use crossbeam::select;
+
+// error codes of the planners that are reported at several places
+const PLANNING_TO_SMTLIB2_ERROR: &str = "5b83243d-b958-4b2f-9fb4-f4d54e99a382";
+const INCREMENTAL_ERROR: &str = "1df21171-16ea-4f99-a677-3f859cca67e1";
+const INCREMENTAL_STOPPED: &str = "7e2a9c41-5b8d-4f63-a1e7-3c9d0b6f8a52";
+const INCREMENTAL_DENIAL_ERROR: &str = "c7b164a9-f14c-46ab-8493-f1b9b13758b1";
+const MULT_GOALS_INCREMENTAL_ERROR: &str = "e96f2e9e-ca09-4166-a666-51dcfdcff031";
 
 #[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord)]
 pub struct Transition {
@@ -51,6 +63,14 @@ pub struct Incremental {
     pub prob: PlanningProblem
 }
 
+/// Stop button for a running planner. Clones share the same state, so one
+/// clone can be handed to the operator while the planner runs with another.
+#[derive(Debug, Clone, Default)]
+pub struct StopPlanning {
+    stopped: Arc<AtomicBool>,
+    listeners: Arc<Mutex<Vec<Sender<()>>>>
+}
+
 #[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord)]
 pub struct IncrementalDenial {
     pub prob: PlanningProblem
@@ -71,29 +91,38 @@ pub struct KeepVariableValues<'ctx> {
     pub ctx: &'ctx ContextZ3
 }
 
+#[derive(Clone)]
+pub struct BoundVariableDomains<'ctx> {
+    pub ctx: &'ctx ContextZ3
+}
+
+#[derive(Clone)]
+pub struct TransitionsAtStep<'ctx> {
+    pub ctx: &'ctx ContextZ3
+}
+
+pub struct PlanningToSmtLib2 {}
+
+pub struct WritePlanningSmtLib2 {}
+
 #[derive(PartialEq, Eq, Clone, Debug, PartialOrd, Ord)]
 pub struct PlanningFrame {
-    pub state: Vec<String>,
+    pub state: State,
     pub trans: String,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, PartialOrd, Ord)]
 pub struct PlanningFrame2 {
-    pub source: Vec<String>,
-    pub sink: Vec<String>,
+    pub source: State,
+    pub sink: State,
     pub trans: String,
 }
 
-// maybe implement in the future when all works
-// #[derive(PartialEq, Eq, Clone, Debug, PartialOrd, Ord)]
-// pub struct PlanningFrame {
-//     pub state: State,
-//     pub trans: Transition,
-// }
-
 pub struct GetPlanningResultZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub model: Z3_model,
+    pub model: Model<'ctx>,
+    pub vars: Vec<Variable>,
+    pub trans: Vec<Transition>,
     pub nr_steps: u32,
     pub frames: PlanningResult
 }
@@ -180,35 +209,224 @@ impl MultGoalsPlanningProblem {
 }
 
 impl <'ctx> KeepVariableValues<'ctx> {
-    pub fn new(ctx: &'ctx ContextZ3, vars: &Vec<EnumVariable>, trans: &Transition, step: &u32) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, vars: &Vec<Variable>, trans: &Transition, step: &u32) -> Ast<'ctx> {
 
-        let changed = GetPredicateVars::new(&trans.update);
+        let changed = GetUpdatedVars::new(&trans.update);
         let unchanged = IterOps::difference(vars, &changed);
         let mut assert_vec = vec!();
         for u in unchanged {
-            let sort = EnumSortZ3::new(&ctx, &u.r#type, u.domain.iter().map(|x| x.as_str()).collect());
-            let v_1 = EnumVarZ3::new(&ctx, sort.r, format!("{}_s{}", u.name.to_string(), step).as_str());
-            let v_2 = EnumVarZ3::new(&ctx, sort.r, format!("{}_s{}", u.name.to_string(), step - 1).as_str());
-            assert_vec.push(EQZ3::new(&ctx, v_1, v_2));
+            match u {
+                Variable::Bool(b) => {
+                    let v_1 = BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), format!("{}_s{}", b.name.to_string(), step).as_str());
+                    let v_2 = BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), format!("{}_s{}", b.name.to_string(), step - 1).as_str());
+                    assert_vec.push(EQZ3::new(&ctx, v_1, v_2));
+                },
+                Variable::Int(i) => {
+                    let v_1 = IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), format!("{}_s{}", i.name.to_string(), step).as_str());
+                    let v_2 = IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), format!("{}_s{}", i.name.to_string(), step - 1).as_str());
+                    assert_vec.push(EQZ3::new(&ctx, v_1, v_2));
+                },
+                Variable::Enum(e) => {
+                    let sort = EnumSortZ3::new(&ctx, &e.r#type, e.domain.iter().map(|x| x.as_str()).collect());
+                    let v_1 = EnumVarZ3::new(&ctx, &sort, format!("{}_s{}", e.name.to_string(), step).as_str());
+                    let v_2 = EnumVarZ3::new(&ctx, &sort, format!("{}_s{}", e.name.to_string(), step - 1).as_str());
+                    assert_vec.push(EQZ3::new(&ctx, v_1, v_2));
+                }
+            }
+        }
+        ANDZ3::new(&ctx, assert_vec)
+    }
+}
+
+impl <'ctx> BoundVariableDomains<'ctx> {
+    pub fn new(ctx: &'ctx ContextZ3, vars: &Vec<Variable>, step: &u32) -> Ast<'ctx> {
+        
+        // enum sorts already bound their variables, ints have to be bound explicitly
+        let mut assert_vec = vec!();
+        for var in vars {
+            if let Variable::Int(i) = var {
+                let v = IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), format!("{}_s{}", i.name.to_string(), step).as_str());
+                assert_vec.push(ORZ3::new(&ctx, i.domain.iter().map(|d| 
+                    EQZ3::new(&ctx, &v, IntZ3::new(&ctx, &IntSortZ3::new(&ctx), *d))).collect()));
+            }
         }
         ANDZ3::new(&ctx, assert_vec)
     }
 }
 
+impl <'ctx> TransitionsAtStep<'ctx> {
+    pub fn new(ctx: &'ctx ContextZ3, vars: &Vec<Variable>, trans: &Vec<Transition>, step: &u32) -> Ast<'ctx> {
+        match TransitionsAtStep::try_new(ctx, vars, trans, step) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    /// One of the transitions is taken from step - 1 to step, the variables it doesn't update keep their values.
+    pub fn try_new(ctx: &'ctx ContextZ3, vars: &Vec<Variable>, trans: &Vec<Transition>, step: &u32) -> SPResult<Ast<'ctx>> {
+        let mut all_trans = vec!();
+        for t in trans {
+            let name = format!("{}_t{}", &t.name, step);
+            let guard = PredicateToAstZ3::try_new(&ctx, &t.guard, "guard", &(step - 1))?;
+            let update = PredicateToAstZ3::try_new(&ctx, &t.update, "update", step)?;
+            let keeps = KeepVariableValues::new(&ctx, vars, &t, step);
+
+            all_trans.push(ANDZ3::new(&ctx,
+                vec!(EQZ3::new(&ctx,
+                    BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), name.as_str()),
+                    BoolZ3::new(&ctx, true)),
+                guard, update, keeps)));
+        }
+        Ok(ORZ3::new(&ctx, all_trans))
+    }
+}
+
+impl PlanningToSmtLib2 {
+    pub fn new(prob: &PlanningProblem, steps: &u32) -> String {
+        match PlanningToSmtLib2::try_new(prob, steps) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    /// The encoding that `Incremental` checks after `steps` transitions, as a standalone SMT-LIB2 benchmark:
+    /// init, the transitions and domain bounds of every step, and the specs and goal in the last step.
+    /// The benchmark is sat if and only if there is a plan of length `steps`.
+    pub fn try_new(prob: &PlanningProblem, steps: &u32) -> SPResult<String> {
+
+        let cfg = ConfigZ3::new();
+        let ctx = ContextZ3::new(&cfg);
+        let slv = SolverZ3::new(&ctx);
+
+        let problem_vars = GetProblemVars::new(&prob);
+        let all_vars = GetAllProblemVars::new(&prob);
+
+        SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &prob.init, "state", &0)?).map_err(z3_error(PLANNING_TO_SMTLIB2_ERROR))?;
+        SlvAssertZ3::try_new(&ctx, &slv, BoundVariableDomains::new(&ctx, &all_vars, &0)).map_err(z3_error(PLANNING_TO_SMTLIB2_ERROR))?;
+
+        for step in 1..steps + 1 {
+            SlvAssertZ3::try_new(&ctx, &slv, TransitionsAtStep::try_new(&ctx, &problem_vars, &prob.trans, &step)?).map_err(z3_error(PLANNING_TO_SMTLIB2_ERROR))?;
+            SlvAssertZ3::try_new(&ctx, &slv, BoundVariableDomains::new(&ctx, &all_vars, &step)).map_err(z3_error(PLANNING_TO_SMTLIB2_ERROR))?;
+        }
+
+        SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &prob.ltl_specs, "specs", steps)?).map_err(z3_error(PLANNING_TO_SMTLIB2_ERROR))?;
+        SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &prob.goal, "specs", steps)?).map_err(z3_error(PLANNING_TO_SMTLIB2_ERROR))?;
+
+        SlvToSmtLib2Z3::try_new(&ctx, &slv, &format!("{} at step {}", prob.name, steps)).map_err(z3_error(PLANNING_TO_SMTLIB2_ERROR))
+    }
+}
+
+impl WritePlanningSmtLib2 {
+    pub fn new(prob: &PlanningProblem, steps: &u32, path: &str) -> () {
+        match WritePlanningSmtLib2::try_new(prob, steps, path) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    /// Write the encoding from `PlanningToSmtLib2` to an .smt2 file.
+    pub fn try_new(prob: &PlanningProblem, steps: &u32, path: &str) -> SPResult<()> {
+        let script = PlanningToSmtLib2::try_new(prob, steps)?;
+        match File::create(path).and_then(|mut file| file.write_all(script.as_bytes())) {
+            Ok(()) => Ok(()),
+            Err(e) => Err(SPError::FileAccess {
+                code: "0d7c3b5e-8a41-4f96-b2e8-6c1f9a4d7e23",
+                path: path.to_string(),
+                reason: e.to_string()
+            })
+        }
+    }
+}
+
 impl Incremental {
     pub fn new(prob: &PlanningProblem) -> PlanningResult {
+        match Incremental::try_new(prob) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
 
+    pub fn try_new(prob: &PlanningProblem) -> SPResult<PlanningResult> {
         let cfg = ConfigZ3::new();
         let ctx = ContextZ3::new(&cfg);
-        let slv = SolverZ3::new(&ctx);
+        Incremental::plan(&ctx, prob, &StopPlanning::new(), None)
+    }
+
+    /// Plan like `Incremental::new`, and also return a snapshot of the solver
+    /// statistics after every check. The statistics accumulate over the checks.
+    pub fn new_with_statistics(prob: &PlanningProblem) -> (PlanningResult, Vec<StatisticsZ3>) {
+        match Incremental::try_new_with_statistics(prob) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new_with_statistics(prob: &PlanningProblem) -> SPResult<(PlanningResult, Vec<StatisticsZ3>)> {
+        let cfg = ConfigZ3::new();
+        let ctx = ContextZ3::new(&cfg);
+        let mut statistics = vec!();
+        let result = Incremental::plan(&ctx, prob, &StopPlanning::new(), Some(&mut statistics))?;
+        Ok((result, statistics))
+    }
+
+    /// Plan like `Incremental::new`, but give up as soon as `stop` is stopped,
+    /// also in the middle of a check.
+    ///
+    /// NOTE: The check is interrupted from a `crossbeam::scope` thread, since
+    /// `std::thread::scope` would raise the minimum supported Rust version to 1.63.
+    pub fn new_with_stop(prob: &PlanningProblem, stop: &StopPlanning) -> PlanningResult {
+        match Incremental::try_new_with_stop(prob, stop) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new_with_stop(prob: &PlanningProblem, stop: &StopPlanning) -> SPResult<PlanningResult> {
+        let cfg = ConfigZ3::new();
+        let ctx = ContextZ3::new(&cfg);
+        let handle = InterruptHandleZ3::new(&ctx);
+        let stopped = stop.listen();
+        let (done_sender, done) = channel::bounded::<()>(0);
+
+        // the watcher sleeps until the planning is stopped and then interrupts the running
+        // check once, the planner notices the stop before its next check. When the planner
+        // returns (or panics) the done sender is dropped and the watcher exits.
+        let result = crossbeam::scope(|s| {
+            s.spawn(|_| {
+                select! {
+                    recv(stopped) -> msg => if msg.is_ok() { handle.interrupt() },
+                    recv(done) -> _ => ()
+                }
+            });
+            let _done_sender = done_sender;
+            Incremental::plan(&ctx, prob, stop, None)
+        });
+        match result {
+            // a stop that lands between two checks cancels the next call to z3 instead,
+            // any other error is returned as it is
+            Ok(Err(SPError::Z3Error { ref reason, .. })) if stop.is_stopped() && reason.contains("canceled") =>
+                Err(SPError::PlanningStopped { code: INCREMENTAL_STOPPED }),
+            Ok(x) => x,
+            Err(e) => std::panic::resume_unwind(e)
+        }
+    }
+
+    // a snapshot of the statistics is pushed to `statistics` after every check, if given
+    fn plan(ctx: &ContextZ3, prob: &PlanningProblem, stop: &StopPlanning, mut statistics: Option<&mut Vec<StatisticsZ3>>) -> SPResult<PlanningResult> {
+        let slv = SolverZ3::new(ctx);
 
         let problem_vars = GetProblemVars::new(&prob);
 
-        SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.init, "state", &0));
+        // the trace also reports variables that only appear in init, goal or specs,
+        // and their domains have to be bound as well
+        let trace_vars = GetAllProblemVars::new(&prob);
+
+        SlvAssertZ3::try_new(ctx, &slv, PredicateToAstZ3::try_new(ctx, &prob.init, "state", &0)?).map_err(z3_error(INCREMENTAL_ERROR))?;
+        SlvAssertZ3::try_new(ctx, &slv, BoundVariableDomains::new(ctx, &trace_vars, &0)).map_err(z3_error(INCREMENTAL_ERROR))?;
 
-        SlvPushZ3::new(&ctx, &slv); // create backtracking point
-        SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.ltl_specs, "specs", &0));
-        SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.goal, "specs", &0));
+        let mut scope = slv.try_push().map_err(z3_error(INCREMENTAL_ERROR))?; // create backtracking point, popped when planning ends
+        SlvAssertZ3::try_new(ctx, &slv, PredicateToAstZ3::try_new(ctx, &prob.ltl_specs, "specs", &0)?).map_err(z3_error(INCREMENTAL_ERROR))?;
+        SlvAssertZ3::try_new(ctx, &slv, PredicateToAstZ3::try_new(ctx, &prob.goal, "specs", &0)?).map_err(z3_error(INCREMENTAL_ERROR))?;
 
         let now = Instant::now();
         let mut plan_found: bool = false;
@@ -217,28 +435,30 @@ impl Incremental {
 
         while step < prob.max_steps + 1 {
             step = step + 1;
-            if SlvCheckZ3::new(&ctx, &slv) != 1 {
-                SlvPopZ3::new(&ctx, &slv, 1);
-
-                let mut all_trans = vec!();
-                for t in &prob.trans {
-                    let name = format!("{}_t{}", &t.name, step);
-                    let guard = PredicateToAstZ3::new(&ctx, &t.guard, "guard", &(step - 1));
-                    let update = PredicateToAstZ3::new(&ctx, &t.update, "update", &(step));
-                    let keeps = KeepVariableValues::new(&ctx, &problem_vars, &t, &step);
-
-                    all_trans.push(ANDZ3::new(&ctx, 
-                        vec!(EQZ3::new(&ctx, 
-                            BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), name.as_str()), 
-                            BoolZ3::new(&ctx, true)),
-                        guard, update, keeps)));
+            if stop.is_stopped() {
+                return Err(SPError::PlanningStopped { code: INCREMENTAL_STOPPED })
+            }
+            let check = SlvCheckZ3::try_new(ctx, &slv).map_err(z3_error(INCREMENTAL_ERROR))?;
+            if let Some(x) = statistics.as_mut() {
+                x.push(SlvGetStatisticsZ3::try_new(ctx, &slv).map_err(z3_error(INCREMENTAL_ERROR))?);
+            }
+            if let SatResultZ3::Unknown(reason) = check {
+                if stop.is_stopped() {
+                    return Err(SPError::PlanningStopped { code: INCREMENTAL_STOPPED })
                 }
-
-                SlvAssertZ3::new(&ctx, &slv, ORZ3::new(&ctx, all_trans));
+                return Err(SPError::SolverUnknown { 
+                    code: "a3e5c7d9-2f4b-4e6a-8c1d-9b7f5e3a1c08", 
+                    reason
+                })
+            } else if check == SatResultZ3::Unsat {
+                scope.try_discard().map_err(z3_error(INCREMENTAL_ERROR))?;
+
+                SlvAssertZ3::try_new(ctx, &slv, TransitionsAtStep::try_new(ctx, &problem_vars, &prob.trans, &step)?).map_err(z3_error(INCREMENTAL_ERROR))?;
+                SlvAssertZ3::try_new(ctx, &slv, BoundVariableDomains::new(ctx, &trace_vars, &step)).map_err(z3_error(INCREMENTAL_ERROR))?;
                 
-                SlvPushZ3::new(&ctx, &slv);
-                SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.ltl_specs, "specs", &step));
-                SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.goal, "specs", &step));
+                scope = slv.try_push().map_err(z3_error(INCREMENTAL_ERROR))?;
+                SlvAssertZ3::try_new(ctx, &slv, PredicateToAstZ3::try_new(ctx, &prob.ltl_specs, "specs", &step)?).map_err(z3_error(INCREMENTAL_ERROR))?;
+                SlvAssertZ3::try_new(ctx, &slv, PredicateToAstZ3::try_new(ctx, &prob.goal, "specs", &step)?).map_err(z3_error(INCREMENTAL_ERROR))?;
                 
             } else {
                 plan_found = true;
@@ -248,27 +468,61 @@ impl Incremental {
 
         let planning_time = now.elapsed();
 
-        // let asserts = SlvGetAssertsZ3::new(&ctx, &slv);
-        // let asrtvec = Z3AstVectorToVectorAstZ3::new(&ctx, asserts);
+        // let asserts = SlvGetAssertsZ3::new(ctx, &slv);
+        // let asrtvec = Z3AstVectorToVectorAstZ3::new(ctx, asserts);
         // for asrt in asrtvec {
-        //     println!("{}", AstToStringZ3::new(&ctx, asrt));
+        //     println!("{}", AstToStringZ3::new(ctx, asrt));
         // }
-        // let cnf = GetCnfVectorZ3::new(&ctx, asrtvec);
+        // let cnf = GetCnfVectorZ3::new(ctx, asrtvec);
         
         if plan_found == true {
-            let model = SlvGetModelZ3::new(&ctx, &slv);
-            let result = GetPlanningResultZ3::new(&ctx, model, step, planning_time, plan_found);
-            result
+            let model = SlvGetModelZ3::try_new(ctx, &slv).map_err(z3_error(INCREMENTAL_ERROR))?;
+            GetPlanningResultZ3::try_new(ctx, &model, &trace_vars, &prob.trans, step, planning_time, plan_found)
         } else {
-            let model = FreshModelZ3::new(&ctx);
-            let result = GetPlanningResultZ3::new(&ctx, model, step, planning_time, plan_found);
-            result
+            let model = FreshModelZ3::new(ctx);
+            GetPlanningResultZ3::try_new(ctx, &model, &trace_vars, &prob.trans, step, planning_time, plan_found)
         }              
     }   
 }
 
+impl StopPlanning {
+    pub fn new() -> StopPlanning {
+        StopPlanning::default()
+    }
+
+    /// Stop the planners that run with this or a cloned `StopPlanning`.
+    pub fn stop(&self) -> () {
+        self.stopped.store(true, Ordering::SeqCst);
+        for listener in self.listeners.lock().unwrap().drain(..) {
+            let _ = listener.send(());
+        }
+    }
+
+    // a message is sent on the returned channel when the planning is stopped
+    fn listen(&self) -> Receiver<()> {
+        let (sender, receiver) = channel::unbounded();
+        let mut listeners = self.listeners.lock().unwrap();
+        match self.is_stopped() {
+            true => { let _ = sender.send(()); },
+            false => listeners.push(sender)
+        }
+        receiver
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+}
+
 impl IncrementalDenial {
     pub fn new(prob: &PlanningProblem, deny: &Vec<&PlanningResult>) -> PlanningResult {
+        match IncrementalDenial::try_new(prob, deny) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(prob: &PlanningProblem, deny: &Vec<&PlanningResult>) -> SPResult<PlanningResult> {
 
         let cfg = ConfigZ3::new();
         let ctx = ContextZ3::new(&cfg);
@@ -276,8 +530,13 @@ impl IncrementalDenial {
 
         let problem_vars = GetProblemVars::new(&prob);
 
-        SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.init, "state", &0));
-        SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.init, "state", &0));
+        // the trace also reports variables that only appear in init, goal or specs,
+        // and their domains have to be bound as well
+        let trace_vars = GetAllProblemVars::new(&prob);
+
+        SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &prob.init, "state", &0)?).map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
+        SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &prob.init, "state", &0)?).map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
+        SlvAssertZ3::try_new(&ctx, &slv, BoundVariableDomains::new(&ctx, &trace_vars, &0)).map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
 
         // deny previous solutions:
         let mut denied = vec!();
@@ -304,11 +563,11 @@ impl IncrementalDenial {
         // }
         
 
-        SlvAssertZ3::new(&ctx, &slv, ANDZ3::new(&ctx, denied));
+        SlvAssertZ3::try_new(&ctx, &slv, ANDZ3::new(&ctx, denied)).map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
 
-        SlvPushZ3::new(&ctx, &slv); // create backtracking point
-        SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.ltl_specs, "specs", &0));
-        SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.goal, "specs", &0));
+        let mut scope = slv.try_push().map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?; // create backtracking point, popped when planning ends
+        SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &prob.ltl_specs, "specs", &0)?).map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
+        SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &prob.goal, "specs", &0)?).map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
 
         let now = Instant::now();
         let mut plan_found: bool = false;
@@ -317,28 +576,21 @@ impl IncrementalDenial {
 
         while step < prob.max_steps + 1 {
             step = step + 1;
-            if SlvCheckZ3::new(&ctx, &slv) != 1 {
-                SlvPopZ3::new(&ctx, &slv, 1);
-
-                let mut all_trans = vec!();
-                for t in &prob.trans {
-                    let name = format!("{}_t{}", &t.name, step);
-                    let guard = PredicateToAstZ3::new(&ctx, &t.guard, "guard", &(step - 1));
-                    let update = PredicateToAstZ3::new(&ctx, &t.update, "update", &(step));
-                    let keeps = KeepVariableValues::new(&ctx, &problem_vars, &t, &step);
-
-                    all_trans.push(ANDZ3::new(&ctx, 
-                        vec!(EQZ3::new(&ctx, 
-                            BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), name.as_str()), 
-                            BoolZ3::new(&ctx, true)),
-                        guard, update, keeps)));
-                }
-
-                SlvAssertZ3::new(&ctx, &slv, ORZ3::new(&ctx, all_trans));
+            let check = SlvCheckZ3::try_new(&ctx, &slv).map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
+            if let SatResultZ3::Unknown(reason) = check {
+                return Err(SPError::SolverUnknown { 
+                    code: "d4f6a8b0-3c5e-4f7a-9d2e-0c8a6f4b2d19", 
+                    reason
+                })
+            } else if check == SatResultZ3::Unsat {
+                scope.try_discard().map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
+
+                SlvAssertZ3::try_new(&ctx, &slv, TransitionsAtStep::try_new(&ctx, &problem_vars, &prob.trans, &step)?).map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
+                SlvAssertZ3::try_new(&ctx, &slv, BoundVariableDomains::new(&ctx, &trace_vars, &step)).map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
                 
-                SlvPushZ3::new(&ctx, &slv);
-                SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.ltl_specs, "specs", &step));
-                SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.goal, "specs", &step));
+                scope = slv.try_push().map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
+                SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &prob.ltl_specs, "specs", &step)?).map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
+                SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &prob.goal, "specs", &step)?).map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
                 
             } else {
                 plan_found = true;
@@ -356,40 +608,47 @@ impl IncrementalDenial {
         // let cnf = GetCnfVectorZ3::new(&ctx, asrtvec);
         
         if plan_found == true {
-            let model = SlvGetModelZ3::new(&ctx, &slv);
-            let result = GetPlanningResultZ3::new(&ctx, model, step, planning_time, plan_found);
-            result
+            let model = SlvGetModelZ3::try_new(&ctx, &slv).map_err(z3_error(INCREMENTAL_DENIAL_ERROR))?;
+            let result = GetPlanningResultZ3::try_new(&ctx, &model, &trace_vars, &prob.trans, step, planning_time, plan_found)?;
+            Ok(result)
         } else {
             let model = FreshModelZ3::new(&ctx);
-            let result = GetPlanningResultZ3::new(&ctx, model, step, planning_time, plan_found);
-            result
+            let result = GetPlanningResultZ3::try_new(&ctx, &model, &trace_vars, &prob.trans, step, planning_time, plan_found)?;
+            Ok(result)
         }              
     }   
 }
 
 impl IncrementalAll {
     pub fn new(prob: &PlanningProblem, many: u32) -> Vec<PlanningResult> {
+        match IncrementalAll::try_new(prob, many) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(prob: &PlanningProblem, many: u32) -> SPResult<Vec<PlanningResult>> {
 
         let mut res = vec!();
-        let first_result = Incremental::new(&prob);
+        let first_result = Incremental::try_new(&prob)?;
         res.push(first_result.clone());
 
         let return_result = match first_result.plan_found {
             false => res,
-            true => recursive_subfn(prob, res)
+            true => recursive_subfn(prob, res)?
         };
 
-        fn recursive_subfn(prob: &PlanningProblem, mut results: Vec<PlanningResult>) -> Vec<PlanningResult> {
+        fn recursive_subfn(prob: &PlanningProblem, mut results: Vec<PlanningResult>) -> SPResult<Vec<PlanningResult>> {
             let mut final_results = results.to_owned();
 
             if results.iter().all(|x| x.plan_found == true) {
-                results.push(IncrementalDenial::new(&prob, &results.iter().map(|x| x).collect()));
-                final_results = recursive_subfn(&prob, results.clone());
+                results.push(IncrementalDenial::try_new(&prob, &results.iter().map(|x| x).collect())?);
+                final_results = recursive_subfn(&prob, results.clone())?;
             }
-            final_results
+            Ok(final_results)
         }
 
-        return_result
+        Ok(return_result)
 
     
     }
@@ -428,8 +687,7 @@ impl GenerateDigraph {
         let mut file = File::create("graph.dot")?;
         file.write(b"digraph example1 {")?;
         for s in states {
-            // s.iter().map(|x| x.split(" -> "))
-            let mut name = s.join("");
+            let mut name = s.assignments.iter().map(|x| x.to_string()).collect::<Vec<String>>().join("");
             name.retain(|c| c != '>');
             name.retain(|c| c != '-');
             name.retain(|c| c != ' ');
@@ -441,8 +699,8 @@ impl GenerateDigraph {
         }
 
         for res in results {
-            let mut nsource = res.source.join("");
-            let mut nsink = res.sink.join("");
+            let mut nsource = res.source.assignments.iter().map(|x| x.to_string()).collect::<Vec<String>>().join("");
+            let mut nsink = res.sink.assignments.iter().map(|x| x.to_string()).collect::<Vec<String>>().join("");
             nsource.retain(|c| c != '>');
             nsink.retain(|c| c != '>');
             nsource.retain(|c| c != '-');
@@ -483,6 +741,13 @@ impl GenerateDigraph {
 
 impl MultGoalsIncremental {
     pub fn new(prob: &MultGoalsPlanningProblem) -> PlanningResult {
+        match MultGoalsIncremental::try_new(prob) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(prob: &MultGoalsPlanningProblem) -> SPResult<PlanningResult> {
 
         let cfg = ConfigZ3::new();
         let ctx = ContextZ3::new(&cfg);
@@ -497,17 +762,30 @@ impl MultGoalsIncremental {
             &prob.max_steps)
         );
 
-        SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.init, "state", &0));
+        // the trace also reports variables that only appear in init, goals or specs,
+        // and their domains have to be bound as well
+        let mut trace_vars = problem_vars.clone();
+        trace_vars.extend(GetPredicateVars::new(&prob.init));
+        trace_vars.extend(GetPredicateVars::new(&prob.ltl_specs));
+        for g in &prob.goals {
+            trace_vars.extend(GetPredicateVars::new(&g.0));
+            trace_vars.extend(GetPredicateVars::new(&g.1));
+        }
+        trace_vars.sort();
+        trace_vars.dedup();
 
-        SlvPushZ3::new(&ctx, &slv); // create backtracking point
-        SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.ltl_specs, "specs", &0));
+        SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &prob.init, "state", &0)?).map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?;
+        SlvAssertZ3::try_new(&ctx, &slv, BoundVariableDomains::new(&ctx, &trace_vars, &0)).map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?;
+
+        let mut scope = slv.try_push().map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?; // create backtracking point, popped when planning ends
+        SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &prob.ltl_specs, "specs", &0)?).map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?;
         for g in &prob.goals {
-            SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &Predicate::EVENTUALLY(Box::new(g.0.to_owned())), "state", &0));
+            SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &Predicate::EVENTUALLY(Box::new(g.0.to_owned())), "state", &0)?).map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?;
             match g.1 {
                 Predicate::TRUE => {
                     
                 },
-                _ => SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &Predicate::UNTIL(Box::new(g.1.to_owned()), Box::new(g.0.to_owned())), "specs", &0))
+                _ => SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &Predicate::UNTIL(Box::new(g.1.to_owned()), Box::new(g.0.to_owned())), "specs", &0)?).map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?
             }
         }
 
@@ -518,35 +796,28 @@ impl MultGoalsIncremental {
 
         while step < prob.max_steps + 1 {
             step = step + 1;
-            if SlvCheckZ3::new(&ctx, &slv) != 1 {
-                SlvPopZ3::new(&ctx, &slv, 1);
-
-                let mut all_trans = vec!();
-                for t in &prob.trans {
-                    let name = format!("{}_t{}", &t.name, step);
-                    let guard = PredicateToAstZ3::new(&ctx, &t.guard, "guard", &(step - 1));
-                    let update = PredicateToAstZ3::new(&ctx, &t.update, "update", &(step));
-                    let keeps = KeepVariableValues::new(&ctx, &problem_vars, &t, &step);
-
-                    all_trans.push(ANDZ3::new(&ctx, 
-                        vec!(EQZ3::new(&ctx, 
-                            BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), name.as_str()), 
-                            BoolZ3::new(&ctx, true)),
-                        guard, update, keeps)));
-                }
-
-                SlvAssertZ3::new(&ctx, &slv, ORZ3::new(&ctx, all_trans));
+            let check = SlvCheckZ3::try_new(&ctx, &slv).map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?;
+            if let SatResultZ3::Unknown(reason) = check {
+                return Err(SPError::SolverUnknown { 
+                    code: "e5a7b9c1-4d6f-4a8b-8e3f-1d9b7a5c3e20", 
+                    reason
+                })
+            } else if check == SatResultZ3::Unsat {
+                scope.try_discard().map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?;
+
+                SlvAssertZ3::try_new(&ctx, &slv, TransitionsAtStep::try_new(&ctx, &problem_vars, &prob.trans, &step)?).map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?;
+                SlvAssertZ3::try_new(&ctx, &slv, BoundVariableDomains::new(&ctx, &trace_vars, &step)).map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?;
                 
-                SlvPushZ3::new(&ctx, &slv);
-                SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &prob.ltl_specs, "specs", &step));
+                scope = slv.try_push().map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?;
+                SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &prob.ltl_specs, "specs", &step)?).map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?;
                 for g in &prob.goals {
-                    SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &Predicate::EVENTUALLY(Box::new(g.0.to_owned())), "state", &step));
+                    SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &Predicate::EVENTUALLY(Box::new(g.0.to_owned())), "state", &step)?).map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?;
                     match g.1 {
                         Predicate::TRUE => {
                             
                         },
                         _ => 
-                        SlvAssertZ3::new(&ctx, &slv, PredicateToAstZ3::new(&ctx, &Predicate::UNTIL(Box::new(g.1.to_owned()), Box::new(g.0.to_owned())), "specs", &step))
+                        SlvAssertZ3::try_new(&ctx, &slv, PredicateToAstZ3::try_new(&ctx, &Predicate::UNTIL(Box::new(g.1.to_owned()), Box::new(g.0.to_owned())), "specs", &step)?).map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?
                     }
                 }
         
@@ -566,13 +837,13 @@ impl MultGoalsIncremental {
         // let cnf = GetCnfVectorZ3::new(&ctx, asrtvec);
         
         if plan_found == true {
-            let model = SlvGetModelZ3::new(&ctx, &slv);
-            let result = GetPlanningResultZ3::new(&ctx, model, step, planning_time, plan_found);
-            result
+            let model = SlvGetModelZ3::try_new(&ctx, &slv).map_err(z3_error(MULT_GOALS_INCREMENTAL_ERROR))?;
+            let result = GetPlanningResultZ3::try_new(&ctx, &model, &trace_vars, &prob.trans, step, planning_time, plan_found)?;
+            Ok(result)
         } else {
             let model = FreshModelZ3::new(&ctx);
-            let result = GetPlanningResultZ3::new(&ctx, model, step, planning_time, plan_found);
-            result
+            let result = GetPlanningResultZ3::try_new(&ctx, &model, &trace_vars, &prob.trans, step, planning_time, plan_found)?;
+            Ok(result)
         }              
     }   
 }
@@ -587,19 +858,19 @@ impl MultGoalsIncremental {
 // }
 
 impl PlanningFrame {
-    pub fn new(state: &Vec<&str>, trans: &str) -> PlanningFrame {
+    pub fn new(state: &State, trans: &str) -> PlanningFrame {
         PlanningFrame {
-            state: state.iter().map(|x| x.to_string()).collect(),
+            state: state.to_owned(),
             trans: trans.to_string()
         }
     }
 }
 
 impl PlanningFrame2 {
-    pub fn new(source: &Vec<&str>, sink: &Vec<&str>, trans: &str) -> PlanningFrame2 {
+    pub fn new(source: &State, sink: &State, trans: &str) -> PlanningFrame2 {
         PlanningFrame2 {
-            source: source.iter().map(|x| x.to_string()).collect(),
-            sink: sink.iter().map(|x| x.to_string()).collect(),
+            source: source.to_owned(),
+            sink: sink.to_owned(),
             trans: trans.to_string()
         }
     }
@@ -607,77 +878,115 @@ impl PlanningFrame2 {
 
 
 impl <'ctx> GetPlanningResultZ3<'ctx> {
-    pub fn new(ctx: &'ctx ContextZ3, model: Z3_model, nr_steps: u32, 
+    pub fn new(ctx: &'ctx ContextZ3, model: &Model<'ctx>, vars: &Vec<Variable>, trans: &[Transition], nr_steps: u32, 
     planning_time: std::time::Duration, plan_found: bool) -> PlanningResult {
-        let model_str = ModelToStringZ3::new(&ctx, model);
-        let mut model_vec = vec!();
+        match GetPlanningResultZ3::try_new(ctx, model, vars, trans, nr_steps, planning_time, plan_found) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
 
-        let num = ModelGetNumConstsZ3::new(&ctx, model);
-        let mut lines = model_str.lines();
-        let mut i: u32 = 0;
+    pub fn try_new(ctx: &'ctx ContextZ3, model: &Model<'ctx>, vars: &Vec<Variable>, trans: &[Transition], nr_steps: u32, 
+    planning_time: std::time::Duration, plan_found: bool) -> SPResult<PlanningResult> {
 
-        while i < num {
-            model_vec.push(lines.next().unwrap_or(""));
-            i = i + 1;
+        // map the const names back to the variables, transitions and steps they were made from
+        let mut state_map: HashMap<String, (u32, &Variable)> = HashMap::new();
+        let mut trans_map: HashMap<String, (u32, usize)> = HashMap::new();
+        for i in 0..nr_steps {
+            for v in vars {
+                state_map.insert(format!("{}_s{}", v.name(), i), (i, v));
+            }
+            for (pos, t) in trans.iter().enumerate() {
+                trans_map.insert(format!("{}_t{}", t.name, i), (i, pos));
+            }
         }
 
-        // println!("{:#?}", model_vec);
+        let mut assignments: Vec<Vec<Assignment>> = (0..nr_steps).map(|_| vec!()).collect();
+        let mut taken: Vec<Option<usize>> = (0..nr_steps).map(|_| None).collect();
 
-        let mut trace: Vec<PlanningFrame> = vec!();
-        let mut raw_trace: Vec<PlanningFrame> = vec!();
-        
-        for i in 0..nr_steps {
-            let mut frame: PlanningFrame = PlanningFrame::new(&vec!(), "");
-            let mut raw_frame: PlanningFrame = PlanningFrame::new(&vec!(), "");
-            for j in &model_vec {
-                let sep: Vec<&str> = j.split(" -> ").collect();
-                if sep[0].ends_with(&format!("_s{}", i)){
-                    // raw_frame.state.push(j.to_string());
-                    let trimmed_state = sep[0].trim_end_matches(&format!("_s{}", i));
-                    match sep[1] {
-                        "false" => {
-                            frame.state.push(sep[0].to_string());
-                            // raw_frame.state.push(j.to_string());
-                        },
-                        "true" => {
-                            frame.state.push(sep[0].to_string());
-                            // raw_frame.state.push(j.to_string());
+        let num = ModelGetNumConstsZ3::new(&ctx, model);
+        for k in 0..num {
+            let decl = ModelGetConstDeclZ3::new(&ctx, model, k);
+            let name = Z3StringToStringZ3::new(GetSymbolStringZ3::new(&ctx, GetDeclNameZ3::new(&ctx, model, &decl)));
+            let value = ModelEvalZ3::try_new(&ctx, model, ModelGetConstInterpZ3::new(&ctx, model, &decl), true)
+                .map_err(z3_error("8c2e4a6f-1b3d-4f5e-9a7c-0d2b4f6e8a13"))?;
+            match state_map.get(&name) {
+                Some((i, v)) => {
+                    let val = match (v, value) {
+                        (Variable::Bool(_), ModelValueZ3::Bool(x)) => Value::Bool(x),
+                        // an int that doesn't fit in an i32 can't be in the domain
+                        (Variable::Int(_), ModelValueZ3::Int(x)) => match i32::try_from(x) {
+                            Ok(x) => Value::Int(x),
+                            Err(_) => return Err(SPError::NotInDomain {
+                                code: "3f5a7c9e-2d4b-4e6f-8a1c-5b7d9f1e3a26",
+                                value: x.to_string(),
+                                var: v.name()
+                            })
                         },
-                        _ => {
-                            frame.state.push(format!("{} -> {}", trimmed_state, sep[1]));
-                            // raw_frame.state.push(j.to_string());
+                        (Variable::Enum(_), ModelValueZ3::Enum(x)) => Value::Enum(x),
+                        (_, x) => return Err(SPError::WrongType {
+                            code: "6a8c0e2f-4b6d-4f8a-9c3e-7d9f1b3a5c48",
+                            value: x.to_string(),
+                            var: v.name()
+                        })
+                    };
+                    assignments[*i as usize].push(Assignment::try_new(v, &val)?);
+                },
+                None => match trans_map.get(&name) {
+                    // if more transitions are true in a step, take the first one in the problem
+                    Some((i, pos)) if value == ModelValueZ3::Bool(true) => {
+                        taken[*i as usize] = match taken[*i as usize] {
+                            Some(prev) if prev < *pos => Some(prev),
+                            _ => Some(*pos)
                         }
-                    }
-                } else if sep[0].ends_with(&format!("_t{}", i)) && sep[1] == "true" {
-                    let trimmed_trans = sep[0].trim_end_matches(&format!("_t{}", i));
-                    frame.trans = trimmed_trans.to_string();  
-                    raw_frame.trans = sep[0].to_string();
+                    },
+                    _ => ()
                 }
             }
-            if model_vec.len() != 0 {
-                trace.push(frame);
-                raw_trace.push(raw_frame);
+        }
+
+        let mut trace: Vec<PlanningFrame> = vec!();
+        let mut raw_trace: Vec<PlanningFrame> = vec!();
+
+        if num != 0 {
+            for i in 0..nr_steps {
+                let state = State::new(vars, &assignments[i as usize]);
+                match taken[i as usize] {
+                    Some(pos) => {
+                        trace.push(PlanningFrame::new(&state, &trans[pos].name));
+                        raw_trace.push(PlanningFrame::new(&State::default(), &format!("{}_t{}", trans[pos].name, i)));
+                    },
+                    None => {
+                        trace.push(PlanningFrame::new(&state, ""));
+                        raw_trace.push(PlanningFrame::new(&State::default(), ""));
+                    }
+                }
             }
         }
 
-        PlanningResult {
+        Ok(PlanningResult {
             plan_found: plan_found,
             plan_length: nr_steps - 1,
             trace: trace,
             raw_trace: raw_trace,
             time_to_solve: planning_time,
-        }
+        })
     }
 }
 
+/// Keep the Z3 error message in an `SPError`, the code tells which planner failed.
+fn z3_error(code: &'static str) -> impl Fn(ErrorZ3) -> SPError {
+    move |e| SPError::Z3Error { code, reason: e.to_string() }
+}
+
 impl GetPlanningResult2Z3 {
     pub fn new(prob: &PlanningResult) -> PlanningResult2 {
     
         let mut new_trace = vec!();
         let mut new = prob.trace.iter();
-        let mut prev = vec!();
+        let mut prev = State::default();
         'breakable: loop {
-            let mut frame: PlanningFrame2 = PlanningFrame2::new(&vec!(), &vec!(), "");
+            let mut frame: PlanningFrame2 = PlanningFrame2::new(&State::default(), &State::default(), "");
             
             // match new.next() {
             //     Some(x) => frame.source = x.state.clone(),
@@ -1124,8 +1433,374 @@ fn test_incremental_1(){
     for t in &result.trace{
  
         println!("state: {:?}", t.state);
-        println!("state_pred: {:?}", StateToPredicate::new(&t.state.iter().map(|x| x.as_str()).collect(), &problem));
+        println!("state_pred: {:?}", StateToPredicate::new(&t.state));
         println!("trans: {:?}", t.trans);
         println!("=========================");
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_incremental_bool_and_int(){
+
+    let count = IntVariable::new("count", &vec!(&0, &1, &2, &3), None);
+    let done = BoolVariable::new("done", None);
+    let tag = BoolVariable::new("tag", None);
+
+    let t1 = Transition::new(
+        "count_to_1", 
+        &Predicate::AND(vec!(Predicate::IEQRL(count.clone(), 0), Predicate::BEQRL(done.clone(), false))),
+        &Predicate::IEQRL(count.clone(), 1)
+    );
+
+    let t2 = Transition::new(
+        "count_to_2", 
+        &Predicate::IEQRL(count.clone(), 1),
+        &Predicate::IEQRL(count.clone(), 2)
+    );
+
+    let t3 = Transition::new(
+        "count_to_3", 
+        &Predicate::IEQRL(count.clone(), 2),
+        &Predicate::IEQRL(count.clone(), 3)
+    );
+
+    let t4 = Transition::new(
+        "finish", 
+        &Predicate::AND(vec!(Predicate::IEQRL(count.clone(), 3), Predicate::BEQRL(done.clone(), false))),
+        &Predicate::BEQRL(done.clone(), true)
+    );
+
+    let init = Predicate::AND(vec!(Predicate::IEQRL(count.clone(), 0), Predicate::BEQRL(done.clone(), false), Predicate::BEQRL(tag.clone(), true)));
+    let goal = Predicate::BEQRL(done.clone(), true);
+
+    let problem = PlanningProblem::new("counter", &init, &goal, &vec!(t1, t2, t3, t4), &Predicate::TRUE, &10);
+    let result = Incremental::new(&problem);
+
+    assert!(result.plan_found);
+    assert_eq!(4, result.plan_length);
+    assert_eq!(vec!("", "count_to_1", "count_to_2", "count_to_3", "finish"), 
+        result.trace.iter().map(|x| x.trans.as_str()).collect::<Vec<&str>>());
+
+    // tag only appears in init, so it is unassigned in the later states
+    let first = &result.trace[0].state;
+    let last = &result.trace[4].state;
+    assert!(!first.partial);
+    assert!(last.partial);
+    assert_eq!(3, last.variables.len());
+    assert_eq!(vec!("done -> false", "tag -> true", "count -> 0"), first.assignments.iter().map(|x| x.to_string()).collect::<Vec<String>>());
+    assert_eq!(vec!(
+        Assignment::new(&Variable::Bool(done.clone()), &Value::Bool(true)), 
+        Assignment::new(&Variable::Int(count.clone()), &Value::Int(3))), last.assignments);
+
+    let pred = StateToPredicate::new(&last);
+    assert_eq!(Predicate::AND(vec!(Predicate::BEQRL(done, true), Predicate::IEQRL(count, 3))), pred);
+}
+
+#[test]
+fn test_incremental_relative_update(){
+
+    let count = IntVariable::new("count", &vec!(&0, &2, &3, &4, &5), None);
+
+    let inc = Transition::new(
+        "inc", 
+        &Predicate::ILT(Term::VAR(count.clone()), Term::VAL(5)),
+        &Predicate::IEQRT(count.clone(), Term::ADD(vec!(Term::VAR(count.clone()), Term::VAL(2))))
+    );
+
+    let dec = Transition::new(
+        "dec", 
+        &Predicate::TRUE,
+        &Predicate::IEQRT(count.clone(), Term::SUB(vec!(Term::VAR(count.clone()), Term::VAL(1))))
+    );
+
+    let init = Predicate::IEQRL(count.clone(), 0);
+    let goal = Predicate::IEQRL(count.clone(), 5);
+
+    let problem = PlanningProblem::new("relative", &init, &goal, &vec!(inc, dec), &Predicate::TRUE, &10);
+    let result = Incremental::new(&problem);
+
+    // 0 -> 2 -> 4 -> 3 -> 5 is the only plan, since 1 and 6 are out of the domain
+    assert!(result.plan_found);
+    assert_eq!(4, result.plan_length);
+    assert_eq!(vec!("count -> 0", "count -> 2", "count -> 4", "count -> 3", "count -> 5"), 
+        result.trace.iter().map(|x| x.state.assignments[0].to_string()).collect::<Vec<String>>());
+}
+
+#[test]
+fn test_incremental_relative_update_keeps_rhs(){
+
+    // y is only read by the update, so it has to keep its value
+    let x = IntVariable::new("x", &vec!(&0, &1, &2, &3, &4, &5), None);
+    let y = IntVariable::new("y", &vec!(&0, &1, &2, &3), None);
+
+    let sub = Transition::new(
+        "sub", 
+        &Predicate::TRUE,
+        &Predicate::IEQRT(x.clone(), Term::SUB(vec!(Term::VAR(x.clone()), Term::VAR(y.clone()))))
+    );
+
+    let init = Predicate::AND(vec!(Predicate::IEQRL(x.clone(), 5), Predicate::IEQRL(y.clone(), 2)));
+    let goal = Predicate::IEQRL(x.clone(), 1);
+
+    let problem = PlanningProblem::new("keeps_rhs", &init, &goal, &vec!(sub.clone()), &Predicate::TRUE, &5);
+    let result = Incremental::new(&problem);
+
+    assert!(result.plan_found);
+    assert_eq!(2, result.plan_length);
+    assert_eq!(vec!("y -> 2", "y -> 2", "y -> 2"), 
+        result.trace.iter().map(|s| s.state.assignments.iter().find(|a| a.var.name() == "y").unwrap().to_string()).collect::<Vec<String>>());
+
+    // 5 -> 3 -> 1 and then out of the domain, changing y to reach 0 is not allowed
+    let goal = Predicate::IEQRL(x.clone(), 0);
+    let problem = PlanningProblem::new("keeps_rhs", &init, &goal, &vec!(sub), &Predicate::TRUE, &5);
+    let result = Incremental::new(&problem);
+
+    assert!(!result.plan_found);
+}
+
+#[test]
+fn test_incremental_goal_only_int(){
+
+    // level only appears in the goal, its domain still has to be bound in every step
+    let level = IntVariable::new("level", &vec!(&0, &1, &2), None);
+    let done = BoolVariable::new("done", None);
+
+    let t1 = Transition::new(
+        "finish", 
+        &Predicate::BEQRL(done.clone(), false),
+        &Predicate::BEQRL(done.clone(), true)
+    );
+
+    let init = Predicate::BEQRL(done.clone(), false);
+    let goal = Predicate::AND(vec!(Predicate::BEQRL(done.clone(), true), Predicate::IGT(Term::VAR(level.clone()), Term::VAL(1))));
+
+    let problem = PlanningProblem::new("goal_only", &init, &goal, &vec!(t1.clone()), &Predicate::TRUE, &5);
+    let result = Incremental::new(&problem);
+
+    assert!(result.plan_found);
+    assert_eq!(1, result.plan_length);
+    assert!(result.trace[1].state.assignments.contains(&Assignment::new(&Variable::Int(level.clone()), &Value::Int(2))));
+
+    let goal = Predicate::AND(vec!(Predicate::BEQRL(done.clone(), true), Predicate::IGT(Term::VAR(level.clone()), Term::VAL(2))));
+    let problem = PlanningProblem::new("goal_only", &init, &goal, &vec!(t1), &Predicate::TRUE, &5);
+    let result = Incremental::new(&problem);
+
+    assert!(!result.plan_found);
+}
+
+#[test]
+fn test_incremental_suffix_names(){
+
+    // names that end like step suffixes used to confuse the trace extraction
+    let pos = EnumVariable::new("pos_s1", "pose", &vec!("a", "b", "c"), None);
+
+    let t1 = Transition::new(
+        "a_to_b_t1", 
+        &Predicate::EQRL(pos.clone(), String::from("a")),
+        &Predicate::EQRL(pos.clone(), String::from("b"))
+    );
+
+    let t2 = Transition::new(
+        "b_to_c_t", 
+        &Predicate::EQRL(pos.clone(), String::from("b")),
+        &Predicate::EQRL(pos.clone(), String::from("c"))
+    );
+
+    let init = Predicate::EQRL(pos.clone(), String::from("a"));
+    let goal = Predicate::EQRL(pos.clone(), String::from("c"));
+
+    let problem = PlanningProblem::new("suffixes", &init, &goal, &vec!(t1, t2), &Predicate::TRUE, &10);
+    let result = Incremental::new(&problem);
+
+    assert!(result.plan_found);
+    assert_eq!(2, result.plan_length);
+    assert_eq!(vec!("", "a_to_b_t1", "b_to_c_t"), 
+        result.trace.iter().map(|x| x.trans.as_str()).collect::<Vec<&str>>());
+    assert_eq!(vec!("", "a_to_b_t1_t1", "b_to_c_t_t2"), 
+        result.raw_trace.iter().map(|x| x.trans.as_str()).collect::<Vec<&str>>());
+    assert_eq!(vec!("pos_s1 -> a", "pos_s1 -> b", "pos_s1 -> c"), 
+        result.trace.iter().map(|x| x.state.assignments[0].to_string()).collect::<Vec<String>>());
+}
+
+#[test]
+fn test_incremental_try_new_domain_error(){
+
+    let pos = EnumVariable::new("pos", "pos", &vec!("a", "b"), None);
+
+    let t1 = Transition::new(
+        "a_to_b",
+        &Predicate::EQRL(pos.clone(), String::from("a")),
+        &Predicate::EQRL(pos.clone(), String::from("b"))
+    );
+
+    let problem = PlanningProblem::new(
+        "problem_1",
+        &Predicate::EQRL(pos.clone(), String::from("a")),
+        &Predicate::EQRL(pos.clone(), String::from("c")),
+        &vec!(t1),
+        &Predicate::TRUE,
+        &5
+    );
+
+    let result = Incremental::try_new(&problem);
+    assert_eq!("6f789b86-7f6c-4426-ab0f-6b5b72dd2c55", result.unwrap_err().code());
+}
+
+#[test]
+fn test_get_planning_result_try_new_errors(){
+
+    let count = IntVariable::new("count", &vec!(&0, &1, &2), None);
+    let vars = vec!(Variable::Int(count));
+
+    let cfg = ConfigZ3::new();
+    let ctx = ContextZ3::new(&cfg);
+    let slv = SolverZ3::new(&ctx);
+    let c = IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), "count_s0");
+
+    // too big for an i32
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &c, ADDZ3::new(&ctx, vec!(
+        IntZ3::new(&ctx, &IntSortZ3::new(&ctx), 2000000000), IntZ3::new(&ctx, &IntSortZ3::new(&ctx), 2000000000)))));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let result = GetPlanningResultZ3::try_new(&ctx, &model, &vars, &vec!(), 1, std::time::Duration::from_secs(0), true);
+    assert_eq!("3f5a7c9e-2d4b-4e6f-8a1c-5b7d9f1e3a26", result.unwrap_err().code());
+
+    // fits, but isn't in the domain
+    SlvResetZ3::new(&ctx, &slv);
+    SlvAssertZ3::new(&ctx, &slv, EQZ3::new(&ctx, &c, IntZ3::new(&ctx, &IntSortZ3::new(&ctx), 7)));
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+    let model = SlvGetModelZ3::new(&ctx, &slv);
+    let result = GetPlanningResultZ3::try_new(&ctx, &model, &vars, &vec!(), 1, std::time::Duration::from_secs(0), true);
+    assert_eq!("2c7f9e31-4a8d-4b6e-9f15-8d3a6c0e7b49", result.unwrap_err().code());
+}
+
+#[test]
+fn test_planning_to_smtlib2(){
+
+    let pos = EnumVariable::new("pos", "pos", &vec!("a", "b", "c"), None);
+
+    let t1 = Transition::new(
+        "a_to_b", 
+        &Predicate::EQRL(pos.clone(), String::from("a")),
+        &Predicate::EQRL(pos.clone(), String::from("b"))
+    );
+
+    let t2 = Transition::new(
+        "b_to_c", 
+        &Predicate::EQRL(pos.clone(), String::from("b")),
+        &Predicate::EQRL(pos.clone(), String::from("c"))
+    );
+
+    let init = Predicate::EQRL(pos.clone(), String::from("a"));
+    let goal = Predicate::EQRL(pos.clone(), String::from("c"));
+
+    let problem = PlanningProblem::new("abc", &init, &goal, &vec!(t1, t2), &Predicate::TRUE, &10);
+    let script = PlanningToSmtLib2::new(&problem, &2);
+
+    assert!(script.starts_with("; abc at step 2\n"));
+    assert!(script.contains("(declare-datatypes ((pos 0)) (((a) (b) (c))))"));
+    assert!(script.contains("(declare-fun a_to_b_t2 () Bool)"));
+    assert!(script.ends_with("(check-sat)\n"));
+
+    // the benchmark is sat exactly at the length of the shortest plan
+    let result = Incremental::new(&problem);
+    for steps in 0..4 {
+        let cfg = ConfigZ3::new();
+        let ctx = ContextZ3::new(&cfg);
+        let slv = SolverZ3::new(&ctx);
+        SlvFromSmtLib2Z3::new(&ctx, &slv, &PlanningToSmtLib2::new(&problem, &steps), Vec::<Sort>::new(), Vec::<FuncDecl>::new());
+        assert_eq!(steps == result.plan_length, SlvCheckZ3::new(&ctx, &slv) == SatResultZ3::Sat);
+    }
+}
+
+#[test]
+fn test_write_planning_smtlib2(){
+
+    let count = IntVariable::new("count", &vec!(&0, &1, &2), None);
+
+    let inc = Transition::new(
+        "inc", 
+        &Predicate::ILT(Term::VAR(count.clone()), Term::VAL(2)),
+        &Predicate::IEQRT(count.clone(), Term::ADD(vec!(Term::VAR(count.clone()), Term::VAL(1))))
+    );
+
+    let init = Predicate::IEQRL(count.clone(), 0);
+    let goal = Predicate::IEQRL(count.clone(), 2);
+    let problem = PlanningProblem::new("counter", &init, &goal, &vec!(inc), &Predicate::TRUE, &10);
+
+    let path = std::env::temp_dir().join("mini_sp_tools_write_planning_smtlib2.smt2");
+    WritePlanningSmtLib2::new(&problem, &2, path.to_str().unwrap());
+
+    let cfg = ConfigZ3::new();
+    let ctx = ContextZ3::new(&cfg);
+    let slv = SolverZ3::new(&ctx);
+    SlvFromSmtLib2FileZ3::new(&ctx, &slv, path.to_str().unwrap(), Vec::<Sort>::new(), Vec::<FuncDecl>::new());
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(SatResultZ3::Sat, SlvCheckZ3::new(&ctx, &slv));
+
+    let missing = std::env::temp_dir().join("mini_sp_tools_missing_dir").join("counter.smt2");
+    let result = WritePlanningSmtLib2::try_new(&problem, &2, missing.to_str().unwrap());
+    assert_eq!("0d7c3b5e-8a41-4f96-b2e8-6c1f9a4d7e23", result.unwrap_err().code());
+}
+
+#[test]
+fn test_incremental_statistics(){
+
+    let pos = EnumVariable::new("pos", "pos", &vec!("a", "b", "c"), None);
+
+    let t1 = Transition::new(
+        "a_to_b", 
+        &Predicate::EQRL(pos.clone(), String::from("a")),
+        &Predicate::EQRL(pos.clone(), String::from("b"))
+    );
+
+    let t2 = Transition::new(
+        "b_to_c", 
+        &Predicate::EQRL(pos.clone(), String::from("b")),
+        &Predicate::EQRL(pos.clone(), String::from("c"))
+    );
+
+    let init = Predicate::EQRL(pos.clone(), String::from("a"));
+    let goal = Predicate::EQRL(pos.clone(), String::from("c"));
+
+    let problem = PlanningProblem::new("abc", &init, &goal, &vec!(t1, t2), &Predicate::TRUE, &10);
+    let (result, statistics) = Incremental::new_with_statistics(&problem);
+
+    // one snapshot for every check, the last one found the plan
+    assert!(result.plan_found);
+    assert_eq!(3, statistics.len());
+    assert!(statistics.iter().all(|x| x.get("memory").is_some()));
+    assert!(statistics[2].value("max memory") >= statistics[0].value("max memory"));
+}
+
+#[test]
+fn test_incremental_stop(){
+
+    let count = IntVariable::new("count", &vec!(&0, &1, &2), None);
+
+    let inc = Transition::new(
+        "inc", 
+        &Predicate::ILT(Term::VAR(count.clone()), Term::VAL(2)),
+        &Predicate::IEQRT(count.clone(), Term::ADD(vec!(Term::VAR(count.clone()), Term::VAL(1))))
+    );
+
+    // the goal is unreachable, the planner would try all the steps
+    let init = Predicate::IEQRL(count.clone(), 0);
+    let goal = Predicate::ILT(Term::VAR(count.clone()), Term::VAL(0));
+    let problem = PlanningProblem::new("counter", &init, &goal, &vec!(inc), &Predicate::TRUE, &1000000);
+
+    let stop = StopPlanning::new();
+    let button = stop.clone();
+    let operator = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        button.stop();
+    });
+    let result = Incremental::try_new_with_stop(&problem, &stop);
+    operator.join().unwrap();
+    assert!(stop.is_stopped());
+    assert_eq!("7e2a9c41-5b8d-4f63-a1e7-3c9d0b6f8a52", result.unwrap_err().code());
+
+    let short = PlanningProblem::new("counter", &init, &Predicate::IEQRL(count.clone(), 2), &problem.trans, &Predicate::TRUE, &10);
+    let result = Incremental::new_with_stop(&short, &StopPlanning::new());
+    assert_eq!(2, result.plan_length);
+}
@@ -1,7 +1,10 @@
 //! # mini sp tools
 
+pub mod errors;
+pub use crate::errors::{SPError, SPResult};
+
 pub mod basics;
-pub use crate::basics::{Parameter, EnumVariable}; //, EnumAssignment, State};
+pub use crate::basics::{Parameter, BoolVariable, IntVariable, EnumVariable, Variable, Value, Assignment, State};
 
 pub mod ltlf;
 pub use crate::ltlf::{NextZ3, AlwaysZ3, EventuallyZ3, UntilZ3, ReleaseZ3}; // WeakNextZ3
@@ -12,7 +15,8 @@ pub use crate::nsltlf::{AfterZ3, SomewhenAfterZ3,  SequenceZ3, TracePBEQZ3};
 pub mod incremental;
 pub use crate::incremental::{Transition, PlanningProblem, NonDetPlanningProblem ,Incremental, KeepVariableValues,
     PlanningFrame, GetPlanningResultZ3, PlanningResult, MultGoalsPlanningProblem,
-    MultGoalsIncremental, IncrementalDenial, IncrementalAll, PlanningFrame2, GetPlanningResult2Z3, GetAllFrames2, GenerateDigraph};
+    MultGoalsIncremental, IncrementalDenial, IncrementalAll, PlanningFrame2, GetPlanningResult2Z3, GetAllFrames2, GenerateDigraph,
+    TransitionsAtStep, PlanningToSmtLib2, WritePlanningSmtLib2, StopPlanning};
 
 pub mod paramincremental;
 pub use crate::paramincremental::{ParamTransition, ParamPlanningProblem, ParamIncremental, 
@@ -23,11 +27,11 @@ pub mod compositional;
 pub use crate::compositional::{Activate, StateToParamPredicate, Concatenate, RemoveLoops, Compositional};
 
 pub mod predicates;
-pub use crate::predicates::{Predicate, ParamPredicate, PredicateToAstZ3};
+pub use crate::predicates::{Predicate, Term, ParamPredicate, PredicateToAstZ3, TermToAstZ3};
 
 pub mod utils;
-pub use crate::utils::{IterOps, GetPredicateVars, GetProblemVars, GetParamPredicateVars,
-    GetParamProblemVars};
+pub use crate::utils::{IterOps, GetPredicateVars, GetUpdatedVars, GetTermVars, GetProblemVars, GetAllProblemVars,
+    GetParamPredicateVars, GetParamProblemVars};
 
 pub mod safety;
-pub use crate::safety::{VerifySafety, StateToPredicate};
\ No newline at end of file
+pub use crate::safety::{VerifySafety, AssignmentToPredicate, StateToPredicate};
\ No newline at end of file
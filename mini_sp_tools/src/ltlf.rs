@@ -1,90 +1,117 @@
-use z3_sys::*;
 use mini_sp_smt::*;
 use super::*;
 
 pub struct NextZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub x: Z3_ast
+    pub x: Ast<'ctx>
 }
 
 pub struct AlwaysZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub x: Z3_ast
+    pub x: Ast<'ctx>
 }
 
 pub struct EventuallyZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub x: Z3_ast
+    pub x: Ast<'ctx>
 }
 
 pub struct UntilZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub x: Z3_ast,
-    pub y: Z3_ast
+    pub x: Ast<'ctx>,
+    pub y: Ast<'ctx>
 }
 
 pub struct ReleaseZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub x: Z3_ast,
-    pub y: Z3_ast
+    pub x: Ast<'ctx>,
+    pub y: Ast<'ctx>
 }
 
 impl <'ctx> NextZ3<'ctx> {
-    pub fn new(ctx: &ContextZ3, x: &Predicate, r#type: &str, step: &u32) -> Z3_ast {
-        PredicateToAstZ3::new(&ctx, x, r#type, &(step + 1))
+    pub fn new(ctx: &'ctx ContextZ3, x: &Predicate, r#type: &str, step: &u32) -> Ast<'ctx> {
+        match NextZ3::try_new(ctx, x, r#type, step) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, x: &Predicate, r#type: &str, step: &u32) -> SPResult<Ast<'ctx>> {
+        PredicateToAstZ3::try_new(&ctx, x, r#type, &(step + 1))
     } 
 }
 
 impl <'ctx> AlwaysZ3<'ctx> {
-    pub fn new(ctx: &ContextZ3, x: &Predicate, r#type: &str, step: &u32) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, x: &Predicate, r#type: &str, step: &u32) -> Ast<'ctx> {
+        match AlwaysZ3::try_new(ctx, x, r#type, step) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, x: &Predicate, r#type: &str, step: &u32) -> SPResult<Ast<'ctx>> {
         let mut assert_vec = vec!();
         for s in 0..step + 1 {
             assert_vec.push(
-                PredicateToAstZ3::new(&ctx, x, r#type, &s)
+                PredicateToAstZ3::try_new(&ctx, x, r#type, &s)?
             )
         }
-        ANDZ3::new(&ctx, assert_vec)
+        Ok(ANDZ3::new(&ctx, assert_vec))
     }
 }
 
 impl <'ctx> EventuallyZ3<'ctx> {
-    pub fn new(ctx: &ContextZ3, x: &Predicate, r#type: &str, step: &u32) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, x: &Predicate, r#type: &str, step: &u32) -> Ast<'ctx> {
+        match EventuallyZ3::try_new(ctx, x, r#type, step) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, x: &Predicate, r#type: &str, step: &u32) -> SPResult<Ast<'ctx>> {
         let mut assert_vec = vec!();
         for s in 0..step + 1 {
             assert_vec.push(
-                PredicateToAstZ3::new(&ctx, x, r#type, &s)
+                PredicateToAstZ3::try_new(&ctx, x, r#type, &s)?
             )
         }
-        ORZ3::new(&ctx, assert_vec)
+        Ok(ORZ3::new(&ctx, assert_vec))
     }
 }
 
 impl <'ctx> UntilZ3<'ctx> {
-    pub fn new(ctx: &ContextZ3, x: &Predicate, y: &Predicate, r#type: &str, step: &u32) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, x: &Predicate, y: &Predicate, r#type: &str, step: &u32) -> Ast<'ctx> {
+        match UntilZ3::try_new(ctx, x, y, r#type, step) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, x: &Predicate, y: &Predicate, r#type: &str, step: &u32) -> SPResult<Ast<'ctx>> {
         let from_step: u32 = 0;
-        fn recursive_subfn(ctx: &ContextZ3, x: &Predicate, y: &Predicate, 
-            r#type: &str, mut from_step: u32, until_step: u32) -> Z3_ast {
+        fn recursive_subfn<'ctx>(ctx: &'ctx ContextZ3, x: &Predicate, y: &Predicate, 
+            r#type: &str, mut from_step: u32, until_step: u32) -> SPResult<Ast<'ctx>> {
             if from_step < until_step {
                 from_step = from_step + 1;
-                ORZ3::new(&ctx, 
+                Ok(ORZ3::new(&ctx, 
                     vec!(
-                        PredicateToAstZ3::new(&ctx, y, r#type, &(from_step - 1)),
+                        PredicateToAstZ3::try_new(&ctx, y, r#type, &(from_step - 1))?,
                         ANDZ3::new(&ctx, 
                             vec!(
-                                PredicateToAstZ3::new(&ctx, x, r#type, &(from_step - 1)),
-                                recursive_subfn(&ctx, &x, &y, r#type, from_step, until_step)
+                                PredicateToAstZ3::try_new(&ctx, x, r#type, &(from_step - 1))?,
+                                recursive_subfn(&ctx, &x, &y, r#type, from_step, until_step)?
                             )
                         )
                     )
-                )
+                ))
             } else if from_step == until_step{
-                ANDZ3::new(&ctx, 
+                Ok(ANDZ3::new(&ctx, 
                     vec!(
-                        PredicateToAstZ3::new(&ctx, y, r#type, &(from_step))
+                        PredicateToAstZ3::try_new(&ctx, y, r#type, &(from_step))?
                     )
-                )
+                ))
             } else {
-                panic! ("Error 76f32414-b307-4c41-a497-86746c009e56: from_step > until_step in Until ltlf.")
+                Err(SPError::InvalidStep { code: "76f32414-b307-4c41-a497-86746c009e56", ltlf: "Until".to_string() })
             }
         }
         recursive_subfn(&ctx, &x, &y, r#type, from_step, *step)
@@ -92,31 +119,38 @@ impl <'ctx> UntilZ3<'ctx> {
 }
 
 impl <'ctx> ReleaseZ3<'ctx> {
-    pub fn new(ctx: &ContextZ3, x: &Predicate, y: &Predicate, r#type: &str, step: &u32) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, x: &Predicate, y: &Predicate, r#type: &str, step: &u32) -> Ast<'ctx> {
+        match ReleaseZ3::try_new(ctx, x, y, r#type, step) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, x: &Predicate, y: &Predicate, r#type: &str, step: &u32) -> SPResult<Ast<'ctx>> {
         let from_step: u32 = 0;
-        fn recursive_subfn(ctx: &ContextZ3, x: &Predicate, y: &Predicate, 
-            r#type: &str, mut from_step: u32, until_step: u32) -> Z3_ast {
+        fn recursive_subfn<'ctx>(ctx: &'ctx ContextZ3, x: &Predicate, y: &Predicate, 
+            r#type: &str, mut from_step: u32, until_step: u32) -> SPResult<Ast<'ctx>> {
             if from_step < until_step {
                 from_step = from_step + 1;
-                ANDZ3::new(&ctx, 
+                Ok(ANDZ3::new(&ctx, 
                     vec!(
-                        PredicateToAstZ3::new(&ctx, y, r#type, &(from_step - 1)),
+                        PredicateToAstZ3::try_new(&ctx, y, r#type, &(from_step - 1))?,
                         ORZ3::new(&ctx, 
                             vec!(
-                                PredicateToAstZ3::new(&ctx, x, r#type, &(from_step - 1)),
-                                recursive_subfn(&ctx, &x, &y, r#type, from_step, until_step)
+                                PredicateToAstZ3::try_new(&ctx, x, r#type, &(from_step - 1))?,
+                                recursive_subfn(&ctx, &x, &y, r#type, from_step, until_step)?
                             )
                         )
                     )
-                )
+                ))
             } else if from_step == until_step{
-                ORZ3::new(&ctx, 
+                Ok(ORZ3::new(&ctx, 
                     vec!(
-                        PredicateToAstZ3::new(&ctx, y, r#type, &(from_step))
+                        PredicateToAstZ3::try_new(&ctx, y, r#type, &(from_step))?
                     )
-                )
+                ))
             } else {
-                panic! ("Error cbf10fd3-6845-4786-bdaa-fa5ee564b4f8: from_step > until_step in Release ltlf.")
+                Err(SPError::InvalidStep { code: "cbf10fd3-6845-4786-bdaa-fa5ee564b4f8", ltlf: "Release".to_string() })
             }
         }
         recursive_subfn(&ctx, &x, &y, r#type, from_step, *step)
@@ -207,4 +241,27 @@ fn test_release_ltlf(){
     let release_ltlf = ReleaseZ3::new(&ctx, &p1, &p2, "guard", &2);
 
     assert_eq!("(and (= y_s0 b) (or (= x_s0 b) (and (= y_s1 b) (or (= x_s1 b) (= y_s2 b)))))", ast_to_string_z3!(&ctx, release_ltlf));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_soft_ltlf(){
+
+    let x = EnumVariable::new("x", "rooms", &vec!("a", "b", "c"), None);
+
+    let cfg = ConfigZ3::new();
+    let ctx = ContextZ3::new(&cfg);
+    let opt = OptimizerZ3::new(&ctx);
+
+    // start in room a and eventually reach room c
+    OptAssertZ3::new(&ctx, &opt, PredicateToAstZ3::new(&ctx, &Predicate::EQRL(x.clone(), "a".to_string()), "guard", &0));
+    OptAssertZ3::new(&ctx, &opt, EventuallyZ3::new(&ctx, &Predicate::EQRL(x.clone(), "c".to_string()), "guard", &3));
+
+    // avoid room b, and prefer to stay in room a which is impossible
+    OptAssertSoftZ3::new(&ctx, &opt, AlwaysZ3::new(&ctx, &Predicate::NEQRL(x.clone(), "b".to_string()), "guard", &3), 3, "avoid_b");
+    OptAssertSoftZ3::new(&ctx, &opt, AlwaysZ3::new(&ctx, &Predicate::EQRL(x.clone(), "a".to_string()), "guard", &3), 1, "stay_in_a");
+    OptCheckZ3::new(&ctx, &opt, Vec::<Ast>::new());
+
+    let violated = OptGetViolatedSoftZ3::new(&ctx, &opt);
+    assert_eq!(vec!("stay_in_a"), violated.iter().map(|x| x.group.as_str()).collect::<Vec<&str>>());
+    assert_eq!(1, OptGetPenaltyZ3::new(&ctx, &opt));
+}
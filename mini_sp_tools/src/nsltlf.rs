@@ -1,67 +1,87 @@
-use z3_sys::*;
 use mini_sp_smt::*;
 use super::*;
 
 pub struct AfterZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub x: Z3_ast,
-    pub y: Z3_ast
+    pub x: Ast<'ctx>,
+    pub y: Ast<'ctx>
 }
 
 pub struct SomewhenAfterZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub x: Z3_ast,
-    pub y: Z3_ast
+    pub x: Ast<'ctx>,
+    pub y: Ast<'ctx>
 }
 
 pub struct SequenceZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub preds: Vec<Z3_ast>
+    pub preds: Vec<Ast<'ctx>>
 }
 
 pub struct TracePBEQZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub x: Z3_ast
+    pub x: Ast<'ctx>
 }
 
 // chronological order
 impl <'ctx> AfterZ3<'ctx> {
-    pub fn new(ctx: &ContextZ3, x: &Predicate, y: &Predicate, r#type: &str, step: &u32) -> Z3_ast {
-        ANDZ3::new(&ctx, vec!(
-            PredicateToAstZ3::new(&ctx, x, r#type, &step),
-            PredicateToAstZ3::new(&ctx, y, r#type, &(step + 1))))
+    pub fn new(ctx: &'ctx ContextZ3, x: &Predicate, y: &Predicate, r#type: &str, step: &u32) -> Ast<'ctx> {
+        match AfterZ3::try_new(ctx, x, y, r#type, step) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, x: &Predicate, y: &Predicate, r#type: &str, step: &u32) -> SPResult<Ast<'ctx>> {
+        Ok(ANDZ3::new(&ctx, vec!(
+            PredicateToAstZ3::try_new(&ctx, x, r#type, &step)?,
+            PredicateToAstZ3::try_new(&ctx, y, r#type, &(step + 1))?)))
     } 
 }
 
 // chronological order
 impl <'ctx> SomewhenAfterZ3<'ctx> {
-    pub fn new(ctx: &ContextZ3, x: &Predicate, y: &Predicate, r#type: &str, step: &u32) -> Z3_ast {
-        let mut disj_vec: Vec<Z3_ast> = vec!();
+    pub fn new(ctx: &'ctx ContextZ3, x: &Predicate, y: &Predicate, r#type: &str, step: &u32) -> Ast<'ctx> {
+        match SomewhenAfterZ3::try_new(ctx, x, y, r#type, step) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, x: &Predicate, y: &Predicate, r#type: &str, step: &u32) -> SPResult<Ast<'ctx>> {
+        let mut disj_vec: Vec<Ast<'ctx>> = vec!();
         if step.to_owned() >= 1 {
             for i in 1..step.to_owned() {
                 for j in 0..i {
                     disj_vec.push(
                         ANDZ3::new(&ctx, vec!(
-                            PredicateToAstZ3::new(&ctx, x, r#type, &j),
-                            PredicateToAstZ3::new(&ctx, y, r#type, &i))
+                            PredicateToAstZ3::try_new(&ctx, x, r#type, &j)?,
+                            PredicateToAstZ3::try_new(&ctx, y, r#type, &i)?)
                         ) 
                     )
                 }
             }
         }
-        ORZ3::new(&ctx, disj_vec)
+        Ok(ORZ3::new(&ctx, disj_vec))
     }
 }
 
 impl <'ctx> SequenceZ3<'ctx> {
-    pub fn new(ctx: &ContextZ3, pred: &Vec<&Predicate>, r#type: &str, step: &u32) -> Z3_ast {
-        let mut conj_vec: Vec<Z3_ast> = vec!();
+    pub fn new(ctx: &'ctx ContextZ3, pred: &[&Predicate], r#type: &str, step: &u32) -> Ast<'ctx> {
+        match SequenceZ3::try_new(ctx, pred, r#type, step) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, pred: &[&Predicate], r#type: &str, step: &u32) -> SPResult<Ast<'ctx>> {
+        let mut conj_vec: Vec<Ast<'ctx>> = vec!();
         if step.to_owned() as usize >= pred.len() {
             for i in 0..pred.len() - 1{
-                conj_vec.push(SomewhenAfterZ3::new(&ctx, &pred[i as usize], &pred[(i + 1) as usize], r#type, step));
+                conj_vec.push(SomewhenAfterZ3::try_new(&ctx, &pred[i as usize], &pred[(i + 1) as usize], r#type, step)?);
             }
         }
-        ANDZ3::new(&ctx, conj_vec)
+        Ok(ANDZ3::new(&ctx, conj_vec))
     }
 }
 
@@ -83,14 +103,21 @@ impl <'ctx> SequenceZ3<'ctx> {
 
 // Exactly n times true in a trace
 impl <'ctx> TracePBEQZ3<'ctx> {
-    pub fn new(ctx: &ContextZ3, x: &Predicate, r#type: &str, be_true: &u32, step: &u32) -> Z3_ast {
+    pub fn new(ctx: &'ctx ContextZ3, x: &Predicate, r#type: &str, be_true: &u32, step: &u32) -> Ast<'ctx> {
+        match TracePBEQZ3::try_new(ctx, x, r#type, be_true, step) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, x: &Predicate, r#type: &str, be_true: &u32, step: &u32) -> SPResult<Ast<'ctx>> {
         let mut assert_vec = vec!();
         for s in 0..step + 1 {
             assert_vec.push(
-                PredicateToAstZ3::new(&ctx, x, r#type, &s)
+                PredicateToAstZ3::try_new(&ctx, x, r#type, &s)?
             )
         }
-        PBEQZ3::new(&ctx, assert_vec, *be_true as i32)
+        Ok(PBEQZ3::new(&ctx, assert_vec, *be_true as i32))
     }
 }
 
@@ -166,7 +193,7 @@ fn test_trace_pbeq_ltlf(){
 
     let trace_pbeq_ltlf = TracePBEQZ3::new(&ctx, &pred, "guard", &2, &4);
 
-    assert_eq!("((_ pbeq 2 1 1 1 1 1) (= x_s0 b) (= x_s1 b) (= x_s2 b) (= x_s3 b) (= x_s4 b))", ast_to_string_z3!(&ctx, trace_pbeq_ltlf));
+    assert_eq!("((_ pbeq 2 1 1 1 1 1) (= x_s0 b) (= x_s1 b) (= x_s2 b) (= x_s3 b) (= x_s4 b))", ast_to_string_z3!(&ctx, &trace_pbeq_ltlf));
 
     slv_assert_z3!(&ctx, &slv, trace_pbeq_ltlf);
     slv_check_z3!(&ctx, &slv);
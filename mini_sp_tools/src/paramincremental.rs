@@ -1,4 +1,3 @@
-use z3_sys::*;
 use mini_sp_smt::*;
 use super::*;
 
@@ -69,7 +68,7 @@ pub struct ParamPlanningResult {
 
 pub struct GetParamPlanningResultZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
-    pub model: Z3_model,
+    pub model: Model<'ctx>,
     pub level: u32,
     pub concat: u32,
     pub nr_steps: u32,
@@ -83,9 +82,9 @@ impl GeneratePredicate {
         p_own.push(&default_param);
         let mut pred_vec = vec!();
         for pred in &ppred.preds {
-            let pred_vars: Vec<EnumVariable> = GetPredicateVars::new(&pred);
+            let pred_vars: Vec<Variable> = GetPredicateVars::new(&pred);
             for param in &p_own {
-                if pred_vars.iter().any(|x| x.param.name == param.name) && param.value {
+                if pred_vars.iter().any(|x| x.param().name == param.name) && param.value {
                     pred_vec.push(pred.to_owned())
                 }
             }
@@ -155,6 +154,13 @@ impl MultGoalsParamPlanningProblem {
 
 impl ParamIncremental {
     pub fn new(prob: &ParamPlanningProblem, params: &Vec<&Parameter>, level: &u32, concat: &u32) -> ParamPlanningResult {
+        match ParamIncremental::try_new(prob, params, level, concat) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(prob: &ParamPlanningProblem, params: &Vec<&Parameter>, level: &u32, concat: &u32) -> SPResult<ParamPlanningResult> {
         let generated_init = GeneratePredicate::new(&params, &prob.init);
         let generated_goals = GeneratePredicate::new(&params, &prob.goal);
         let generated_trans = GenerateTransitions::new(&params, &prob.trans);
@@ -168,21 +174,28 @@ impl ParamIncremental {
             &prob.max_steps
         );
 
-        let inc_result = Incremental::new(&generated_prob);
+        let inc_result = Incremental::try_new(&generated_prob)?;
 
-        ParamPlanningResult {
+        Ok(ParamPlanningResult {
             plan_found: inc_result.plan_found,
             plan_length: inc_result.plan_length,
             level: *level,
             concat: *concat,
             trace: inc_result.trace,
             time_to_solve: inc_result.time_to_solve
-        }
+        })
     }
 }
 
 impl MultGoalsParamIncremental {
     pub fn new(prob: &MultGoalsParamPlanningProblem, params: &Vec<&Parameter>, level: &u32, concat: &u32) -> ParamPlanningResult {
+        match MultGoalsParamIncremental::try_new(prob, params, level, concat) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(prob: &MultGoalsParamPlanningProblem, params: &Vec<&Parameter>, level: &u32, concat: &u32) -> SPResult<ParamPlanningResult> {
         let generated_init = GeneratePredicate::new(&params, &prob.init);
         let generated_goals: Vec<(Predicate, Option<&Predicate>)> = prob.goals.iter().map(|x| (GeneratePredicate::new(&params, &x.0), Some(&x.1))).collect(); 
         let generated_trans = GenerateTransitions::new(&params, &prob.trans);
@@ -196,16 +209,16 @@ impl MultGoalsParamIncremental {
             &prob.max_steps
         );
 
-        let inc_result = MultGoalsIncremental::new(&generated_prob);
+        let inc_result = MultGoalsIncremental::try_new(&generated_prob)?;
 
-        ParamPlanningResult {
+        Ok(ParamPlanningResult {
             plan_found: inc_result.plan_found,
             plan_length: inc_result.plan_length,
             level: *level,
             concat: *concat,
             trace: inc_result.trace,
             time_to_solve: inc_result.time_to_solve
-        }
+        })
     }
 }
 
@@ -1,4 +1,3 @@
-use z3_sys::*;
 use mini_sp_smt::*;
 use super::*;
 
@@ -17,6 +16,19 @@ pub enum Predicate {
     NEQRR(EnumVariable, EnumVariable),
     NEQLR(String, EnumVariable),
     NEQPP(Box<Predicate>, Box<Predicate>), 
+    BEQRL(BoolVariable, bool),
+    BEQRR(BoolVariable, BoolVariable),
+    BNEQRL(BoolVariable, bool),
+    BNEQRR(BoolVariable, BoolVariable),
+    IEQRL(IntVariable, i32),
+    IEQRR(IntVariable, IntVariable),
+    INEQRL(IntVariable, i32),
+    INEQRR(IntVariable, IntVariable),
+    IEQRT(IntVariable, Term), // in updates, the term is evaluated in the previous step, elsewhere it can't contain the variable
+    ILT(Term, Term),
+    ILE(Term, Term),
+    IGT(Term, Term),
+    IGE(Term, Term),
     PBEQ(Vec<Predicate>, i32), // exactly n true predicates in a step
     NEXT(Box<Predicate>), // in the next step
     ALWAYS(Box<Predicate>), // in every step of the trace
@@ -30,6 +42,14 @@ pub enum Predicate {
     TPBEQ(Box<Predicate>, u32) // exactly n times true in a trace
 }
 
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord)]
+pub enum Term {
+    VAR(IntVariable),
+    VAL(i32),
+    ADD(Vec<Term>),
+    SUB(Vec<Term>) // first term minus the rest
+}
+
 #[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord)]
 pub struct ParamPredicate {
     pub preds: Vec<Predicate>
@@ -39,7 +59,14 @@ pub struct PredicateToAstZ3<'ctx> {
     pub ctx: &'ctx ContextZ3,
     pub pred: Predicate,
     pub step: u32,
-    pub r: Z3_ast
+    pub r: Ast<'ctx>
+}
+
+pub struct TermToAstZ3<'ctx> {
+    pub ctx: &'ctx ContextZ3,
+    pub term: Term,
+    pub step: u32,
+    pub r: Ast<'ctx>
 }
 
 impl ParamPredicate {
@@ -50,47 +77,80 @@ impl ParamPredicate {
     }
 }
 
+impl <'ctx> TermToAstZ3<'ctx> {
+    pub fn new(ctx: &'ctx ContextZ3, term: &Term, step: &u32) -> Ast<'ctx> {
+        match term {
+            Term::VAR(x) => IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), format!("{}_s{}", x.name.to_string(), step).as_str()),
+            Term::VAL(x) => IntZ3::new(&ctx, &IntSortZ3::new(&ctx), *x),
+            Term::ADD(x) => ADDZ3::new(&ctx, x.iter().map(|y| TermToAstZ3::new(&ctx, y, step)).collect()),
+            Term::SUB(x) => SUBZ3::new(&ctx, x.iter().map(|y| TermToAstZ3::new(&ctx, y, step)).collect())
+        }
+    }
+}
+
 impl <'ctx> PredicateToAstZ3<'ctx> {
-    pub fn new(ctx: &'ctx ContextZ3, pred: &Predicate, r#type: &str, step: &u32) -> Z3_ast {
-        match pred {
+    pub fn new(ctx: &'ctx ContextZ3, pred: &Predicate, r#type: &str, step: &u32) -> Ast<'ctx> {
+        match PredicateToAstZ3::try_new(ctx, pred, r#type, step) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(ctx: &'ctx ContextZ3, pred: &Predicate, r#type: &str, step: &u32) -> SPResult<Ast<'ctx>> {
+        
+        // the step in which the right hand side of a predicate is evaluated
+        fn rhs_step(r#type: &str, step: &u32, code: &'static str) -> SPResult<u32> {
+            match r#type {
+                "guard" | "state" | "specs" => Ok(*step),
+                "update" => Ok(step - 1),
+                _ => Err(SPError::PredicateType { code: code, r#type: r#type.to_string() })
+            }
+        }
+
+        fn not_in_domain(value: &str, var: &str, code: &'static str) -> SPError {
+            SPError::NotInDomain { code: code, value: value.to_string(), var: var.to_string() }
+        }
+
+        fn term_contains(term: &Term, var: &IntVariable) -> bool {
+            match term {
+                Term::VAR(x) => x.name == var.name,
+                Term::VAL(_) => false,
+                Term::ADD(x) | Term::SUB(x) => x.iter().any(|y| term_contains(y, var))
+            }
+        }
+
+        let ast = match pred {
             Predicate::TRUE => BoolZ3::new(&ctx, true),
             Predicate::FALSE => BoolZ3::new(&ctx, false),
-            Predicate::NOT(p) => NOTZ3::new(&ctx, PredicateToAstZ3::new(&ctx, p, r#type, step)),
-            Predicate::AND(p) => ANDZ3::new(&ctx, p.iter().map(|x| PredicateToAstZ3::new(&ctx, x, r#type, step)).collect()),
-            Predicate::OR(p) => ORZ3::new(&ctx, p.iter().map(|x| PredicateToAstZ3::new(&ctx, x, r#type, step)).collect()),
+            Predicate::NOT(p) => NOTZ3::new(&ctx, PredicateToAstZ3::try_new(&ctx, p, r#type, step)?),
+            Predicate::AND(p) => ANDZ3::new(&ctx, p.iter().map(|x| PredicateToAstZ3::try_new(&ctx, x, r#type, step)).collect::<SPResult<Vec<Ast<'ctx>>>>()?),
+            Predicate::OR(p) => ORZ3::new(&ctx, p.iter().map(|x| PredicateToAstZ3::try_new(&ctx, x, r#type, step)).collect::<SPResult<Vec<Ast<'ctx>>>>()?),
             Predicate::EQRL(x, y) => {
                 match x.domain.contains(&y) {
                     true => {
                         let sort = EnumSortZ3::new(&ctx, &x.r#type, x.domain.iter().map(|x| x.as_str()).collect());
                         let elems = &sort.enum_asts;
                         let index = x.domain.iter().position(|r| *r == y.to_string()).unwrap();
-                        EQZ3::new(&ctx, EnumVarZ3::new(&ctx, sort.r, format!("{}_s{}", x.name.to_string(), step).as_str()), elems[index])      
+                        EQZ3::new(&ctx, EnumVarZ3::new(&ctx, &sort, format!("{}_s{}", x.name.to_string(), step).as_str()), &elems[index])      
                     },
-                    false => panic!("Error 6f789b86-7f6c-4426-ab0f-6b5b72dd2c55: Value '{}' not in the domain of variable '{}'.", y, x.name)
+                    false => return Err(not_in_domain(y, &x.name, "6f789b86-7f6c-4426-ab0f-6b5b72dd2c55"))
                 }
             },
             Predicate::EQRR(x, y) => {
                 match x.r#type == y.r#type {
                     true => {
-                        match r#type {
-                            "guard" | "state" | "specs" => {
-                                let sort_1 = EnumSortZ3::new(&ctx, &x.r#type, x.domain.iter().map(|x| x.as_str()).collect());
-                                let sort_2 = EnumSortZ3::new(&ctx, &y.r#type, y.domain.iter().map(|y| y.as_str()).collect());
-                                let v_1 = EnumVarZ3::new(&ctx, sort_1.r, format!("{}_s{}", x.name.to_string(), step).as_str());
-                                let v_2 = EnumVarZ3::new(&ctx, sort_2.r, format!("{}_s{}", y.name.to_string(), step).as_str());
-                                EQZ3::new(&ctx, v_1, v_2)
-                            },
-                            "update" => {
-                                let sort_1 = EnumSortZ3::new(&ctx, &x.r#type, x.domain.iter().map(|x| x.as_str()).collect());
-                                let sort_2 = EnumSortZ3::new(&ctx, &y.r#type, y.domain.iter().map(|y| y.as_str()).collect());
-                                let v_1 = EnumVarZ3::new(&ctx, sort_1.r, format!("{}_s{}", x.name.to_string(), step).as_str());
-                                let v_2 = EnumVarZ3::new(&ctx, sort_2.r, format!("{}_s{}", y.name.to_string(), step - 1).as_str());
-                                EQZ3::new(&ctx, v_1, v_2)
-                            },
-                            _ => panic!("Error 53b0fd14-1ddd-4bf0-8dc7-d372d6ad8c99: Predicate type '{}' is not allowed.", r#type)
-                        }
+                        let prev = rhs_step(r#type, step, "53b0fd14-1ddd-4bf0-8dc7-d372d6ad8c99")?;
+                        let sort_1 = EnumSortZ3::new(&ctx, &x.r#type, x.domain.iter().map(|x| x.as_str()).collect());
+                        let sort_2 = EnumSortZ3::new(&ctx, &y.r#type, y.domain.iter().map(|y| y.as_str()).collect());
+                        let v_1 = EnumVarZ3::new(&ctx, &sort_1, format!("{}_s{}", x.name.to_string(), step).as_str());
+                        let v_2 = EnumVarZ3::new(&ctx, &sort_2, format!("{}_s{}", y.name.to_string(), prev).as_str());
+                        EQZ3::new(&ctx, v_1, v_2)
                     },
-                    false => panic!("Error c8022e33-ed30-43af-8e45-8cfdaf09e8a5: Sorts '{}' and '{}' are incompatible.", x.r#type, y.r#type)
+                    false => return Err(SPError::SortMismatch { 
+                        code: "c8022e33-ed30-43af-8e45-8cfdaf09e8a5", 
+                        left: x.r#type.to_string(), 
+                        right: y.r#type.to_string() 
+                    })
                 }
             },
             Predicate::EQLR(y, x) => {
@@ -99,45 +159,38 @@ impl <'ctx> PredicateToAstZ3<'ctx> {
                         let sort = EnumSortZ3::new(&ctx, &x.r#type, x.domain.iter().map(|x| x.as_str()).collect());
                         let elems = &sort.enum_asts;
                         let index = x.domain.iter().position(|r| *r == y.to_string()).unwrap();
-                        EQZ3::new(&ctx, EnumVarZ3::new(&ctx, sort.r, format!("{}_s{}", x.name.to_string(), step).as_str()), elems[index])      
+                        EQZ3::new(&ctx, EnumVarZ3::new(&ctx, &sort, format!("{}_s{}", x.name.to_string(), step).as_str()), &elems[index])      
                     },
-                    false => panic!("Error c8250dfd-6d3c-4371-8fee-813ba5100d80: Value '{}' not in the domain of variable '{}'.", y, x.name)
+                    false => return Err(not_in_domain(y, &x.name, "c8250dfd-6d3c-4371-8fee-813ba5100d80"))
                 }
             },
-            Predicate::EQPP(x, y) => EQZ3::new(&ctx, PredicateToAstZ3::new(&ctx, x, r#type, step), PredicateToAstZ3::new(&ctx, y, r#type, step)),
+            Predicate::EQPP(x, y) => EQZ3::new(&ctx, PredicateToAstZ3::try_new(&ctx, x, r#type, step)?, PredicateToAstZ3::try_new(&ctx, y, r#type, step)?),
             Predicate::NEQRL(x, y) => {
                 match x.domain.contains(&y) {
                     true => {
                         let sort = EnumSortZ3::new(&ctx, &x.r#type, x.domain.iter().map(|x| x.as_str()).collect());
                         let elems = &sort.enum_asts;
                         let index = x.domain.iter().position(|r| *r == y.to_string()).unwrap();
-                        NEQZ3::new(&ctx, EnumVarZ3::new(&ctx, sort.r, format!("{}_s{}", x.name.to_string(), step).as_str()), elems[index])      
+                        NEQZ3::new(&ctx, EnumVarZ3::new(&ctx, &sort, format!("{}_s{}", x.name.to_string(), step).as_str()), &elems[index])      
                     },
-                    false => panic!("Error 82ffe471-c922-43b0-bfa0-98c27626408e: Value '{}' not in the domain of variable '{}'.", y, x.name)
+                    false => return Err(not_in_domain(y, &x.name, "82ffe471-c922-43b0-bfa0-98c27626408e"))
                 }
             },
             Predicate::NEQRR(x, y) => {
                 match x.r#type == y.r#type {
                     true => {
-                        match r#type {
-                            "guard" | "state" | "specs" => {
-                                let sort_1 = EnumSortZ3::new(&ctx, &x.r#type, x.domain.iter().map(|x| x.as_str()).collect());
-                                let sort_2 = EnumSortZ3::new(&ctx, &y.r#type, y.domain.iter().map(|y| y.as_str()).collect());
-                                let v_1 = EnumVarZ3::new(&ctx, sort_1.r, format!("{}_s{}", x.name.to_string(), step).as_str());
-                                let v_2 = EnumVarZ3::new(&ctx, sort_2.r, format!("{}_s{}", y.name.to_string(), step).as_str());
-                                NEQZ3::new(&ctx, v_1, v_2)
-                            },
-                            "update" => {
-                                let sort_1 = EnumSortZ3::new(&ctx, &x.r#type, x.domain.iter().map(|x| x.as_str()).collect());
-                                let sort_2 = EnumSortZ3::new(&ctx, &y.r#type, y.domain.iter().map(|y| y.as_str()).collect());
-                                let v_1 = EnumVarZ3::new(&ctx, sort_1.r, format!("{}_s{}", x.name.to_string(), step).as_str());
-                                let v_2 = EnumVarZ3::new(&ctx, sort_2.r, format!("{}_s{}", y.name.to_string(), step - 1).as_str());
-                                NEQZ3::new(&ctx, v_1, v_2)
-                            },
-                            _ => panic!("Error 9ed281a9-173c-44ab-9226-cdbcdd13cc83: Predicate type '{}' is not allowed.", r#type)
-                        }
+                        let prev = rhs_step(r#type, step, "9ed281a9-173c-44ab-9226-cdbcdd13cc83")?;
+                        let sort_1 = EnumSortZ3::new(&ctx, &x.r#type, x.domain.iter().map(|x| x.as_str()).collect());
+                        let sort_2 = EnumSortZ3::new(&ctx, &y.r#type, y.domain.iter().map(|y| y.as_str()).collect());
+                        let v_1 = EnumVarZ3::new(&ctx, &sort_1, format!("{}_s{}", x.name.to_string(), step).as_str());
+                        let v_2 = EnumVarZ3::new(&ctx, &sort_2, format!("{}_s{}", y.name.to_string(), prev).as_str());
+                        NEQZ3::new(&ctx, v_1, v_2)
                     },
-                    false => panic!("Error 708e3200-2c6c-4a2b-a283-1e0b5b87b2cc: Sorts '{}' and '{}' are incompatible.", x.r#type, y.r#type)
+                    false => return Err(SPError::SortMismatch { 
+                        code: "708e3200-2c6c-4a2b-a283-1e0b5b87b2cc", 
+                        left: x.r#type.to_string(), 
+                        right: y.r#type.to_string() 
+                    })
                 }
             },
             Predicate::NEQLR(y, x) => {
@@ -146,24 +199,95 @@ impl <'ctx> PredicateToAstZ3<'ctx> {
                         let sort = EnumSortZ3::new(&ctx, &x.r#type, x.domain.iter().map(|x| x.as_str()).collect());
                         let elems = &sort.enum_asts;
                         let index = x.domain.iter().position(|r| *r == y.to_string()).unwrap();
-                        NEQZ3::new(&ctx, EnumVarZ3::new(&ctx, sort.r, format!("{}_s{}", x.name.to_string(), step).as_str()), elems[index])      
+                        NEQZ3::new(&ctx, EnumVarZ3::new(&ctx, &sort, format!("{}_s{}", x.name.to_string(), step).as_str()), &elems[index])      
                     },
-                    false => panic!("Error 44d999a5-4dac-4957-ac02-7806ce8f0ea8: Value '{}' not in the domain of variable '{}'.", y, x.name)
+                    false => return Err(not_in_domain(y, &x.name, "44d999a5-4dac-4957-ac02-7806ce8f0ea8"))
                 }
             },
-            Predicate::NEQPP(x, y) => NEQZ3::new(&ctx, PredicateToAstZ3::new(&ctx, x, r#type, step), PredicateToAstZ3::new(&ctx, y, r#type, step)),
-            Predicate::PBEQ(x, k) => PBEQZ3::new(&ctx, x.iter().map(|z| PredicateToAstZ3::new(&ctx, z, r#type, step)).collect(), *k),
-            Predicate::NEXT(x) => NextZ3::new(&ctx, &x, r#type, step),
-            Predicate::ALWAYS(x) => AlwaysZ3::new(&ctx, &x, r#type, step),
-            Predicate::NEVER(x) => AlwaysZ3::new(&ctx, &Predicate::NOT(x.clone()), r#type, step),
-            Predicate::EVENTUALLY(x) => EventuallyZ3::new(&ctx, &x, r#type, step),
-            Predicate::UNTIL(x, y) => UntilZ3::new(&ctx, &x, &y, r#type, step),
-            Predicate::RELEASE(x, y) => ReleaseZ3::new(&ctx, &x, &y, r#type, step),
-            Predicate::AFTER(x, y) => AfterZ3::new(&ctx, &x, &y, r#type, step),
-            Predicate::SAFTER(x, y) => SomewhenAfterZ3::new(&ctx, &x, &y, r#type, step),
-            Predicate::SEQUENCE(x) => SequenceZ3::new(&ctx, &x.iter().map(|y| y).collect(), r#type, step),
-            Predicate::TPBEQ(x, y) => TracePBEQZ3::new(&ctx, &x, r#type, &y, step)
-        }
+            Predicate::NEQPP(x, y) => NEQZ3::new(&ctx, PredicateToAstZ3::try_new(&ctx, x, r#type, step)?, PredicateToAstZ3::try_new(&ctx, y, r#type, step)?),
+            Predicate::BEQRL(x, y) => {
+                let v = BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), format!("{}_s{}", x.name.to_string(), step).as_str());
+                EQZ3::new(&ctx, v, BoolZ3::new(&ctx, *y))
+            },
+            Predicate::BEQRR(x, y) => {
+                let prev = rhs_step(r#type, step, "0e7e5a1c-6f5b-4c0b-9d52-6b3f8e0c9a41")?;
+                let v_1 = BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), format!("{}_s{}", x.name.to_string(), step).as_str());
+                let v_2 = BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), format!("{}_s{}", y.name.to_string(), prev).as_str());
+                EQZ3::new(&ctx, v_1, v_2)
+            },
+            Predicate::BNEQRL(x, y) => {
+                let v = BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), format!("{}_s{}", x.name.to_string(), step).as_str());
+                NEQZ3::new(&ctx, v, BoolZ3::new(&ctx, *y))
+            },
+            Predicate::BNEQRR(x, y) => {
+                let prev = rhs_step(r#type, step, "3b9d2f47-8a1e-4c6d-b5f0-2e7c1a9d4b83")?;
+                let v_1 = BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), format!("{}_s{}", x.name.to_string(), step).as_str());
+                let v_2 = BoolVarZ3::new(&ctx, &BoolSortZ3::new(&ctx), format!("{}_s{}", y.name.to_string(), prev).as_str());
+                NEQZ3::new(&ctx, v_1, v_2)
+            },
+            Predicate::IEQRL(x, y) => {
+                match x.domain.contains(&y) {
+                    true => {
+                        let v = IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), format!("{}_s{}", x.name.to_string(), step).as_str());
+                        EQZ3::new(&ctx, v, IntZ3::new(&ctx, &IntSortZ3::new(&ctx), *y))
+                    },
+                    false => return Err(not_in_domain(&y.to_string(), &x.name, "9c4e7b12-5d3a-4f8e-a6b1-7d2c0e5f9a36"))
+                }
+            },
+            Predicate::IEQRR(x, y) => {
+                let prev = rhs_step(r#type, step, "5f1a8d3e-2b7c-4e9a-8d6f-1c3b5a7e9d20")?;
+                let v_1 = IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), format!("{}_s{}", x.name.to_string(), step).as_str());
+                let v_2 = IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), format!("{}_s{}", y.name.to_string(), prev).as_str());
+                EQZ3::new(&ctx, v_1, v_2)
+            },
+            Predicate::INEQRL(x, y) => {
+                match x.domain.contains(&y) {
+                    true => {
+                        let v = IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), format!("{}_s{}", x.name.to_string(), step).as_str());
+                        NEQZ3::new(&ctx, v, IntZ3::new(&ctx, &IntSortZ3::new(&ctx), *y))
+                    },
+                    false => return Err(not_in_domain(&y.to_string(), &x.name, "e2d6b9a4-7c1f-4a3e-9b58-0f4d2c6e8a17"))
+                }
+            },
+            Predicate::INEQRR(x, y) => {
+                let prev = rhs_step(r#type, step, "7a3c5e9f-1d8b-4b2a-a4e6-9c0f3d7b5e28")?;
+                let v_1 = IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), format!("{}_s{}", x.name.to_string(), step).as_str());
+                let v_2 = IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), format!("{}_s{}", y.name.to_string(), prev).as_str());
+                NEQZ3::new(&ctx, v_1, v_2)
+            },
+            Predicate::IEQRT(x, y) => {
+                let prev = rhs_step(r#type, step, "4d8e2a6c-9b3f-4e1d-a7c5-3f6b0d2e8c94")?;
+                // outside of updates both sides are in the same step, so x = f(x) would be unsatisfiable
+                if r#type != "update" && term_contains(y, x) {
+                    return Err(SPError::SelfReference { code: "c520a787-5911-487f-90b7-86eb831861aa", var: x.name.to_string() })
+                }
+                let v = IntVarZ3::new(&ctx, &IntSortZ3::new(&ctx), format!("{}_s{}", x.name.to_string(), step).as_str());
+                EQZ3::new(&ctx, v, TermToAstZ3::new(&ctx, y, &prev))
+            },
+            Predicate::ILT(x, y) | Predicate::ILE(x, y) | Predicate::IGT(x, y) | Predicate::IGE(x, y) => {
+                let prev = rhs_step(r#type, step, "b1f7c3e9-6a2d-4c8b-9e4f-5d0a7c3b1e62")?;
+                let left = TermToAstZ3::new(&ctx, x, step);
+                let right = TermToAstZ3::new(&ctx, y, &prev);
+                match pred {
+                    Predicate::ILT(_, _) => LTZ3::new(&ctx, left, right),
+                    Predicate::ILE(_, _) => LEZ3::new(&ctx, left, right),
+                    Predicate::IGT(_, _) => GTZ3::new(&ctx, left, right),
+                    _ => GEZ3::new(&ctx, left, right)
+                }
+            },
+            Predicate::PBEQ(x, k) => PBEQZ3::new(&ctx, x.iter().map(|z| PredicateToAstZ3::try_new(&ctx, z, r#type, step)).collect::<SPResult<Vec<Ast<'ctx>>>>()?, *k),
+            Predicate::NEXT(x) => NextZ3::try_new(&ctx, &x, r#type, step)?,
+            Predicate::ALWAYS(x) => AlwaysZ3::try_new(&ctx, &x, r#type, step)?,
+            Predicate::NEVER(x) => AlwaysZ3::try_new(&ctx, &Predicate::NOT(x.clone()), r#type, step)?,
+            Predicate::EVENTUALLY(x) => EventuallyZ3::try_new(&ctx, &x, r#type, step)?,
+            Predicate::UNTIL(x, y) => UntilZ3::try_new(&ctx, &x, &y, r#type, step)?,
+            Predicate::RELEASE(x, y) => ReleaseZ3::try_new(&ctx, &x, &y, r#type, step)?,
+            Predicate::AFTER(x, y) => AfterZ3::try_new(&ctx, &x, &y, r#type, step)?,
+            Predicate::SAFTER(x, y) => SomewhenAfterZ3::try_new(&ctx, &x, &y, r#type, step)?,
+            Predicate::SEQUENCE(x) => SequenceZ3::try_new(&ctx, &x.iter().collect::<Vec<_>>(), r#type, step)?,
+            Predicate::TPBEQ(x, y) => TracePBEQZ3::try_new(&ctx, &x, r#type, &y, step)?
+        };
+        Ok(ast)
     }
 }
 
@@ -529,11 +653,148 @@ fn test_trace_pbeq_predicate(){
     let trace_pbeq = Predicate::TPBEQ(Box::new(pred), 2);
     let trace_pbeq_pred = PredicateToAstZ3::new(&ctx, &trace_pbeq, "guard", &4);
 
-    assert_eq!("((_ pbeq 2 1 1 1 1 1) (= x_s0 b) (= x_s1 b) (= x_s2 b) (= x_s3 b) (= x_s4 b))", ast_to_string_z3!(&ctx, trace_pbeq_pred));
+    assert_eq!("((_ pbeq 2 1 1 1 1 1) (= x_s0 b) (= x_s1 b) (= x_s2 b) (= x_s3 b) (= x_s4 b))", ast_to_string_z3!(&ctx, &trace_pbeq_pred));
 
     slv_assert_z3!(&ctx, &slv, trace_pbeq_pred);
     slv_check_z3!(&ctx, &slv);
 
     let model = slv_get_model_z3!(&ctx, &slv);
     assert_eq!("x_s2 -> a\nx_s0 -> b\nx_s3 -> a\nx_s1 -> b\nx_s4 -> a\n", model_to_string_z3!(&ctx, model));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_beqrl_predicate(){
+
+    let x = BoolVariable::new("x", None);
+
+    let cfg = ConfigZ3::new();
+    let ctx = ContextZ3::new(&cfg);
+    let n = Predicate::BEQRL(x, true);
+    let pred = PredicateToAstZ3::new(&ctx, &n, "guard", &3);
+    assert_eq!("(= x_s3 true)", ast_to_string_z3!(&ctx, pred));
+}
+
+#[test]
+fn test_bneqrr_predicate(){
+
+    let x = BoolVariable::new("x", None);
+    let y = BoolVariable::new("y", None);
+
+    let cfg = ConfigZ3::new();
+    let ctx = ContextZ3::new(&cfg);
+    let n = Predicate::BNEQRR(x, y);
+    let guard = PredicateToAstZ3::new(&ctx, &n, "guard", &3);
+    let update = PredicateToAstZ3::new(&ctx, &n, "update", &3);
+    assert_eq!("(not (= x_s3 y_s3))", ast_to_string_z3!(&ctx, guard));
+    assert_eq!("(not (= x_s3 y_s2))", ast_to_string_z3!(&ctx, update));
+}
+
+#[test]
+fn test_ieqrl_predicate(){
+
+    let x = IntVariable::new("x", &vec!(&0, &1, &2, &3), None);
+
+    let cfg = ConfigZ3::new();
+    let ctx = ContextZ3::new(&cfg);
+    let n = Predicate::IEQRL(x, 2);
+    let pred = PredicateToAstZ3::new(&ctx, &n, "guard", &3);
+    assert_eq!("(= x_s3 2)", ast_to_string_z3!(&ctx, pred));
+}
+
+#[test]
+#[should_panic(expected = "Error 9c4e7b12-5d3a-4f8e-a6b1-7d2c0e5f9a36: Value '5' not in the domain of variable 'x'.")]
+fn test_ieqrl_predicate_panic(){
+
+    let x = IntVariable::new("x", &vec!(&0, &1, &2, &3), None);
+
+    let cfg = ConfigZ3::new();
+    let ctx = ContextZ3::new(&cfg);
+    let n = Predicate::IEQRL(x, 5);
+    PredicateToAstZ3::new(&ctx, &n, "guard", &3);
+}
+
+#[test]
+fn test_ieqrr_predicate(){
+
+    let x = IntVariable::new("x", &vec!(&0, &1, &2, &3), None);
+    let y = IntVariable::new("y", &vec!(&0, &1, &2, &3), None);
+
+    let cfg = ConfigZ3::new();
+    let ctx = ContextZ3::new(&cfg);
+    let n = Predicate::IEQRR(x, y);
+    let pred = PredicateToAstZ3::new(&ctx, &n, "update", &3);
+    assert_eq!("(= x_s3 y_s2)", ast_to_string_z3!(&ctx, pred));
+}
+
+#[test]
+#[should_panic(expected = "Error 7a3c5e9f-1d8b-4b2a-a4e6-9c0f3d7b5e28: Predicate type 'other' is not allowed.")]
+fn test_ineqrr_predicate_panic(){
+
+    let x = IntVariable::new("x", &vec!(&0, &1, &2, &3), None);
+    let y = IntVariable::new("y", &vec!(&0, &1, &2, &3), None);
+
+    let cfg = ConfigZ3::new();
+    let ctx = ContextZ3::new(&cfg);
+    let n = Predicate::INEQRR(x, y);
+    PredicateToAstZ3::new(&ctx, &n, "other", &3);
+}
+
+#[test]
+fn test_ieqrt_predicate(){
+
+    let x = IntVariable::new("x", &vec!(&0, &1, &2, &3), None);
+    let y = IntVariable::new("y", &vec!(&0, &1, &2, &3), None);
+
+    let cfg = ConfigZ3::new();
+    let ctx = ContextZ3::new(&cfg);
+    let n = Predicate::IEQRT(x.clone(), Term::SUB(vec!(Term::VAR(x.clone()), Term::VAL(3))));
+    let update = PredicateToAstZ3::new(&ctx, &n, "update", &3);
+    assert_eq!("(= x_s3 (- x_s2 3))", ast_to_string_z3!(&ctx, update));
+    let m = Predicate::IEQRT(y.clone(), Term::ADD(vec!(Term::VAR(x.clone()), Term::VAL(1))));
+    let guard = PredicateToAstZ3::new(&ctx, &m, "guard", &3);
+    assert_eq!("(= y_s3 (+ x_s3 1))", ast_to_string_z3!(&ctx, guard));
+    let s = Predicate::IEQRT(y.clone(), Term::ADD(vec!(Term::VAR(y), Term::VAL(1))));
+    let result = PredicateToAstZ3::try_new(&ctx, &s, "guard", &3);
+    assert_eq!("c520a787-5911-487f-90b7-86eb831861aa", result.unwrap_err().code());
+}
+
+#[test]
+fn test_int_comparison_predicates(){
+
+    let x = IntVariable::new("x", &vec!(&0, &1, &2, &3), None);
+    let y = IntVariable::new("y", &vec!(&0, &1, &2, &3), None);
+
+    let cfg = ConfigZ3::new();
+    let ctx = ContextZ3::new(&cfg);
+    let lt = Predicate::ILT(Term::VAR(x.clone()), Term::VAR(y.clone()));
+    let le = Predicate::ILE(Term::VAR(x.clone()), Term::VAL(2));
+    let gt = Predicate::IGT(Term::SUB(vec!(Term::VAR(x.clone()), Term::VAR(y.clone()))), Term::VAL(0));
+    let ge = Predicate::IGE(Term::VAR(x.clone()), Term::VAR(x.clone()));
+    assert_eq!("(< x_s3 y_s3)", ast_to_string_z3!(&ctx, PredicateToAstZ3::new(&ctx, &lt, "guard", &3)));
+    assert_eq!("(<= x_s3 2)", ast_to_string_z3!(&ctx, PredicateToAstZ3::new(&ctx, &le, "state", &3)));
+    assert_eq!("(> (- x_s3 y_s3) 0)", ast_to_string_z3!(&ctx, PredicateToAstZ3::new(&ctx, &gt, "specs", &3)));
+    assert_eq!("(>= x_s3 x_s2)", ast_to_string_z3!(&ctx, PredicateToAstZ3::new(&ctx, &ge, "update", &3)));
+}
+
+#[test]
+fn test_try_new_predicate_errors(){
+
+    let x = EnumVariable::new("x", "letters", &vec!("a", "b", "c", "d"), None);
+    let y = EnumVariable::new("y", "numbers", &vec!("1", "2", "3", "4"), None);
+
+    let cfg = ConfigZ3::new();
+    let ctx = ContextZ3::new(&cfg);
+
+    // errors nested in ltlf predicates are returned as well
+    let n = Predicate::ALWAYS(Box::new(Predicate::EQRL(x.clone(), "e".to_string())));
+    assert_eq!(Err(SPError::NotInDomain { 
+        code: "6f789b86-7f6c-4426-ab0f-6b5b72dd2c55", 
+        value: "e".to_string(), 
+        var: "x".to_string() }), PredicateToAstZ3::try_new(&ctx, &n, "guard", &3));
+
+    let m = Predicate::EQRR(x.clone(), y.clone());
+    assert_eq!("c8022e33-ed30-43af-8e45-8cfdaf09e8a5", PredicateToAstZ3::try_new(&ctx, &m, "guard", &3).unwrap_err().code());
+
+    let k = Predicate::EQRR(x.clone(), x.clone());
+    assert_eq!("53b0fd14-1ddd-4bf0-8dc7-d372d6ad8c99", PredicateToAstZ3::try_new(&ctx, &k, "other", &3).unwrap_err().code());
+}
@@ -3,9 +3,13 @@ use z3_sys::*;
 use mini_sp_smt::*;
 use super::*;
 
+pub struct AssignmentToPredicate {
+    pub assignment: Assignment,
+    pub pred: Predicate
+}
+
 pub struct StateToPredicate {
-    pub state: Vec<String>,
-    pub prob: PlanningProblem,
+    pub state: State,
     pub pred: Predicate
 }
 
@@ -16,42 +20,61 @@ pub struct VerifySafety {
     pub safe: bool
 }
 
+impl AssignmentToPredicate {
+    pub fn new(assignment: &Assignment) -> Predicate {
+        match AssignmentToPredicate::try_new(assignment) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(assignment: &Assignment) -> SPResult<Predicate> {
+        match (&assignment.var, &assignment.val) {
+            (Variable::Bool(x), Value::Bool(y)) => Ok(Predicate::BEQRL(x.to_owned(), *y)),
+            (Variable::Int(x), Value::Int(y)) => Ok(Predicate::IEQRL(x.to_owned(), *y)),
+            (Variable::Enum(x), Value::Enum(y)) => Ok(Predicate::EQRL(x.to_owned(), y.to_owned())),
+            _ => Err(SPError::WrongType { 
+                code: "a61d4b8e-3f2c-4e7a-b9d0-6e5c1f8a2d37", 
+                value: assignment.val.to_string(), 
+                var: assignment.var.name() 
+            })
+        }
+    }
+}
+
 impl StateToPredicate {
-    pub fn new(state: &Vec<&str>, prob: &PlanningProblem) -> Predicate {
-        let mut pred_vec: Vec<Predicate> = vec!();
-        let prob_vars = GetProblemVars::new(prob);
-        for s in state {
-            let sep: Vec<&str> = s.split(" -> ").collect();
-            let mut var: EnumVariable = EnumVariable::default();
-            for v in &prob_vars {
-                match v.name == sep[0] {
-                    true => var = v.to_owned(),
-                    false => ()
-                };
-            };
-            // hackidy hack
-            if !(var.name == "EMPTY") {
-                pred_vec.push(Predicate::EQRL(var, String::from(sep[1])));
-            };
-        } 
-        Predicate::AND(pred_vec)
+    pub fn new(state: &State) -> Predicate {
+        match StateToPredicate::try_new(state) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(state: &State) -> SPResult<Predicate> {
+        Ok(Predicate::AND(state.assignments.iter().map(|x| AssignmentToPredicate::try_new(x)).collect::<SPResult<Vec<Predicate>>>()?))
     }
 }
 
 impl VerifySafety {
     pub fn new(prob: &PlanningProblem, res: &PlanningResult, forb: &Predicate) -> bool {
+        match VerifySafety::try_new(prob, res, forb) {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_new(prob: &PlanningProblem, res: &PlanningResult, forb: &Predicate) -> SPResult<bool> {
         let mut founds = vec!();
         for t in &res.trace {
             let new_prob = PlanningProblem::new(
                 prob.name.as_str(), 
-                &StateToPredicate::new(&t.state.iter().map(|x| x.as_str()).collect(), &prob), 
+                &StateToPredicate::try_new(&t.state)?, 
                 forb, 
                 &prob.trans, 
                 &prob.ltl_specs, 
                 &prob.max_steps);
-            founds.push(Incremental::new(&new_prob).plan_found);
+            founds.push(Incremental::try_new(&new_prob)?.plan_found);
         }
-        founds.iter().all(|x| *x)
+        Ok(founds.iter().all(|x| *x))
     }
 }
-
@@ -2,22 +2,37 @@ use super::*;
 
 pub struct GetPredicateVars {
     pub pred: Predicate,
-    pub vars: Vec<EnumVariable>
+    pub vars: Vec<Variable>
+}
+
+pub struct GetUpdatedVars {
+    pub pred: Predicate,
+    pub vars: Vec<Variable>
+}
+
+pub struct GetTermVars {
+    pub term: Term,
+    pub vars: Vec<Variable>
 }
 
 pub struct GetParamPredicateVars {
     pub pred: ParamPredicate,
-    pub vars: Vec<EnumVariable>
+    pub vars: Vec<Variable>
 }
 
 pub struct GetProblemVars {
     pub pred: PlanningProblem,
-    pub vars: Vec<EnumVariable>
+    pub vars: Vec<Variable>
+}
+
+pub struct GetAllProblemVars {
+    pub pred: PlanningProblem,
+    pub vars: Vec<Variable>
 }
 
 pub struct GetParamProblemVars {
     pub pred: ParamPlanningProblem,
-    pub vars: Vec<EnumVariable>
+    pub vars: Vec<Variable>
 }
 
 pub trait IterOps<T, I>: IntoIterator<Item = T>
@@ -63,7 +78,7 @@ impl<T, I> IterOps<T, I> for I
 }
 
 impl GetPredicateVars {
-    pub fn new(pred: &Predicate) -> Vec<EnumVariable> {
+    pub fn new(pred: &Predicate) -> Vec<Variable> {
         let mut s = Vec::new();
         match pred {
             Predicate::TRUE => {},
@@ -71,26 +86,54 @@ impl GetPredicateVars {
             Predicate::AND(x) => s.extend(x.iter().flat_map(|p| GetPredicateVars::new(p))),
             Predicate::OR(x) => s.extend(x.iter().flat_map(|p| GetPredicateVars::new(p))),
             Predicate::NOT(x) => s.extend(GetPredicateVars::new(x)),
-            Predicate::EQRL(x, _) => s.push(x.clone()),
+            Predicate::EQRL(x, _) => s.push(Variable::Enum(x.clone())),
             Predicate::EQRR(x, y) => {
-                s.push(x.clone());
-                s.push(y.clone());
+                s.push(Variable::Enum(x.clone()));
+                s.push(Variable::Enum(y.clone()));
             },
-            Predicate::EQLR(_, x) => s.push(x.clone()),
+            Predicate::EQLR(_, x) => s.push(Variable::Enum(x.clone())),
             Predicate::EQPP(x, y) => {
                 s.extend(GetPredicateVars::new(x));
                 s.extend(GetPredicateVars::new(y));
             },
-            Predicate::NEQRL(x, _) => s.push(x.clone()),
+            Predicate::NEQRL(x, _) => s.push(Variable::Enum(x.clone())),
             Predicate::NEQRR(x, y) => {
-                s.push(x.clone());
-                s.push(y.clone());
+                s.push(Variable::Enum(x.clone()));
+                s.push(Variable::Enum(y.clone()));
             },
-            Predicate::NEQLR(_, x) => s.push(x.clone()),
+            Predicate::NEQLR(_, x) => s.push(Variable::Enum(x.clone())),
             Predicate::NEQPP(x, y) => {
                 s.extend(GetPredicateVars::new(x));
                 s.extend(GetPredicateVars::new(y));
             },
+            Predicate::BEQRL(x, _) => s.push(Variable::Bool(x.clone())),
+            Predicate::BEQRR(x, y) => {
+                s.push(Variable::Bool(x.clone()));
+                s.push(Variable::Bool(y.clone()));
+            },
+            Predicate::BNEQRL(x, _) => s.push(Variable::Bool(x.clone())),
+            Predicate::BNEQRR(x, y) => {
+                s.push(Variable::Bool(x.clone()));
+                s.push(Variable::Bool(y.clone()));
+            },
+            Predicate::IEQRL(x, _) => s.push(Variable::Int(x.clone())),
+            Predicate::IEQRR(x, y) => {
+                s.push(Variable::Int(x.clone()));
+                s.push(Variable::Int(y.clone()));
+            },
+            Predicate::INEQRL(x, _) => s.push(Variable::Int(x.clone())),
+            Predicate::INEQRR(x, y) => {
+                s.push(Variable::Int(x.clone()));
+                s.push(Variable::Int(y.clone()));
+            },
+            Predicate::IEQRT(x, y) => {
+                s.push(Variable::Int(x.clone()));
+                s.extend(GetTermVars::new(y));
+            },
+            Predicate::ILT(x, y) | Predicate::ILE(x, y) | Predicate::IGT(x, y) | Predicate::IGE(x, y) => {
+                s.extend(GetTermVars::new(x));
+                s.extend(GetTermVars::new(y));
+            },
             Predicate::PBEQ(x, _) => s.extend(x.iter().flat_map(|p| GetPredicateVars::new(p))),
             Predicate::NEXT(x) => s.extend(GetPredicateVars::new(x)),
             Predicate::ALWAYS(x) => s.extend(GetPredicateVars::new(x)),
@@ -121,8 +164,48 @@ impl GetPredicateVars {
     }
 }
 
+impl GetUpdatedVars {
+    /// The variables that an update assigns, in relative updates the variables
+    /// that only appear on the right side are read from the previous step and keep their values.
+    pub fn new(pred: &Predicate) -> Vec<Variable> {
+        let mut s = Vec::new();
+        match pred {
+            Predicate::AND(x) => s.extend(x.iter().flat_map(|p| GetUpdatedVars::new(p))),
+            Predicate::OR(x) => s.extend(x.iter().flat_map(|p| GetUpdatedVars::new(p))),
+            Predicate::NOT(x) => s.extend(GetUpdatedVars::new(x)),
+            Predicate::EQRL(x, _) | Predicate::EQRR(x, _) | Predicate::NEQRL(x, _) | Predicate::NEQRR(x, _) => 
+                s.push(Variable::Enum(x.clone())),
+            Predicate::BEQRL(x, _) | Predicate::BEQRR(x, _) | Predicate::BNEQRL(x, _) | Predicate::BNEQRR(x, _) => 
+                s.push(Variable::Bool(x.clone())),
+            Predicate::IEQRL(x, _) | Predicate::IEQRR(x, _) | Predicate::INEQRL(x, _) | Predicate::INEQRR(x, _) | 
+            Predicate::IEQRT(x, _) => s.push(Variable::Int(x.clone())),
+            Predicate::ILT(x, _) | Predicate::ILE(x, _) | Predicate::IGT(x, _) | Predicate::IGE(x, _) => 
+                s.extend(GetTermVars::new(x)),
+            _ => s.extend(GetPredicateVars::new(pred))
+        }
+        s.sort();
+        s.dedup();
+        s
+    }
+}
+
+impl GetTermVars {
+    pub fn new(term: &Term) -> Vec<Variable> {
+        let mut s = Vec::new();
+        match term {
+            Term::VAR(x) => s.push(Variable::Int(x.clone())),
+            Term::VAL(_) => {},
+            Term::ADD(x) => s.extend(x.iter().flat_map(|t| GetTermVars::new(t))),
+            Term::SUB(x) => s.extend(x.iter().flat_map(|t| GetTermVars::new(t)))
+        }
+        s.sort();
+        s.dedup();
+        s
+    }
+}
+
 impl GetParamPredicateVars {
-    pub fn new(ppred: &ParamPredicate) -> Vec<EnumVariable> {
+    pub fn new(ppred: &ParamPredicate) -> Vec<Variable> {
         let mut s = Vec::new();
         for p in &ppred.preds {
             s.extend(GetPredicateVars::new(&p));
@@ -134,7 +217,7 @@ impl GetParamPredicateVars {
 }
 
 impl GetProblemVars {
-    pub fn new(prob: &PlanningProblem) -> Vec<EnumVariable> {
+    pub fn new(prob: &PlanningProblem) -> Vec<Variable> {
         let mut s = Vec::new();
         for t in &prob.trans {
             s.extend(GetPredicateVars::new(&t.guard));
@@ -146,8 +229,21 @@ impl GetProblemVars {
     }
 }
 
+impl GetAllProblemVars {
+    /// The variables of the transitions and the ones that only appear in init, goal or specs.
+    pub fn new(prob: &PlanningProblem) -> Vec<Variable> {
+        let mut s = GetProblemVars::new(prob);
+        for p in vec!(&prob.init, &prob.goal, &prob.ltl_specs) {
+            s.extend(GetPredicateVars::new(p));
+        }
+        s.sort();
+        s.dedup();
+        s
+    }
+}
+
 impl GetParamProblemVars {
-    pub fn new(prob: &ParamPlanningProblem) -> Vec<EnumVariable> {
+    pub fn new(prob: &ParamPlanningProblem) -> Vec<Variable> {
         let mut s = Vec::new();
         for t in &prob.trans {
             s.extend(GetParamPredicateVars::new(&t.guard));
@@ -159,6 +255,23 @@ impl GetParamProblemVars {
     }
 }
 
+#[test]
+fn test_get_updated_vars(){
+
+    let x = IntVariable::new("x", &vec!(&0, &1, &2, &3), None);
+    let y = IntVariable::new("y", &vec!(&0, &1, &2, &3), None);
+    let z = IntVariable::new("z", &vec!(&0, &1, &2, &3), None);
+    let b = BoolVariable::new("b", None);
+
+    let n = Predicate::AND(vec!(
+        Predicate::IEQRT(x.clone(), Term::SUB(vec!(Term::VAR(x.clone()), Term::VAR(y.clone())))),
+        Predicate::IEQRR(z.clone(), y.clone()),
+        Predicate::BEQRL(b.clone(), true)
+    ));
+
+    assert_eq!(vec!(Variable::Bool(b), Variable::Int(x), Variable::Int(z)), GetUpdatedVars::new(&n));
+}
+
 // maybe write some more tests for this fn
 #[test]
 fn test_get_predicate_vars(){